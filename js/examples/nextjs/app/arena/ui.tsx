@@ -818,6 +818,13 @@ async function verifyProof(
   return json;
 }
 
+// Custom return_to schemes must be listed explicitly; the demo allows
+// whichever scheme was entered
+function returnToSchemes(returnTo: string | undefined): string[] | undefined {
+  const scheme = returnTo?.split(":")[0];
+  return scheme && scheme !== "https" ? [scheme] : undefined;
+}
+
 export function ArenaClient(): ReactElement {
   const [isLightTheme, setIsLightTheme] = useState(false);
   const [environment, setEnvironment] = useState<"production" | "staging">(
@@ -1324,6 +1331,7 @@ export function ArenaClient(): ReactElement {
           environment={environment}
           override_connect_base_url={overrideConnectBaseUrl}
           return_to={effectiveReturnTo}
+          return_to_schemes={returnToSchemes(effectiveReturnTo)}
         />
       )}
 
@@ -555,6 +555,13 @@ function createChromeAppDeeplink(url: string): string {
   return parsed.protocol === "https:" ? "googlechromes://" : "googlechrome://";
 }
 
+// Custom return_to schemes must be listed explicitly; the demo allows
+// whichever scheme was entered
+function returnToSchemes(returnTo: string | undefined): string[] | undefined {
+  const scheme = returnTo?.split(":")[0];
+  return scheme && scheme !== "https" ? [scheme] : undefined;
+}
+
 function createPreset(kind: PresetKind, signal: string) {
   switch (kind) {
     case "orb":
@@ -1708,6 +1715,7 @@ export function DemoClient(): ReactElement {
             onError={handleWidgetError}
             environment={environment}
             return_to={effectiveReturnTo}
+            return_to_schemes={returnToSchemes(effectiveReturnTo)}
           />
         ) : (
           <IDKitRequestWidget
@@ -1732,6 +1740,7 @@ export function DemoClient(): ReactElement {
             onError={handleWidgetError}
             environment={environment}
             return_to={effectiveReturnTo}
+            return_to_schemes={returnToSchemes(effectiveReturnTo)}
           />
         ))}
 
@@ -1759,6 +1768,7 @@ export function DemoClient(): ReactElement {
           onError={handleWidgetError}
           environment={environment}
           return_to={effectiveReturnTo}
+          return_to_schemes={returnToSchemes(effectiveReturnTo)}
         />
       )}
 
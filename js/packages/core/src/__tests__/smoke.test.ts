@@ -368,6 +368,44 @@ describe("IDKitRequest API", () => {
       { type: "nationality", value: "JPN" },
     ]);
   });
+
+  it("should forward the locale hint to the native payload", () => {
+    const builderWithLocale = (locale: string) =>
+      WasmModule.request(
+        "app_staging_test",
+        "idkit_js_core",
+        packageJson.version,
+        "test-action",
+        new WasmModule.RpContextWasm(
+          "rp_123456789abcdef0",
+          "0x0000000000000000000000000000000000000000000000000000000000000001",
+          1n,
+          2n,
+          "0x" + "00".repeat(64) + "1b",
+        ),
+        null,
+        null,
+        false,
+        false,
+        null,
+        null,
+        null,
+        locale,
+        null,
+      );
+    const preset = identityCheck({
+      attributes: [{ type: "minimum_age", value: 21 }],
+    });
+
+    const result = builderWithLocale("en-US").nativePayloadFromPreset(
+      preset,
+    ) as { payload: { locale?: string } };
+    expect(result.payload.locale).toBe("en-US");
+
+    expect(() =>
+      builderWithLocale("english").nativePayloadFromPreset(preset),
+    ).toThrow();
+  });
 });
 
 describe("Enums", () => {
@@ -0,0 +1 @@
+export { normalizeAction } from "@worldcoin/idkit-server";
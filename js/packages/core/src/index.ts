@@ -91,3 +91,6 @@ export type { RpSignature, SignRequestParams } from "./signing";
 
 // Hashing utilities
 export { hashSignal } from "./lib/hashing";
+
+// Action normalization
+export { normalizeAction } from "./action";
@@ -46,12 +46,14 @@ export interface Status {
     | "failed";
   result?: IDKitResult;
   error?: IDKitErrorCodes;
+  /** Suggested wait in seconds before retrying, set when the inclusion proof is still pending */
+  retry_after_secs?: number;
 }
 
 /** Result from pollUntilCompletion() — discriminated union, never throws */
 export type IDKitCompletionResult =
   | { success: true; result: IDKitResult }
-  | { success: false; error: IDKitErrorCodes };
+  | { success: false; error: IDKitErrorCodes; retry_after_secs?: number };
 
 const SESSION_ID_PATTERN = /^session_[0-9a-fA-F]{128}$/;
 type RustBridgeDebugReport = DebugReportWithoutVersion;
@@ -121,6 +123,9 @@ async function pollUntilCompletionLoop(
         success: false,
         error:
           (status.error as IDKitErrorCodes) ?? IDKitErrorCodes.GenericError,
+        ...(status.retry_after_secs !== undefined && {
+          retry_after_secs: status.retry_after_secs,
+        }),
       };
     }
 
@@ -185,6 +190,8 @@ class IDKitRequestImpl implements IDKitRequest {
 export interface IDKitInviteCodeRequest {
   /** URL to display to the user. Same shape as URL/QR mode's `connectorURI` with `&c=<code>&a=<app_id>` appended. */
   readonly connectorURI: string;
+  /** `worldapp://` deep link with the same params as `connectorURI`. Opens World App directly, so only use it when World App is installed. */
+  readonly deepLinkURI: string;
   /** Unix-seconds expiry of the unredeemed code. After this point bridge will reject the redeem. */
   readonly expiresAt: number;
   /** Unique request ID for this verification */
@@ -205,12 +212,14 @@ export interface IDKitInviteCodeRequest {
 class IDKitInviteCodeRequestImpl implements IDKitInviteCodeRequest {
   private wasmRequest: WasmModule.IDKitInviteCodeRequest;
   private _connectorURI: string;
+  private _deepLinkURI: string;
   private _expiresAt: number;
   private _requestId: string;
 
   constructor(wasmRequest: WasmModule.IDKitInviteCodeRequest) {
     this.wasmRequest = wasmRequest;
     this._connectorURI = wasmRequest.connectUrl();
+    this._deepLinkURI = wasmRequest.deepLinkUrl();
     this._expiresAt = wasmRequest.expiresAt();
     this._requestId = wasmRequest.requestId();
   }
@@ -219,6 +228,10 @@ class IDKitInviteCodeRequestImpl implements IDKitInviteCodeRequest {
     return this._connectorURI;
   }
 
+  get deepLinkURI(): string {
+    return this._deepLinkURI;
+  }
+
   get expiresAt(): number {
     return this._expiresAt;
   }
@@ -567,6 +580,8 @@ function createWasmBuilderFromConfig(
       config.override_connect_base_url ?? null,
       config.return_to ?? null,
       config.environment ?? null,
+      config.locale ?? null,
+      config.return_to_schemes ?? null,
     );
   }
 
@@ -583,6 +598,8 @@ function createWasmBuilderFromConfig(
       config.override_connect_base_url ?? null,
       config.return_to ?? null,
       config.environment ?? null,
+      config.locale ?? null,
+      config.return_to_schemes ?? null,
     );
   }
 
@@ -598,6 +615,8 @@ function createWasmBuilderFromConfig(
     config.override_connect_base_url ?? null,
     config.return_to ?? null,
     config.environment ?? null,
+    config.locale ?? null,
+    config.return_to_schemes ?? null,
   );
 }
 
@@ -743,6 +762,21 @@ class IDKitBuilder {
     )) as unknown as WasmModule.IDKitRequest;
     return new IDKitRequestImpl(wasmRequest);
   }
+
+  /**
+   * Creates an Orb-only IDKit request, the most common case
+   *
+   * Shorthand for `.preset(orbLegacy(opts))`, so it returns World ID 3.0 proofs only.
+   *
+   * @example
+   * ```typescript
+   * const request = await IDKit.request({ app_id, action, rp_context, allow_legacy_proofs: true })
+   *   .orb({ signal: 'user-123' });
+   * ```
+   */
+  async orb(opts: { signal?: string } = {}): Promise<IDKitRequest> {
+    return this.preset(orbLegacy(opts));
+  }
 }
 
 /**
@@ -886,10 +920,12 @@ function createRequestForNamespace(
     action_description: config.action_description,
     bridge_url: config.bridge_url,
     return_to: config.return_to,
+    return_to_schemes: config.return_to_schemes,
     allow_legacy_proofs: config.allow_legacy_proofs,
     require_user_presence: config.require_user_presence ?? false,
     override_connect_base_url: config.override_connect_base_url,
     environment: config.environment,
+    locale: config.locale,
   });
 }
 
@@ -951,10 +987,12 @@ function createRequestWithInviteCodeForNamespace(
     action_description: config.action_description,
     bridge_url: config.bridge_url,
     return_to: config.return_to,
+    return_to_schemes: config.return_to_schemes,
     allow_legacy_proofs: config.allow_legacy_proofs,
     require_user_presence: config.require_user_presence ?? false,
     override_connect_base_url: config.override_connect_base_url,
     environment: config.environment,
+    locale: config.locale,
   });
 }
 
@@ -1016,9 +1054,11 @@ function createSessionForNamespace(
     action_description: config.action_description,
     bridge_url: config.bridge_url,
     return_to: config.return_to,
+    return_to_schemes: config.return_to_schemes,
     require_user_presence: config.require_user_presence ?? false,
     override_connect_base_url: config.override_connect_base_url,
     environment: config.environment,
+    locale: config.locale,
   });
 }
 
@@ -1085,9 +1125,11 @@ function proveSessionForNamespace(
     action_description: config.action_description,
     bridge_url: config.bridge_url,
     return_to: config.return_to,
+    return_to_schemes: config.return_to_schemes,
     require_user_presence: config.require_user_presence ?? false,
     override_connect_base_url: config.override_connect_base_url,
     environment: config.environment,
+    locale: config.locale,
   });
 }
 
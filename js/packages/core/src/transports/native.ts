@@ -120,10 +120,12 @@ export interface BuilderConfig {
   action_description?: string;
   bridge_url?: string;
   return_to?: string;
+  return_to_schemes?: string[];
   allow_legacy_proofs?: boolean;
   require_user_presence?: boolean;
   override_connect_base_url?: string;
   environment?: string;
+  locale?: string;
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -48,6 +48,8 @@ export type IDKitRequestConfig = {
   bridge_url?: string;
   /** Optional deep-link callback URL appended as `return_to` on the connector URL. */
   return_to?: string;
+  /** Custom URL schemes `return_to` may use besides https, e.g. `["myapp"]` for `myapp://callback`. */
+  return_to_schemes?: string[];
 
   /**
    * Whether to accept legacy (v3) World ID proofs as fallback.
@@ -66,6 +68,9 @@ export type IDKitRequestConfig = {
 
   /** Optional environment override. Defaults to "production". */
   environment?: "production" | "staging" | "sandbox";
+
+  /** Optional language hint for World App, such as "en" or "en-US". Must be a two-letter language code with an optional two-letter region. */
+  locale?: string;
 };
 
 /**
@@ -87,6 +92,8 @@ export type IDKitSessionConfig = {
   bridge_url?: string;
   /** Optional deep-link callback URL appended as `return_to` on the connector URL. */
   return_to?: string;
+  /** Custom URL schemes `return_to` may use besides https, e.g. `["myapp"]` for `myapp://callback`. */
+  return_to_schemes?: string[];
   /** Require World App to perform a user-presence check before verification. Defaults to false. */
   require_user_presence?: boolean;
   /** Optional connect base URL override; takes precedence over the environment mapping. */
@@ -94,4 +101,7 @@ export type IDKitSessionConfig = {
 
   /** Optional environment override. Defaults to "production". */
   environment?: "production" | "staging" | "sandbox";
+
+  /** Optional language hint for World App, such as "en" or "en-US". Must be a two-letter language code with an optional two-letter region. */
+  locale?: string;
 };
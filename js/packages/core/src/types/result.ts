@@ -80,6 +80,8 @@ export enum IDKitErrorCodes {
   InvalidTimestamp = "invalid_timestamp",
   RpSignatureExpired = "rp_signature_expired",
   IdentityAttributesNotMatched = "identity_attributes_not_matched",
+  RequestNotFound = "request_not_found",
+  RateLimited = "rate_limited",
   GenericError = "generic_error",
   // Client-side errors
   InvalidRpIdFormat = "invalid_rp_id_format",
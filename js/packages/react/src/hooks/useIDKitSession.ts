@@ -39,6 +39,7 @@ export function useIDKitSession(
           require_user_presence: config.require_user_presence ?? false,
           override_connect_base_url: config.override_connect_base_url,
           return_to: config.return_to,
+          return_to_schemes: config.return_to_schemes,
           environment: config.environment,
         })
       : IDKit.createSession({
@@ -49,6 +50,7 @@ export function useIDKitSession(
           require_user_presence: config.require_user_presence ?? false,
           override_connect_base_url: config.override_connect_base_url,
           return_to: config.return_to,
+          return_to_schemes: config.return_to_schemes,
           environment: config.environment,
         });
     return builder.constraints(config.constraints);
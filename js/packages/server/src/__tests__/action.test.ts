@@ -0,0 +1,63 @@
+import { readFile } from "node:fs/promises";
+import { dirname, join } from "node:path";
+import { fileURLToPath } from "node:url";
+import { describe, it, expect, beforeAll } from "vitest";
+import initWasm, {
+  normalizeAction as wasmNormalizeAction,
+} from "../../../core/wasm/idkit_wasm.js";
+import { normalizeAction } from "../lib/action";
+
+beforeAll(async () => {
+  const __filename = fileURLToPath(import.meta.url);
+  const __dirname = dirname(__filename);
+  const wasmPath = join(__dirname, "../../../core/wasm/idkit_wasm_bg.wasm");
+  const wasmBuffer = await readFile(wasmPath);
+  await initWasm({ module_or_path: wasmBuffer });
+});
+
+describe("normalizeAction", () => {
+  it("should compose combining characters to NFC", () => {
+    expect(normalizeAction("cafe\u0301")).toBe("caf\u00e9");
+  });
+
+  it("should lowercase only when asked", () => {
+    expect(normalizeAction("Login")).toBe("Login");
+    expect(normalizeAction("Login", true)).toBe("login");
+  });
+
+  it("should reject invalid actions", () => {
+    for (const action of [
+      "",
+      "login ",
+      " login",
+      "log\u200Bin",
+      "log\u0000in",
+      "a".repeat(201),
+    ]) {
+      expect(() => normalizeAction(action)).toThrow(/Invalid action/);
+    }
+  });
+});
+
+describe("normalizeAction parity (server JS vs Rust WASM)", () => {
+  it("should match for valid actions", () => {
+    for (const action of [
+      "login",
+      "cafe\u0301",
+      "U\u0308nicode",
+      "a".repeat(200),
+    ]) {
+      expect(normalizeAction(action)).toBe(wasmNormalizeAction(action));
+      expect(normalizeAction(action, true)).toBe(
+        wasmNormalizeAction(action, true),
+      );
+    }
+  });
+
+  it("should reject the same actions", () => {
+    for (const action of ["", "login\n", "\u2060login", "log\u00ADin"]) {
+      expect(() => normalizeAction(action)).toThrow();
+      expect(() => wasmNormalizeAction(action)).toThrow();
+    }
+  });
+});
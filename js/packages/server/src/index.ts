@@ -5,3 +5,4 @@ export {
   type SignRequestParams,
 } from "./lib/signing";
 export { getSessionCommitment } from "./lib/session";
+export { normalizeAction } from "./lib/action";
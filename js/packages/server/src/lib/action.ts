@@ -0,0 +1,54 @@
+/** Maximum length of an action, in characters. Matches Rust `MAX_ACTION_LENGTH`. */
+export const MAX_ACTION_LENGTH = 200;
+
+// Characters that render as nothing but still change the action's bytes.
+// Matches Rust `is_invisible` in rust/core/src/types.rs
+const INVISIBLE =
+  /[\u00AD\u034F\u061C\u115F\u1160\u180E\u200B-\u200F\u202A-\u202E\u2060-\u206F\u3164\uFEFF\uFFA0]/u;
+const CONTROL = /\p{Cc}/u;
+const WHITESPACE_EDGE = /^\p{White_Space}|\p{White_Space}$/u;
+
+/**
+ * Normalizes an action the way IDKit requests do.
+ *
+ * Actions are hashed byte for byte into the nullifier, so sign and verify
+ * with the returned action. Matches Rust `Action::new` (and
+ * `Action::new_lowercase` when `lowercase` is set).
+ *
+ * @param action - The action as configured by the app
+ * @param lowercase - Also fold case, so `Login` and `login` are the same action
+ * @returns The NFC-normalized action
+ * @throws If the action is empty, longer than 200 characters, starts or ends
+ * with whitespace, or contains control or invisible characters
+ */
+export function normalizeAction(action: string, lowercase = false): string {
+  // Lowercasing can decompose characters, so normalize last
+  const normalized = (lowercase ? action.toLowerCase() : action).normalize(
+    "NFC",
+  );
+
+  if (normalized.length === 0) {
+    throw new Error("Invalid action: action cannot be empty");
+  }
+  if ([...normalized].length > MAX_ACTION_LENGTH) {
+    throw new Error(
+      `Invalid action: action must be at most ${MAX_ACTION_LENGTH} characters`,
+    );
+  }
+  if (WHITESPACE_EDGE.test(normalized)) {
+    throw new Error(
+      "Invalid action: action cannot start or end with whitespace",
+    );
+  }
+  const invalid = [...normalized].find(
+    (c) => CONTROL.test(c) || INVISIBLE.test(c),
+  );
+  if (invalid !== undefined) {
+    const codePoint = invalid.codePointAt(0)!.toString(16).toUpperCase();
+    throw new Error(
+      `Invalid action: action contains the control or invisible character U+${codePoint.padStart(4, "0")}`,
+    );
+  }
+
+  return normalized;
+}
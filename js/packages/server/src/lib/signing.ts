@@ -3,6 +3,7 @@ import { bytesToHex, hexToBytes } from "@noble/hashes/utils";
 import { hmac } from "@noble/hashes/hmac";
 import { sha256 } from "@noble/hashes/sha2";
 import { sign, etc } from "@noble/secp256k1";
+import { normalizeAction } from "./action";
 import { isServerEnvironment } from "./platform";
 
 // Node <19 CJS doesn't expose globalThis.crypto; polyfill it so @noble libs and our code can use it
@@ -96,7 +97,8 @@ function hashEthereumMessage(message: Uint8Array): Uint8Array {
  * https://github.com/worldcoin/world-id-protocol/blob/31405df8bcd5a2784e04ad9890cf095111dcac13/crates/primitives/src/lib.rs#L134-L149
  *
  * Accepts a single options object: `signRequest({ signingKeyHex, action?, ttl? })`.
- * When `action` is provided, it is hashed to a field element and appended to the signed message.
+ * When `action` is provided, it is normalized like the request (see `normalizeAction`),
+ * hashed to a field element and appended to the signed message.
  * This is required for non-session proofs. Session proofs omit the action.
  * @returns RpSignature object with sig, nonce, createdAt, expiresAt
  */
@@ -149,12 +151,12 @@ export function signRequest(params: SignRequestParams): RpSignature {
   const createdAt = Math.floor(Date.now() / 1000);
   const expiresAt = createdAt + ttl;
 
-  // 4. Build message and sign
+  // 4. Build message and sign. Requests carry the normalized action, so sign that.
   const message = computeRpSignatureMessage(
     nonceBytes,
     createdAt,
     expiresAt,
-    action,
+    action === undefined ? undefined : normalizeAction(action),
   );
   const msgHash = hashEthereumMessage(message);
 
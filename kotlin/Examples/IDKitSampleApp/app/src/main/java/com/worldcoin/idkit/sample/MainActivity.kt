@@ -380,6 +380,7 @@ private class SampleModel {
                     requireUserPresence = false,
                     overrideConnectBaseUrl = null,
                     returnTo = returnToURL,
+                    returnToSchemes = listOf("idkitsample"),
                     environment = when (environment) {
                         SampleEnvironment.PRODUCTION -> Environment.PRODUCTION
                         SampleEnvironment.STAGING -> Environment.STAGING
@@ -56,8 +56,15 @@ data class IDKitRequestConfig(
     val requireUserPresence: Boolean = false,
     val overrideConnectBaseUrl: String? = null,
     val returnTo: String? = null,
+    val returnToSchemes: List<String>? = null,
     val environment: Environment? = null,
     val connectUrlMode: ConnectUrlMode? = null,
+    val connectTimeoutMs: ULong? = null,
+    val requestTimeoutMs: ULong? = null,
+    val normalizeConstraints: Boolean? = null,
+    val cancelOnDrop: Boolean? = null,
+    val singleConsumption: Boolean? = null,
+    val compressPayload: Boolean? = null,
 ) {
     internal fun toNative(): NativeIDKitRequestConfig =
         NativeIDKitRequestConfig(
@@ -74,6 +81,13 @@ data class IDKitRequestConfig(
             returnTo = returnTo,
             environment = environment,
             connectUrlMode = connectUrlMode,
+            connectTimeoutMs = connectTimeoutMs,
+            requestTimeoutMs = requestTimeoutMs,
+            normalizeConstraints = normalizeConstraints,
+            cancelOnDrop = cancelOnDrop,
+            singleConsumption = singleConsumption,
+            returnToSchemes = returnToSchemes,
+            compressPayload = compressPayload,
         )
 }
 
@@ -85,7 +99,14 @@ data class IDKitSessionConfig(
     val requireUserPresence: Boolean = false,
     val overrideConnectBaseUrl: String? = null,
     val returnTo: String? = null,
+    val returnToSchemes: List<String>? = null,
     val environment: Environment? = null,
+    val connectTimeoutMs: ULong? = null,
+    val requestTimeoutMs: ULong? = null,
+    val normalizeConstraints: Boolean? = null,
+    val cancelOnDrop: Boolean? = null,
+    val singleConsumption: Boolean? = null,
+    val compressPayload: Boolean? = null,
 ) {
     internal fun toNative(): NativeIDKitSessionConfig =
         NativeIDKitSessionConfig(
@@ -99,6 +120,13 @@ data class IDKitSessionConfig(
             overrideConnectBaseUrl = overrideConnectBaseUrl,
             returnTo = returnTo,
             environment = environment,
+            connectTimeoutMs = connectTimeoutMs,
+            requestTimeoutMs = requestTimeoutMs,
+            normalizeConstraints = normalizeConstraints,
+            cancelOnDrop = cancelOnDrop,
+            singleConsumption = singleConsumption,
+            returnToSchemes = returnToSchemes,
+            compressPayload = compressPayload,
         )
 }
 
@@ -129,38 +157,47 @@ enum class IDKitErrorCode(val rawValue: String) {
     INVALID_TIMESTAMP("invalid_timestamp"),
     RP_SIGNATURE_EXPIRED("rp_signature_expired"),
     IDENTITY_ATTRIBUTES_NOT_MATCHED("identity_attributes_not_matched"),
+    REQUEST_NOT_FOUND("request_not_found"),
+    RATE_LIMITED("rate_limited"),
     GENERIC_ERROR("generic_error"),
     TIMEOUT("timeout"),
     CANCELLED("cancelled");
 
     internal companion object {
         fun from(error: AppError): IDKitErrorCode = when (error) {
-            AppError.USER_REJECTED -> USER_REJECTED
-            AppError.VERIFICATION_REJECTED -> VERIFICATION_REJECTED
-            AppError.CREDENTIAL_UNAVAILABLE -> CREDENTIAL_UNAVAILABLE
-            AppError.WORLD_ID4_NOT_AVAILABLE -> WORLD_ID_4_NOT_AVAILABLE
-            AppError.WORLD_ID3_NOT_AVAILABLE -> WORLD_ID_3_NOT_AVAILABLE
-            AppError.MALFORMED_REQUEST -> MALFORMED_REQUEST
-            AppError.INVALID_NETWORK -> INVALID_NETWORK
-            AppError.INCLUSION_PROOF_PENDING -> INCLUSION_PROOF_PENDING
-            AppError.INCLUSION_PROOF_FAILED -> INCLUSION_PROOF_FAILED
-            AppError.UNEXPECTED_RESPONSE -> UNEXPECTED_RESPONSE
-            AppError.CONNECTION_FAILED -> CONNECTION_FAILED
-            AppError.MAX_VERIFICATIONS_REACHED -> MAX_VERIFICATIONS_REACHED
-            AppError.FAILED_BY_HOST_APP -> FAILED_BY_HOST_APP
-            AppError.USER_PRESENCE_FAILED -> USER_PRESENCE_FAILED
-            AppError.INVALID_RP_SIGNATURE -> INVALID_RP_SIGNATURE
-            AppError.NULLIFIER_REPLAYED -> NULLIFIER_REPLAYED
-            AppError.DUPLICATE_NONCE -> DUPLICATE_NONCE
-            AppError.UNKNOWN_RP -> UNKNOWN_RP
-            AppError.INACTIVE_RP -> INACTIVE_RP
-            AppError.TIMESTAMP_TOO_OLD -> TIMESTAMP_TOO_OLD
-            AppError.TIMESTAMP_TOO_FAR_IN_FUTURE -> TIMESTAMP_TOO_FAR_IN_FUTURE
-            AppError.INVALID_TIMESTAMP -> INVALID_TIMESTAMP
-            AppError.RP_SIGNATURE_EXPIRED -> RP_SIGNATURE_EXPIRED
-            AppError.IDENTITY_ATTRIBUTES_NOT_MATCHED -> IDENTITY_ATTRIBUTES_NOT_MATCHED
-            AppError.GENERIC_ERROR -> GENERIC_ERROR
+            AppError.UserRejected -> USER_REJECTED
+            AppError.VerificationRejected -> VERIFICATION_REJECTED
+            AppError.CredentialUnavailable -> CREDENTIAL_UNAVAILABLE
+            AppError.WorldId4NotAvailable -> WORLD_ID_4_NOT_AVAILABLE
+            AppError.WorldId3NotAvailable -> WORLD_ID_3_NOT_AVAILABLE
+            AppError.MalformedRequest -> MALFORMED_REQUEST
+            AppError.InvalidNetwork -> INVALID_NETWORK
+            AppError.InclusionProofPending -> INCLUSION_PROOF_PENDING
+            AppError.InclusionProofFailed -> INCLUSION_PROOF_FAILED
+            AppError.UnexpectedResponse -> UNEXPECTED_RESPONSE
+            AppError.ConnectionFailed -> CONNECTION_FAILED
+            AppError.MaxVerificationsReached -> MAX_VERIFICATIONS_REACHED
+            AppError.FailedByHostApp -> FAILED_BY_HOST_APP
+            AppError.UserPresenceFailed -> USER_PRESENCE_FAILED
+            AppError.InvalidRpSignature -> INVALID_RP_SIGNATURE
+            AppError.NullifierReplayed -> NULLIFIER_REPLAYED
+            AppError.DuplicateNonce -> DUPLICATE_NONCE
+            AppError.UnknownRp -> UNKNOWN_RP
+            AppError.InactiveRp -> INACTIVE_RP
+            AppError.TimestampTooOld -> TIMESTAMP_TOO_OLD
+            AppError.TimestampTooFarInFuture -> TIMESTAMP_TOO_FAR_IN_FUTURE
+            AppError.InvalidTimestamp -> INVALID_TIMESTAMP
+            AppError.RpSignatureExpired -> RP_SIGNATURE_EXPIRED
+            AppError.IdentityAttributesNotMatched -> IDENTITY_ATTRIBUTES_NOT_MATCHED
+            AppError.RequestNotFound -> REQUEST_NOT_FOUND
+            AppError.RateLimited -> RATE_LIMITED
+            AppError.GenericError -> GENERIC_ERROR
+            // Codes added in newer World App versions; see `rawCode`
+            is AppError.Other -> GENERIC_ERROR
         }
+
+        /** World App's code when [from] could only map it to [GENERIC_ERROR]. */
+        fun rawCode(error: AppError): String? = (error as? AppError.Other)?.v1
     }
 }
 
@@ -168,13 +205,25 @@ sealed interface IDKitStatus {
     data object WaitingForConnection : IDKitStatus
     data object AwaitingConfirmation : IDKitStatus
     data class Confirmed(val result: IDKitResult) : IDKitStatus
-    data class Failed(val error: IDKitErrorCode) : IDKitStatus
+    /**
+     * [retryAfterSecs] suggests when a new request is expected to succeed.
+     * [rawCode] keeps World App's code when this version does not know it and reports [IDKitErrorCode.GENERIC_ERROR].
+     */
+    data class Failed(
+        val error: IDKitErrorCode,
+        val retryAfterSecs: ULong? = null,
+        val rawCode: String? = null,
+    ) : IDKitStatus
     data class NetworkingError(val error: IDKitErrorCode) : IDKitStatus
 }
 
 sealed interface IDKitCompletionResult {
     data class Success(val result: IDKitResult) : IDKitCompletionResult
-    data class Failure(val error: IDKitErrorCode) : IDKitCompletionResult
+    data class Failure(
+        val error: IDKitErrorCode,
+        val retryAfterSecs: ULong? = null,
+        val rawCode: String? = null,
+    ) : IDKitCompletionResult
 }
 
 data class IDKitPollOptions(
@@ -197,6 +246,10 @@ class IDKitBuilder internal constructor(
 
     fun preset(preset: Preset): IDKitRequest =
         IDKitRequest(inner.preset(preset))
+
+    /** Orb-only request, shorthand for `preset(orbLegacy(signal))`. */
+    fun orb(signal: String? = null): IDKitRequest =
+        IDKitRequest(inner.orb(signal))
 }
 
 class IDKitRequest internal constructor(
@@ -234,7 +287,8 @@ class IDKitRequest internal constructor(
 
                 when (val status = pollStatusOnce()) {
                     is IDKitStatus.Confirmed -> return IDKitCompletionResult.Success(status.result)
-                    is IDKitStatus.Failed -> return IDKitCompletionResult.Failure(status.error)
+                    is IDKitStatus.Failed ->
+                        return IDKitCompletionResult.Failure(status.error, status.retryAfterSecs, status.rawCode)
                     is IDKitStatus.NetworkingError -> delay(pollIntervalMs.toLong())
                     IDKitStatus.AwaitingConfirmation,
                     IDKitStatus.WaitingForConnection -> delay(pollIntervalMs.toLong())
@@ -256,7 +310,11 @@ class IDKitRequest internal constructor(
             StatusWrapper.WaitingForConnection -> IDKitStatus.WaitingForConnection
             StatusWrapper.AwaitingConfirmation -> IDKitStatus.AwaitingConfirmation
             is StatusWrapper.Confirmed -> IDKitStatus.Confirmed(status.result)
-            is StatusWrapper.Failed -> IDKitStatus.Failed(IDKitErrorCode.from(status.error))
+            is StatusWrapper.Failed -> IDKitStatus.Failed(
+                IDKitErrorCode.from(status.error),
+                status.retryAfterSecs,
+                IDKitErrorCode.rawCode(status.error),
+            )
             is StatusWrapper.NetworkingError -> IDKitStatus.NetworkingError(IDKitErrorCode.from(status.error))
         }
     }
@@ -99,6 +99,7 @@ class IDKitTests {
             requireUserPresence = true,
             overrideConnectBaseUrl = null,
             returnTo = "idkitsample://callback",
+            returnToSchemes = listOf("idkitsample"),
             environment = Environment.STAGING,
             connectUrlMode = null,
         )
@@ -213,51 +214,59 @@ class IDKitTests {
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.INVALID_NETWORK),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.INVALID_NETWORK)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.InvalidNetwork, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.USER_PRESENCE_FAILED),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.USER_PRESENCE_FAILED)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.UserPresenceFailed, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.INVALID_RP_SIGNATURE),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.INVALID_RP_SIGNATURE)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.InvalidRpSignature, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.NULLIFIER_REPLAYED),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.NULLIFIER_REPLAYED)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.NullifierReplayed, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.DUPLICATE_NONCE),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.DUPLICATE_NONCE)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.DuplicateNonce, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.UNKNOWN_RP),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.UNKNOWN_RP)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.UnknownRp, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.INACTIVE_RP),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.INACTIVE_RP)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.InactiveRp, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.TIMESTAMP_TOO_OLD),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.TIMESTAMP_TOO_OLD)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.TimestampTooOld, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.TIMESTAMP_TOO_FAR_IN_FUTURE),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.TIMESTAMP_TOO_FAR_IN_FUTURE)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.TimestampTooFarInFuture, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.INVALID_TIMESTAMP),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.INVALID_TIMESTAMP)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.InvalidTimestamp, null)),
         )
         assertEquals(
             IDKitStatus.Failed(IDKitErrorCode.RP_SIGNATURE_EXPIRED),
-            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.RP_SIGNATURE_EXPIRED)),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.RpSignatureExpired, null)),
+        )
+        assertEquals(
+            IDKitStatus.Failed(IDKitErrorCode.INCLUSION_PROOF_PENDING, 1_800u),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.InclusionProofPending, 1_800u)),
+        )
+        assertEquals(
+            IDKitStatus.Failed(IDKitErrorCode.GENERIC_ERROR, rawCode = "new_world_app_code"),
+            IDKitRequest.mapStatus(StatusWrapper.Failed(AppError.Other("new_world_app_code"), null)),
         )
         assertEquals(
             IDKitStatus.NetworkingError(IDKitErrorCode.CONNECTION_FAILED),
-            IDKitRequest.mapStatus(StatusWrapper.NetworkingError(AppError.CONNECTION_FAILED)),
+            IDKitRequest.mapStatus(StatusWrapper.NetworkingError(AppError.ConnectionFailed)),
         )
     }
 
@@ -344,6 +353,19 @@ class IDKitTests {
         assertEquals(IDKitCompletionResult.Failure(IDKitErrorCode.USER_REJECTED), completion)
     }
 
+    @Test
+    fun `pollUntilCompletion keeps the retry hint of a pending inclusion proof`() = runBlocking {
+        val request = IDKitRequest.forTesting(
+            connectorURI = "https://world.org/verify?t=wld",
+            requestId = "7a6ff287-c95f-4330-b3de-9447f77ca3f9",
+        ) {
+            IDKitStatus.Failed(IDKitErrorCode.INCLUSION_PROOF_PENDING, 1_800u)
+        }
+
+        val completion = request.pollUntilCompletion(IDKitPollOptions(pollIntervalMs = 1u, timeoutMs = 1_000u))
+        assertEquals(IDKitCompletionResult.Failure(IDKitErrorCode.INCLUSION_PROOF_PENDING, 1_800u), completion)
+    }
+
     @Test
     fun `hashSignal string and bytes overloads are deterministic`() {
         val raw = "test-signal"
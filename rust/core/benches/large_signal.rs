@@ -0,0 +1,49 @@
+//! Building and sealing a request that carries a 2 MiB signal
+//!
+//! Run with `cargo bench -p idkit-core --bench large_signal`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use idkit::{
+    bridge::{
+        build_request_payload_json,
+        mock::{large_signal_params, CapturingTransport},
+    },
+    BridgeConnection,
+};
+
+fn bench_large_signal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_signal");
+    group.sample_size(10);
+
+    let params = large_signal_params(false);
+    group.bench_function("build_payload", |b| {
+        b.iter(|| build_request_payload_json(&params, false).unwrap());
+    });
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    for (name, compress) in [("create", false), ("create_compressed", true)] {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || large_signal_params(compress),
+                |params| {
+                    runtime
+                        .block_on(
+                            BridgeConnection::builder(params)
+                                .transport(Box::new(CapturingTransport::default()))
+                                .create(),
+                        )
+                        .unwrap()
+                },
+                BatchSize::PerIteration,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_large_signal);
+criterion_main!(benches);
@@ -3,11 +3,14 @@
 #[cfg(feature = "ffi")]
 use crate::preset::Preset;
 use crate::{
-    crypto::{base64_decode, base64_encode, decrypt, encrypt},
-    error::{AppError, Error, Result},
+    constraints::SatisfiedBranch,
+    crypto::{base64_decode, base64_encode, decrypt, encrypt_in_place, SecretBytes},
+    error::{AppError, BridgeEndpoint, DecryptionFailureKind, Error, Result},
+    http::{default_client, ensure_within_limit, read_capped_json, DEFAULT_MAX_RESPONSE_BYTES},
     types::{
-        AppId, BridgeResponseV1, BridgeUrl, IDKitResult, IdentityAttribute, IntegrityBundle,
-        ResponseItem, RpContext, VerificationLevel,
+        is_default_proof, AppId, BridgeResponseV1, BridgeUrl, CredentialType, IDKitResult,
+        IdentityAttribute, IntegrityBundle, ProofRequestExt, ResponseItem, RpContext,
+        VerificationLevel,
     },
     ConstraintNode, Signal,
 };
@@ -18,51 +21,32 @@ use world_id_primitives::{
     ResponseItem as ProtocolResponseItem, SessionId,
 };
 
+pub use crate::http::HttpTimeouts;
+
+#[cfg(all(feature = "bridge", any(test, feature = "test-utils")))]
+pub mod mock;
+
 #[cfg(feature = "native-crypto")]
 use crate::crypto::CryptoKey;
+#[cfg(feature = "bridge")]
+use crate::error::BridgeHealthFailureKind;
 
 use std::{
     str::FromStr,
-    sync::{Arc, Mutex, PoisonError},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Environment
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Environment for the bridge request
-#[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    Eq,
-    Serialize,
-    Deserialize,
-    Default,
-    strum::AsRefStr,
-    strum::Display,
-)]
-#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum Environment {
-    #[default]
-    Production,
-    Staging,
-    Sandbox,
-}
+pub use crate::types::Environment;
 
-impl Environment {
-    #[must_use]
-    pub const fn connect_base_url(self) -> &'static str {
-        match self {
-            Self::Production => "https://world.org/verify",
-            Self::Staging => "https://staging.world.org/verify",
-            Self::Sandbox => "https://sandbox.world.org/verify",
-        }
-    }
-}
+/// Base of the native World App deep link, see [`BridgeConnection::deep_link_url`]
+pub const DEEP_LINK_BASE_URL: &str = "worldapp://verify";
 
 /// Controls the format of the connect URL returned by `IDKitRequestWrapper`
 #[cfg(feature = "ffi")]
@@ -74,6 +58,148 @@ pub enum ConnectUrlMode {
     AppClip,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Connect URL
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A parsed connect URL, as built by [`BridgeConnection::connect_url`]
+///
+/// Formats back to the same URL with [`ToString::to_string`]. The URL carries
+/// the key that decrypts World App's response, so the `Debug` output redacts it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConnectUrl {
+    /// Everything before the query, e.g. `https://world.org/verify` or
+    /// [`DEEP_LINK_BASE_URL`]
+    pub base_url: String,
+    /// Bridge request ID (`i`)
+    pub request_id: String,
+    /// AES-256 key of the request (`k`)
+    pub key: Vec<u8>,
+    /// Deep-link callback URL (`return_to`)
+    pub return_to: Option<String>,
+    /// Bridge other than [`BridgeUrl::DEFAULT`] (`b`)
+    pub bridge_url: Option<BridgeUrl>,
+    /// Canonical invite code (`c`), only in invite-code mode
+    pub invite_code: Option<String>,
+    /// App ID (`a`), only in invite-code mode
+    pub app_id: Option<String>,
+}
+
+impl ConnectUrl {
+    /// Parses a connect URL or `worldapp://` deep link
+    ///
+    /// Without an app ID in the URL, `b` is validated like a staging app's
+    /// bridge URL, since only the creator of the request knows the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if the URL is not a World ID
+    /// connect URL, its request ID is not URL-safe, its key is not 32 bytes
+    /// of base64, or its bridge URL is invalid
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| Error::InvalidConfiguration(format!("Invalid connect URL: {reason}"));
+
+        let (base_url, _) = url
+            .split_once('?')
+            .ok_or_else(|| invalid("missing query"))?;
+        let mut params: std::collections::HashMap<String, String> = url::Url::parse(url)
+            .map_err(|_| invalid("not a URL"))?
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+
+        if params.remove("t").as_deref() != Some("wld") {
+            return Err(invalid("missing t=wld"));
+        }
+        let request_id = params
+            .remove("i")
+            .filter(|id| {
+                !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+            .ok_or_else(|| invalid("missing or malformed request ID"))?;
+        // A literal `+` in hand-edited URLs decodes as a space
+        let key = params
+            .remove("k")
+            .and_then(|key| base64_decode(&key.replace(' ', "+")).ok())
+            .filter(|key| key.len() == 32)
+            .ok_or_else(|| invalid("missing or malformed key"))?;
+        let app_id = params.remove("a");
+        let bridge_url = params
+            .remove("b")
+            .map(|bridge_url| match &app_id {
+                Some(app_id) => BridgeUrl::new(bridge_url, &AppId::new(app_id)?),
+                None => BridgeUrl::validated(bridge_url, true),
+            })
+            .transpose()?;
+
+        Ok(Self {
+            base_url: base_url.to_string(),
+            request_id,
+            key,
+            return_to: params.remove("return_to"),
+            bridge_url,
+            invite_code: params.remove("c"),
+            app_id,
+        })
+    }
+
+    /// Returns the URL with the key replaced by `k=REDACTED`
+    #[must_use]
+    pub fn redacted(&self) -> String {
+        let mut url = String::new();
+        // Writing to a `String` cannot fail
+        let _ = self.write(&mut url, true);
+        url
+    }
+
+    fn write(&self, out: &mut impl std::fmt::Write, redact_key: bool) -> std::fmt::Result {
+        write!(out, "{}?t=wld&i={}&k=", self.base_url, self.request_id)?;
+        if redact_key {
+            out.write_str(SecretBytes::REDACTED)?;
+        } else {
+            out.write_str(&urlencoding::encode(&base64_encode(&self.key)))?;
+        }
+        if let Some(return_to) = &self.return_to {
+            write!(out, "&return_to={}", urlencoding::encode(return_to))?;
+        }
+        if let Some(bridge_url) = &self.bridge_url {
+            write!(out, "&b={}", urlencoding::encode(bridge_url.as_str()))?;
+        }
+        // The canonical code is Crockford Base32, so it's URL-safe by
+        // construction; the app id is too, but is encoded defensively in case
+        // the format ever loosens.
+        if let Some(invite_code) = &self.invite_code {
+            write!(out, "&c={invite_code}")?;
+        }
+        if let Some(app_id) = &self.app_id {
+            write!(out, "&a={}", urlencoding::encode(app_id))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ConnectUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, false)
+    }
+}
+
+// Leaves out the key, which decrypts World App's response
+impl std::fmt::Debug for ConnectUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectUrl")
+            .field("base_url", &self.base_url)
+            .field("request_id", &self.request_id)
+            .field("key", &SecretBytes::REDACTED)
+            .field("return_to", &self.return_to)
+            .field("bridge_url", &self.bridge_url)
+            .field("invite_code", &self.invite_code)
+            .field("app_id", &self.app_id)
+            .finish()
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Request Kind (internal)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -81,28 +207,49 @@ pub enum ConnectUrlMode {
 /// Enum representing the type of proof request
 pub enum RequestKind {
     /// Uniqueness proof
-    Uniqueness { action: String },
+    Uniqueness { action: crate::Action },
+    /// Uniqueness proof for an action already encoded as a field element
+    ///
+    /// Not compatible with legacy (World ID 3.0) proofs, which hash the action string.
+    UniquenessField { action: FieldElement },
     /// Create a new session (returns `session_id` in response)
     CreateSession,
     /// Prove ownership of an existing session
     ProveSession { session_id: String },
 }
 
+impl RequestKind {
+    /// Returns the action as reported in the result, if this is a uniqueness proof.
+    ///
+    /// Field-element actions are reported in their `0x`-prefixed hex form.
+    fn action(&self) -> Option<String> {
+        match self {
+            Self::Uniqueness { action } => Some(action.to_string()),
+            Self::UniquenessField { action } => Some(action.to_string()),
+            Self::CreateSession | Self::ProveSession { .. } => None,
+        }
+    }
+}
+
 /// Bridge request payload sent to initialize a session
+///
+/// Borrows what it can from the [`BridgeConnectionParams`] it is built from,
+/// so building it copies no caller strings. The protocol [`ProofRequest`]
+/// owns its items, so signals are still copied into it once.
 #[derive(Debug, Serialize)]
 #[allow(dead_code)]
-struct BridgeRequestPayload {
+struct BridgeRequestPayload<'a> {
     // ---------------------------------------------------
     // -- Legacy fields for World ID 3.0 compatibility --
     // ---------------------------------------------------
     /// Application ID from the Developer Portal
-    app_id: String,
+    app_id: &'a str,
 
     /// Normalized SDK package identifier for request attribution.
-    package_name: String,
+    package_name: &'a str,
 
     /// SDK package version for request attribution.
-    package_version: String,
+    package_version: &'a str,
 
     /// Action ID from the Developer Portal.
     /// Session flows serialize this as an empty string for mobile bridge compatibility.
@@ -111,7 +258,7 @@ struct BridgeRequestPayload {
 
     /// Optional action description
     #[serde(skip_serializing_if = "Option::is_none")]
-    action_description: Option<String>,
+    action_description: Option<&'a str>,
 
     /// Hashed signal for legacy compatibility (World App 3.0)
     /// Derived from the request with the max verification level credential type
@@ -138,7 +285,7 @@ struct BridgeRequestPayload {
     /// Optional identity attribute filters for identity-attestation presets.
     /// Only present for World ID 4.0 identity check requests.
     #[serde(skip_serializing_if = "Option::is_none")]
-    identity_attributes: Option<Vec<IdentityAttribute>>,
+    identity_attributes: Option<&'a [IdentityAttribute]>,
 
     /// Whether to accept legacy (v3) proofs as fallback.
     /// - `true`: Accept both v3 and v4 proofs. Use during migration.
@@ -153,17 +300,119 @@ struct BridgeRequestPayload {
 
     /// Optional deep-link callback URL for the World App to redirect to after verification.
     #[serde(skip_serializing_if = "Option::is_none", rename = "return_to_url")]
-    return_to: Option<String>,
+    return_to: Option<&'a str>,
+
+    /// Optional BCP 47 language hint (`en` or `en-US`) so World App can match
+    /// its confirmation screen to a localized `action_description`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locale: Option<&'a str>,
 }
 
 /// Encrypted payload sent to/from the bridge
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedPayload {
     /// Base64-encoded initialization vector
     pub iv: String,
 
     /// Base64-encoded encrypted payload
     pub payload: String,
+
+    /// Whether the plaintext was deflated before encryption. Left out of the
+    /// JSON when false, so small payloads keep their original shape.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub compressed: bool,
+}
+
+/// Request payloads longer than this many bytes are deflated before
+/// encryption, if the request opts in with
+/// [`BridgeConnectionParams::compress_payload`]
+pub const PAYLOAD_COMPRESSION_THRESHOLD: usize = 2048;
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+const fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Deflates a serialized payload longer than [`PAYLOAD_COMPRESSION_THRESHOLD`].
+///
+/// Returns `None` for short payloads and for payloads that would not shrink,
+/// which are sent as is.
+fn compress_payload(plaintext: &[u8]) -> Option<Vec<u8>> {
+    if plaintext.len() <= PAYLOAD_COMPRESSION_THRESHOLD {
+        return None;
+    }
+    Some(miniz_oxide::deflate::compress_to_vec(plaintext, 6))
+        .filter(|compressed| compressed.len() < plaintext.len())
+}
+
+/// Inflates a payload deflated by [`compress_payload`].
+///
+/// Stops after `limit + 1` bytes, so a size check on the output still rejects
+/// oversized payloads without inflating them in full. Returns `None` if the
+/// data is not valid deflate.
+fn decompress_payload(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    match miniz_oxide::inflate::decompress_to_vec_with_limit(data, limit.saturating_add(1)) {
+        Ok(plaintext) => Some(plaintext),
+        Err(error) if error.status == miniz_oxide::inflate::TINFLStatus::HasMoreOutput => {
+            Some(error.output)
+        }
+        Err(_) => None,
+    }
+}
+
+/// Largest payload [`SupportBundle::decrypt_and_validate`] inflates, well
+/// above any request payload the SDK builds
+const MAX_SUPPORT_BUNDLE_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Bytes reserved for the fields of a serialized request payload other than
+/// its signals
+const PAYLOAD_JSON_HEADROOM: usize = 8 * 1024;
+
+/// Serializes, optionally compresses and encrypts a request payload for the
+/// bridge
+///
+/// Byte signals can make payloads megabytes large, so the JSON is written
+/// once and encrypted in place rather than copied into a new buffer.
+fn seal_request_payload(
+    payload: &BridgeRequestPayload<'_>,
+    key: &[u8],
+    nonce: &[u8],
+    compress: bool,
+) -> Result<EncryptedPayload> {
+    // Hex doubles each signal byte. Sizing the buffer up front, with room for
+    // the other fields and the GCM tag, avoids regrowing it while writing.
+    let signal_bytes: usize = payload
+        .proof_request
+        .iter()
+        .flat_map(|proof_request| &proof_request.requests)
+        .filter_map(|item| item.signal.as_ref())
+        .map(Vec::len)
+        .sum();
+    let mut plaintext = Vec::with_capacity(2 * signal_bytes + PAYLOAD_JSON_HEADROOM);
+    serde_json::to_writer(&mut plaintext, payload)?;
+    trace_event!(
+        plaintext_bytes = plaintext.len(),
+        "request payload serialized"
+    );
+
+    let deflated = if compress {
+        compress_payload(&plaintext)
+    } else {
+        None
+    };
+    let (mut buffer, compressed) = deflated.map_or((plaintext, false), |deflated| (deflated, true));
+    encrypt_in_place(key, nonce, &mut buffer)?;
+
+    trace_event!(
+        compressed,
+        ciphertext_bytes = buffer.len(),
+        "request payload encrypted"
+    );
+    Ok(EncryptedPayload {
+        iv: base64_encode(nonce),
+        payload: base64_encode(&buffer),
+        compressed,
+    })
 }
 
 /// Body sent on `POST /request`. `request_id` is optional: when present, the
@@ -175,6 +424,8 @@ pub struct EncryptedPayload {
 struct CreateRequestBody {
     iv: String,
     payload: String,
+    #[serde(skip_serializing_if = "is_false")]
+    compressed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     request_id: Option<String>,
 }
@@ -187,19 +438,65 @@ struct BridgeCreateResponse {
     request_id: String,
 }
 
-/// TTL applied to an invite-code request by the bridge (`EXPIRE_AFTER_SECONDS`
-/// in wallet-bridge). Used to compute `code_expires_at` locally since the
-/// bridge no longer returns it on `POST /request`.
-const INVITE_CODE_TTL_SECONDS: u64 = 900;
+/// Header carrying the client-side idempotency key on `POST /request`
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Idempotency key and key material of an earlier creation attempt
+struct EarlierAttempt {
+    idempotency_key: String,
+    key_bytes: [u8; 32],
+    nonce_bytes: [u8; 12],
+}
+
+impl EarlierAttempt {
+    /// Reads the attempt saved in `previous`, which must belong to `app_id`
+    fn from_resumable(previous: &ResumableConnection, app_id: &AppId) -> Result<Self> {
+        let idempotency_key = previous
+            .idempotency_key
+            .clone()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| {
+                Error::InvalidConfiguration("idempotency_key must not be empty".to_string())
+            })?;
+        if previous.app_id != app_id.as_str() {
+            return Err(Error::InvalidConfiguration(
+                "The earlier attempt belongs to another app".to_string(),
+            ));
+        }
+        let key_bytes: [u8; 32] = base64_decode(&previous.key)?
+            .try_into()
+            .map_err(|_| Error::InvalidConfiguration("Invalid resumable key length".into()))?;
+        let nonce_bytes: [u8; 12] = base64_decode(&previous.encrypted_request.iv)?
+            .try_into()
+            .map_err(|_| Error::InvalidConfiguration("Invalid resumable IV length".into()))?;
+        Ok(Self {
+            idempotency_key,
+            key_bytes,
+            nonce_bytes,
+        })
+    }
+}
+
+/// How long the bridge keeps a request after it was created
+/// (`EXPIRE_AFTER_SECONDS` in wallet-bridge)
+///
+/// The bridge applies the same TTL to every request and takes no per-request
+/// value; see [`BridgeConnection::with_session_ttl`].
+pub const BRIDGE_REQUEST_TTL: std::time::Duration = std::time::Duration::from_mins(15);
+
+/// TTL applied to an invite-code request by the bridge. Used to compute
+/// `code_expires_at` locally since the bridge no longer returns it on
+/// `POST /request`.
+const INVITE_CODE_TTL_SECONDS: u64 = BRIDGE_REQUEST_TTL.as_secs();
 
 /// Response from bridge when polling for status
-#[derive(Debug, Deserialize)]
-struct BridgePollResponse {
-    /// Current status
-    status: String,
+#[derive(Debug, Clone, Deserialize)]
+pub struct BridgePollResponse {
+    /// Current status: `initialized`, `retrieved` or `completed`
+    pub status: String,
 
     /// Encrypted response (only present when status is "completed")
-    response: Option<EncryptedPayload>,
+    pub response: Option<EncryptedPayload>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -215,22 +512,332 @@ pub struct BridgeDebugReport {
     pub response_payload: Option<String>,
 }
 
+/// Current [`SupportBundle`] schema version
+pub const SUPPORT_BUNDLE_SCHEMA_VERSION: u32 = 1;
+
+/// Placeholder written over signal values in a [`SupportBundle`]
+const REDACTED: &str = "[redacted]";
+
+/// Whether [`BridgeConnection::support_bundle`] embeds the AES key
+///
+/// The key decrypts both the request and World App's response, so it must
+/// only be shared with the support team over a trusted channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SupportBundleKey {
+    /// Leave the key out of the bundle
+    #[default]
+    Omit,
+    /// Embed the raw AES key, marking the bundle with `WARNING_CONTAINS_KEY`
+    IncludeSensitiveKey,
+}
+
+/// Self-contained snapshot of a bridge request for support tickets
+///
+/// Carries the ciphertext exactly as sent to the bridge together with a
+/// redacted plaintext copy, so a failed World App parse can be reproduced.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupportBundle {
+    pub schema_version: u32,
+    pub sdk_version: String,
+    pub request_id: String,
+    pub bridge_url: String,
+    /// Base64-encoded IV used to encrypt the request
+    pub iv: String,
+    /// Base64-encoded encrypted request payload
+    pub ciphertext: String,
+    /// Whether the request payload was deflated before encryption
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub compressed: bool,
+    /// Base64-encoded AES key, only with [`SupportBundleKey::IncludeSensitiveKey`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(
+        default,
+        rename = "WARNING_CONTAINS_KEY",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub key_warning: Option<String>,
+    /// Request payload with signal values replaced by `[redacted]`
+    pub redacted_payload: serde_json::Value,
+}
+
+// `key` is opt-in for support tickets; keep it out of `{:?}` all the same
+impl std::fmt::Debug for SupportBundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SupportBundle")
+            .field("schema_version", &self.schema_version)
+            .field("sdk_version", &self.sdk_version)
+            .field("request_id", &self.request_id)
+            .field("bridge_url", &self.bridge_url)
+            .field("iv", &self.iv)
+            .field("ciphertext", &self.ciphertext)
+            .field("compressed", &self.compressed)
+            .field("key", &self.key.as_ref().map(|_| SecretBytes::REDACTED))
+            .field("key_warning", &self.key_warning)
+            .field("redacted_payload", &self.redacted_payload)
+            .finish()
+    }
+}
+
+impl SupportBundle {
+    /// Serializes the bundle to a single JSON blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Parses a bundle produced by [`SupportBundle::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not a valid bundle
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Decrypts the ciphertext and checks it matches the redacted payload.
+    ///
+    /// Returns the full plaintext request payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema version is unknown, the bundle carries no
+    /// key, decryption fails, or the plaintext disagrees with `redacted_payload`
+    pub fn decrypt_and_validate(&self) -> Result<serde_json::Value> {
+        if self.schema_version != SUPPORT_BUNDLE_SCHEMA_VERSION {
+            return Err(Error::InvalidConfiguration(format!(
+                "Unsupported support bundle schema version {}",
+                self.schema_version
+            )));
+        }
+        let Some(key) = &self.key else {
+            return Err(Error::InvalidConfiguration(
+                "Support bundle does not include the key".to_string(),
+            ));
+        };
+
+        let key = base64_decode(key)?;
+        let iv = base64_decode(&self.iv)?;
+        let ciphertext = base64_decode(&self.ciphertext)?;
+        let mut plaintext = decrypt(&key, &iv, &ciphertext)?;
+        if self.compressed {
+            plaintext = decompress_payload(&plaintext, MAX_SUPPORT_BUNDLE_PAYLOAD_BYTES)
+                .ok_or_else(|| {
+                    Error::InvalidConfiguration(
+                        "Support bundle payload is not valid deflate data".to_string(),
+                    )
+                })?;
+            if plaintext.len() > MAX_SUPPORT_BUNDLE_PAYLOAD_BYTES {
+                return Err(Error::InvalidConfiguration(
+                    "Support bundle payload is too large".to_string(),
+                ));
+            }
+        }
+        let payload: serde_json::Value = serde_json::from_slice(&plaintext)?;
+
+        if redact_signals(payload.clone()) != self.redacted_payload {
+            return Err(Error::InvalidConfiguration(
+                "Decrypted payload does not match the redacted payload".to_string(),
+            ));
+        }
+
+        Ok(payload)
+    }
+}
+
+/// A confirmed proof along with the constraint branch it satisfied
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+pub struct VerifiedResult {
+    /// The proof World App returned
+    pub proof: IDKitResult,
+    /// `None` for requests without constraints or with only legacy proofs
+    pub branch: Option<SatisfiedBranch>,
+}
+
+/// A confirmed proof with the credential it carries and when it arrived
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationOutcome {
+    /// The proof World App returned and the constraint branch it satisfied
+    pub result: VerifiedResult,
+    /// The credential the proof was made with
+    ///
+    /// The satisfied branch's credential when there is one, otherwise the
+    /// first World ID 4.0 credential in the proof. `None` for legacy-only
+    /// proofs.
+    pub credential: Option<CredentialType>,
+    /// Whether the request asked World App for a user-presence check
+    pub user_presence_required: bool,
+    /// How long the wait for the proof took
+    pub waited: std::time::Duration,
+    /// Unix seconds when the proof was received, `None` if the system clock
+    /// could not be read
+    pub completed_at: Option<u64>,
+}
+
+/// A status change seen while polling a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTransition {
+    /// The status the request moved to
+    pub status: Status,
+    /// Unix seconds when a poll first saw `status`
+    pub observed_at: u64,
+}
+
+/// Serializable state for resuming a [`BridgeConnection`] in another process
+///
+/// **Sensitive:** this contains the AES key that decrypts World App's
+/// response, which includes the user's proofs. Store it like a credential,
+/// e.g. encrypted at rest and never logged.
+#[derive(Serialize, Deserialize)]
+pub struct ResumableConnection {
+    bridge_url: String,
+    /// Base64-encoded AES-256 key
+    key: String,
+    request_id: String,
+    app_id: String,
+    signal_hashes: CachedSignalHashes,
+    action: Option<String>,
+    action_description: Option<String>,
+    nonce: String,
+    override_connect_base_url: Option<String>,
+    return_to: Option<String>,
+    environment: Environment,
+    require_user_presence: bool,
+    /// Missing from states saved before constraints were kept
+    #[serde(default)]
+    constraints: Option<ConstraintNode>,
+    /// Missing from states saved before legacy verification levels were kept
+    #[serde(default)]
+    legacy_verification_level: Option<VerificationLevel>,
+    /// Missing from states saved before idempotency keys were sent
+    #[serde(default)]
+    idempotency_key: Option<String>,
+    request_payload: serde_json::Value,
+    encrypted_request: EncryptedPayload,
+    acknowledged: bool,
+    max_response_bytes: usize,
+    /// Missing from states saved before creation times were kept
+    #[serde(default)]
+    created_at: Option<u64>,
+    /// Missing from states saved before session TTLs were kept
+    #[serde(default)]
+    session_ttl_ms: Option<u64>,
+    invite_code: Option<String>,
+    code_expires_at: Option<u64>,
+}
+
+// Leaves out the key and the plaintext request, whose signals may identify the user
+impl std::fmt::Debug for ResumableConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResumableConnection")
+            .field("request_id", &self.request_id)
+            .field("bridge_url", &self.bridge_url)
+            .field("app_id", &self.app_id)
+            .field("key", &SecretBytes::REDACTED)
+            .field("environment", &self.environment)
+            .field("acknowledged", &self.acknowledged)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Key of every connection rebuilt by [`BridgeConnection::from_fixture`], so
+/// tests can encrypt responses for it
+#[cfg(any(test, feature = "test-utils"))]
+pub const FIXTURE_KEY: [u8; 32] = [0x5a; 32];
+
+/// IV the request of a fixture connection is encrypted with
+#[cfg(any(test, feature = "test-utils"))]
+const FIXTURE_IV: [u8; 12] = [0xa5; 12];
+
+/// The inputs a [`BridgeConnection`] was built from, for bug reports and
+/// regression tests, see [`BridgeConnection::fixture`]
+///
+/// Unlike [`ResumableConnection`] it holds no key, so it is safe to attach
+/// to an issue. The plaintext request still carries the request's signals.
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionFixture {
+    /// Application ID from the Developer Portal
+    pub app_id: String,
+    /// Bridge the request was sent to
+    pub bridge_url: String,
+    /// Request ID the bridge assigned
+    pub request_id: String,
+    /// Action identifier (only for uniqueness proofs)
+    pub action: Option<String>,
+    /// Action description, if one was set
+    pub action_description: Option<String>,
+    /// Nonce from the RP context
+    pub nonce: String,
+    /// Environment the request targets
+    pub environment: Environment,
+    /// Deep-link callback URL, if one was set
+    pub return_to: Option<String>,
+    /// Whether the request itself requires a user presence check
+    pub require_user_presence: bool,
+    /// Constraints, and with them the credential requests
+    pub constraints: Option<ConstraintNode>,
+    /// Signal hashes of the credential requests, keyed by credential type
+    pub signal_hashes: std::collections::HashMap<String, String>,
+    /// Hash of the legacy World ID 3.0 signal
+    pub legacy_signal_hash: Option<String>,
+    /// Plaintext request payload as sent to World App
+    pub request_payload: serde_json::Value,
+}
+
+/// Replaces every `signal`/`signal_hash` value, which may identify the user.
+fn redact_signals(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| {
+                let value = if matches!(key.as_str(), "signal" | "signal_hash") {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else {
+                    redact_signals(value)
+                };
+                (key, value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.into_iter().map(redact_signals).collect(),
+        other => other,
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Internal Bridge Response Types (for deserialization)
 // ─────────────────────────────────────────────────────────────────────────────
 
 impl BridgeResponseV1 {
-    fn into_response_item(self, signal_hash: String) -> ResponseItem {
-        ResponseItem::V3 {
+    fn into_response_item(self, signal_hash: String) -> Result<ResponseItem> {
+        if self.is_placeholder() {
+            return Err(Error::InvalidProof(format!(
+                "Received placeholder {} proof with all-zero values",
+                self.verification_level
+            )));
+        }
+
+        Ok(ResponseItem::V3 {
             identifier: self.verification_level.to_string(),
             signal_hash,
             proof: self.proof,
             merkle_root: self.merkle_root,
             nullifier: self.nullifier_hash,
-        }
+        })
     }
 }
 
+/// Returns true if a legacy proof at `returned` level meets the `requested`
+/// level. Orb proofs meet every level.
+fn legacy_level_accepts(requested: VerificationLevel, returned: &str) -> bool {
+    VerificationLevel::from_str(returned)
+        .is_ok_and(|returned| requested.to_credentials().contains(&returned))
+}
+
 impl ResponseItem {
     /// Converts a protocol `ResponseItem` to an `IDKit` `ResponseItem`.
     ///
@@ -239,15 +846,23 @@ impl ResponseItem {
         item: world_id_primitives::ResponseItem,
         signal_hash: Option<String>,
     ) -> Result<Self> {
+        let proof: Vec<String> = item
+            .proof
+            .as_ethereum_representation()
+            .map(|v| v.to_string())
+            .to_vec();
+        if is_default_proof(&proof) {
+            return Err(Error::InvalidProof(format!(
+                "Response item '{}' carries the default all-zero proof",
+                item.identifier
+            )));
+        }
+
         if let Some(session_nullifier) = item.session_nullifier {
             Ok(Self::Session {
                 identifier: item.identifier,
                 signal_hash,
-                proof: item
-                    .proof
-                    .as_ethereum_representation()
-                    .map(|v| v.to_string())
-                    .to_vec(),
+                proof,
                 session_nullifier: vec![
                     session_nullifier.nullifier().to_string(),
                     session_nullifier.action().to_string(),
@@ -259,11 +874,7 @@ impl ResponseItem {
             Ok(Self::V4 {
                 identifier: item.identifier,
                 signal_hash,
-                proof: item
-                    .proof
-                    .as_ethereum_representation()
-                    .map(|v| v.to_string())
-                    .to_vec(),
+                proof,
                 nullifier: nullifier.inner.to_string(),
                 issuer_schema_id: item.issuer_schema_id,
                 expires_at_min: item.expires_at_min,
@@ -376,6 +987,9 @@ enum BridgeResponse {
         integrity_bundle: Option<IntegrityBundle>,
     },
 
+    /// Multi-credential legacy sent as a bare array of v3 proofs
+    LegacyResponseList(Vec<BridgeResponseV1>),
+
     /// V1 legacy (old World App 3.0, single credential)
     ResponseV1 {
         #[serde(flatten)]
@@ -392,6 +1006,7 @@ struct BridgeResponseV2 {
     /// The response id references request id
     id: String,
     /// Version corresponding to request version
+    #[serde(deserialize_with = "crate::types::deserialize_request_version")]
     version: RequestVersion,
     /// Optional session identifier for session proofs
     #[serde(default)]
@@ -430,8 +1045,13 @@ fn user_presence_failure_status(
 }
 
 /// Status of a verification request
+///
+/// Serializes as `{"type": "confirmed", "result": {...}}`, the shape the JS
+/// SDK returns from `pollForStatus`. A failure carries its `AppError` code
+/// under `error`.
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "StatusJson", from = "StatusJson")]
 pub enum Status {
     /// Waiting for World App to retrieve the request
     WaitingForConnection,
@@ -447,41 +1067,394 @@ pub enum Status {
     Failed(AppError),
 }
 
-/// Parameters for creating a `BridgeConnection`
-pub struct BridgeConnectionParams {
-    pub app_id: AppId,
-    pub package_name: String,
-    pub package_version: String,
-    pub kind: RequestKind,
-    /// Present only on in World ID 4.0 requests
-    pub constraints: Option<ConstraintNode>,
-    pub rp_context: RpContext,
-    pub action_description: Option<String>,
-    pub legacy_verification_level: VerificationLevel,
-    pub legacy_signal: String,
-    pub bridge_url: Option<BridgeUrl>,
-    pub allow_legacy_proofs: bool,
-    pub require_user_presence: bool,
-    /// Optional connect base URL override; takes precedence over the environment mapping.
-    pub override_connect_base_url: Option<String>,
-    /// Optional deep-link callback URL appended as `return_to` on the connector URL
-    pub return_to: Option<String>,
-    /// Optional environment override (defaults to Production when not specified)
-    pub environment: Option<Environment>,
-    /// Present only on World ID 4.0 requests created from `IdentityCheck` presets
-    pub identity_attributes: Option<Vec<IdentityAttribute>>,
+impl Status {
+    /// Names the state without its payload, for diagnostics
+    #[cfg(feature = "tracing")]
+    const fn label(&self) -> &'static str {
+        match self {
+            Self::WaitingForConnection => "waiting_for_connection",
+            Self::AwaitingConfirmation => "awaiting_confirmation",
+            Self::Confirmed(_) => "confirmed",
+            Self::Failed(_) => "failed",
+        }
+    }
+
+    /// Returns `true` once the request can no longer change state.
+    #[must_use]
+    pub const fn is_terminal(&self) -> bool {
+        matches!(self, Self::Confirmed(_) | Self::Failed(_))
+    }
 }
 
-/// A helper struct to cache the signal hashes of a request
-/// used to include the signal hash back in the response for convenience, since the bridge does not return them
-#[derive(serde::Serialize)]
-pub struct CachedSignalHashes {
-    /// Signal hashes computed from constraints, keyed by identifier
-    pub(crate) signal_hashes: std::collections::HashMap<String, String>,
-    /// Legacy signal hash for v3 compatibility
+/// Tagged wire form of [`Status`]; the payloads need field names to sit
+/// next to `type`
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StatusJson {
+    WaitingForConnection,
+    AwaitingConfirmation,
+    Confirmed { result: IDKitResult },
+    Failed { error: AppError },
+}
+
+impl From<Status> for StatusJson {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::WaitingForConnection => Self::WaitingForConnection,
+            Status::AwaitingConfirmation => Self::AwaitingConfirmation,
+            Status::Confirmed(result) => Self::Confirmed { result },
+            Status::Failed(error) => Self::Failed { error },
+        }
+    }
+}
+
+impl From<StatusJson> for Status {
+    fn from(status: StatusJson) -> Self {
+        match status {
+            StatusJson::WaitingForConnection => Self::WaitingForConnection,
+            StatusJson::AwaitingConfirmation => Self::AwaitingConfirmation,
+            StatusJson::Confirmed { result } => Self::Confirmed(result),
+            StatusJson::Failed { error } => Self::Failed(error),
+        }
+    }
+}
+
+/// Retry policy for the `POST /request` call that creates a connection
+///
+/// Connection errors, timeouts and 502/503/504 responses are retried with
+/// exponential backoff; any other failure is returned immediately. WASM builds
+/// have no timer to back off with and always make a single attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreateRetryConfig {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each further attempt
+    pub initial_backoff: std::time::Duration,
+}
+
+impl Default for CreateRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: std::time::Duration::from_millis(250),
+        }
+    }
+}
+
+impl CreateRetryConfig {
+    /// Makes a single attempt
+    #[must_use]
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.max_attempts == 0 {
+            return Err(Error::InvalidConfiguration(
+                "Create retries need at least one attempt".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// How each poll of `/response/:id` waits for the next status
+#[cfg(feature = "bridge")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PollMode {
+    /// Return straight away and sleep between polls
+    #[default]
+    Interval,
+    /// Ask the bridge to hold each poll for up to `max_wait` until the
+    /// status changes
+    ///
+    /// If the bridge answers early without a status change, polling falls
+    /// back to [`PollMode::Interval`] for the rest of the wait.
+    LongPoll {
+        /// Longest time the bridge may hold a poll, in whole seconds
+        max_wait: std::time::Duration,
+    },
+}
+
+#[cfg(feature = "bridge")]
+impl PollMode {
+    /// Returns the long-poll wait, if any
+    const fn max_wait(self) -> Option<std::time::Duration> {
+        match self {
+            Self::Interval => None,
+            Self::LongPoll { max_wait } => Some(max_wait),
+        }
+    }
+
+    fn validate(self, timeout: Option<std::time::Duration>) -> Result<()> {
+        let Some(max_wait) = self.max_wait() else {
+            return Ok(());
+        };
+        if max_wait < std::time::Duration::from_secs(1) {
+            return Err(Error::InvalidConfiguration(
+                "Long-poll wait must be at least one second".to_string(),
+            ));
+        }
+        if timeout.is_some_and(|timeout| max_wait >= timeout) {
+            return Err(Error::InvalidConfiguration(
+                "Long-poll wait must be shorter than the timeout".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Query parameter asking the bridge to hold a poll, in seconds
+const LONG_POLL_QUERY_PARAM: &str = "wait";
+
+/// Extra time a long poll gets over its wait before the request times out
+const LONG_POLL_TIMEOUT_MARGIN: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Per-request timeout for a long poll held for up to `max_wait`
+const fn long_poll_timeout(max_wait: std::time::Duration) -> std::time::Duration {
+    max_wait.saturating_add(LONG_POLL_TIMEOUT_MARGIN)
+}
+
+/// Builds `/response/:id`, adding the long-poll wait when there is one
+fn response_url(
+    bridge_url: &BridgeUrl,
+    request_id: &str,
+    max_wait: Option<std::time::Duration>,
+) -> Result<url::Url> {
+    let mut url = bridge_url.join(&format!("/response/{request_id}"))?;
+    if let Some(max_wait) = max_wait {
+        url.query_pairs_mut()
+            .append_pair(LONG_POLL_QUERY_PARAM, &max_wait.as_secs().to_string());
+    }
+    Ok(url)
+}
+
+/// Polling options for [`BridgeConnection::wait_for_proof_with_config`]
+#[cfg(feature = "bridge")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollConfig {
+    /// Delay between polls until World App retrieves the request
+    pub interval: std::time::Duration,
+    /// Delay between polls once World App has retrieved the request; see
+    /// [`PollSchedule`]. Never slower than `interval`: a shorter `interval`
+    /// also caps this one.
+    pub awaiting_confirmation_interval: std::time::Duration,
+    /// Overall time limit before giving up with `Error::Timeout`
+    pub timeout: std::time::Duration,
+    /// Whether polls return straight away or are held by the bridge
+    pub mode: PollMode,
+}
+
+/// Delay between polls in each phase of a request
+///
+/// Once World App has retrieved the request, the user is looking at the
+/// prompt and their confirmation is the latency-critical moment, so polling
+/// speeds up. Every poll is a request to the bridge: a shorter
+/// `awaiting_confirmation` delay trades bridge load for latency, but it only
+/// applies for the few seconds a user takes to confirm, while the slower
+/// `waiting_for_connection` delay covers the often much longer wait for a
+/// scan.
+#[cfg(feature = "bridge")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollSchedule {
+    /// Delay while World App has not retrieved the request yet
+    pub waiting_for_connection: std::time::Duration,
+    /// Delay once World App has retrieved the request
+    pub awaiting_confirmation: std::time::Duration,
+}
+
+#[cfg(feature = "bridge")]
+impl PollSchedule {
+    /// Polls every `interval` in every phase
+    #[must_use]
+    pub const fn fixed(interval: std::time::Duration) -> Self {
+        Self {
+            waiting_for_connection: interval,
+            awaiting_confirmation: interval,
+        }
+    }
+
+    /// Returns the delay before the poll that follows `status`
+    #[must_use]
+    pub const fn interval_after(&self, status: &Status) -> std::time::Duration {
+        match status {
+            Status::AwaitingConfirmation => self.awaiting_confirmation,
+            _ => self.waiting_for_connection,
+        }
+    }
+}
+
+/// Suggested wait before retrying once an inclusion proof is still pending
+pub const INCLUSION_PENDING_RETRY_HINT: std::time::Duration = std::time::Duration::from_mins(30);
+
+#[cfg(feature = "bridge")]
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: std::time::Duration::from_secs(1),
+            awaiting_confirmation_interval: std::time::Duration::from_millis(750),
+            timeout: std::time::Duration::from_mins(15),
+            mode: PollMode::Interval,
+        }
+    }
+}
+
+#[cfg(feature = "bridge")]
+impl PollConfig {
+    /// Long-polls the bridge, letting it hold each poll for up to `max_wait`.
+    /// `interval` still paces polls if the bridge does not support it.
+    #[must_use]
+    pub const fn long_poll(mut self, max_wait: std::time::Duration) -> Self {
+        self.mode = PollMode::LongPoll { max_wait };
+        self
+    }
+
+    /// Returns the per-phase poll delays
+    #[must_use]
+    pub fn schedule(&self) -> PollSchedule {
+        PollSchedule {
+            waiting_for_connection: self.interval,
+            awaiting_confirmation: self.awaiting_confirmation_interval.min(self.interval),
+        }
+    }
+
+    /// Checks that both intervals are non-zero, that `interval` is shorter
+    /// than `timeout`, and that a long-poll wait is at least a second and
+    /// shorter than `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if an interval or wait is invalid
+    pub fn validate(&self) -> Result<()> {
+        if self.interval.is_zero() || self.awaiting_confirmation_interval.is_zero() {
+            return Err(Error::InvalidConfiguration(
+                "Poll interval must be greater than zero".to_string(),
+            ));
+        }
+        if self.interval >= self.timeout {
+            return Err(Error::InvalidConfiguration(
+                "Poll interval must be shorter than the timeout".to_string(),
+            ));
+        }
+        self.mode.validate(Some(self.timeout))
+    }
+}
+
+/// Polling options for [`BridgeConnection::status_stream`]
+#[cfg(feature = "bridge")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusStreamOptions {
+    /// Delay before the first re-poll after a state change
+    pub initial_interval: std::time::Duration,
+    /// Upper bound for the delay while the state stays unchanged
+    pub max_interval: std::time::Duration,
+    /// Overall time limit, after which the stream yields `Error::Timeout` and ends
+    pub deadline: Option<std::time::Duration>,
+    /// Whether polls return straight away or are held by the bridge
+    pub mode: PollMode,
+}
+
+#[cfg(feature = "bridge")]
+impl Default for StatusStreamOptions {
+    fn default() -> Self {
+        Self {
+            initial_interval: std::time::Duration::from_secs(1),
+            max_interval: std::time::Duration::from_secs(5),
+            deadline: None,
+            mode: PollMode::Interval,
+        }
+    }
+}
+
+/// Parameters for creating a `BridgeConnection`
+// The flags are independent per-request switches, not a state machine
+#[allow(clippy::struct_excessive_bools)]
+pub struct BridgeConnectionParams {
+    pub app_id: AppId,
+    pub package_name: String,
+    pub package_version: String,
+    pub kind: RequestKind,
+    /// Present only on in World ID 4.0 requests
+    pub constraints: Option<ConstraintNode>,
+    pub rp_context: RpContext,
+    pub action_description: Option<String>,
+    pub legacy_verification_level: VerificationLevel,
+    pub legacy_signal: String,
+    pub bridge_url: Option<BridgeUrl>,
+    pub allow_legacy_proofs: bool,
+    pub require_user_presence: bool,
+    /// Collapse single-child `any`/`all` wrappers before the constraints are
+    /// sent, see [`ConstraintNode::normalized`]
+    pub normalize_constraints: bool,
+    /// Deflate serialized payloads longer than
+    /// [`PAYLOAD_COMPRESSION_THRESHOLD`] before encryption. Only World App
+    /// versions that read the `compressed` flag can open such requests.
+    pub compress_payload: bool,
+    /// Optional connect base URL override; takes precedence over the environment mapping.
+    pub override_connect_base_url: Option<String>,
+    /// Optional deep-link callback URL appended as `return_to` on the connector URL
+    pub return_to: Option<String>,
+    /// Custom URL schemes `return_to` may use besides `https`, e.g. `myapp`
+    /// for `myapp://callback`
+    pub return_to_schemes: Vec<String>,
+    /// Optional environment override (defaults to Production when not specified)
+    pub environment: Option<Environment>,
+    /// Present only on World ID 4.0 requests created from `IdentityCheck` presets
+    pub identity_attributes: Option<Vec<IdentityAttribute>>,
+    /// Optional language hint for World App, such as `en` or `en-US`
+    pub locale: Option<String>,
+}
+
+impl BridgeConnectionParams {
+    /// Returns the constraints in the form they are sent to World App
+    fn outgoing_constraints(&self) -> Option<ConstraintNode> {
+        let constraints = self.constraints.clone()?;
+        Some(if self.normalize_constraints {
+            constraints.normalized()
+        } else {
+            constraints
+        })
+    }
+
+    /// Hints the language World App should use, such as `en` or `en-US`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` unless `locale` is a two-letter
+    /// lowercase language code with an optional two-letter uppercase region
+    pub fn with_locale(mut self, locale: &str) -> Result<Self> {
+        validate_locale(locale)?;
+        self.locale = Some(locale.to_string());
+        Ok(self)
+    }
+
+    /// Returns true if the request or any of its credential leaves requires
+    /// a user presence check
+    #[must_use]
+    pub fn requires_user_presence(&self) -> bool {
+        self.require_user_presence
+            || self
+                .constraints
+                .as_ref()
+                .is_some_and(ConstraintNode::requires_user_presence)
+    }
+}
+
+/// A helper struct to cache the signal hashes of a request
+/// used to include the signal hash back in the response for convenience, since the bridge does not return them
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct CachedSignalHashes {
+    /// Signal hashes computed from constraints, keyed by identifier
+    pub(crate) signal_hashes: std::collections::HashMap<String, String>,
+    /// Legacy signal hash for v3 compatibility
     /// **Note** In legacy bridge requests requesting VerificationLevel.Device
-    /// could return an ORB ZKP we still want to return that signal hash
-    pub(crate) legacy_signal_hash: String,
+    /// could return an ORB ZKP we still want to return that signal hash.
+    /// Unset on connections resumed from only their ID and key.
+    pub(crate) legacy_signal_hash: Option<String>,
 }
 
 impl CachedSignalHashes {
@@ -497,19 +1470,20 @@ impl CachedSignalHashes {
         // Compute signal hashes for V4 constraints (when present)
         if let Some(ref constraints) = params.constraints {
             for item in constraints.collect_items() {
-                if let Some(ref signal) = item.signal {
-                    let hash = crate::crypto::hash_signal(signal);
+                if let Some(hash) = crate::crypto::compute_signal_hash(item.signal.as_ref()) {
                     signal_hashes.insert(item.credential_type.to_string(), hash);
                 }
             }
         }
 
+        // World ID 3.0 hashes `""` when no signal is set, so the legacy hash is
+        // always computed
         let legacy_signal_hash =
             crate::crypto::hash_signal(&Signal::from_string(params.legacy_signal.clone()));
 
         Self {
             signal_hashes,
-            legacy_signal_hash,
+            legacy_signal_hash: Some(legacy_signal_hash),
         }
     }
 
@@ -520,26 +1494,41 @@ impl CachedSignalHashes {
     }
 
     /// Gets the legacy signal hash (used for V3 response matching)
-    #[must_use]
-    pub fn legacy(&self) -> String {
-        self.legacy_signal_hash.clone()
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if the legacy signal is unknown,
+    /// i.e. the connection was resumed from only its ID and key
+    pub fn legacy(&self) -> Result<String> {
+        self.legacy_signal_hash.clone().ok_or_else(|| {
+            Error::InvalidConfiguration(
+                "Legacy results cannot be matched to their signal on a connection resumed \
+                 from only its ID and key; resume from a ResumableConnection instead"
+                    .to_string(),
+            )
+        })
     }
 }
 
 /// A World ID verification connection to the bridge
 ///
 /// Manages the verification flow with World App via the bridge.
+// The flags are independent per-connection switches, not a state machine
+#[allow(clippy::struct_excessive_bools)]
 pub struct BridgeConnection {
     bridge_url: BridgeUrl,
     #[cfg(feature = "native-crypto")]
     key: CryptoKey,
-    key_bytes: Vec<u8>,
+    key_bytes: SecretBytes,
     request_id: String,
     /// Application ID, kept on the struct so `connect_url()` can stamp it
     /// onto the connector URL as the `a` query param (consumed by the
     /// `world.org/verify` landing page in invite-code mode).
     app_id: String,
-    client: reqwest::Client,
+    /// Carries the encrypted traffic to the bridge
+    transport: Arc<dyn BridgeTransport>,
+    /// Wall clock for status history, expiry and completion times
+    clock: Arc<dyn Clock>,
     /// Cached signal hashes of the request
     /// Used to add the `signal_hash` back to the idkit response for convenience
     cached_signal_hashes: CachedSignalHashes,
@@ -557,10 +1546,46 @@ pub struct BridgeConnection {
     environment: Environment,
     /// Whether a successful response must prove user presence was completed.
     require_user_presence: bool,
+    /// Constraints the request was created with, used to check responses and
+    /// report which branch a response satisfied
+    constraints: Option<ConstraintNode>,
+    /// Legacy verification level the request accepts, `None` when unknown,
+    /// e.g. for a resumed request
+    legacy_verification_level: Option<VerificationLevel>,
+    /// Whether polls check confirmed results against what the request accepts
+    check_constraints: bool,
     /// Decrypted request payload used to create this bridge request.
     request_payload: serde_json::Value,
+    /// Encrypted request payload exactly as sent to the bridge.
+    encrypted_request: EncryptedPayload,
     /// Latest decrypted bridge response payload captured during polling.
     latest_bridge_payload: Mutex<Option<String>>,
+    /// Whether the bridge has already acknowledged deletion of the response.
+    acknowledged: AtomicBool,
+    /// Whether waits acknowledge the response once they return a proof
+    auto_ack: AtomicBool,
+    /// Whether a poll has returned a terminal status
+    finished: AtomicBool,
+    /// Whether dropping the connection before a terminal status deletes the
+    /// request from the bridge
+    cancel_on_drop: bool,
+    /// Whether only the first confirmed poll hands out the proof
+    single_consumption: bool,
+    /// Whether a confirmed poll has handed out the proof
+    consumed: AtomicBool,
+    /// Latest proof a poll confirmed, kept for [`BridgeConnection::peek_proof`]
+    latest_proof: Mutex<Option<IDKitResult>>,
+    /// Status changes seen by polls, oldest first
+    status_history: Mutex<Vec<StatusTransition>>,
+    /// Idempotency key sent when creating the request (None in invite-code mode)
+    idempotency_key: Option<String>,
+    /// Largest bridge response body, and decrypted payload, accepted when polling.
+    max_response_bytes: usize,
+    /// Unix seconds when the bridge request was created, `None` when unknown,
+    /// e.g. for a request rebuilt with [`BridgeConnection::resume`]
+    created_at: Option<u64>,
+    /// How long after `created_at` the request is treated as expired
+    session_ttl: std::time::Duration,
     // ─── Invite-code mode (WDP-73) — None for the legacy URL/QR path ────────
     /// Canonical 6-char Crockford Base32 invite code shown to the user.
     pub(crate) invite_code: Option<String>,
@@ -568,6 +1593,100 @@ pub struct BridgeConnection {
     pub(crate) code_expires_at: Option<u64>,
 }
 
+// Hand-written so the key material can never end up in logs
+impl std::fmt::Debug for BridgeConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BridgeConnection")
+            .field("request_id", &self.request_id)
+            .field("bridge_url", &self.bridge_url.as_str())
+            .field("environment", &self.environment)
+            .field("acknowledged", &self.acknowledged.load(Ordering::Acquire))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "bridge")]
+impl Drop for BridgeConnection {
+    fn drop(&mut self) {
+        if !self.cancel_on_drop
+            || self.finished.load(Ordering::Acquire)
+            || self.acknowledged.swap(true, Ordering::AcqRel)
+        {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            trace_event!(request_id = %self.request_id, "no runtime to cancel dropped request");
+            return;
+        };
+
+        let transport = Arc::clone(&self.transport);
+        let bridge_url = self.bridge_url.clone();
+        let request_id = std::mem::take(&mut self.request_id);
+        // A runtime that is shutting down drops the task instead of panicking
+        runtime.spawn(async move {
+            let _ = transport.acknowledge(&bridge_url, &request_id).await;
+            trace_event!(request_id = %request_id, "dropped request cancelled");
+        });
+    }
+}
+
+/// Checks that `return_to` is an absolute URL using `https` or one of the
+/// custom app schemes in `schemes`.
+///
+/// Blank values are allowed and treated as absent, matching `connect_url`.
+fn validate_return_to(return_to: &str, schemes: &[String]) -> Result<()> {
+    let return_to = return_to.trim();
+    if return_to.is_empty() {
+        return Ok(());
+    }
+
+    let parsed = url::Url::parse(return_to).map_err(|_| {
+        Error::InvalidConfiguration("return_to must be an absolute URL".to_string())
+    })?;
+    let scheme = parsed.scheme();
+    let allowed = scheme == "https"
+        || schemes
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(scheme));
+    if !allowed {
+        return Err(Error::InvalidConfiguration(format!(
+            "return_to must use https or one of the configured return_to_schemes, got {scheme}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks that `locale` is a two-letter language code with an optional
+/// two-letter region, e.g. `en` or `en-US`.
+fn validate_locale(locale: &str) -> Result<()> {
+    let (language, region) = match locale.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (locale, None),
+    };
+    let valid = language.len() == 2
+        && language.bytes().all(|b| b.is_ascii_lowercase())
+        && region.is_none_or(|region| {
+            region.len() == 2 && region.bytes().all(|b| b.is_ascii_uppercase())
+        });
+    if !valid {
+        return Err(Error::InvalidConfiguration(format!(
+            "locale must be a BCP 47 tag of the form `ll` or `ll-RR` (e.g. `en` or `en-US`), got {locale:?}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Formats a request's `created_at` as the RFC 3339 timestamp native
+/// transport payloads carry.
+fn native_timestamp(created_at: u64) -> Result<String> {
+    time::OffsetDateTime::from_unix_timestamp(created_at.cast_signed())
+        .map_err(|_| Error::InvalidConfiguration("Invalid timestamp".to_string()))?
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(|_| Error::InvalidConfiguration("Failed to format timestamp".to_string()))
+}
+
 /// Builds a `BridgeRequestPayload` from params without connecting to the bridge.
 ///
 /// This is the single source of truth for payload construction, used by both
@@ -579,10 +1698,16 @@ pub struct BridgeConnection {
 fn build_request_payload(
     params: &BridgeConnectionParams,
     native: bool,
-) -> Result<BridgeRequestPayload> {
+) -> Result<BridgeRequestPayload<'_>> {
     if let Some(ref constraints) = params.constraints {
         constraints.validate()?;
     }
+    if let Some(ref return_to) = params.return_to {
+        validate_return_to(return_to, &params.return_to_schemes)?;
+    }
+    if let Some(ref locale) = params.locale {
+        validate_locale(locale)?;
+    }
 
     // Extract action and session_id from kind
     // TODO: Clean up session_id handling once the SDK surface can carry the
@@ -590,8 +1715,26 @@ fn build_request_payload(
     // string form at this bridge boundary.
     let (proof_type, action_fe, session_id_fe, action_str) = match &params.kind {
         RequestKind::Uniqueness { action } => {
-            let fe = FieldElement::from_arbitrary_raw_bytes(action.as_bytes());
-            (ProofType::Uniqueness, Some(fe), None, Some(action.clone()))
+            let fe = crate::crypto::encode_action_field(action.as_str());
+            (
+                ProofType::Uniqueness,
+                Some(fe),
+                None,
+                Some(action.to_string()),
+            )
+        }
+        RequestKind::UniquenessField { action } => {
+            if params.allow_legacy_proofs {
+                return Err(Error::InvalidConfiguration(
+                    "A field-element action cannot be used with legacy proofs".to_string(),
+                ));
+            }
+            (
+                ProofType::Uniqueness,
+                Some(*action),
+                None,
+                Some(action.to_string()),
+            )
         }
         RequestKind::CreateSession => (ProofType::CreateSession, None, None, Some(String::new())),
         RequestKind::ProveSession { session_id } => {
@@ -606,8 +1749,7 @@ fn build_request_payload(
 
     // Build ProofRequest only when v4 constraints are present
     let proof_request = params
-        .constraints
-        .as_ref()
+        .outgoing_constraints()
         .map(|constraints| -> Result<ProofRequest> {
             let (request_items, constraint_expr) = constraints.to_protocol_top_level()?;
             let signature = alloy_primitives::Signature::from_str(&params.rp_context.signature)
@@ -634,6 +1776,10 @@ fn build_request_payload(
             })
         })
         .transpose()?;
+    if let Some(proof_request) = &proof_request {
+        proof_request.validate_timestamps()?;
+        proof_request.validate_constraint_references()?;
+    }
 
     // Legacy v3 payloads send only the signal hash. `Signal::from_string`
     // intentionally mirrors JS `hashSignal`, including decoding valid `0x`
@@ -641,35 +1787,27 @@ fn build_request_payload(
     let legacy_signal_hash =
         crate::crypto::hash_signal(&Signal::from_string(params.legacy_signal.clone()));
 
-    let timestamp = if native {
-        Some(
-            time::OffsetDateTime::from_unix_timestamp(params.rp_context.created_at.cast_signed())
-                .map_err(|_| Error::InvalidConfiguration("Invalid timestamp".to_string()))?
-                .format(&time::format_description::well_known::Rfc3339)
-                .map_err(|_| {
-                    Error::InvalidConfiguration("Failed to format timestamp".to_string())
-                })?,
-        )
-    } else {
-        None
-    };
+    let timestamp = native
+        .then(|| native_timestamp(params.rp_context.created_at))
+        .transpose()?;
 
     // Prepare the payload
     let payload = BridgeRequestPayload {
-        app_id: params.app_id.as_str().to_string(),
-        package_name: params.package_name.clone(),
-        package_version: params.package_version.clone(),
+        app_id: params.app_id.as_str(),
+        package_name: &params.package_name,
+        package_version: &params.package_version,
         action: action_str,
-        action_description: params.action_description.clone(),
+        action_description: params.action_description.as_deref(),
         proof_request,
-        identity_attributes: params.identity_attributes.clone(),
+        identity_attributes: params.identity_attributes.as_deref(),
         verification_level: params.legacy_verification_level,
         signal: legacy_signal_hash,
         timestamp,
         allow_legacy_proofs: params.allow_legacy_proofs,
-        require_user_presence: params.require_user_presence,
+        require_user_presence: params.requires_user_presence(),
         environment: params.environment.unwrap_or_default(),
-        return_to: params.return_to.clone(),
+        return_to: params.return_to.as_deref(),
+        locale: params.locale.as_deref(),
     };
 
     Ok(payload)
@@ -734,97 +1872,730 @@ pub fn build_native_v1_payload(params: &BridgeConnectionParams) -> Result<serde_
     Ok(payload)
 }
 
-impl BridgeConnection {
-    /// Creates a new bridge connection
-    ///
-    /// # Arguments
-    ///
-    /// * `params` - Parameters for creating the connection
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the request cannot be created or the bridge call fails
-    #[allow(dead_code, clippy::too_many_lines)]
-    pub(crate) async fn create(params: BridgeConnectionParams) -> Result<Self> {
-        // Generate encryption key and IV
-        #[cfg(feature = "native-crypto")]
-        let (key_bytes, nonce_bytes) = crate::crypto::generate_key()?;
+/// Cap on the body of a bridge's `GET /health` answer
+#[cfg(feature = "bridge")]
+const MAX_HEALTH_BODY_BYTES: usize = 4 * 1024;
 
-        #[cfg(feature = "native-crypto")]
-        let key = CryptoKey::new(key_bytes, nonce_bytes);
+/// Outcome of a successful [`health_check`]
+#[cfg(feature = "bridge")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeHealth {
+    /// Time from sending the check to reading the full answer
+    pub latency: std::time::Duration,
+    /// Version the bridge reported, if any
+    pub version: Option<String>,
+}
 
-        #[cfg(not(feature = "native-crypto"))]
-        let (key_bytes, nonce_bytes) = crate::crypto::generate_key()?;
+/// Checks that `bridge_url` points to a reachable bridge
+///
+/// Calls `GET /health`, which a bridge answers with a success status and
+/// either an empty body, a plain `ok`, or a JSON object whose optional
+/// `version` field is reported back. Useful to validate a self-hosted bridge
+/// before building a user-facing flow on it.
+///
+/// # Errors
+///
+/// Returns [`Error::BridgeUnhealthy`] if the host does not resolve, the TLS
+/// handshake fails, the host cannot be reached or is too slow, or the server
+/// answering is not a bridge. A bridge answering with a 5xx status is
+/// reported as [`Error::BridgeHttp`].
+#[cfg(feature = "bridge")]
+pub async fn health_check(
+    bridge_url: &BridgeUrl,
+    client: &reqwest::Client,
+) -> Result<BridgeHealth> {
+    let unhealthy = |kind| Error::BridgeUnhealthy { kind };
+
+    let health_url = bridge_url.join("/health")?;
+    let started = std::time::Instant::now();
+    let response = match client.get(health_url.clone()).send().await {
+        Ok(response) => response,
+        Err(err) => return Err(unhealthy(health_failure_kind(&err, &health_url).await)),
+    };
 
-        // Build the payload using the shared function (borrows params).
-        // Bridge path does not need the timestamp field.
-        let payload = build_request_payload(&params, false)?;
-        let request_payload = serde_json::to_value(&payload)?;
-        let payload_json = serde_json::to_vec(&payload)?;
+    let status = response.status();
+    if status.is_server_error() {
+        return Err(bridge_http_error(response, BridgeEndpoint::Health).await);
+    }
+    if !status.is_success() {
+        return Err(unhealthy(BridgeHealthFailureKind::NotABridge));
+    }
 
-        // Compute signal hashes before partial moves
-        let cached_signal_hashes = CachedSignalHashes::compute(&params);
+    let body = match crate::http::read_capped_body(response, MAX_HEALTH_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(Error::Http(err)) => {
+            return Err(unhealthy(health_failure_kind(&err, &health_url).await));
+        }
+        Err(_) => return Err(unhealthy(BridgeHealthFailureKind::NotABridge)),
+    };
+    let latency = started.elapsed();
 
-        // Extract bridge_url after the borrow is done
-        let bridge_url = params.bridge_url.unwrap_or_default();
+    let version = parse_health_body(&body)?;
+    Ok(BridgeHealth { latency, version })
+}
 
-        // Encrypt the payload
-        #[cfg(feature = "native-crypto")]
-        let encrypted = encrypt(&key_bytes, &nonce_bytes, &payload_json)?;
+/// Reads the version out of a `GET /health` body, failing if the body is not
+/// one a bridge sends
+#[cfg(feature = "bridge")]
+fn parse_health_body(body: &[u8]) -> Result<Option<String>> {
+    let not_a_bridge = || Error::BridgeUnhealthy {
+        kind: BridgeHealthFailureKind::NotABridge,
+    };
 
-        #[cfg(not(feature = "native-crypto"))]
-        let encrypted = encrypt(&key_bytes, &nonce_bytes, &payload_json)?;
+    let text = std::str::from_utf8(body)
+        .map_err(|_| not_a_bridge())?
+        .trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("ok") {
+        return Ok(None);
+    }
 
-        let body = CreateRequestBody {
-            iv: base64_encode(&nonce_bytes),
-            payload: base64_encode(&encrypted),
-            // URL/QR mode lets the bridge mint the request_id (UUID v4).
-            request_id: None,
-        };
+    let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(text) else {
+        return Err(not_a_bridge());
+    };
+    Ok(fields
+        .get("version")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string))
+}
 
-        // Send to bridge
-        let client = reqwest::Client::builder()
-            .user_agent(format!("idkit-core/{}", env!("CARGO_PKG_VERSION")))
-            .build()?;
-
-        let response = client
-            .post(bridge_url.join("/request")?)
-            .json(&body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::BridgeError(format!(
-                "Bridge request failed with status {}: {}",
-                status,
-                if body.is_empty() {
-                    "no error details"
-                } else {
-                    &body
-                }
-            )));
+/// Classifies a failed health check request to `url`
+///
+/// Timeouts and TLS failures are recognized from the error and its source
+/// chain. hyper-util's connector error, which would tell a resolver failure
+/// apart, is private, so a failed connection counts as a DNS failure when
+/// the host does not resolve on a second lookup.
+#[cfg(feature = "bridge")]
+async fn health_failure_kind(error: &reqwest::Error, url: &url::Url) -> BridgeHealthFailureKind {
+    if error.is_timeout() {
+        return BridgeHealthFailureKind::Timeout;
+    }
+    if has_tls_error(error) {
+        return BridgeHealthFailureKind::Tls;
+    }
+    if error.is_connect() && !resolves(url).await {
+        return BridgeHealthFailureKind::Dns;
+    }
+    BridgeHealthFailureKind::Connect
+}
+
+/// Returns true if a `rustls::Error` is somewhere in the source chain of `error`
+#[cfg(feature = "bridge")]
+fn has_tls_error(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        // TLS failures arrive as `rustls::Error`s wrapped in nested IO errors
+        let mut io_error = err.downcast_ref::<std::io::Error>();
+        while let Some(inner) = io_error.and_then(std::io::Error::get_ref) {
+            if inner.is::<rustls::Error>() {
+                return true;
+            }
+            io_error = inner.downcast_ref::<std::io::Error>();
         }
+        source = err.source();
+    }
+    false
+}
+
+/// Returns true if the host of `url` resolves to at least one address
+#[cfg(feature = "bridge")]
+async fn resolves(url: &url::Url) -> bool {
+    let (Some(url::Host::Domain(host)), Some(port)) = (url.host(), url.port_or_known_default())
+    else {
+        // IP literals need no resolution
+        return url.host().is_some();
+    };
+    tokio::net::lookup_host((host, port))
+        .await
+        .is_ok_and(|mut addrs| addrs.next().is_some())
+}
 
-        let create_response: BridgeCreateResponse = response.json().await?;
+/// Future returned by [`BridgeTransport`] methods
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a, T> =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<T>> + Send + 'a>>;
 
-        // Extract action from kind for result
-        let action = match &params.kind {
-            RequestKind::Uniqueness { action } => Some(action.clone()),
-            _ => None,
+/// Future returned by [`BridgeTransport`] methods
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a, T> =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<T>> + 'a>>;
+
+/// Carries already-encrypted traffic between a [`BridgeConnection`] and the bridge
+///
+/// Implement this to route bridge calls through infrastructure that plain
+/// `reqwest` settings cannot express, such as an authenticating egress proxy.
+/// Encryption, decryption, retries and status mapping stay in
+/// [`BridgeConnection`], so a transport only moves opaque payloads.
+///
+/// A transport should report a non-success answer from the bridge as
+/// [`Error::BridgeHttp`] with the original status code: the connection maps
+/// 404, 410 and 429 on polls to terminal statuses and retries 502 to 504 on
+/// creation.
+pub trait BridgeTransport: Send + Sync {
+    /// Stores an encrypted request on the bridge and returns its request ID
+    fn create_request<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+    ) -> TransportFuture<'a, String>;
+
+    /// Fetches the state of a request, reading at most `max_response_bytes`
+    fn poll<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+        max_response_bytes: usize,
+    ) -> TransportFuture<'a, BridgePollResponse>;
+
+    /// Fetches the state of a request like [`BridgeTransport::poll`], asking
+    /// the bridge to hold the answer for up to `max_wait` until it changes
+    ///
+    /// The default implementation polls without waiting, which makes the
+    /// connection fall back to interval polling.
+    fn long_poll<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+        max_response_bytes: usize,
+        max_wait: std::time::Duration,
+    ) -> TransportFuture<'a, BridgePollResponse> {
+        let _ = max_wait;
+        self.poll(bridge_url, request_id, max_response_bytes)
+    }
+
+    /// Deletes the stored response of a request
+    fn acknowledge<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+    ) -> TransportFuture<'a, ()>;
+
+    /// Stores an encrypted request like [`BridgeTransport::create_request`],
+    /// tagged with `idempotency_key`
+    ///
+    /// When the bridge already holds a request for the key, this returns that
+    /// request's ID instead of failing. The default implementation ignores
+    /// the key.
+    fn create_request_idempotent<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+        idempotency_key: &'a str,
+    ) -> TransportFuture<'a, String> {
+        let _ = idempotency_key;
+        self.create_request(bridge_url, payload)
+    }
+
+    /// Stores an encrypted request under the caller-chosen `request_id`, as
+    /// invite-code mode does, and returns the ID the bridge stored it under
+    ///
+    /// Fails with a 409 [`Error::BridgeHttp`] if the bridge already holds a
+    /// request with that ID. The default implementation fails, so transports
+    /// must opt in to invite codes.
+    fn create_request_with_id<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+        request_id: &'a str,
+    ) -> TransportFuture<'a, String> {
+        let _ = (bridge_url, payload, request_id);
+        Box::pin(std::future::ready(Err(Error::BridgeError(
+            "This transport does not support invite codes".to_string(),
+        ))))
+    }
+}
+
+/// Default [`BridgeTransport`], talking HTTP to the bridge with `reqwest`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport sending every bridge call through `client`
+    #[must_use]
+    pub const fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates a transport using the shared default HTTP client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default client cannot be built
+    pub fn shared() -> Result<Self> {
+        default_client().map(Self::new)
+    }
+}
+
+impl ReqwestTransport {
+    async fn post_request(
+        &self,
+        bridge_url: &BridgeUrl,
+        payload: &EncryptedPayload,
+        idempotency_key: Option<&str>,
+        request_id: Option<&str>,
+    ) -> Result<String> {
+        let body = CreateRequestBody {
+            iv: payload.iv.clone(),
+            payload: payload.payload.clone(),
+            compressed: payload.compressed,
+            // URL/QR mode lets the bridge mint the request_id (UUID v4).
+            request_id: request_id.map(str::to_string),
+        };
+        let mut request = self.client.post(bridge_url.join("/request")?).json(&body);
+        if let Some(key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        let response = request.send().await?;
+        // A repeated idempotency key answers 409 with the existing request
+        let already_exists =
+            idempotency_key.is_some() && response.status() == reqwest::StatusCode::CONFLICT;
+        if !response.status().is_success() && !already_exists {
+            return Err(bridge_http_error(response, BridgeEndpoint::Request).await);
+        }
+
+        let create_response: BridgeCreateResponse =
+            read_capped_json(response, DEFAULT_MAX_RESPONSE_BYTES).await?;
+        if already_exists {
+            trace_event!(request_id = %create_response.request_id, "adopted existing bridge request");
+        }
+        Ok(create_response.request_id)
+    }
+}
+
+impl BridgeTransport for ReqwestTransport {
+    fn create_request<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+    ) -> TransportFuture<'a, String> {
+        Box::pin(self.post_request(bridge_url, payload, None, None))
+    }
+
+    fn create_request_idempotent<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+        idempotency_key: &'a str,
+    ) -> TransportFuture<'a, String> {
+        Box::pin(self.post_request(bridge_url, payload, Some(idempotency_key), None))
+    }
+
+    fn create_request_with_id<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+        request_id: &'a str,
+    ) -> TransportFuture<'a, String> {
+        Box::pin(self.post_request(bridge_url, payload, None, Some(request_id)))
+    }
+
+    fn poll<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+        max_response_bytes: usize,
+    ) -> TransportFuture<'a, BridgePollResponse> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(response_url(bridge_url, request_id, None)?)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(bridge_http_error(response, BridgeEndpoint::Response).await);
+            }
+            read_capped_json(response, max_response_bytes).await
+        })
+    }
+
+    fn long_poll<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+        max_response_bytes: usize,
+        max_wait: std::time::Duration,
+    ) -> TransportFuture<'a, BridgePollResponse> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(response_url(bridge_url, request_id, Some(max_wait))?)
+                .timeout(long_poll_timeout(max_wait))
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(bridge_http_error(response, BridgeEndpoint::Response).await);
+            }
+            read_capped_json(response, max_response_bytes).await
+        })
+    }
+
+    fn acknowledge<'a>(
+        &'a self,
+        bridge_url: &'a BridgeUrl,
+        request_id: &'a str,
+    ) -> TransportFuture<'a, ()> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .delete(response_url(bridge_url, request_id, None)?)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(bridge_http_error(response, BridgeEndpoint::Response).await);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// How a creation request identifies the request it stores
+#[derive(Clone, Copy)]
+enum CreateKey<'a> {
+    /// The bridge picks the request ID; a repeated key adopts that request
+    Idempotency(&'a str),
+    /// Invite-code mode picks the request ID itself
+    RequestId(&'a str),
+}
+
+/// Stores `payload` on the bridge through `transport`, retrying transient failures.
+async fn post_create_request(
+    transport: &dyn BridgeTransport,
+    bridge_url: &BridgeUrl,
+    payload: &EncryptedPayload,
+    key: CreateKey<'_>,
+    retry: CreateRetryConfig,
+) -> Result<String> {
+    let max_attempts = if cfg!(feature = "bridge") {
+        retry.max_attempts
+    } else {
+        1
+    };
+    let mut backoff = retry.initial_backoff;
+    let mut attempt = 1;
+
+    loop {
+        let created = match key {
+            CreateKey::Idempotency(idempotency_key) => {
+                transport
+                    .create_request_idempotent(bridge_url, payload, idempotency_key)
+                    .await
+            }
+            CreateKey::RequestId(request_id) => {
+                transport
+                    .create_request_with_id(bridge_url, payload, request_id)
+                    .await
+            }
+        };
+        let error = match created {
+            Ok(request_id) => return Ok(request_id),
+            Err(error) if is_transient_create_error(&error) => error,
+            Err(error) => return Err(map_timeout(error)),
+        };
+
+        if attempt >= max_attempts {
+            return Err(map_timeout(error));
+        }
+        trace_event!(
+            attempt,
+            http_status = ?error.bridge_status(),
+            "retrying bridge request creation"
+        );
+        attempt += 1;
+
+        #[cfg(feature = "bridge")]
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+}
+
+/// Returns true for creation failures worth another attempt: bridge gateway
+/// errors and connection problems
+fn is_transient_create_error(error: &Error) -> bool {
+    match error {
+        Error::BridgeHttp { status, .. } => matches!(status, 502..=504),
+        Error::Http(error) => is_transient_send_error(error),
+        Error::Timeout => true,
+        _ => false,
+    }
+}
+
+/// Reports an HTTP client timeout as `Error::Timeout`
+fn map_timeout(error: Error) -> Error {
+    match error {
+        Error::Http(error) if error.is_timeout() => Error::Timeout,
+        error => error,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_transient_send_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_transient_send_error(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+}
+
+/// Creates a [`BridgeConnection`] with any combination of creation options
+///
+/// Start from [`BridgeConnection::builder`]. Without options, [`Self::create`]
+/// behaves like [`BridgeConnection::create`].
+#[must_use]
+pub struct BridgeConnectionBuilder {
+    params: BridgeConnectionParams,
+    client: Option<reqwest::Client>,
+    transport: Option<Box<dyn BridgeTransport>>,
+    retry: CreateRetryConfig,
+    earlier_attempt: Option<Result<EarlierAttempt>>,
+    #[cfg(feature = "bridge")]
+    health_check: bool,
+    precheck: Option<crate::portal::PrecheckOptions>,
+    invite_code: bool,
+}
+
+impl BridgeConnectionBuilder {
+    /// Sends every bridge call of the connection through `client`
+    ///
+    /// Lets callers that create many connections share their own connection
+    /// pool, proxy and TLS settings. A [`Self::transport`] takes precedence
+    /// for bridge calls, but the health check still uses this client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sends every bridge call of the connection through `transport`
+    ///
+    /// `params.bridge_url` is still passed to it and embedded in the connect
+    /// URL for World App.
+    pub fn transport(mut self, transport: Box<dyn BridgeTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Retries transient failures of the creation request according to
+    /// `retry`; every attempt sends the same encrypted payload
+    pub const fn retry(mut self, retry: CreateRetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Creates the request under the idempotency key and key material of an
+    /// earlier attempt, see [`BridgeConnection::idempotency_key`]
+    ///
+    /// If the bridge already holds a request for the key, the connection
+    /// adopts that request's ID instead of creating a second live request.
+    /// The request is encrypted with the key and IV of `previous`, so
+    /// responses to an adopted request stay decryptable.
+    ///
+    /// [`Self::create`] fails with `Error::InvalidConfiguration` if `previous`
+    /// has no idempotency key, belongs to another app or holds invalid key
+    /// material.
+    pub fn earlier_attempt(mut self, previous: &ResumableConnection) -> Self {
+        self.earlier_attempt = Some(EarlierAttempt::from_resumable(
+            previous,
+            &self.params.app_id,
+        ));
+        self
+    }
+
+    /// Makes the request a uniqueness proof for an action already encoded as
+    /// a field element, overriding `params.kind`
+    ///
+    /// String actions are encoded with [`crate::crypto::encode_action_field`], so
+    /// passing its output here produces the same proof request. Creation
+    /// fails if `params.allow_legacy_proofs` is set.
+    pub fn action_field(mut self, action: FieldElement) -> Self {
+        self.params.kind = RequestKind::UniquenessField { action };
+        self
+    }
+
+    /// Checks with [`health_check`] that `params.bridge_url`, or the default
+    /// bridge, is reachable before creating the request
+    ///
+    /// The check goes through the [`Self::client`], or the shared client.
+    #[cfg(feature = "bridge")]
+    pub const fn health_check(mut self) -> Self {
+        self.health_check = true;
+        self
+    }
+
+    /// Checks with the Developer Portal that the request's action exists
+    /// before creating the request
+    ///
+    /// Session requests and field-element actions have no portal action and
+    /// skip the check. See [`crate::portal::PrecheckOptions::best_effort`] for
+    /// how portal outages are handled.
+    pub fn precheck(mut self, precheck: crate::portal::PrecheckOptions) -> Self {
+        self.precheck = Some(precheck);
+        self
+    }
+
+    /// Creates the request in invite-code mode, see
+    /// [`BridgeConnection::create_for_invite_code`]
+    ///
+    /// The transport must support [`BridgeTransport::create_request_with_id`].
+    pub const fn invite_code(mut self) -> Self {
+        self.invite_code = true;
+        self
+    }
+
+    /// Runs the requested checks, then creates the bridge request
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`health_check`] if the bridge is not healthy,
+    /// `Error::InvalidConfiguration` if the precheck finds the action missing,
+    /// the earlier attempt is invalid or is combined with an invite code, or
+    /// an error if the request cannot be created or the bridge call still
+    /// fails after the last attempt
+    pub async fn create(self) -> Result<BridgeConnection> {
+        let Self {
+            params,
+            client,
+            transport,
+            retry,
+            earlier_attempt,
+            #[cfg(feature = "bridge")]
+                health_check: check_health,
+            precheck,
+            invite_code,
+        } = self;
+        if invite_code && earlier_attempt.is_some() {
+            return Err(Error::InvalidConfiguration(
+                "Invite-code requests cannot resume an earlier attempt".to_string(),
+            ));
+        }
+        let earlier_attempt = earlier_attempt.transpose()?;
+
+        #[cfg(feature = "bridge")]
+        if check_health {
+            let bridge_url = params.bridge_url.clone().unwrap_or_default();
+            let client = match &client {
+                Some(client) => client.clone(),
+                None => default_client()?,
+            };
+            health_check(&bridge_url, &client).await?;
+        }
+        if let Some(precheck) = &precheck {
+            if let RequestKind::Uniqueness { action } = &params.kind {
+                crate::portal::ensure_action_exists(&params.app_id, action.as_str(), precheck)
+                    .await?;
+            }
+        }
+
+        let transport = match (transport, client) {
+            (Some(transport), _) => transport,
+            (None, Some(client)) => Box::new(ReqwestTransport::new(client)),
+            (None, None) => Box::new(ReqwestTransport::shared()?),
+        };
+        if invite_code {
+            BridgeConnection::create_invite_code_via(params, transport, retry).await
+        } else {
+            BridgeConnection::create_via(params, transport, retry, earlier_attempt).await
+        }
+    }
+}
+
+impl BridgeConnection {
+    /// Creates a new bridge connection using the shared default HTTP client
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be created or the bridge call fails
+    pub async fn create(params: BridgeConnectionParams) -> Result<Self> {
+        Self::builder(params).create().await
+    }
+
+    /// Starts creating a bridge connection with further options, such as a
+    /// custom client or transport, retries or prechecks
+    pub fn builder(params: BridgeConnectionParams) -> BridgeConnectionBuilder {
+        BridgeConnectionBuilder {
+            params,
+            client: None,
+            transport: None,
+            retry: CreateRetryConfig::default(),
+            earlier_attempt: None,
+            #[cfg(feature = "bridge")]
+            health_check: false,
+            precheck: None,
+            invite_code: false,
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "idkit.create", skip_all, fields(app_id = %params.app_id))
+    )]
+    async fn create_via(
+        params: BridgeConnectionParams,
+        transport: Box<dyn BridgeTransport>,
+        retry: CreateRetryConfig,
+        earlier_attempt: Option<EarlierAttempt>,
+    ) -> Result<Self> {
+        retry.validate()?;
+        // The key is shared by every attempt, so a retry after a lost
+        // response adopts the request the bridge already stored. Adopted
+        // requests were encrypted with the same key material.
+        let (idempotency_key, key_bytes, nonce_bytes) = if let Some(attempt) = earlier_attempt {
+            (
+                attempt.idempotency_key,
+                attempt.key_bytes,
+                attempt.nonce_bytes,
+            )
+        } else {
+            let (key_bytes, nonce_bytes) = crate::crypto::generate_key()?;
+            (uuid::Uuid::new_v4().to_string(), key_bytes, nonce_bytes)
         };
 
+        #[cfg(feature = "native-crypto")]
+        let key = CryptoKey::new(key_bytes, nonce_bytes);
+
+        // Build the payload using the shared function (borrows params).
+        // Bridge path does not need the timestamp field.
+        let payload = build_request_payload(&params, false)?;
+        let request_payload = serde_json::to_value(&payload)?;
+        let encrypted_request =
+            seal_request_payload(&payload, &key_bytes, &nonce_bytes, params.compress_payload)?;
+
+        // Compute signal hashes before partial moves
+        let cached_signal_hashes = CachedSignalHashes::compute(&params);
+        let require_user_presence = params.requires_user_presence();
+
+        // Extract bridge_url after the borrow is done
+        let bridge_url = params.bridge_url.unwrap_or_default();
+
+        // Send to bridge
+        let request_id = post_create_request(
+            transport.as_ref(),
+            &bridge_url,
+            &encrypted_request,
+            CreateKey::Idempotency(&idempotency_key),
+            retry,
+        )
+        .await?;
+        trace_event!(request_id = %request_id, "bridge request created");
+
+        // Extract action from kind for result
+        let action = params.kind.action();
+
         let app_id = params.app_id.as_str().to_string();
 
         Ok(Self {
             bridge_url,
             #[cfg(feature = "native-crypto")]
             key,
-            key_bytes: key_bytes.to_vec(),
-            request_id: create_response.request_id,
+            key_bytes: SecretBytes::new(key_bytes),
+            request_id,
             app_id,
-            client,
+            transport: Arc::from(transport),
+            clock: Arc::new(SystemClock),
             cached_signal_hashes,
             action,
             action_description: params.action_description,
@@ -832,9 +2603,25 @@ impl BridgeConnection {
             override_connect_base_url: params.override_connect_base_url,
             return_to: params.return_to,
             environment: params.environment.unwrap_or_default(),
-            require_user_presence: params.require_user_presence,
+            require_user_presence,
+            legacy_verification_level: Some(params.legacy_verification_level),
+            constraints: params.constraints,
+            check_constraints: true,
             request_payload,
+            encrypted_request,
             latest_bridge_payload: Mutex::new(None),
+            acknowledged: AtomicBool::new(false),
+            auto_ack: AtomicBool::new(true),
+            finished: AtomicBool::new(false),
+            cancel_on_drop: false,
+            single_consumption: false,
+            consumed: AtomicBool::new(false),
+            latest_proof: Mutex::new(None),
+            status_history: Mutex::new(Vec::new()),
+            idempotency_key: Some(idempotency_key),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            created_at: current_unix_seconds().ok(),
+            session_ttl: BRIDGE_REQUEST_TTL,
             invite_code: None,
             code_expires_at: None,
         })
@@ -856,25 +2643,34 @@ impl BridgeConnection {
     ///
     /// Returns an error if the request cannot be created or the bridge call
     /// fails after retries.
-    #[allow(dead_code)]
-    pub(crate) async fn create_for_invite_code(params: BridgeConnectionParams) -> Result<Self> {
+    pub async fn create_for_invite_code(params: BridgeConnectionParams) -> Result<Self> {
+        Self::builder(params).invite_code().create().await
+    }
+
+    async fn create_invite_code_via(
+        params: BridgeConnectionParams,
+        transport: Box<dyn BridgeTransport>,
+        retry: CreateRetryConfig,
+    ) -> Result<Self> {
         const MAX_ATTEMPTS: u8 = 2;
 
+        retry.validate()?;
+        let transport: Arc<dyn BridgeTransport> = Arc::from(transport);
         // Silent retry: a single collision is statistically expected zero
         // times across the lifetime of a healthy deployment. If we ever burn
         // both attempts, the BridgeError surfaces with enough detail for the
         // caller's log infrastructure to flag it.
         for attempt in 1..=MAX_ATTEMPTS {
-            match try_create_invite_code_request(&params).await {
+            match try_create_invite_code_request(&params, &transport, retry).await {
                 Ok(connection) => return Ok(connection),
-                Err(CreateCodeError::Conflict) if attempt < MAX_ATTEMPTS => {}
-                Err(CreateCodeError::Conflict) => {
+                Err(e) if e.bridge_status() == Some(409) && attempt < MAX_ATTEMPTS => {}
+                Err(e) if e.bridge_status() == Some(409) => {
                     return Err(Error::BridgeError(
                         "invite-code index collision after retries — bridge or entropy budget misconfigured"
                             .to_string(),
                     ));
                 }
-                Err(CreateCodeError::Other(e)) => return Err(e),
+                Err(e) => return Err(e),
             }
         }
         unreachable!("loop returns or errors on the final attempt")
@@ -890,45 +2686,115 @@ impl BridgeConnection {
     /// `c` / `a` see the original URL/QR-mode shape.
     #[must_use]
     pub fn connect_url(&self) -> String {
-        let key_b64 = base64_encode(&self.key_bytes);
-        let bridge_param = if self.bridge_url == BridgeUrl::default() {
-            String::new()
-        } else {
-            format!("&b={}", urlencoding::encode(self.bridge_url.as_str()))
-        };
-        let return_to_param = self
-            .return_to
-            .as_deref()
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
-            .map(|value| format!("&return_to={}", urlencoding::encode(value)))
-            .unwrap_or_default();
-        // Invite-code mode adds `c` (canonical code) and `a` (app id). The
-        // canonical code is Crockford Base32, so it's URL-safe by construction
-        // and doesn't need percent-encoding; the app id is too, but we encode
-        // it defensively in case the format ever loosens.
-        let invite_code_params = self
-            .invite_code
-            .as_deref()
-            .map(|code| format!("&c={}&a={}", code, urlencoding::encode(&self.app_id)))
-            .unwrap_or_default();
+        self.typed_connect_url().to_string()
+    }
+
+    /// Returns [`Self::connect_url`] as a [`ConnectUrl`]
+    #[must_use]
+    pub fn typed_connect_url(&self) -> ConnectUrl {
+        self.connect_url_with_base(self.connect_base_url())
+    }
+
+    /// Returns [`Self::connect_url`] with the key replaced by `k=REDACTED`.
+    ///
+    /// The connect URL carries the key that decrypts World App's response, so
+    /// log this form instead.
+    #[must_use]
+    pub fn connect_url_redacted(&self) -> String {
+        self.typed_connect_url().redacted()
+    }
 
-        let base_url = self
-            .override_connect_base_url
+    fn connect_base_url(&self) -> &str {
+        self.override_connect_base_url
             .as_deref()
-            .unwrap_or_else(|| self.environment.connect_base_url());
-
-        format!(
-            "{}?t=wld&i={}&k={}{}{}{}",
-            base_url,
-            self.request_id,
-            urlencoding::encode(&key_b64),
-            return_to_param,
-            bridge_param,
-            invite_code_params
+            .unwrap_or_else(|| self.environment.connect_base_url())
+    }
+
+    /// Returns the `worldapp://` deep link for this request.
+    ///
+    /// Carries the same query parameters as [`Self::connect_url`], but opens
+    /// World App directly instead of going through the browser. Only use it
+    /// when World App is known to be installed.
+    #[must_use]
+    pub fn deep_link_url(&self) -> String {
+        self.connect_url_with_base(DEEP_LINK_BASE_URL).to_string()
+    }
+
+    /// Renders [`Self::connect_url`] as an SVG QR code.
+    ///
+    /// `size` is the minimum width and height in pixels, including the quiet
+    /// zone; the image grows to a whole number of pixels per module.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if `size` is zero or the URL is
+    /// too long to fit in a QR code
+    #[cfg(feature = "qr")]
+    pub fn connect_url_qr_svg(&self, size: u32) -> Result<String> {
+        if size == 0 {
+            return Err(Error::InvalidConfiguration(
+                "QR code size must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(self
+            .connect_url_qr_code()?
+            .render::<qrcode::render::svg::Color<'_>>()
+            .min_dimensions(size, size)
+            .build())
+    }
+
+    /// Returns the QR modules for [`Self::connect_url`], row by row, with
+    /// `true` for dark modules.
+    ///
+    /// The matrix excludes the quiet zone; leave a light border of at least
+    /// four modules around it when drawing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if the URL is too long to fit in a
+    /// QR code
+    #[cfg(feature = "qr")]
+    pub fn connect_url_qr_matrix(&self) -> Result<Vec<Vec<bool>>> {
+        let code = self.connect_url_qr_code()?;
+        Ok(code
+            .to_colors()
+            .chunks(code.width())
+            .map(|row| {
+                row.iter()
+                    .map(|&color| color == qrcode::Color::Dark)
+                    .collect()
+            })
+            .collect())
+    }
+
+    #[cfg(feature = "qr")]
+    fn connect_url_qr_code(&self) -> Result<qrcode::QrCode> {
+        qrcode::QrCode::with_error_correction_level(self.connect_url(), qrcode::EcLevel::M).map_err(
+            |e| Error::InvalidConfiguration(format!("Connect URL does not fit in a QR code: {e}")),
         )
     }
 
+    /// Connect URL parameters shared by [`Self::connect_url`] and [`Self::deep_link_url`]
+    fn connect_url_with_base(&self, base_url: &str) -> ConnectUrl {
+        // Invite-code mode adds `c` (canonical code) and `a` (app id)
+        let invite_mode = self.invite_code.is_some();
+        ConnectUrl {
+            base_url: base_url.to_string(),
+            request_id: self.request_id.clone(),
+            key: self.key_bytes.expose().to_vec(),
+            return_to: self
+                .return_to
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            bridge_url: (!self.bridge_url.is_default()).then(|| self.bridge_url.clone()),
+            invite_code: self.invite_code.clone(),
+            app_id: invite_mode.then(|| self.app_id.clone()),
+        }
+    }
+
     fn store_bridge_payload(&self, payload: String) {
         *self
             .latest_bridge_payload
@@ -953,58 +2819,557 @@ impl BridgeConnection {
         }
     }
 
-    /// Polls the bridge for the current status (non-blocking)
+    /// Reads wall-clock time from `clock` instead of the system clock.
     ///
-    /// `GET /response/:id` is unauthenticated for both URL/QR and invite-code
-    /// modes. The endpoint returns AES-GCM ciphertext keyed under a secret
-    /// (the URL fragment key in QR mode, or HKDF(code, "key") in code mode)
-    /// that never reaches the bridge — encryption is the security boundary,
-    /// not endpoint auth.
+    /// Affects status history, expiry and completion times; the creation
+    /// time was already taken. Elapsed times are always measured with
+    /// [`std::time::Instant`].
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets the largest bridge response body accepted when polling.
     ///
-    /// # Errors
+    /// Defaults to [`DEFAULT_MAX_RESPONSE_BYTES`]. Larger responses fail with
+    /// `Error::BridgeError("response too large")`.
+    #[must_use]
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = limit;
+        self
+    }
+
+    /// Deletes the request from the bridge when the connection is dropped
+    /// before a poll returned a terminal status (off by default).
     ///
-    /// Returns an error if the request fails or the response is invalid
-    #[allow(clippy::too_many_lines)]
-    pub async fn poll_for_status(&self) -> Result<Status> {
-        let response = self
-            .client
-            .get(
-                self.bridge_url
-                    .join(&format!("/response/{}", self.request_id))?,
-            )
-            .send()
-            .await?;
+    /// Dropping inside a Tokio runtime spawns the `DELETE` without waiting for
+    /// it. Outside a runtime nothing is sent, so call
+    /// [`BridgeConnection::close`] instead. A connection rebuilt with
+    /// [`BridgeConnection::from_resumable`] starts with this off; turn it off
+    /// here too before handing a request to another process.
+    #[must_use]
+    pub fn with_cancel_on_drop(mut self, enabled: bool) -> Self {
+        self.cancel_on_drop = enabled;
+        self
+    }
 
-        if !response.status().is_success() {
-            return Ok(Status::Failed(AppError::ConnectionFailed));
-        }
+    /// Hands out the proof only once (off by default).
+    ///
+    /// The first poll or wait that sees the confirmed proof returns it; every
+    /// later one fails with `Error::ProofAlreadyConsumed`, even when racing
+    /// from another task. Use [`BridgeConnection::peek_proof`] for read-only
+    /// access, e.g. for logging. A connection rebuilt with
+    /// [`BridgeConnection::from_resumable`] starts unconsumed with this off.
+    #[must_use]
+    pub fn with_single_consumption(mut self, enabled: bool) -> Self {
+        self.single_consumption = enabled;
+        self
+    }
 
-        let poll_response: BridgePollResponse = response.json().await?;
+    /// Checks confirmed results against the request's constraints and legacy
+    /// verification level (on by default).
+    ///
+    /// A poll that receives credentials the request would not accept fails with
+    /// `Error::InvalidProof` instead of confirming them; see
+    /// [`BridgeConnection::verify_confirmed_proof`]. Turn this off if World App
+    /// may answer with credentials this version of the crate does not know yet.
+    #[must_use]
+    pub fn with_constraint_check(mut self, enabled: bool) -> Self {
+        self.check_constraints = enabled;
+        self
+    }
 
-        match poll_response.status.as_str() {
-            "initialized" => Ok(Status::WaitingForConnection),
-            "retrieved" => Ok(Status::AwaitingConfirmation),
-            "completed" => {
-                let Some(encrypted) = poll_response.response else {
-                    return Err(Error::UnexpectedResponse);
-                };
+    /// Treats the request as expired `ttl` after it was created (defaults to
+    /// [`BRIDGE_REQUEST_TTL`]).
+    ///
+    /// The bridge keeps every request for its own fixed TTL and takes no
+    /// per-request value, so this is only tracked locally: waits give up with
+    /// `Error::Timeout` once it passes instead of polling a request the
+    /// bridge may already have dropped. Values above the bridge's TTL are
+    /// capped to it.
+    #[must_use]
+    pub fn with_session_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.session_ttl = ttl.min(BRIDGE_REQUEST_TTL);
+        self
+    }
+
+    /// Returns the latest proof a poll confirmed, without consuming it.
+    #[must_use]
+    pub fn peek_proof(&self) -> Option<IDKitResult> {
+        self.latest_proof
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the status changes polls have seen, oldest first.
+    ///
+    /// Only changes are recorded, so repeated polls returning the same status
+    /// add nothing. The first poll always adds an entry.
+    #[must_use]
+    pub fn status_history(&self) -> Vec<StatusTransition> {
+        self.status_history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Time from the first poll that saw the request waiting until World App
+    /// retrieved it
+    ///
+    /// Returns `None` until both were observed. Resolution is one second.
+    #[must_use]
+    pub fn time_to_connection(&self) -> Option<std::time::Duration> {
+        self.time_between(
+            |status| matches!(status, Status::WaitingForConnection),
+            |status| matches!(status, Status::AwaitingConfirmation),
+        )
+    }
+
+    /// Time from World App retrieving the request until the user confirmed it
+    ///
+    /// Returns `None` until both were observed. Resolution is one second.
+    #[must_use]
+    pub fn time_to_confirmation(&self) -> Option<std::time::Duration> {
+        self.time_between(
+            |status| matches!(status, Status::AwaitingConfirmation),
+            |status| matches!(status, Status::Confirmed(_)),
+        )
+    }
+
+    fn time_between(
+        &self,
+        from: impl Fn(&Status) -> bool,
+        to: impl Fn(&Status) -> bool,
+    ) -> Option<std::time::Duration> {
+        let observed_at = |matches: &dyn Fn(&Status) -> bool| {
+            self.status_history
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .find(|entry| matches(&entry.status))
+                .map(|entry| entry.observed_at)
+        };
+        let seconds = observed_at(&to)?.checked_sub(observed_at(&from)?)?;
+        Some(std::time::Duration::from_secs(seconds))
+    }
+
+    /// Appends `status` to the history unless it repeats the latest entry
+    fn record_status(&self, status: &Status) {
+        let mut history = self
+            .status_history
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let unchanged = history.last().is_some_and(|latest| {
+            std::mem::discriminant(&latest.status) == std::mem::discriminant(status)
+        });
+        if unchanged {
+            return;
+        }
+        // A clock stepping backwards must not yield negative durations
+        let not_before = history
+            .last()
+            .map(|latest| latest.observed_at)
+            .max(self.created_at);
+        // Without a clock there is nothing meaningful to record
+        if let Some(observed_at) = self.wall_clock_now(not_before) {
+            history.push(StatusTransition {
+                status: status.clone(),
+                observed_at,
+            });
+        }
+    }
+
+    /// Deletes the request from the bridge unless a poll already returned a
+    /// terminal status or the response was acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the bridge rejects the deletion
+    pub async fn close(&self) -> Result<()> {
+        if self.finished.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        self.acknowledge().await
+    }
+
+    /// Captures the state needed to resume this connection elsewhere.
+    ///
+    /// The result contains the request key; see [`ResumableConnection`].
+    #[must_use]
+    pub fn to_resumable(&self) -> ResumableConnection {
+        ResumableConnection {
+            bridge_url: self.bridge_url.as_str().to_string(),
+            key: base64_encode(self.key_bytes.expose()),
+            request_id: self.request_id.clone(),
+            app_id: self.app_id.clone(),
+            signal_hashes: CachedSignalHashes {
+                signal_hashes: self.cached_signal_hashes.signal_hashes.clone(),
+                legacy_signal_hash: self.cached_signal_hashes.legacy_signal_hash.clone(),
+            },
+            action: self.action.clone(),
+            action_description: self.action_description.clone(),
+            nonce: self.nonce.clone(),
+            override_connect_base_url: self.override_connect_base_url.clone(),
+            return_to: self.return_to.clone(),
+            environment: self.environment,
+            require_user_presence: self.require_user_presence,
+            constraints: self.constraints.clone(),
+            legacy_verification_level: self.legacy_verification_level,
+            idempotency_key: self.idempotency_key.clone(),
+            request_payload: self.request_payload.clone(),
+            encrypted_request: self.encrypted_request.clone(),
+            acknowledged: self.acknowledged.load(Ordering::Acquire),
+            max_response_bytes: self.max_response_bytes,
+            created_at: self.created_at,
+            session_ttl_ms: u64::try_from(self.session_ttl.as_millis()).ok(),
+            invite_code: self.invite_code.clone(),
+            code_expires_at: self.code_expires_at,
+        }
+    }
+
+    /// Rebuilds a connection from [`BridgeConnection::to_resumable`] output.
+    ///
+    /// The resumed connection polls with the shared default HTTP client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge URL, app ID or key material is invalid
+    pub fn from_resumable(state: ResumableConnection) -> Result<Self> {
+        let app_id = AppId::new(&state.app_id)?;
+        let bridge_url = BridgeUrl::new(state.bridge_url, &app_id)?;
+        let key_bytes: [u8; 32] = base64_decode(&state.key)?
+            .try_into()
+            .map_err(|_| Error::InvalidConfiguration("Invalid resumable key length".into()))?;
+
+        #[cfg(feature = "native-crypto")]
+        let key = {
+            let nonce: [u8; 12] = base64_decode(&state.encrypted_request.iv)?
+                .try_into()
+                .map_err(|_| Error::InvalidConfiguration("Invalid resumable IV length".into()))?;
+            CryptoKey::new(key_bytes, nonce)
+        };
+
+        Ok(Self {
+            bridge_url,
+            #[cfg(feature = "native-crypto")]
+            key,
+            key_bytes: SecretBytes::new(key_bytes),
+            request_id: state.request_id,
+            app_id: state.app_id,
+            transport: Arc::new(ReqwestTransport::shared()?),
+            clock: Arc::new(SystemClock),
+            cached_signal_hashes: state.signal_hashes,
+            action: state.action,
+            action_description: state.action_description,
+            nonce: state.nonce,
+            override_connect_base_url: state.override_connect_base_url,
+            return_to: state.return_to,
+            environment: state.environment,
+            require_user_presence: state.require_user_presence,
+            constraints: state.constraints,
+            legacy_verification_level: state.legacy_verification_level,
+            check_constraints: true,
+            request_payload: state.request_payload,
+            encrypted_request: state.encrypted_request,
+            latest_bridge_payload: Mutex::new(None),
+            acknowledged: AtomicBool::new(state.acknowledged),
+            auto_ack: AtomicBool::new(true),
+            finished: AtomicBool::new(false),
+            cancel_on_drop: false,
+            single_consumption: false,
+            consumed: AtomicBool::new(false),
+            latest_proof: Mutex::new(None),
+            status_history: Mutex::new(Vec::new()),
+            idempotency_key: state.idempotency_key,
+            max_response_bytes: state.max_response_bytes,
+            created_at: state.created_at,
+            session_ttl: state
+                .session_ttl_ms
+                .map_or(BRIDGE_REQUEST_TTL, std::time::Duration::from_millis),
+            invite_code: state.invite_code,
+            code_expires_at: state.code_expires_at,
+        })
+    }
+
+    /// Captures the inputs this connection was built from, without its key
+    #[cfg(any(test, feature = "test-utils"))]
+    #[must_use]
+    pub fn fixture(&self) -> ConnectionFixture {
+        ConnectionFixture {
+            app_id: self.app_id.clone(),
+            bridge_url: self.bridge_url.as_str().to_string(),
+            request_id: self.request_id.clone(),
+            action: self.action.clone(),
+            action_description: self.action_description.clone(),
+            nonce: self.nonce.clone(),
+            environment: self.environment,
+            return_to: self.return_to.clone(),
+            require_user_presence: self.require_user_presence,
+            constraints: self.constraints.clone(),
+            signal_hashes: self.cached_signal_hashes.signal_hashes.clone(),
+            legacy_signal_hash: self.cached_signal_hashes.legacy_signal_hash.clone(),
+            request_payload: self.request_payload.clone(),
+        }
+    }
+
+    /// Rebuilds a connection from [`BridgeConnection::fixture`] output
+    ///
+    /// The connection uses [`FIXTURE_KEY`] and a fixed IV, so the same
+    /// fixture always yields the same encrypted request and connect URL.
+    /// Nothing is sent to the bridge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge URL or app ID is invalid
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn from_fixture(fixture: ConnectionFixture) -> Result<Self> {
+        let ciphertext = crate::crypto::encrypt(
+            &FIXTURE_KEY,
+            &FIXTURE_IV,
+            &serde_json::to_vec(&fixture.request_payload)?,
+        )?;
+
+        Self::from_resumable(ResumableConnection {
+            bridge_url: fixture.bridge_url,
+            key: base64_encode(&FIXTURE_KEY),
+            request_id: fixture.request_id,
+            app_id: fixture.app_id,
+            signal_hashes: CachedSignalHashes {
+                signal_hashes: fixture.signal_hashes,
+                legacy_signal_hash: fixture.legacy_signal_hash,
+            },
+            action: fixture.action,
+            action_description: fixture.action_description,
+            nonce: fixture.nonce,
+            override_connect_base_url: None,
+            return_to: fixture.return_to,
+            environment: fixture.environment,
+            require_user_presence: fixture.require_user_presence,
+            constraints: fixture.constraints,
+            legacy_verification_level: None,
+            idempotency_key: None,
+            request_payload: fixture.request_payload,
+            encrypted_request: EncryptedPayload {
+                iv: base64_encode(&FIXTURE_IV),
+                payload: base64_encode(&ciphertext),
+                compressed: false,
+            },
+            acknowledged: false,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            created_at: None,
+            session_ttl_ms: None,
+            invite_code: None,
+            code_expires_at: None,
+        })
+    }
+
+    /// Rebuilds a connection to an existing URL/QR mode request from only
+    /// its ID and key, without creating a new request
+    ///
+    /// For apps that lost their state after creating a request, e.g. in a
+    /// crash, but kept the ID and key. The connection can poll, acknowledge
+    /// and rebuild the connect URL the user may already be looking at. It
+    /// knows nothing else about the request, so results carry no action or
+    /// nonce, confirmed proofs are not checked against the request, and
+    /// legacy (World ID 3.0) results are rejected because their signal hash
+    /// is unknown. Prefer
+    /// [`BridgeConnection::from_resumable`] whenever the full state was saved.
+    ///
+    /// `bridge_url` defaults to the production bridge.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if `request_id` is empty or not
+    /// URL-safe or `key_bytes` is not 32 bytes long, or an error if the HTTP
+    /// client cannot be built
+    pub fn resume(
+        app_id: &AppId,
+        request_id: impl Into<String>,
+        key_bytes: Vec<u8>,
+        bridge_url: Option<BridgeUrl>,
+    ) -> Result<Self> {
+        let request_id = request_id.into();
+        if request_id.is_empty()
+            || !request_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(Error::InvalidConfiguration(format!(
+                "Invalid request ID to resume: {request_id:?}"
+            )));
+        }
+        let key_bytes: [u8; 32] = key_bytes
+            .try_into()
+            .map_err(|_| Error::InvalidConfiguration("Request key must be 32 bytes".to_string()))?;
+
+        Self::from_resumable(ResumableConnection {
+            bridge_url: bridge_url.unwrap_or_default().as_str().to_string(),
+            key: base64_encode(&key_bytes),
+            request_id,
+            app_id: app_id.as_str().to_string(),
+            signal_hashes: CachedSignalHashes {
+                signal_hashes: std::collections::HashMap::new(),
+                legacy_signal_hash: None,
+            },
+            action: None,
+            action_description: None,
+            nonce: String::new(),
+            override_connect_base_url: None,
+            return_to: None,
+            environment: Environment::default(),
+            require_user_presence: false,
+            constraints: None,
+            legacy_verification_level: None,
+            idempotency_key: None,
+            request_payload: serde_json::Value::Null,
+            // Responses carry their own IV; the request IV is never used again
+            encrypted_request: EncryptedPayload {
+                iv: base64_encode(&[0; 12]),
+                payload: String::new(),
+                compressed: false,
+            },
+            acknowledged: false,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            created_at: None,
+            session_ttl_ms: None,
+            invite_code: None,
+            code_expires_at: None,
+        })
+    }
 
-                let iv = base64_decode(&encrypted.iv)?;
-                let ciphertext = base64_decode(&encrypted.payload)?;
+    /// Builds a [`SupportBundle`] for reproducing this request.
+    ///
+    /// The key is only embedded with [`SupportBundleKey::IncludeSensitiveKey`].
+    #[must_use]
+    pub fn support_bundle(&self, key: SupportBundleKey) -> SupportBundle {
+        let (key, key_warning) = match key {
+            SupportBundleKey::Omit => (None, None),
+            SupportBundleKey::IncludeSensitiveKey => (
+                Some(base64_encode(self.key_bytes.expose())),
+                Some(
+                    "This bundle contains the request key. Share it only with World support."
+                        .to_string(),
+                ),
+            ),
+        };
+
+        SupportBundle {
+            schema_version: SUPPORT_BUNDLE_SCHEMA_VERSION,
+            sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+            request_id: self.request_id.clone(),
+            bridge_url: self.bridge_url.as_str().to_string(),
+            iv: self.encrypted_request.iv.clone(),
+            ciphertext: self.encrypted_request.payload.clone(),
+            compressed: self.encrypted_request.compressed,
+            key,
+            key_warning,
+            redacted_payload: redact_signals(self.request_payload.clone()),
+        }
+    }
 
-                // Both paths use the IV from the encrypted response (not stored nonce)
-                // because the authenticator encrypts with its own nonce
-                #[cfg(feature = "native-crypto")]
-                let plaintext = decrypt(&self.key.key, &iv, &ciphertext)?;
+    /// Polls the bridge for the current status (non-blocking)
+    ///
+    /// `GET /response/:id` is unauthenticated for both URL/QR and invite-code
+    /// modes. The endpoint returns AES-GCM ciphertext keyed under a secret
+    /// (the URL fragment key in QR mode, or HKDF(code, "key") in code mode)
+    /// that never reaches the bridge — encryption is the security boundary,
+    /// not endpoint auth.
+    ///
+    /// A 404 or 410 from the bridge means the request expired or was already
+    /// consumed, and is reported as `Status::Failed(AppError::RequestNotFound)`.
+    /// A 429 is reported as `Status::Failed(AppError::RateLimited)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response is invalid
+    pub async fn poll_for_status(&self) -> Result<Status> {
+        self.poll_for_status_with(None).await
+    }
 
-                #[cfg(not(feature = "native-crypto"))]
-                let plaintext = decrypt(&self.key_bytes, &iv, &ciphertext)?;
+    /// Polls like [`BridgeConnection::poll_for_status`], letting the bridge
+    /// hold the poll for up to `max_wait` when one is given
+    async fn poll_for_status_with(&self, max_wait: Option<std::time::Duration>) -> Result<Status> {
+        let status = self.fetch_status(max_wait).await?;
+        if let Status::Confirmed(result) = &status {
+            if self.check_constraints {
+                if let Err(err) = self.verify_confirmed_proof(result) {
+                    // The bridge has handed out its only response, so the
+                    // request cannot complete any more
+                    self.record_status(&Status::Failed(AppError::UnexpectedResponse));
+                    self.finished.store(true, Ordering::Release);
+                    return Err(err);
+                }
+            }
+        }
+        self.record_status(&status);
+        if status.is_terminal() {
+            self.finished.store(true, Ordering::Release);
+        }
+        if let Status::Confirmed(result) = &status {
+            *self
+                .latest_proof
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(result.clone());
+            if self.single_consumption && self.consumed.swap(true, Ordering::AcqRel) {
+                return Err(Error::ProofAlreadyConsumed);
+            }
+        }
+        Ok(status)
+    }
 
-                // Capture the decrypted plaintext for debugging only once the
-                // response is both decoded and decrypted successfully.
-                self.store_bridge_payload(String::from_utf8_lossy(&plaintext).into_owned());
+    #[allow(clippy::too_many_lines)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "idkit.poll",
+            skip_all,
+            fields(request_id = %self.request_id, long_poll = max_wait.is_some())
+        )
+    )]
+    async fn fetch_status(&self, max_wait: Option<std::time::Duration>) -> Result<Status> {
+        let poll = max_wait.map_or_else(
+            || {
+                self.transport
+                    .poll(&self.bridge_url, &self.request_id, self.max_response_bytes)
+            },
+            |max_wait| {
+                self.transport.long_poll(
+                    &self.bridge_url,
+                    &self.request_id,
+                    self.max_response_bytes,
+                    max_wait,
+                )
+            },
+        );
+        let poll_result = poll.await;
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &poll_result {
+            tracing::debug!(http_status = ?error.bridge_status(), "bridge poll failed");
+        }
+        let poll_response = match poll_result {
+            Ok(poll_response) => poll_response,
+            // The bridge drops requests once they expire or their response is
+            // acknowledged, so polling again can never succeed
+            Err(Error::BridgeHttp {
+                status: 404 | 410, ..
+            }) => return Ok(Status::Failed(AppError::RequestNotFound)),
+            Err(Error::BridgeHttp { status: 429, .. }) => {
+                return Ok(Status::Failed(AppError::RateLimited))
+            }
+            Err(error) => return Err(map_timeout(error)),
+        };
 
-                let bridge_response: BridgeResponse = serde_json::from_slice(&plaintext)?;
+        trace_event!(bridge_status = %poll_response.status, "bridge poll answered");
+        match poll_response.status.as_str() {
+            "initialized" => Ok(Status::WaitingForConnection),
+            "retrieved" => Ok(Status::AwaitingConfirmation),
+            "completed" => {
+                let Some(encrypted) = poll_response.response else {
+                    return Err(Error::UnexpectedResponse);
+                };
+                let bridge_response = self.decrypt_bridge_response(&encrypted)?;
 
                 match bridge_response {
                     BridgeResponse::Error { error_code } => Ok(Status::Failed(error_code)),
@@ -1040,32 +3405,26 @@ impl BridgeConnection {
                         ) {
                             return Ok(status);
                         }
-                        let responses: Vec<ResponseItem> = legacy_responses
-                            .into_iter()
-                            .map(|item| {
-                                // Search the `signal_hashes` or fallback to legacy signal hash for v3 responses since the bridge does not return signal hashes
-                                let signal_hash = self
-                                    .cached_signal_hashes
-                                    .get(item.verification_level.as_ref())
-                                    .unwrap_or_else(|| self.cached_signal_hashes.legacy());
-                                item.into_response_item(signal_hash)
-                            })
-                            .collect();
-
-                        let mut result = IDKitResult::new(
-                            "3.0",
-                            self.nonce.clone(),
-                            self.action.clone(),
-                            self.action_description.clone(),
-                            responses,
-                            user_presence_completed,
-                            self.environment.as_ref(),
-                        );
+                        let mut result = self
+                            .legacy_responses_result(legacy_responses, user_presence_completed)?;
                         result.identity_attested = identity_attested;
                         result.integrity_bundle = integrity_bundle;
 
                         Ok(Status::Confirmed(result))
                     }
+                    BridgeResponse::LegacyResponseList(legacy_responses) => {
+                        if let Some(status) =
+                            user_presence_failure_status(self.require_user_presence, false)
+                        {
+                            return Ok(status);
+                        }
+                        if legacy_responses.is_empty() {
+                            return Err(Error::UnexpectedResponse);
+                        }
+                        let result = self.legacy_responses_result(legacy_responses, false)?;
+
+                        Ok(Status::Confirmed(result))
+                    }
                     BridgeResponse::ResponseV1 {
                         response,
                         user_presence_completed,
@@ -1081,8 +3440,8 @@ impl BridgeConnection {
 
                         // V1 responses are always protocol 3.0
                         // For V1 we don't have identifier, use verification_level as key
-                        let signal_hash = self.cached_signal_hashes.legacy();
-                        let item = response.into_response_item(signal_hash);
+                        let signal_hash = self.cached_signal_hashes.legacy()?;
+                        let item = response.into_response_item(signal_hash)?;
                         let mut result = IDKitResult::new(
                             "3.0",
                             self.nonce.clone(),
@@ -1103,6 +3462,87 @@ impl BridgeConnection {
         }
     }
 
+    /// Decodes, decrypts and parses a completed bridge response.
+    ///
+    /// Failures are reported as [`Error::ResponseDecryption`] with the stage
+    /// that failed, so a regenerated key can be told apart from corruption.
+    fn decrypt_bridge_response(&self, encrypted: &EncryptedPayload) -> Result<BridgeResponse> {
+        let failure = |kind: DecryptionFailureKind| {
+            trace_event!(kind = kind.as_str(), "response decryption failed");
+            Error::ResponseDecryption {
+                request_id: self.request_id.clone(),
+                kind,
+            }
+        };
+
+        let (Ok(iv), Ok(ciphertext)) = (
+            base64_decode(&encrypted.iv),
+            base64_decode(&encrypted.payload),
+        ) else {
+            return Err(failure(DecryptionFailureKind::MalformedCiphertext));
+        };
+        // AES-GCM needs a 12-byte IV and appends a 16-byte tag
+        if iv.len() != 12 || ciphertext.len() < 16 {
+            return Err(failure(DecryptionFailureKind::MalformedCiphertext));
+        }
+
+        // Both paths use the IV from the encrypted response (not stored nonce)
+        // because the authenticator encrypts with its own nonce
+        #[cfg(feature = "native-crypto")]
+        let plaintext = decrypt(&self.key.key, &iv, &ciphertext);
+
+        #[cfg(not(feature = "native-crypto"))]
+        let plaintext = decrypt(self.key_bytes.expose(), &iv, &ciphertext);
+
+        let mut plaintext =
+            plaintext.map_err(|_| failure(DecryptionFailureKind::KeyMismatchSuspected))?;
+        if encrypted.compressed {
+            plaintext = decompress_payload(&plaintext, self.max_response_bytes)
+                .ok_or_else(|| failure(DecryptionFailureKind::PlaintextNotJson))?;
+        }
+        trace_event!(plaintext_bytes = plaintext.len(), "response decrypted");
+
+        // Capture the decrypted plaintext for debugging only once the
+        // response is both decoded and decrypted successfully.
+        self.store_bridge_payload(String::from_utf8_lossy(&plaintext).into_owned());
+
+        ensure_within_limit(&plaintext, self.max_response_bytes)?;
+        serde_json::from_slice(&plaintext)
+            .map_err(|_| failure(DecryptionFailureKind::PlaintextNotJson))
+    }
+
+    /// Builds a protocol 3.0 result holding every legacy proof in `legacy_responses`
+    fn legacy_responses_result(
+        &self,
+        legacy_responses: Vec<BridgeResponseV1>,
+        user_presence_completed: bool,
+    ) -> Result<IDKitResult> {
+        let responses: Vec<ResponseItem> = legacy_responses
+            .into_iter()
+            .map(|item| {
+                // Search the `signal_hashes` or fallback to legacy signal hash for v3 responses since the bridge does not return signal hashes
+                let signal_hash = match self
+                    .cached_signal_hashes
+                    .get(item.verification_level.as_ref())
+                {
+                    Some(hash) => hash,
+                    None => self.cached_signal_hashes.legacy()?,
+                };
+                item.into_response_item(signal_hash)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(IDKitResult::new(
+            "3.0",
+            self.nonce.clone(),
+            self.action.clone(),
+            self.action_description.clone(),
+            responses,
+            user_presence_completed,
+            self.environment.as_ref(),
+        ))
+    }
+
     fn handle_bridge_v2_response(
         &self,
         proof_response: world_id_primitives::ProofResponse,
@@ -1138,48 +3578,627 @@ impl BridgeConnection {
         Ok(Status::Confirmed(result))
     }
 
-    /// Returns the request ID for this request.
+    /// Polls the bridge and yields each status transition.
     ///
-    /// In URL/QR mode this is a UUID v4 generated by the bridge; in
-    /// invite-code mode it is the lowercase-hex `HKDF(C, "dx")` the RP sent
-    /// on `POST /request`.
-    #[must_use]
-    pub fn request_id(&self) -> &str {
-        &self.request_id
+    /// Polls back off exponentially from `initial_interval` to `max_interval`
+    /// while the status is unchanged. The stream ends after a terminal status
+    /// (`Confirmed`/`Failed`), after the first error, or with `Error::Timeout`
+    /// once the deadline has passed. A response that arrives corrupted
+    /// ([`DecryptionFailureKind::MalformedCiphertext`]) is polled once more
+    /// before its error ends the stream.
+    ///
+    /// With [`PollMode::LongPoll`] the bridge holds each poll instead, and
+    /// polls follow each other without a delay. Once a poll comes back early
+    /// with an unchanged status, the bridge is assumed not to support long
+    /// polling and the stream switches to interval polling.
+    #[cfg(feature = "bridge")]
+    pub fn status_stream(
+        &self,
+        options: StatusStreamOptions,
+    ) -> impl futures_util::Stream<Item = Result<Status>> + '_ {
+        self.scheduled_status_stream(options, None)
     }
 
-    /// Unix-seconds expiry of the unredeemed code, if this connection was
-    /// created in invite-code mode.
-    #[must_use]
-    pub const fn code_expires_at(&self) -> Option<u64> {
-        self.code_expires_at
+    /// Streams statuses like [`BridgeConnection::status_stream`]. With a
+    /// `schedule`, each status change switches to that phase's fixed delay
+    /// instead of backing off.
+    #[cfg(feature = "bridge")]
+    fn scheduled_status_stream(
+        &self,
+        options: StatusStreamOptions,
+        schedule: Option<PollSchedule>,
+    ) -> impl futures_util::Stream<Item = Result<Status>> + '_ {
+        struct State {
+            last: Option<Status>,
+            interval: std::time::Duration,
+            max_interval: std::time::Duration,
+            started: std::time::Instant,
+            polled: bool,
+            retried_malformed: bool,
+            long_poll: Option<std::time::Duration>,
+            done: bool,
+        }
+
+        let state = State {
+            last: None,
+            interval: options.initial_interval,
+            max_interval: options.max_interval,
+            started: std::time::Instant::now(),
+            polled: false,
+            retried_malformed: false,
+            long_poll: options.mode.max_wait(),
+            done: false,
+        };
+
+        futures_util::stream::unfold(state, move |mut state| async move {
+            if state.done {
+                return None;
+            }
+
+            loop {
+                if state.polled && state.long_poll.is_none() {
+                    tokio::time::sleep(state.interval).await;
+                    state.interval = (state.interval * 2).min(state.max_interval);
+                }
+                state.polled = true;
+
+                let remaining = options
+                    .deadline
+                    .map(|deadline| deadline.saturating_sub(state.started.elapsed()));
+                if remaining.is_some_and(|remaining| remaining.is_zero()) {
+                    state.done = true;
+                    return Some((Err(Error::Timeout), state));
+                }
+
+                // Never hold a poll past the deadline
+                let max_wait = state.long_poll.map(|max_wait| {
+                    remaining.map_or(max_wait, |remaining| max_wait.min(remaining))
+                });
+                let poll_started = std::time::Instant::now();
+                match self.poll_for_status_with(max_wait).await {
+                    Ok(status) if state.last.as_ref() == Some(&status) => {
+                        if max_wait.is_some_and(|max_wait| poll_started.elapsed() < max_wait / 2) {
+                            state.long_poll = None;
+                        }
+                    }
+                    Ok(status) => {
+                        trace_event!(status = status.label(), "status changed");
+                        state.done = status.is_terminal();
+                        let phase_interval =
+                            schedule.map(|schedule| schedule.interval_after(&status));
+                        state.interval = phase_interval.unwrap_or(options.initial_interval);
+                        state.max_interval = phase_interval.unwrap_or(options.max_interval);
+                        state.last = Some(status.clone());
+                        return Some((Ok(status), state));
+                    }
+                    Err(Error::ResponseDecryption { kind, .. })
+                        if kind.is_retryable() && !state.retried_malformed =>
+                    {
+                        state.retried_malformed = true;
+                    }
+                    Err(err) => {
+                        state.done = true;
+                        return Some((Err(err), state));
+                    }
+                }
+            }
+        })
     }
-}
 
-/// Internal error type for the invite-code create path. Lets the retry loop
-/// distinguish 409-on-collision (retryable) from anything else (not).
-enum CreateCodeError {
-    Conflict,
-    Other(Error),
-}
+    /// Polls like [`BridgeConnection::wait_for_proof`] and reports which
+    /// constraint branch the proof satisfied.
+    ///
+    /// # Errors
+    ///
+    /// See [`BridgeConnection::wait_for_proof_with_config`].
+    #[cfg(feature = "bridge")]
+    pub async fn wait_for_result(&self) -> Result<VerifiedResult> {
+        let proof = self.wait_for_proof().await?;
+        let branch = self.satisfied_branch(&proof);
+        Ok(VerifiedResult { proof, branch })
+    }
 
-impl From<Error> for CreateCodeError {
-    fn from(e: Error) -> Self {
-        Self::Other(e)
+    /// Polls like [`BridgeConnection::wait_for_proof`] and reports which
+    /// credential the proof carries, whether user presence was checked, and
+    /// how long the wait took.
+    ///
+    /// # Errors
+    ///
+    /// See [`BridgeConnection::wait_for_proof_with_config`].
+    #[cfg(feature = "bridge")]
+    pub async fn wait_for_proof_detailed(&self) -> Result<VerificationOutcome> {
+        let started = std::time::Instant::now();
+        let proof = self.wait_for_proof().await?;
+        Ok(self.verification_outcome(proof, started.elapsed()))
     }
-}
 
-/// Current Unix-seconds, branching on target. `std::time::SystemTime::now()`
-/// panics on `wasm32-unknown-unknown` (no system clock); the WASM build uses
-/// `js_sys::Date::now()` against the host's clock instead.
-fn current_unix_seconds() -> Result<u64> {
-    #[cfg(target_arch = "wasm32")]
-    {
-        let ms = js_sys::Date::now();
-        if !ms.is_finite() || ms < 0.0 {
-            return Err(Error::BridgeError(
-                "host clock returned a non-finite or negative timestamp".into(),
-            ));
+    #[cfg(feature = "bridge")]
+    fn verification_outcome(
+        &self,
+        proof: IDKitResult,
+        waited: std::time::Duration,
+    ) -> VerificationOutcome {
+        let branch = self.satisfied_branch(&proof);
+        let credential = branch.as_ref().map(|branch| branch.credential).or_else(|| {
+            proof.responses.iter().find_map(|item| match item {
+                ResponseItem::V4 { identifier, .. } | ResponseItem::Session { identifier, .. } => {
+                    CredentialType::from_str(identifier).ok()
+                }
+                ResponseItem::V3 { .. } => None,
+            })
+        });
+        VerificationOutcome {
+            result: VerifiedResult { proof, branch },
+            credential,
+            user_presence_required: self.require_user_presence,
+            waited,
+            // A clock error must not discard a proof that already arrived
+            completed_at: self.wall_clock_now(self.created_at),
+        }
+    }
+
+    /// Returns which branch of this request's constraints `result` satisfies.
+    ///
+    /// `None` for requests without constraints, for legacy-only results, and
+    /// for results that don't satisfy the constraints.
+    #[must_use]
+    pub fn satisfied_branch(&self, result: &IDKitResult) -> Option<SatisfiedBranch> {
+        let returned = result
+            .responses
+            .iter()
+            .filter_map(|item| match item {
+                ResponseItem::V4 { identifier, .. } | ResponseItem::Session { identifier, .. } => {
+                    CredentialType::from_str(identifier).ok()
+                }
+                ResponseItem::V3 { .. } => None,
+            })
+            .collect();
+        self.constraints.as_ref()?.satisfied_branch(&returned)
+    }
+
+    /// Checks that a confirmed result only contains credentials this request
+    /// accepts and that together they satisfy its constraints.
+    ///
+    /// Polls run this on every confirmed result unless
+    /// [`BridgeConnection::with_constraint_check`] turned it off. It does not
+    /// verify the proofs themselves; send them to the Developer Portal or
+    /// verify them on-chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProof`] if a credential was not requested or the
+    /// constraints are not satisfied
+    pub fn verify_confirmed_proof(&self, result: &IDKitResult) -> Result<()> {
+        if result.responses.is_empty() {
+            return Err(Error::InvalidProof("no credentials returned".to_string()));
+        }
+
+        let mut returned = Vec::new();
+        for item in &result.responses {
+            match item {
+                ResponseItem::V4 { identifier, .. } | ResponseItem::Session { identifier, .. } => {
+                    let credential = CredentialType::from_str(identifier)
+                        .ok()
+                        .filter(|credential| {
+                            self.constraints.as_ref().is_none_or(|constraints| {
+                                constraints.collect_credential_types().contains(credential)
+                            })
+                        })
+                        .ok_or_else(|| {
+                            Error::InvalidProof(format!(
+                                "credential {identifier} was not requested"
+                            ))
+                        })?;
+                    if !returned.contains(&credential) {
+                        returned.push(credential);
+                    }
+                }
+                ResponseItem::V3 { identifier, .. } => {
+                    let Some(requested) = self.legacy_verification_level else {
+                        continue;
+                    };
+                    if !legacy_level_accepts(requested, identifier) {
+                        return Err(Error::InvalidProof(format!(
+                            "received {identifier} proof but constraints require {requested}"
+                        )));
+                    }
+                }
+            }
+        }
+
+        let Some(constraints) = &self.constraints else {
+            return Ok(());
+        };
+        if returned.is_empty() || constraints.evaluate(&returned.iter().copied().collect()) {
+            return Ok(());
+        }
+        let received = returned
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" and ");
+        let missing: Vec<String> = constraints
+            .priority_order()
+            .into_iter()
+            .filter(|credential| !returned.contains(credential))
+            .map(|credential| credential.to_string())
+            .collect();
+        Err(Error::InvalidProof(if missing.is_empty() {
+            format!("received {received} proof but constraints reject it")
+        } else {
+            format!(
+                "received {received} proof but constraints require {}",
+                missing.join(", ")
+            )
+        }))
+    }
+
+    /// Polls until World App responds, using the default [`PollConfig`].
+    ///
+    /// # Errors
+    ///
+    /// See [`BridgeConnection::wait_for_proof_with_config`].
+    #[cfg(feature = "bridge")]
+    pub async fn wait_for_proof(&self) -> Result<IDKitResult> {
+        self.wait_for_proof_with_config(PollConfig::default()).await
+    }
+
+    /// Polls until World App responds or `timeout` passes, at the default interval.
+    ///
+    /// # Errors
+    ///
+    /// See [`BridgeConnection::wait_for_proof_with_config`].
+    #[cfg(feature = "bridge")]
+    pub async fn wait_for_proof_with_timeout(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Result<IDKitResult> {
+        self.wait_for_proof_with_config(PollConfig {
+            timeout,
+            ..PollConfig::default()
+        })
+        .await
+    }
+
+    /// Polls until World App responds, every `config.interval` until World
+    /// App retrieves the request and every
+    /// `config.awaiting_confirmation_interval` after that.
+    ///
+    /// With [`PollMode::LongPoll`] the bridge holds each poll instead; see
+    /// [`BridgeConnection::status_stream`] for the fallback to intervals.
+    /// A pending inclusion proof ends the wait like any other World App
+    /// failure: the bridge keeps serving that response, so the user has to
+    /// try again later with a new request.
+    ///
+    /// Once a proof arrives the response is acknowledged, unless
+    /// [`BridgeConnection::set_auto_ack`] turned that off.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if `config` is invalid,
+    /// `Error::InclusionPending` if the inclusion proof is still pending,
+    /// `Error::AppError` if World App reports any other failure,
+    /// `Error::Timeout` once `config.timeout` or the request's session TTL
+    /// passes, or any polling error
+    #[cfg(feature = "bridge")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "idkit.wait_for_proof",
+            skip_all,
+            fields(request_id = %self.request_id, timeout_ms = config.timeout.as_millis())
+        )
+    )]
+    pub async fn wait_for_proof_with_config(&self, config: PollConfig) -> Result<IDKitResult> {
+        config.validate()?;
+
+        let timeout = self.effective_wait_timeout(config.timeout);
+        match self
+            .next_terminal_status(config.schedule(), timeout, config.mode)
+            .await?
+        {
+            Ok(result) => {
+                if self.auto_ack.load(Ordering::Acquire) {
+                    self.acknowledge_non_fatal().await;
+                }
+                Ok(result)
+            }
+            Err(AppError::InclusionProofPending) => Err(Error::InclusionPending {
+                retry_after_hint: INCLUSION_PENDING_RETRY_HINT,
+            }),
+            Err(error) => Err(Error::AppError(error)),
+        }
+    }
+
+    /// Polls on `schedule`, or long-polls, until the request reaches a
+    /// terminal status: the proof, or the error World App reported.
+    #[cfg(feature = "bridge")]
+    async fn next_terminal_status(
+        &self,
+        schedule: PollSchedule,
+        deadline: std::time::Duration,
+        mode: PollMode,
+    ) -> Result<std::result::Result<IDKitResult, AppError>> {
+        use futures_util::StreamExt;
+
+        let interval = schedule.waiting_for_connection;
+        let mut statuses = std::pin::pin!(self.scheduled_status_stream(
+            StatusStreamOptions {
+                initial_interval: interval,
+                max_interval: interval,
+                deadline: Some(deadline),
+                mode,
+            },
+            Some(schedule)
+        ));
+
+        while let Some(status) = statuses.next().await {
+            match status? {
+                Status::Confirmed(result) => return Ok(Ok(result)),
+                Status::Failed(error) => return Ok(Err(error)),
+                Status::WaitingForConnection | Status::AwaitingConfirmation => {}
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Polls like [`BridgeConnection::wait_for_proof`], returning early once
+    /// `cancel` is triggered.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Cancelled` if `cancel` fires first, otherwise see
+    /// [`BridgeConnection::wait_for_proof_with_config`].
+    #[cfg(feature = "bridge")]
+    pub async fn wait_for_proof_with_cancel(
+        &self,
+        cancel: tokio_util::sync::CancellationToken,
+    ) -> Result<IDKitResult> {
+        self.wait_for_proof_with_config_and_cancel(PollConfig::default(), cancel)
+            .await
+    }
+
+    /// Polls like [`BridgeConnection::wait_for_proof_with_config`], returning
+    /// early once `cancel` is triggered.
+    #[cfg(feature = "bridge")]
+    async fn wait_for_proof_with_config_and_cancel(
+        &self,
+        config: PollConfig,
+        cancel: tokio_util::sync::CancellationToken,
+    ) -> Result<IDKitResult> {
+        tokio::select! {
+            biased;
+            () = cancel.cancelled_owned() => Err(Error::Cancelled),
+            result = self.wait_for_proof_with_config(config) => result,
+        }
+    }
+
+    /// Acknowledges a completed response so the bridge can delete it right away
+    /// instead of waiting for its TTL to expire.
+    ///
+    /// Only the first successful acknowledgment is sent; later calls are no-ops.
+    /// Concurrent calls send it once, and a failed one lets the next call retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the bridge rejects the acknowledgment
+    pub async fn acknowledge(&self) -> Result<()> {
+        // Claim the acknowledgment before sending it, so concurrent callers
+        // cannot both see it unsent
+        if self
+            .acknowledged
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return Ok(());
+        }
+
+        if let Err(e) = self
+            .transport
+            .acknowledge(&self.bridge_url, &self.request_id)
+            .await
+        {
+            self.acknowledged.store(false, Ordering::Release);
+            return Err(map_timeout(e));
+        }
+        trace_event!(request_id = %self.request_id, "response acknowledged");
+        Ok(())
+    }
+
+    /// Acknowledges like [`BridgeConnection::acknowledge`], logging a failure
+    /// instead of returning it
+    ///
+    /// The proof already arrived, and the bridge still deletes the response
+    /// once its TTL expires.
+    #[cfg(feature = "bridge")]
+    async fn acknowledge_non_fatal(&self) {
+        if let Err(error) = self.acknowledge().await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                request_id = %self.request_id,
+                %error,
+                "acknowledging the response failed"
+            );
+            #[cfg(not(feature = "tracing"))]
+            let _ = error;
+        }
+    }
+
+    /// Enables or disables acknowledging the response once a wait returns a
+    /// proof (enabled by default)
+    ///
+    /// Acknowledgment failures do not fail the wait; they are logged with the
+    /// `tracing` feature.
+    pub fn set_auto_ack(&self, enabled: bool) {
+        self.auto_ack.store(enabled, Ordering::Release);
+    }
+
+    /// Returns the request ID for this request.
+    ///
+    /// In URL/QR mode this is a UUID v4 generated by the bridge; in
+    /// invite-code mode it is the lowercase-hex `HKDF(C, "dx")` the RP sent
+    /// on `POST /request`.
+    #[must_use]
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Returns the idempotency key this request was created under.
+    ///
+    /// It is part of [`BridgeConnection::to_resumable`]; pass that state to
+    /// [`BridgeConnectionBuilder::earlier_attempt`] when retrying the
+    /// creation. `None` in invite-code mode.
+    #[must_use]
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.idempotency_key.as_deref()
+    }
+
+    /// Unix-seconds expiry of the unredeemed code, if this connection was
+    /// created in invite-code mode.
+    #[must_use]
+    pub const fn code_expires_at(&self) -> Option<u64> {
+        self.code_expires_at
+    }
+
+    /// Reads the connection's clock, clamped to `not_before`
+    ///
+    /// Wall clocks step backwards, e.g. after an NTP correction; a reading
+    /// earlier than `not_before` is logged and replaced by `not_before`.
+    /// `None` if the clock cannot be read.
+    fn wall_clock_now(&self, not_before: Option<u64>) -> Option<u64> {
+        let now = self.clock.unix_seconds().ok()?;
+        match not_before {
+            Some(floor) if now < floor => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    request_id = %self.request_id,
+                    behind_secs = floor - now,
+                    "wall clock went backwards, clamping"
+                );
+                Some(floor)
+            }
+            _ => Some(now),
+        }
+    }
+
+    /// Unix-seconds time the request expires, see
+    /// [`BridgeConnection::with_session_ttl`]. `None` when the creation time
+    /// is unknown, e.g. for a request rebuilt with
+    /// [`BridgeConnection::resume`].
+    #[must_use]
+    pub fn session_expires_at(&self) -> Option<u64> {
+        self.created_at
+            .map(|created_at| created_at.saturating_add(self.session_ttl.as_secs()))
+    }
+
+    /// Caps a wait's `timeout` at the time left before the request expires
+    #[cfg(feature = "bridge")]
+    fn effective_wait_timeout(&self, timeout: std::time::Duration) -> std::time::Duration {
+        let (Some(expires_at), Some(now)) = (
+            self.session_expires_at(),
+            self.wall_clock_now(self.created_at),
+        ) else {
+            return timeout;
+        };
+        timeout.min(std::time::Duration::from_secs(
+            expires_at.saturating_sub(now),
+        ))
+    }
+}
+
+/// Cap on the body of a non-success bridge response kept in [`Error::BridgeHttp`]
+const MAX_ERROR_BODY_BYTES: usize = 4 * 1024;
+
+/// Builds an [`Error::BridgeHttp`] from a non-success bridge response.
+///
+/// Bodies over [`MAX_ERROR_BODY_BYTES`] are dropped rather than read.
+async fn bridge_http_error(response: reqwest::Response, endpoint: BridgeEndpoint) -> Error {
+    let status = response.status().as_u16();
+    let body = crate::http::read_capped_body(response, MAX_ERROR_BODY_BYTES)
+        .await
+        .map(|body| String::from_utf8_lossy(&body).into_owned())
+        .unwrap_or_default();
+    Error::BridgeHttp {
+        status,
+        endpoint,
+        body,
+    }
+}
+
+/// Source of wall-clock time for a [`BridgeConnection`]
+///
+/// Wall clocks can step backwards, so connections clamp what a clock
+/// reports to the times they already recorded.
+pub trait Clock: Send + Sync {
+    /// Current Unix seconds
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clock cannot be read
+    fn unix_seconds(&self) -> Result<u64>;
+}
+
+/// [`Clock`] reading the system clock, or the host's clock on WASM
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn unix_seconds(&self) -> Result<u64> {
+        current_unix_seconds()
+    }
+}
+
+/// [`Clock`] that only moves when told to
+#[cfg(any(test, feature = "test-utils"))]
+#[derive(Debug, Default)]
+pub struct MockClock {
+    seconds: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl MockClock {
+    /// Creates a clock reading `seconds`
+    #[must_use]
+    pub const fn new(seconds: u64) -> Self {
+        Self {
+            seconds: std::sync::atomic::AtomicU64::new(seconds),
+        }
+    }
+
+    /// Moves the clock forward by `by`
+    pub fn advance(&self, by: std::time::Duration) {
+        self.seconds.fetch_add(by.as_secs(), Ordering::AcqRel);
+    }
+
+    /// Moves the clock backward by `by`, as an NTP correction might
+    pub fn rewind(&self, by: std::time::Duration) {
+        let _ = self
+            .seconds
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |seconds| {
+                Some(seconds.saturating_sub(by.as_secs()))
+            });
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl Clock for MockClock {
+    fn unix_seconds(&self) -> Result<u64> {
+        Ok(self.seconds.load(Ordering::Acquire))
+    }
+}
+
+/// Current Unix-seconds, branching on target. `std::time::SystemTime::now()`
+/// panics on `wasm32-unknown-unknown` (no system clock); the WASM build uses
+/// `js_sys::Date::now()` against the host's clock instead.
+pub(crate) fn current_unix_seconds() -> Result<u64> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let ms = js_sys::Date::now();
+        if !ms.is_finite() || ms < 0.0 {
+            return Err(Error::BridgeError(
+                "host clock returned a non-finite or negative timestamp".into(),
+            ));
         }
         Ok((ms / 1000.0) as u64)
     }
@@ -1207,10 +4226,11 @@ fn current_timestamp_rfc3339() -> String {
         .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
 }
 
-#[allow(dead_code)]
 async fn try_create_invite_code_request(
     params: &BridgeConnectionParams,
-) -> std::result::Result<BridgeConnection, CreateCodeError> {
+    transport: &Arc<dyn BridgeTransport>,
+    retry: CreateRetryConfig,
+) -> Result<BridgeConnection> {
     use crate::crypto::{
         generate_invite_code, generate_nonce, hkdf_invite_index_hex, hkdf_invite_key,
     };
@@ -1230,45 +4250,19 @@ async fn try_create_invite_code_request(
 
     let payload = build_request_payload(params, false)?;
     let request_payload = serde_json::to_value(&payload).map_err(Error::from)?;
-    let payload_json = serde_json::to_vec(&payload).map_err(Error::from)?;
-    let encrypted = encrypt(&key_bytes, &nonce_bytes, &payload_json)?;
-
-    let body = CreateRequestBody {
-        iv: base64_encode(&nonce_bytes),
-        payload: base64_encode(&encrypted),
-        request_id: Some(request_id.clone()),
-    };
+    let sealed = seal_request_payload(&payload, &key_bytes, &nonce_bytes, params.compress_payload)?;
 
     let cached_signal_hashes = CachedSignalHashes::compute(params);
     let bridge_url = params.bridge_url.clone().unwrap_or_default();
-    let client = reqwest::Client::builder()
-        .user_agent(format!("idkit-core/{}", env!("CARGO_PKG_VERSION")))
-        .build()
-        .map_err(Error::from)?;
-
-    let response = client
-        .post(bridge_url.join("/request")?)
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| Error::BridgeError(format!("Bridge request failed: {e}")))?;
 
-    if response.status() == reqwest::StatusCode::CONFLICT {
-        return Err(CreateCodeError::Conflict);
-    }
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response.text().await.unwrap_or_default();
-        return Err(Error::BridgeError(format!(
-            "Bridge /request (code) failed with status {status}: {}",
-            if body.is_empty() {
-                "no error details"
-            } else {
-                &body
-            }
-        ))
-        .into());
-    }
+    let echoed_request_id = post_create_request(
+        transport.as_ref(),
+        &bridge_url,
+        &sealed,
+        CreateKey::RequestId(&request_id),
+        retry,
+    )
+    .await?;
 
     // Validate that the bridge stored the request under the id we sent.
     // World App will derive the same id from the user-typed code and read via
@@ -1277,28 +4271,20 @@ async fn try_create_invite_code_request(
     // the World App side and we'd fail in a confusing way much later in the
     // poll loop. Catching the mismatch here surfaces the contract violation
     // at creation time.
-    let echoed: BridgeCreateResponse = response
-        .json()
-        .await
-        .map_err(|e| Error::BridgeError(format!("Failed to parse bridge response: {e}")))?;
-    if echoed.request_id != request_id {
+    if echoed_request_id != request_id {
         return Err(Error::BridgeError(format!(
-            "Bridge echoed mismatched request_id (sent {request_id}, got {})",
-            echoed.request_id
-        ))
-        .into());
+            "Bridge echoed mismatched request_id (sent {request_id}, got {echoed_request_id})"
+        )));
     }
 
     // Bridge no longer reports the unredeemed-code expiry; it ships
     // `EXPIRE_AFTER_SECONDS` (900s) on every row, including code-mode rows,
     // so we synthesize the deadline here so adopters can still drive
     // countdowns off `code_expires_at()`.
-    let code_expires_at = current_unix_seconds()? + INVITE_CODE_TTL_SECONDS;
+    let created_at = current_unix_seconds()?;
+    let code_expires_at = created_at.saturating_add(INVITE_CODE_TTL_SECONDS);
 
-    let action = match &params.kind {
-        RequestKind::Uniqueness { action } => Some(action.clone()),
-        _ => None,
-    };
+    let action = params.kind.action();
 
     #[cfg(feature = "native-crypto")]
     let key = CryptoKey::new(key_bytes, nonce_bytes);
@@ -1307,10 +4293,11 @@ async fn try_create_invite_code_request(
         bridge_url,
         #[cfg(feature = "native-crypto")]
         key,
-        key_bytes: key_bytes.to_vec(),
+        key_bytes: SecretBytes::new(key_bytes),
         request_id,
         app_id: params.app_id.as_str().to_string(),
-        client,
+        transport: Arc::clone(transport),
+        clock: Arc::new(SystemClock),
         cached_signal_hashes,
         action,
         action_description: params.action_description.clone(),
@@ -1318,9 +4305,25 @@ async fn try_create_invite_code_request(
         override_connect_base_url: params.override_connect_base_url.clone(),
         return_to: params.return_to.clone(),
         environment: params.environment.unwrap_or_default(),
-        require_user_presence: params.require_user_presence,
+        require_user_presence: params.requires_user_presence(),
+        constraints: params.constraints.clone(),
+        legacy_verification_level: Some(params.legacy_verification_level),
+        check_constraints: true,
         request_payload,
+        encrypted_request: sealed,
         latest_bridge_payload: Mutex::new(None),
+        acknowledged: AtomicBool::new(false),
+        auto_ack: AtomicBool::new(true),
+        finished: AtomicBool::new(false),
+        cancel_on_drop: false,
+        single_consumption: false,
+        consumed: AtomicBool::new(false),
+        latest_proof: Mutex::new(None),
+        status_history: Mutex::new(Vec::new()),
+        idempotency_key: None,
+        max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        created_at: Some(created_at),
+        session_ttl: BRIDGE_REQUEST_TTL,
         invite_code: Some(code),
         code_expires_at: Some(code_expires_at),
     })
@@ -1356,6 +4359,7 @@ pub struct BridgeRequestPayloadWrapper {
     pub require_user_presence: bool,
     pub environment: Environment,
     pub return_to_url: Option<String>,
+    pub locale: Option<String>,
 }
 
 /// FFI projection of the protocol-level proof request embedded in
@@ -1576,16 +4580,16 @@ impl TryFrom<ProofRequest> for ProofRequestWrapper {
     }
 }
 
-impl TryFrom<BridgeRequestPayload> for BridgeRequestPayloadWrapper {
+impl TryFrom<BridgeRequestPayload<'_>> for BridgeRequestPayloadWrapper {
     type Error = Error;
 
-    fn try_from(payload: BridgeRequestPayload) -> Result<Self> {
+    fn try_from(payload: BridgeRequestPayload<'_>) -> Result<Self> {
         Ok(Self {
-            app_id: payload.app_id,
-            package_name: payload.package_name,
-            package_version: payload.package_version,
+            app_id: payload.app_id.to_string(),
+            package_name: payload.package_name.to_string(),
+            package_version: payload.package_version.to_string(),
             action: payload.action,
-            action_description: payload.action_description,
+            action_description: payload.action_description.map(str::to_string),
             signal: payload.signal,
             verification_level: payload.verification_level,
             timestamp: payload.timestamp,
@@ -1593,11 +4597,12 @@ impl TryFrom<BridgeRequestPayload> for BridgeRequestPayloadWrapper {
                 .proof_request
                 .map(ProofRequestWrapper::try_from)
                 .transpose()?,
-            identity_attributes: payload.identity_attributes,
+            identity_attributes: payload.identity_attributes.map(<[_]>::to_vec),
             allow_legacy_proofs: payload.allow_legacy_proofs,
             require_user_presence: payload.require_user_presence,
             environment: payload.environment,
-            return_to_url: payload.return_to,
+            return_to_url: payload.return_to.map(str::to_string),
+            locale: payload.locale.map(str::to_string),
         })
     }
 }
@@ -1641,6 +4646,42 @@ pub struct IDKitRequestConfig {
     pub environment: Option<Environment>,
     /// Optional connect URL mode (defaults to `Default`)
     pub connect_url_mode: Option<ConnectUrlMode>,
+    /// Optional limit in milliseconds for connecting to the bridge (defaults to 10 s)
+    #[uniffi(default = None)]
+    pub connect_timeout_ms: Option<u64>,
+    /// Optional limit in milliseconds for each bridge call (defaults to 30 s)
+    #[uniffi(default = None)]
+    pub request_timeout_ms: Option<u64>,
+    /// Whether to collapse single-option `any`/`all` constraint wrappers before
+    /// sending the request (defaults to true)
+    #[uniffi(default = None)]
+    pub normalize_constraints: Option<bool>,
+    /// Whether releasing the request before it finishes deletes it from the
+    /// bridge (defaults to false)
+    #[uniffi(default = None)]
+    pub cancel_on_drop: Option<bool>,
+    /// Whether only the first poll or wait that sees the proof receives it
+    /// (defaults to false)
+    #[uniffi(default = None)]
+    pub single_consumption: Option<bool>,
+    /// Whether polls reject results with credentials the request does not
+    /// accept (defaults to true)
+    #[uniffi(default = None)]
+    pub check_constraints: Option<bool>,
+    /// Optional time in milliseconds after which waits treat the request as
+    /// expired (defaults to and is capped at the bridge's 15 minutes)
+    #[uniffi(default = None)]
+    pub session_ttl_ms: Option<u64>,
+    /// Optional language hint for World App, such as `en` or `en-US`
+    #[uniffi(default = None)]
+    pub locale: Option<String>,
+    /// Custom URL schemes `return_to` may use besides `https`, e.g. `myapp`
+    #[uniffi(default = None)]
+    pub return_to_schemes: Option<Vec<String>>,
+    /// Whether to deflate large request payloads before encryption, for
+    /// World App versions that support it (defaults to false)
+    #[uniffi(default = None)]
+    pub compress_payload: Option<bool>,
 }
 
 /// Configuration for session requests (no action field, v4 only)
@@ -1669,6 +4710,42 @@ pub struct IDKitSessionConfig {
     pub return_to: Option<String>,
     /// Optional environment override (defaults to Production)
     pub environment: Option<Environment>,
+    /// Optional limit in milliseconds for connecting to the bridge (defaults to 10 s)
+    #[uniffi(default = None)]
+    pub connect_timeout_ms: Option<u64>,
+    /// Optional limit in milliseconds for each bridge call (defaults to 30 s)
+    #[uniffi(default = None)]
+    pub request_timeout_ms: Option<u64>,
+    /// Whether to collapse single-option `any`/`all` constraint wrappers before
+    /// sending the request (defaults to true)
+    #[uniffi(default = None)]
+    pub normalize_constraints: Option<bool>,
+    /// Whether releasing the request before it finishes deletes it from the
+    /// bridge (defaults to false)
+    #[uniffi(default = None)]
+    pub cancel_on_drop: Option<bool>,
+    /// Whether only the first poll or wait that sees the proof receives it
+    /// (defaults to false)
+    #[uniffi(default = None)]
+    pub single_consumption: Option<bool>,
+    /// Whether polls reject results with credentials the request does not
+    /// accept (defaults to true)
+    #[uniffi(default = None)]
+    pub check_constraints: Option<bool>,
+    /// Optional time in milliseconds after which waits treat the request as
+    /// expired (defaults to and is capped at the bridge's 15 minutes)
+    #[uniffi(default = None)]
+    pub session_ttl_ms: Option<u64>,
+    /// Optional language hint for World App, such as `en` or `en-US`
+    #[uniffi(default = None)]
+    pub locale: Option<String>,
+    /// Custom URL schemes `return_to` may use besides `https`, e.g. `myapp`
+    #[uniffi(default = None)]
+    pub return_to_schemes: Option<Vec<String>>,
+    /// Whether to deflate large request payloads before encryption, for
+    /// World App versions that support it (defaults to false)
+    #[uniffi(default = None)]
+    pub compress_payload: Option<bool>,
 }
 
 /// Internal enum to store builder configuration
@@ -1695,6 +4772,75 @@ impl IDKitConfig {
         }
     }
 
+    fn cancel_on_drop(&self) -> bool {
+        match self {
+            Self::Request(config) => config.cancel_on_drop,
+            Self::CreateSession(config) | Self::ProveSession { config, .. } => {
+                config.cancel_on_drop
+            }
+        }
+        .unwrap_or(false)
+    }
+
+    fn single_consumption(&self) -> bool {
+        match self {
+            Self::Request(config) => config.single_consumption,
+            Self::CreateSession(config) | Self::ProveSession { config, .. } => {
+                config.single_consumption
+            }
+        }
+        .unwrap_or(false)
+    }
+
+    fn check_constraints(&self) -> bool {
+        match self {
+            Self::Request(config) => config.check_constraints,
+            Self::CreateSession(config) | Self::ProveSession { config, .. } => {
+                config.check_constraints
+            }
+        }
+        .unwrap_or(true)
+    }
+
+    fn session_ttl(&self) -> std::time::Duration {
+        match self {
+            Self::Request(config) => config.session_ttl_ms,
+            Self::CreateSession(config) | Self::ProveSession { config, .. } => {
+                config.session_ttl_ms
+            }
+        }
+        .map_or(BRIDGE_REQUEST_TTL, std::time::Duration::from_millis)
+    }
+
+    fn http_timeouts(&self) -> HttpTimeouts {
+        let (connect_ms, request_ms) = match self {
+            Self::Request(config) => (config.connect_timeout_ms, config.request_timeout_ms),
+            Self::CreateSession(config) | Self::ProveSession { config, .. } => {
+                (config.connect_timeout_ms, config.request_timeout_ms)
+            }
+        };
+        let defaults = HttpTimeouts::default();
+        HttpTimeouts {
+            connect: connect_ms.map_or(defaults.connect, std::time::Duration::from_millis),
+            request: request_ms.map_or(defaults.request, std::time::Duration::from_millis),
+        }
+    }
+
+    /// Returns the transport for the bridge calls of a request: the shared
+    /// client, or one of its own when the config sets custom HTTP timeouts
+    fn bridge_transport(
+        &self,
+    ) -> std::result::Result<Box<dyn BridgeTransport>, crate::error::IdkitError> {
+        let timeouts = self.http_timeouts();
+        let client = if timeouts == HttpTimeouts::default() {
+            default_client()
+        } else {
+            timeouts.build_client()
+        }
+        .map_err(crate::error::IdkitError::from)?;
+        Ok(Box::new(ReqwestTransport::new(client)))
+    }
+
     /// Converts config + constraints to `BridgeConnectionParams`
     fn to_params(
         &self,
@@ -1714,7 +4860,7 @@ impl IDKitConfig {
                     package_name: config.package_name.clone(),
                     package_version: config.package_version.clone(),
                     kind: RequestKind::Uniqueness {
-                        action: config.action.clone(),
+                        action: crate::Action::new(&config.action)?,
                     },
                     constraints: Some(constraints),
                     rp_context: (*config.rp_context).clone(),
@@ -1727,10 +4873,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs: config.allow_legacy_proofs,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: None,
+                    locale: config.locale.clone(),
                 })
             }
             Self::CreateSession(config) => {
@@ -1755,10 +4905,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: None,
+                    locale: config.locale.clone(),
                 })
             }
             Self::ProveSession { session_id, config } => {
@@ -1785,10 +4939,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: None,
+                    locale: config.locale.clone(),
                 })
             }
         }
@@ -1838,7 +4996,7 @@ impl IDKitConfig {
                     package_name: config.package_name.clone(),
                     package_version: config.package_version.clone(),
                     kind: RequestKind::Uniqueness {
-                        action: config.action.clone(),
+                        action: crate::Action::new(&config.action)?,
                     },
                     constraints: bridge_params.constraints,
                     rp_context: (*config.rp_context).clone(),
@@ -1848,10 +5006,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: bridge_params.identity_attributes,
+                    locale: config.locale.clone(),
                 })
             }
             Self::CreateSession(config) => {
@@ -1875,10 +5037,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: bridge_params.identity_attributes,
+                    locale: config.locale.clone(),
                 })
             }
             Self::ProveSession { session_id, config } => {
@@ -1904,10 +5070,14 @@ impl IDKitConfig {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: config.require_user_presence.unwrap_or(false),
+                    normalize_constraints: config.normalize_constraints.unwrap_or(true),
+                    compress_payload: config.compress_payload.unwrap_or(false),
                     override_connect_base_url: config.override_connect_base_url.clone(),
                     return_to: config.return_to.clone(),
+                    return_to_schemes: config.return_to_schemes.clone().unwrap_or_default(),
                     environment: config.environment,
                     identity_attributes: bridge_params.identity_attributes,
+                    locale: config.locale.clone(),
                 })
             }
         }
@@ -1961,7 +5131,7 @@ impl IDKitBuilder {
         })
     }
 
-    /// Creates a `BridgeConnection` with the given constraints
+    /// Creates a request with the given constraints
     ///
     /// # Errors
     ///
@@ -1971,22 +5141,8 @@ impl IDKitBuilder {
         &self,
         constraints: Arc<ConstraintNode>,
     ) -> std::result::Result<Arc<IDKitRequestWrapper>, crate::error::IdkitError> {
-        let runtime =
-            tokio::runtime::Runtime::new().map_err(|e| crate::error::IdkitError::BridgeError {
-                details: format!("Failed to create runtime: {e}"),
-            })?;
-
         let params = self.config.to_params((*constraints).clone())?;
-
-        let inner = runtime
-            .block_on(BridgeConnection::create(params))
-            .map_err(crate::error::IdkitError::from)?;
-
-        Ok(Arc::new(IDKitRequestWrapper {
-            runtime,
-            inner,
-            connect_url_mode: self.config.connect_url_mode(),
-        }))
+        IDKitRequestWrapper::create_blocking(params, &self.config)
     }
 
     /// Builds the plaintext bridge payload JSON for the given constraints without
@@ -2019,7 +5175,7 @@ impl IDKitBuilder {
         build_request_payload_wrapper(&params).map_err(Into::into)
     }
 
-    /// Creates a `BridgeConnection` from a preset (works for all request types)
+    /// Creates a request from a preset (works for all request types)
     ///
     /// Presets provide a simplified way to create requests with predefined
     /// credential configurations.
@@ -2032,22 +5188,23 @@ impl IDKitBuilder {
         &self,
         preset: Preset,
     ) -> std::result::Result<Arc<IDKitRequestWrapper>, crate::error::IdkitError> {
-        let runtime =
-            tokio::runtime::Runtime::new().map_err(|e| crate::error::IdkitError::BridgeError {
-                details: format!("Failed to create runtime: {e}"),
-            })?;
-
         let params = self.config.to_params_from_preset(preset)?;
+        IDKitRequestWrapper::create_blocking(params, &self.config)
+    }
 
-        let inner = runtime
-            .block_on(BridgeConnection::create(params))
-            .map_err(crate::error::IdkitError::from)?;
-
-        Ok(Arc::new(IDKitRequestWrapper {
-            runtime,
-            inner,
-            connect_url_mode: self.config.connect_url_mode(),
-        }))
+    /// Creates an Orb-only request, the most common kind
+    ///
+    /// Shorthand for `preset(Preset::orb_legacy(signal))`, so it returns
+    /// World ID 3.0 proofs only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be created
+    pub fn orb(
+        &self,
+        signal: Option<String>,
+    ) -> std::result::Result<Arc<IDKitRequestWrapper>, crate::error::IdkitError> {
+        self.preset(Preset::orb_legacy(signal))
     }
 
     /// Builds the plaintext bridge payload JSON for the given preset without
@@ -2102,10 +5259,23 @@ impl IDKitBuilder {
         let params = self.config.to_params((*constraints).clone())?;
 
         let inner = runtime
-            .block_on(BridgeConnection::create_for_invite_code(params))
-            .map_err(crate::error::IdkitError::from)?;
+            .block_on(
+                BridgeConnection::builder(params)
+                    .transport(self.config.bridge_transport()?)
+                    .invite_code()
+                    .create(),
+            )
+            .map_err(crate::error::IdkitError::from)?
+            .with_cancel_on_drop(self.config.cancel_on_drop())
+            .with_single_consumption(self.config.single_consumption())
+            .with_constraint_check(self.config.check_constraints())
+            .with_session_ttl(self.config.session_ttl());
 
-        Ok(Arc::new(IDKitInviteCodeRequest { runtime, inner }))
+        Ok(Arc::new(IDKitInviteCodeRequest {
+            runtime,
+            inner,
+            auto_acknowledge: AtomicBool::new(true),
+        }))
     }
 
     /// Creates an invite-code mode `BridgeConnection` from a preset (WDP-73).
@@ -2127,10 +5297,23 @@ impl IDKitBuilder {
         let params = self.config.to_params_from_preset(preset)?;
 
         let inner = runtime
-            .block_on(BridgeConnection::create_for_invite_code(params))
-            .map_err(crate::error::IdkitError::from)?;
+            .block_on(
+                BridgeConnection::builder(params)
+                    .transport(self.config.bridge_transport()?)
+                    .invite_code()
+                    .create(),
+            )
+            .map_err(crate::error::IdkitError::from)?
+            .with_cancel_on_drop(self.config.cancel_on_drop())
+            .with_single_consumption(self.config.single_consumption())
+            .with_constraint_check(self.config.check_constraints())
+            .with_session_ttl(self.config.session_ttl());
 
-        Ok(Arc::new(IDKitInviteCodeRequest { runtime, inner }))
+        Ok(Arc::new(IDKitInviteCodeRequest {
+            runtime,
+            inner,
+            auto_acknowledge: AtomicBool::new(true),
+        }))
     }
 }
 
@@ -2165,12 +5348,142 @@ pub struct IDKitRequestWrapper {
     runtime: tokio::runtime::Runtime,
     inner: BridgeConnection,
     connect_url_mode: ConnectUrlMode,
+    auto_acknowledge: AtomicBool,
 }
 
 #[cfg(feature = "ffi")]
-#[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone, uniffi::Enum)]
-pub enum StatusWrapper {
+impl Drop for IDKitRequestWrapper {
+    fn drop(&mut self) {
+        close_on_drop_blocking(&self.runtime, &self.inner);
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl IDKitRequestWrapper {
+    /// Creates the bridge request on a fresh runtime owned by the wrapper,
+    /// with the HTTP timeouts and connect URL mode of `config`
+    fn create_blocking(
+        params: BridgeConnectionParams,
+        config: &IDKitConfig,
+    ) -> std::result::Result<Arc<Self>, crate::error::IdkitError> {
+        let runtime =
+            tokio::runtime::Runtime::new().map_err(|e| crate::error::IdkitError::BridgeError {
+                details: format!("Failed to create runtime: {e}"),
+            })?;
+
+        let inner = runtime
+            .block_on(
+                BridgeConnection::builder(params)
+                    .transport(config.bridge_transport()?)
+                    .create(),
+            )
+            .map_err(crate::error::IdkitError::from)?
+            .with_cancel_on_drop(config.cancel_on_drop())
+            .with_single_consumption(config.single_consumption())
+            .with_constraint_check(config.check_constraints())
+            .with_session_ttl(config.session_ttl());
+
+        Ok(Arc::new(Self {
+            runtime,
+            inner,
+            connect_url_mode: config.connect_url_mode(),
+            auto_acknowledge: AtomicBool::new(true),
+        }))
+    }
+
+    /// Blocks on [`BridgeConnection::wait_for_proof_with_config`],
+    /// acknowledging the response when enabled.
+    ///
+    /// Not exported over `UniFFI`; for Rust callers such as the C ABI, which
+    /// need the World App error itself rather than its `IdkitError` message.
+    ///
+    /// # Errors
+    ///
+    /// See [`BridgeConnection::wait_for_proof_with_config`]
+    pub fn wait_for_proof_with_config(&self, config: PollConfig) -> Result<IDKitResult> {
+        self.inner
+            .set_auto_ack(self.auto_acknowledge.load(Ordering::Relaxed));
+        self.runtime
+            .block_on(self.inner.wait_for_proof_with_config(config))
+    }
+}
+
+#[cfg(feature = "ffi")]
+#[uniffi::export]
+impl IDKitRequestWrapper {
+    /// Creates a uniqueness request whose credential requests are derived
+    /// from the leaves of `constraints`.
+    ///
+    /// `signal` is applied to every leaf without a signal of its own and is
+    /// also used for legacy (v3) proofs. Like preset signals, it can be a
+    /// plain string or a `0x`-prefixed hex value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration or constraints are invalid, or
+    /// the request cannot be created
+    #[uniffi::constructor(name = "create_from_constraints")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn create_from_constraints(
+        config: IDKitRequestConfig,
+        constraints: Arc<ConstraintNode>,
+        signal: Option<String>,
+    ) -> std::result::Result<Arc<Self>, crate::error::IdkitError> {
+        let config = IDKitConfig::Request(config);
+        let mut constraints = (*constraints).clone();
+        if let Some(signal) = &signal {
+            constraints = constraints.with_default_signal(&Signal::from_string(signal.clone()));
+        }
+
+        let mut params = config.to_params(constraints)?;
+        params.legacy_signal = signal.unwrap_or_default();
+        Self::create_blocking(params, &config)
+    }
+
+    /// Rebuilds a request created earlier from its ID and base64 key, without
+    /// creating a new request.
+    ///
+    /// For apps that lost their state after creating a request, e.g. in a
+    /// crash. Results carry no action or nonce and legacy (World ID 3.0)
+    /// proofs are rejected; see `BridgeConnection::resume`.
+    /// `bridge_url` defaults to the production bridge.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the app ID, request ID, key or bridge URL is
+    /// invalid
+    #[allow(clippy::needless_pass_by_value)]
+    #[uniffi::constructor(name = "resume")]
+    pub fn resume(
+        app_id: String,
+        request_id: String,
+        key_base64: String,
+        bridge_url: Option<String>,
+    ) -> std::result::Result<Arc<Self>, crate::error::IdkitError> {
+        let app_id = AppId::new(&app_id)?;
+        let bridge_url = bridge_url
+            .map(|url| BridgeUrl::new(url, &app_id))
+            .transpose()?;
+        let key_bytes = base64_decode(&key_base64)?;
+        let runtime =
+            tokio::runtime::Runtime::new().map_err(|e| crate::error::IdkitError::BridgeError {
+                details: format!("Failed to create runtime: {e}"),
+            })?;
+        let inner = BridgeConnection::resume(&app_id, request_id, key_bytes, bridge_url)?;
+
+        Ok(Arc::new(Self {
+            runtime,
+            inner,
+            connect_url_mode: ConnectUrlMode::Default,
+            auto_acknowledge: AtomicBool::new(true),
+        }))
+    }
+}
+
+#[cfg(feature = "ffi")]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, uniffi::Enum)]
+pub enum StatusWrapper {
     /// Waiting for World App to retrieve the request
     WaitingForConnection,
     /// World App has retrieved the request, waiting for user confirmation
@@ -2178,12 +5491,36 @@ pub enum StatusWrapper {
     /// User has confirmed and provided proof(s)
     /// For session proofs, `IDKitResult.session_id` will be `Some(id)`
     Confirmed { result: IDKitResult },
-    /// Request has failed
-    Failed { error: AppError },
+    /// Request has failed. `retry_after_secs` suggests when a new request
+    /// is expected to succeed.
+    Failed {
+        error: AppError,
+        retry_after_secs: Option<u64>,
+    },
     /// Network/transport error — safe to retry
     NetworkingError { error: AppError },
 }
 
+/// A status change seen while polling, as exposed to the bindings
+#[cfg(feature = "ffi")]
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct StatusTransitionWrapper {
+    /// The status the request moved to
+    pub status: StatusWrapper,
+    /// Unix seconds when a poll first saw `status`
+    pub observed_at: u64,
+}
+
+#[cfg(feature = "ffi")]
+impl From<StatusTransition> for StatusTransitionWrapper {
+    fn from(transition: StatusTransition) -> Self {
+        Self {
+            status: transition.status.into(),
+            observed_at: transition.observed_at,
+        }
+    }
+}
+
 #[cfg(feature = "ffi")]
 impl From<Status> for StatusWrapper {
     fn from(status: Status) -> Self {
@@ -2191,7 +5528,115 @@ impl From<Status> for StatusWrapper {
             Status::WaitingForConnection => Self::WaitingForConnection,
             Status::AwaitingConfirmation => Self::AwaitingConfirmation,
             Status::Confirmed(result) => Self::Confirmed { result },
-            Status::Failed(app_error) => Self::Failed { error: app_error },
+            Status::Failed(app_error) => Self::failed(app_error),
+        }
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl StatusWrapper {
+    /// Wraps a World App failure, hinting when to retry a retryable one
+    fn failed(error: AppError) -> Self {
+        let retry_after_secs = error
+            .is_retryable()
+            .then(|| INCLUSION_PENDING_RETRY_HINT.as_secs());
+        Self::Failed {
+            error,
+            retry_after_secs,
+        }
+    }
+}
+
+/// Cancels a pending `wait_for_proof_with_cancel` call.
+///
+/// Safe to share across threads; call `cancel()` from any thread, for example
+/// when the user navigates away from the verification screen.
+#[cfg(feature = "ffi")]
+#[derive(uniffi::Object, Default)]
+pub struct CancelHandle {
+    token: tokio_util::sync::CancellationToken,
+}
+
+#[cfg(feature = "ffi")]
+#[uniffi::export]
+impl CancelHandle {
+    /// Creates a handle that has not been cancelled yet
+    #[must_use]
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Cancels every wait using this handle. Later waits return immediately.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Returns true once `cancel()` has been called
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Waits for a proof on the wrapper's runtime until `cancel` fires,
+/// acknowledging the response when enabled.
+#[cfg(feature = "ffi")]
+fn wait_for_proof_blocking(
+    runtime: &tokio::runtime::Runtime,
+    inner: &BridgeConnection,
+    auto_acknowledge: bool,
+    cancel: &CancelHandle,
+) -> std::result::Result<IDKitResult, crate::error::IdkitError> {
+    inner.set_auto_ack(auto_acknowledge);
+    Ok(runtime.block_on(inner.wait_for_proof_with_cancel(cancel.token.clone()))?)
+}
+
+/// Longest a `UniFFI` wrapper blocks on drop while cancelling its request
+#[cfg(feature = "ffi")]
+const CANCEL_ON_DROP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Cancels an unfinished cancel-on-drop request on the wrapper's runtime
+/// before that runtime shuts down.
+///
+/// The wrapper's runtime is dropped before its connection, so the
+/// connection's own `Drop` would find no runtime to spawn on. When the
+/// wrapper is released inside some other runtime, blocking would panic;
+/// the connection's `Drop` then spawns the `DELETE` on that runtime instead.
+#[cfg(feature = "ffi")]
+fn close_on_drop_blocking(runtime: &tokio::runtime::Runtime, inner: &BridgeConnection) {
+    if !inner.cancel_on_drop || tokio::runtime::Handle::try_current().is_ok() {
+        return;
+    }
+    // The timer must be created inside the runtime it runs on
+    let _ = runtime
+        .block_on(async { tokio::time::timeout(CANCEL_ON_DROP_TIMEOUT, inner.close()).await });
+}
+
+/// Polls once on the wrapper's runtime, acknowledging confirmed responses when enabled.
+///
+/// Acknowledgment failures are non-fatal: the proof is still returned and the
+/// bridge falls back to expiring the response on its TTL.
+#[cfg(feature = "ffi")]
+fn poll_status_once_blocking(
+    runtime: &tokio::runtime::Runtime,
+    inner: &BridgeConnection,
+    auto_acknowledge: bool,
+) -> StatusWrapper {
+    match runtime.block_on(inner.poll_for_status()) {
+        Ok(status) => {
+            if auto_acknowledge && matches!(status, Status::Confirmed(_)) {
+                runtime.block_on(inner.acknowledge_non_fatal());
+            }
+            status.into()
+        }
+        Err(err) => {
+            let app_error = to_app_error(&err);
+            if is_networking_error(&err) {
+                StatusWrapper::NetworkingError { error: app_error }
+            } else {
+                StatusWrapper::failed(app_error)
+            }
         }
     }
 }
@@ -2201,15 +5646,21 @@ fn to_app_error(error: &Error) -> AppError {
     match error {
         Error::InvalidConfiguration(_) => AppError::MalformedRequest,
         Error::BridgeError(_) => AppError::ConnectionFailed,
+        Error::BridgeHttp { status: 400, .. } => AppError::MalformedRequest,
+        Error::BridgeHttp { status: 429, .. } => AppError::RateLimited,
+        Error::BridgeHttp { .. } => AppError::ConnectionFailed,
         Error::Json(_) => AppError::UnexpectedResponse,
         Error::Crypto(_) => AppError::UnexpectedResponse,
         Error::Base64(_) => AppError::UnexpectedResponse,
         Error::Url(_) => AppError::ConnectionFailed,
-        Error::AppError(app_error) => *app_error,
+        Error::AppError(app_error) => app_error.clone(),
         Error::UnexpectedResponse => AppError::UnexpectedResponse,
         Error::ConnectionFailed => AppError::ConnectionFailed,
         Error::Timeout => AppError::ConnectionFailed,
-        Error::InvalidProof(_) => AppError::UnexpectedResponse,
+        Error::Cancelled | Error::ProofAlreadyConsumed => AppError::GenericError,
+        Error::InclusionPending { .. } => AppError::InclusionProofPending,
+        Error::InvalidProof(_) | Error::ResponseDecryption { .. } => AppError::UnexpectedResponse,
+        Error::BridgeUnhealthy { .. } => AppError::ConnectionFailed,
         #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
         Error::Http(_) => AppError::ConnectionFailed,
     }
@@ -2222,6 +5673,10 @@ fn to_app_error(error: &Error) -> AppError {
 fn is_networking_error(error: &Error) -> bool {
     match error {
         Error::Timeout | Error::ConnectionFailed | Error::BridgeError(_) => true,
+        // Rate limiting and bridge-side failures clear up on their own
+        Error::BridgeHttp { status, .. } => *status == 429 || *status >= 500,
+        Error::ResponseDecryption { kind, .. } => kind.is_retryable(),
+        Error::BridgeUnhealthy { kind } => kind.is_retryable(),
         #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
         Error::Http(err) => err.is_timeout() || err.is_request(),
         _ => false,
@@ -2245,12 +5700,54 @@ impl IDKitRequestWrapper {
         }
     }
 
+    /// Checks that a confirmed result only contains credentials this request
+    /// accepts and that they satisfy its constraints.
+    ///
+    /// This does not verify the proofs themselves; send them to the Developer
+    /// Portal or verify them on-chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IdkitError::InvalidProof` if a credential was not requested or
+    /// the constraints are not satisfied
+    pub fn verify_confirmed_proof(
+        &self,
+        result: IDKitResult,
+    ) -> std::result::Result<(), crate::error::IdkitError> {
+        self.inner
+            .verify_confirmed_proof(&result)
+            .map_err(Into::into)
+    }
+
+    /// Returns the `worldapp://` deep link, which skips the browser hop when
+    /// World App is installed
+    #[must_use]
+    pub fn deep_link_url(&self) -> String {
+        self.inner.deep_link_url()
+    }
+
     /// Returns the request ID for this request
     #[must_use]
     pub fn request_id(&self) -> String {
         self.inner.request_id().to_string()
     }
 
+    /// Returns the latest confirmed proof without consuming it
+    #[must_use]
+    pub fn peek_proof(&self) -> Option<IDKitResult> {
+        self.inner.peek_proof()
+    }
+
+    /// Returns the status changes polls have seen, oldest first
+    #[must_use]
+    pub fn status_history(&self) -> Vec<StatusTransitionWrapper> {
+        self.inner
+            .status_history()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
     /// Polls the request once for the current status.
     ///
     /// This method preserves the existing FFI signature for compatibility.
@@ -2266,17 +5763,63 @@ impl IDKitRequestWrapper {
 
     /// Polls the request exactly once for updates.
     pub fn poll_status_once(&self) -> StatusWrapper {
-        match self.runtime.block_on(self.inner.poll_for_status()) {
-            Ok(status) => status.into(),
-            Err(err) => {
-                let app_error = to_app_error(&err);
-                if is_networking_error(&err) {
-                    StatusWrapper::NetworkingError { error: app_error }
-                } else {
-                    StatusWrapper::Failed { error: app_error }
-                }
-            }
-        }
+        poll_status_once_blocking(
+            &self.runtime,
+            &self.inner,
+            self.auto_acknowledge.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Enables or disables acknowledging confirmed responses automatically
+    /// (enabled by default).
+    pub fn set_auto_acknowledge(&self, enabled: bool) {
+        self.auto_acknowledge.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Blocks until World App responds, the default timeout passes, or
+    /// `cancel` is triggered from another thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IdkitError::Cancelled` if cancelled, `IdkitError::Timeout` on
+    /// timeout, `IdkitError::InclusionPending` if the inclusion proof is still
+    /// pending, or the World App or polling error otherwise
+    pub fn wait_for_proof_with_cancel(
+        &self,
+        cancel: Arc<CancelHandle>,
+    ) -> std::result::Result<IDKitResult, crate::error::IdkitError> {
+        wait_for_proof_blocking(
+            &self.runtime,
+            &self.inner,
+            self.auto_acknowledge.load(Ordering::Relaxed),
+            &cancel,
+        )
+    }
+
+    /// Like `wait_for_proof_with_cancel`, and also reports which constraint
+    /// branch the proof satisfied
+    ///
+    /// # Errors
+    ///
+    /// Same as `wait_for_proof_with_cancel`
+    pub fn wait_for_result_with_cancel(
+        &self,
+        cancel: Arc<CancelHandle>,
+    ) -> std::result::Result<VerifiedResult, crate::error::IdkitError> {
+        let proof = self.wait_for_proof_with_cancel(cancel)?;
+        let branch = self.inner.satisfied_branch(&proof);
+        Ok(VerifiedResult { proof, branch })
+    }
+
+    /// Acknowledges the completed response so the bridge can delete it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge rejects the acknowledgment
+    pub fn acknowledge(&self) -> std::result::Result<(), crate::error::IdkitError> {
+        self.runtime
+            .block_on(self.inner.acknowledge())
+            .map_err(crate::error::IdkitError::from)
     }
 }
 
@@ -2295,6 +5838,14 @@ impl IDKitRequestWrapper {
 pub struct IDKitInviteCodeRequest {
     runtime: tokio::runtime::Runtime,
     inner: BridgeConnection,
+    auto_acknowledge: AtomicBool,
+}
+
+#[cfg(feature = "ffi")]
+impl Drop for IDKitInviteCodeRequest {
+    fn drop(&mut self) {
+        close_on_drop_blocking(&self.runtime, &self.inner);
+    }
 }
 
 #[cfg(feature = "ffi")]
@@ -2311,6 +5862,14 @@ impl IDKitInviteCodeRequest {
         self.inner.connect_url()
     }
 
+    /// Returns the `worldapp://` deep link, which skips the browser hop when
+    /// World App is installed. Carries the same `c` and `a` params as
+    /// `connect_url()`.
+    #[must_use]
+    pub fn deep_link_url(&self) -> String {
+        self.inner.deep_link_url()
+    }
+
     /// Unix-seconds expiry of the unredeemed code.
     ///
     /// # Panics
@@ -2329,6 +5888,22 @@ impl IDKitInviteCodeRequest {
         self.inner.request_id().to_string()
     }
 
+    /// Returns the latest confirmed proof without consuming it
+    #[must_use]
+    pub fn peek_proof(&self) -> Option<IDKitResult> {
+        self.inner.peek_proof()
+    }
+
+    /// Returns the status changes polls have seen, oldest first
+    #[must_use]
+    pub fn status_history(&self) -> Vec<StatusTransitionWrapper> {
+        self.inner
+            .status_history()
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
     /// Polls the request once for the current status.
     ///
     /// `poll_interval_ms` and `timeout_ms` are accepted for signature parity
@@ -2344,28 +5919,74 @@ impl IDKitInviteCodeRequest {
 
     /// Polls the request exactly once for updates.
     pub fn poll_status_once(&self) -> StatusWrapper {
-        match self.runtime.block_on(self.inner.poll_for_status()) {
-            Ok(status) => status.into(),
-            Err(err) => {
-                let app_error = to_app_error(&err);
-                if is_networking_error(&err) {
-                    StatusWrapper::NetworkingError { error: app_error }
-                } else {
-                    StatusWrapper::Failed { error: app_error }
-                }
-            }
-        }
+        poll_status_once_blocking(
+            &self.runtime,
+            &self.inner,
+            self.auto_acknowledge.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Enables or disables acknowledging confirmed responses automatically
+    /// (enabled by default).
+    pub fn set_auto_acknowledge(&self, enabled: bool) {
+        self.auto_acknowledge.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Blocks until World App responds, the default timeout passes, or
+    /// `cancel` is triggered from another thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IdkitError::Cancelled` if cancelled, `IdkitError::Timeout` on
+    /// timeout, `IdkitError::InclusionPending` if the inclusion proof is still
+    /// pending, or the World App or polling error otherwise
+    pub fn wait_for_proof_with_cancel(
+        &self,
+        cancel: Arc<CancelHandle>,
+    ) -> std::result::Result<IDKitResult, crate::error::IdkitError> {
+        wait_for_proof_blocking(
+            &self.runtime,
+            &self.inner,
+            self.auto_acknowledge.load(Ordering::Relaxed),
+            &cancel,
+        )
+    }
+
+    /// Like `wait_for_proof_with_cancel`, and also reports which constraint
+    /// branch the proof satisfied
+    ///
+    /// # Errors
+    ///
+    /// Same as `wait_for_proof_with_cancel`
+    pub fn wait_for_result_with_cancel(
+        &self,
+        cancel: Arc<CancelHandle>,
+    ) -> std::result::Result<VerifiedResult, crate::error::IdkitError> {
+        let proof = self.wait_for_proof_with_cancel(cancel)?;
+        let branch = self.inner.satisfied_branch(&proof);
+        Ok(VerifiedResult { proof, branch })
+    }
+
+    /// Acknowledges the completed response so the bridge can delete it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge rejects the acknowledgment
+    pub fn acknowledge(&self) -> std::result::Result<(), crate::error::IdkitError> {
+        self.runtime
+            .block_on(self.inner.acknowledge())
+            .map_err(crate::error::IdkitError::from)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{
-        io::{Read, Write},
-        str::FromStr,
-    };
+    use std::str::FromStr;
 
+    use super::mock::{MockBridge, MockStatus};
     use super::*;
+    use crate::crypto::encrypt;
+    use crate::test_utils::server::CannedServer;
     use crate::types::{CredentialRequest, CredentialType, IntegritySignatureFormat, Signal};
 
     fn payload_json(params: &BridgeConnectionParams, native: bool) -> serde_json::Value {
@@ -2416,11 +6037,11 @@ mod tests {
         };
 
         let payload = BridgeRequestPayload {
-            app_id: "app_test".to_string(),
-            package_name: "idkit_core".to_string(),
-            package_version: "1.2.3".to_string(),
+            app_id: "app_test",
+            package_name: "idkit_core",
+            package_version: "1.2.3",
             action: Some("test-action".to_string()),
-            action_description: Some("Test description".to_string()),
+            action_description: Some("Test description"),
             signal: String::new(),
             verification_level: VerificationLevel::Device,
             timestamp: None,
@@ -2430,6 +6051,7 @@ mod tests {
             require_user_presence: false,
             environment: Environment::Production,
             return_to: None,
+            locale: None,
         };
 
         let json = serde_json::to_string(&payload).unwrap();
@@ -2444,6 +6066,126 @@ mod tests {
         assert!(json.contains("require_user_presence"));
     }
 
+    #[test]
+    fn test_proof_request_timestamp_validation() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.constraints = Some(ConstraintNode::item(CredentialRequest::new(
+            CredentialType::ProofOfHuman,
+            None,
+        )));
+
+        // Valid: expires strictly after creation, and round-trips through JSON
+        let proof_request = build_request_payload(&params, false)
+            .unwrap()
+            .proof_request
+            .unwrap();
+        proof_request.validate_timestamps().unwrap();
+        let json = serde_json::to_value(&proof_request).unwrap();
+        let parsed = ProofRequest::from_json_validated(&json.to_string(), false).unwrap();
+        assert_eq!(parsed.expires_at, 1_700_003_600);
+
+        // Zero TTL: expires_at == created_at
+        let mut zero_ttl = json.clone();
+        zero_ttl["expires_at"] = serde_json::json!(1_700_000_000);
+        let err = ProofRequest::from_json_validated(&zero_ttl.to_string(), false).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfiguration(msg) if msg.contains("expiry")));
+
+        // Already expired: expires_at before created_at, even when the RP context bypassed
+        // `RpContext::new`
+        params.rp_context.expires_at = 1_699_999_999;
+        let err = build_request_payload(&params, false).unwrap_err();
+        assert!(matches!(err, Error::InvalidConfiguration(msg) if msg.contains("expiry")));
+        let mut expired = json;
+        expired["expires_at"] = serde_json::json!(1_699_999_999);
+        assert!(ProofRequest::from_json_validated(&expired.to_string(), false).is_err());
+    }
+
+    #[test]
+    fn test_proof_request_constraint_references() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.constraints = Some(ConstraintNode::any(vec![
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None)),
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Mnc, None)),
+        ]));
+        let proof_request = build_request_payload(&params, false)
+            .unwrap()
+            .proof_request
+            .unwrap();
+
+        // Matching: constraints are derived from the same tree as the requests
+        proof_request.validate_constraint_references().unwrap();
+
+        // Superset: unreferenced request items are allowed
+        let mut superset = proof_request.clone();
+        let mut extra = superset.requests[0].clone();
+        extra.identifier = "proof_of_human".to_string();
+        superset.requests.push(extra);
+        superset.validate_constraint_references().unwrap();
+
+        // Disjoint: every referenced credential is reported
+        let mut json = serde_json::to_value(&proof_request).unwrap();
+        json["constraints"] = serde_json::json!({ "any": ["document", "device"] });
+        let err = ProofRequest::from_json_validated(&json.to_string(), false).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidConfiguration(msg) if msg.ends_with("device, document")
+        ));
+
+        // Opting out accepts references to credentials this SDK does not know
+        ProofRequest::from_json_validated(&json.to_string(), true).unwrap();
+    }
+
+    #[test]
+    fn test_build_request_payload_normalizes_single_child_constraints() {
+        let item =
+            |credential_type| ConstraintNode::item(CredentialRequest::new(credential_type, None));
+        let constraints_json = |constraints: ConstraintNode, normalize: bool| {
+            let mut params = sample_create_params(BridgeUrl::default());
+            params.constraints = Some(constraints);
+            params.normalize_constraints = normalize;
+            let proof_request = build_request_payload(&params, false)
+                .unwrap()
+                .proof_request
+                .unwrap();
+            let json = serde_json::to_value(&proof_request).unwrap();
+            // Both forms are accepted on the way back in
+            ProofRequest::from_json_validated(&json.to_string(), false).unwrap();
+            json.get("constraints").cloned().unwrap_or_default()
+        };
+
+        // Single credential: the `any` wrapper is dropped, matching a bare item
+        let single = || ConstraintNode::any(vec![item(CredentialType::ProofOfHuman)]);
+        assert_eq!(
+            constraints_json(single(), false),
+            serde_json::json!({ "any": ["proof_of_human"] })
+        );
+        assert_eq!(
+            constraints_json(single(), true),
+            constraints_json(item(CredentialType::ProofOfHuman), true)
+        );
+
+        // Several credentials: only the nested single-child wrapper collapses
+        let multi = || {
+            ConstraintNode::all(vec![
+                ConstraintNode::any(vec![item(CredentialType::ProofOfHuman)]),
+                ConstraintNode::any(vec![
+                    item(CredentialType::Passport),
+                    item(CredentialType::Mnc),
+                ]),
+            ])
+        };
+        assert_eq!(
+            constraints_json(multi(), false),
+            serde_json::json!({
+                "all": [{ "any": ["proof_of_human"] }, { "any": ["passport", "mnc"] }]
+            })
+        );
+        assert_eq!(
+            constraints_json(multi(), true),
+            serde_json::json!({ "all": ["proof_of_human", { "any": ["passport", "mnc"] }] })
+        );
+    }
+
     #[test]
     fn test_build_request_payload_serializes_selfie_v4_request() {
         let app_id = AppId::new("app_test").unwrap();
@@ -2466,7 +6208,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "9.9.9".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -2476,10 +6218,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -2531,10 +6277,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -2573,10 +6323,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let prove_payload = payload_json(&prove_params, false);
@@ -2608,7 +6362,7 @@ mod tests {
             package_name: "idkit_swift".to_string(),
             package_version: "4.0.9".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -2618,13 +6372,17 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: Some(vec![
                 IdentityAttribute::MinimumAge(21),
                 IdentityAttribute::Nationality("JPN".to_string()),
             ]),
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -2660,7 +6418,7 @@ mod tests {
             package_name: "idkit_swift".to_string(),
             package_version: "4.0.9".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -2670,13 +6428,17 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: true,
             require_user_presence: true,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: Some("idkitsample://callback".to_string()),
+            return_to_schemes: vec!["idkitsample".to_string()],
             environment: Some(Environment::Staging),
             identity_attributes: Some(vec![
                 IdentityAttribute::MinimumAge(21),
                 IdentityAttribute::Nationality("JPN".to_string()),
             ]),
+            locale: None,
         };
 
         let payload =
@@ -2780,6 +6542,7 @@ mod tests {
             let payload = EncryptedPayload {
                 iv: base64_encode(&nonce_bytes),
                 payload: base64_encode(&encrypted),
+                compressed: false,
             };
 
             assert!(!payload.iv.is_empty());
@@ -2803,6 +6566,7 @@ mod tests {
             let payload = EncryptedPayload {
                 iv: base64_encode(&iv),
                 payload: base64_encode(&encrypted),
+                compressed: false,
             };
 
             assert!(!payload.iv.is_empty());
@@ -3030,6 +6794,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bridge_response_v2_default_proof_is_rejected() {
+        let json = format!(
+            r#"{{
+                "id": "req_abc123",
+                "version": 1,
+                "responses": [{{
+                    "identifier": "orb",
+                    "issuer_schema_id": 1,
+                    "proof": "{ZERO_PROOF}",
+                    "nullifier": "{ZERO_NULLIFIER}",
+                    "expires_at_min": 1735689600
+                }}]
+            }}"#
+        );
+
+        let proof_response: ProofResponse = serde_json::from_str(&json).unwrap();
+        let err = sample_connection(None)
+            .handle_bridge_v2_response(proof_response, None, None, false)
+            .unwrap_err();
+
+        assert!(matches!(err, Error::InvalidProof(message) if message.contains("orb")));
+    }
+
     #[test]
     fn test_bridge_response_v2_multiple_credentials() {
         let json = format!(
@@ -3218,9 +7006,54 @@ mod tests {
     }
 
     #[test]
-    fn test_bridge_response_multi_legacy_identity_attested() {
-        let json = r#"{
-            "legacy_responses": [
+    fn test_poll_accepts_single_and_array_legacy_payloads() {
+        let orb = serde_json::json!({
+            "proof": "0xproof1",
+            "merkle_root": "0xroot1",
+            "nullifier_hash": "0xnull1",
+            "verification_level": "orb",
+        });
+        let device = serde_json::json!({
+            "proof": "0xproof2",
+            "merkle_root": "0xroot2",
+            "nullifier_hash": "0xnull2",
+            "verification_level": "device",
+        });
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Completed(orb.clone()),
+                MockStatus::Completed(serde_json::json!([orb, device])),
+                MockStatus::Completed(serde_json::json!([])),
+            ],
+        );
+
+        let Status::Confirmed(single) = poll_once(&connection).unwrap() else {
+            panic!("expected a confirmed status");
+        };
+        assert_eq!(single.responses.len(), 1);
+
+        let Status::Confirmed(multiple) = poll_once(&connection).unwrap() else {
+            panic!("expected a confirmed status");
+        };
+        assert_eq!(multiple.protocol_version, "3.0");
+        assert_eq!(multiple.responses.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&multiple.responses).unwrap()[1]["nullifier"],
+            "0xnull2"
+        );
+
+        assert!(matches!(
+            poll_once(&connection),
+            Err(Error::UnexpectedResponse)
+        ));
+    }
+
+    #[test]
+    fn test_bridge_response_multi_legacy_identity_attested() {
+        let json = r#"{
+            "legacy_responses": [
                 {
                     "proof": "0xproof",
                     "merkle_root": "0xroot",
@@ -3273,6 +7106,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bridge_response_unknown_error_code_is_preserved() {
+        let unknown: BridgeResponse =
+            serde_json::from_str(r#"{"error_code": "verification_cancelled_v2"}"#).unwrap();
+        let BridgeResponse::Error { error_code } = unknown else {
+            panic!("expected an error response");
+        };
+
+        assert_eq!(
+            error_code,
+            AppError::Other("verification_cancelled_v2".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&error_code).unwrap(),
+            serde_json::json!("verification_cancelled_v2")
+        );
+        assert_eq!(AppError::from_code("verification_cancelled_v2"), error_code);
+        assert_eq!(
+            serde_json::from_str::<AppError>(r#""generic_error""#).unwrap(),
+            AppError::GenericError
+        );
+
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Completed(
+                serde_json::json!({"error_code": "verification_cancelled_v2"}),
+            )],
+        );
+        assert_eq!(
+            poll_once(&connection).unwrap(),
+            Status::Failed(AppError::Other("verification_cancelled_v2".to_string()))
+        );
+    }
+
     #[test]
     fn test_bridge_response_new_error_codes_deserialization() {
         let invalid_signature: BridgeResponse =
@@ -3310,6 +7178,8 @@ mod tests {
             ),
             ("invalid_timestamp", AppError::InvalidTimestamp),
             ("rp_signature_expired", AppError::RpSignatureExpired),
+            ("request_not_found", AppError::RequestNotFound),
+            ("rate_limited", AppError::RateLimited),
             (
                 "identity_attributes_not_matched",
                 AppError::IdentityAttributesNotMatched,
@@ -3356,6 +7226,16 @@ mod tests {
             return_to: None,
             environment: None,
             connect_url_mode: None,
+            connect_timeout_ms: None,
+            request_timeout_ms: None,
+            normalize_constraints: None,
+            compress_payload: None,
+            cancel_on_drop: None,
+            single_consumption: None,
+            check_constraints: None,
+            session_ttl_ms: None,
+            locale: None,
+            return_to_schemes: None,
         });
 
         let params = config
@@ -3388,7 +7268,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: bridge_params.constraints,
             rp_context,
@@ -3400,17 +7280,40 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
 
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
         assert_eq!(payload["verification_level"], serde_json::json!("face"));
     }
 
+    #[test]
+    fn test_presets_without_signal_omit_request_signal() {
+        let request_item = |signal: Option<&str>| {
+            let bridge_params = crate::preset::Preset::proof_of_human(signal.map(str::to_string))
+                .into_bridge_params();
+            let mut params = sample_create_params(BridgeUrl::default());
+            params.constraints = bridge_params.constraints;
+            params.legacy_signal = bridge_params.legacy_signal.unwrap_or_default();
+            payload_json(&params, false)["proof_request"]["proof_requests"][0].clone()
+        };
+
+        for signal in [None, Some("")] {
+            let item = request_item(signal);
+            assert_eq!(item["identifier"], "proof_of_human");
+            assert!(item.get("signal").is_none(), "{item}");
+        }
+        assert!(request_item(Some("user-123")).get("signal").is_some());
+    }
+
     #[test]
     fn test_device_legacy_preset_serializes_device_verification_level() {
         let preset = crate::preset::Preset::device_legacy(Some("device-signal".to_string()));
@@ -3432,7 +7335,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: bridge_params.constraints,
             rp_context,
@@ -3444,11 +7347,15 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
 
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -3476,7 +7383,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: bridge_params.constraints,
             rp_context,
@@ -3488,10 +7395,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: bridge_params.allow_legacy_proofs_override.unwrap_or(false),
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -3524,7 +7435,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "test-action".to_string(),
+                action: crate::Action::new("test-action").unwrap(),
             },
             constraints: bridge_params.constraints,
             rp_context,
@@ -3536,10 +7447,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: bridge_params.allow_legacy_proofs_override.unwrap_or(false),
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: Some(Environment::Production),
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -3596,7 +7511,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -3606,11 +7521,15 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
 
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = build_native_v1_payload(&params).unwrap();
@@ -3645,7 +7564,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -3655,10 +7574,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         // native=true includes timestamp
@@ -3699,7 +7622,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: Some(constraints),
             rp_context,
@@ -3709,10 +7632,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: true,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         let bridge_payload = payload_json(&params, false);
@@ -3728,6 +7655,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_request_payload_synthesizes_user_presence_from_leaves() {
+        let plain = || ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None));
+        let attributed = || {
+            ConstraintNode::item(
+                CredentialRequest::new(CredentialType::ProofOfHuman, None).with_user_presence(true),
+            )
+        };
+
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.constraints = Some(ConstraintNode::any(vec![attributed(), plain()]));
+        assert!(params.requires_user_presence());
+        assert_eq!(
+            payload_json(&params, false)["require_user_presence"],
+            serde_json::json!(true)
+        );
+
+        params.constraints = Some(ConstraintNode::any(vec![plain()]));
+        assert_eq!(
+            payload_json(&params, false)["require_user_presence"],
+            serde_json::json!(false)
+        );
+
+        // A negated leaf is never presented, so its attributes do not apply
+        params.constraints = Some(ConstraintNode::all(vec![
+            plain(),
+            ConstraintNode::Not {
+                not: Box::new(attributed()),
+            },
+        ]));
+        assert!(!params.requires_user_presence());
+    }
+
     #[test]
     fn test_build_request_payload_includes_return_to_when_provided() {
         let app_id = AppId::new("app_test").unwrap();
@@ -3746,7 +7706,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: None,
             rp_context,
@@ -3756,10 +7716,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: Some("idkitsample://callback".to_string()),
+            return_to_schemes: vec!["idkitsample".to_string()],
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -3767,6 +7731,124 @@ mod tests {
         assert!(payload.get("return_to").is_none());
     }
 
+    #[test]
+    fn test_build_request_payload_includes_locale_when_provided() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        assert!(payload_json(&params, false).get("locale").is_none());
+
+        for locale in ["en", "pt-BR"] {
+            params = params.with_locale(locale).unwrap();
+            assert_eq!(payload_json(&params, false)["locale"], locale);
+        }
+    }
+
+    #[test]
+    fn test_build_request_payload_rejects_malformed_locale() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        for locale in ["english_US", "", "EN", "en-us", "eng", "en-USA", "en-US-x"] {
+            assert!(sample_create_params(BridgeUrl::default())
+                .with_locale(locale)
+                .is_err());
+            params.locale = Some(locale.to_string());
+            let Err(Error::InvalidConfiguration(message)) = build_request_payload(&params, false)
+            else {
+                panic!("{locale:?} should be rejected");
+            };
+            assert!(message.contains("en-US"), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_build_request_payload_uses_normalized_action() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.kind = RequestKind::Uniqueness {
+            action: crate::Action::new("cafe\u{301}").unwrap(),
+        };
+
+        let payload = build_request_payload(&params, false).unwrap();
+        assert_eq!(payload.action.as_deref(), Some("caf\u{e9}"));
+        assert_eq!(
+            build_native_v1_payload(&params).unwrap()["action"],
+            "caf\u{e9}"
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_request_config_normalizes_action() {
+        let mut config = sample_request_config(BridgeUrl::default());
+        config.action = "cafe\u{301}".to_string();
+        let params = IDKitConfig::Request(config.clone())
+            .to_params(ConstraintNode::Any { any: Vec::new() })
+            .unwrap();
+        let RequestKind::Uniqueness { action } = params.kind else {
+            panic!("expected a uniqueness request");
+        };
+        assert_eq!(action.as_str(), "caf\u{e9}");
+
+        for invalid in ["login ", "log\u{200B}in", ""] {
+            config.action = invalid.to_string();
+            assert!(
+                IDKitConfig::Request(config.clone())
+                    .to_params(ConstraintNode::Any { any: Vec::new() })
+                    .is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_return_to() {
+        let schemes = ["idkitsample".to_string()];
+        assert!(validate_return_to("https://example.com/done?step=1", &[]).is_ok());
+        assert!(validate_return_to("idkitsample://callback?step=proof", &schemes).is_ok());
+        assert!(validate_return_to("IDKitSample://callback", &schemes).is_ok());
+        assert!(validate_return_to("  ", &[]).is_ok());
+
+        for invalid in [
+            "/relative/path",
+            "example.com/callback",
+            "http://example.com/callback",
+            "javascript:alert(1)",
+            "data:text/html,hi",
+            "file:///etc/passwd",
+            "otherapp://callback",
+            "intent://callback#Intent;end",
+        ] {
+            assert!(
+                matches!(
+                    validate_return_to(invalid, &schemes),
+                    Err(Error::InvalidConfiguration(_))
+                ),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_request_payload_requires_custom_return_to_schemes_to_be_listed() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.return_to = Some("idkitsample://callback".to_string());
+        assert!(matches!(
+            build_request_payload(&params, false),
+            Err(Error::InvalidConfiguration(_))
+        ));
+
+        params.return_to_schemes = vec!["idkitsample".to_string()];
+        assert!(build_request_payload(&params, false).is_ok());
+    }
+
+    #[test]
+    fn test_build_request_payload_rejects_invalid_return_to() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.return_to = Some("http://example.com/callback".to_string());
+
+        assert!(matches!(
+            build_request_payload(&params, false),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
     #[test]
     fn test_build_request_payload_omits_return_to_when_none() {
         let app_id = AppId::new("app_test").unwrap();
@@ -3785,7 +7867,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: None,
             rp_context,
@@ -3795,10 +7877,14 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         let payload = payload_json(&params, false);
@@ -3829,7 +7915,7 @@ mod tests {
             package_name: "idkit_test".to_string(),
             package_version: "1.0.0".to_string(),
             kind: RequestKind::Uniqueness {
-                action: "my-action".to_string(),
+                action: crate::Action::new("my-action").unwrap(),
             },
             constraints: None,
             rp_context,
@@ -3839,15 +7925,19 @@ mod tests {
             bridge_url: None,
             allow_legacy_proofs: false,
             require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
 
             override_connect_base_url: None,
             return_to: None,
+            return_to_schemes: Vec::new(),
             environment: None,
             identity_attributes: None,
+            locale: None,
         };
 
         let cached = CachedSignalHashes::compute(&params);
-        assert_eq!(cached.legacy_signal_hash, expected);
+        assert_eq!(cached.legacy_signal_hash, Some(expected.clone()));
 
         let bridge_payload = payload_json(&params, false);
         assert_eq!(bridge_payload["signal"], expected);
@@ -3859,18 +7949,47 @@ mod tests {
         assert_eq!(native_v1_payload["signal"], expected);
     }
 
+    #[test]
+    fn test_cached_signal_hashes_skip_empty_signals() {
+        // Built through serde so the empty signal bypasses constructor normalization
+        let empty: CredentialRequest =
+            serde_json::from_value(serde_json::json!({"type": "proof_of_human", "signal": ""}))
+                .unwrap();
+        let whitespace =
+            CredentialRequest::new(CredentialType::Selfie, Some(Signal::from_string(" ")));
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.constraints = Some(ConstraintNode::any(vec![
+            ConstraintNode::item(empty),
+            ConstraintNode::item(whitespace),
+        ]));
+        params.legacy_signal = String::new();
+
+        let cached = CachedSignalHashes::compute(&params);
+
+        assert_eq!(cached.get("proof_of_human"), None);
+        assert_eq!(
+            cached.get("selfie"),
+            Some(crate::crypto::hash_signal(&Signal::from_string(" ")))
+        );
+        assert_eq!(
+            cached.legacy().unwrap(),
+            crate::crypto::hash_signal(&Signal::from_string(""))
+        );
+    }
+
     fn sample_connection(return_to: Option<String>) -> BridgeConnection {
         BridgeConnection {
             bridge_url: BridgeUrl::default(),
             #[cfg(feature = "native-crypto")]
             key: crate::crypto::CryptoKey::new([0; 32], [0; 12]),
-            key_bytes: vec![0; 32],
+            key_bytes: SecretBytes::new([0; 32]),
             request_id: "64e0ec6b-b4ca-47cc-8f70-504a95189e26".to_string(),
             app_id: "app_test".to_string(),
-            client: reqwest::Client::new(),
+            transport: Arc::new(ReqwestTransport::new(reqwest::Client::new())),
+            clock: Arc::new(SystemClock),
             cached_signal_hashes: CachedSignalHashes {
                 signal_hashes: std::collections::HashMap::new(),
-                legacy_signal_hash: String::new(),
+                legacy_signal_hash: Some(String::new()),
             },
             action: Some("test-action".to_string()),
             action_description: None,
@@ -3879,34 +7998,67 @@ mod tests {
             return_to,
             environment: Environment::Production,
             require_user_presence: false,
+            constraints: None,
+            legacy_verification_level: None,
+            check_constraints: true,
             request_payload: serde_json::json!({
                 "app_id": "app_test",
                 "action": "test-action",
             }),
+            encrypted_request: EncryptedPayload {
+                iv: String::new(),
+                payload: String::new(),
+                compressed: false,
+            },
             latest_bridge_payload: Mutex::new(None),
+            acknowledged: AtomicBool::new(false),
+            auto_ack: AtomicBool::new(true),
+            finished: AtomicBool::new(false),
+            cancel_on_drop: false,
+            single_consumption: false,
+            consumed: AtomicBool::new(false),
+            latest_proof: Mutex::new(None),
+            status_history: Mutex::new(Vec::new()),
+            idempotency_key: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            created_at: None,
+            session_ttl: BRIDGE_REQUEST_TTL,
             invite_code: None,
             code_expires_at: None,
         }
     }
 
-    fn serve_bridge_response(body: String) -> BridgeUrl {
-        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
-        let addr = listener.local_addr().unwrap();
-        std::thread::spawn(move || {
-            let (mut stream, _) = listener.accept().unwrap();
-            let mut request_buffer = [0; 1024];
-            let _ = stream.read(&mut request_buffer);
-            write!(
-                stream,
-                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
-                body.len(),
-                body
-            )
-            .unwrap();
-        });
-
+    /// Starts a mock bridge that staging apps may point requests at
+    fn start_mock_bridge() -> (BridgeUrl, MockBridge) {
+        let bridge = MockBridge::start().unwrap();
         let app_id = AppId::new("app_staging_test").unwrap();
-        BridgeUrl::new(format!("http://{addr}"), &app_id).unwrap()
+        (bridge.bridge_url(&app_id).unwrap(), bridge)
+    }
+
+    /// Points `connection` at a new mock bridge that serves `statuses` to its
+    /// request, one per poll
+    fn mock_bridge_for(
+        connection: &mut BridgeConnection,
+        statuses: impl IntoIterator<Item = MockStatus>,
+    ) -> MockBridge {
+        let (bridge_url, bridge) = start_mock_bridge();
+        connection.bridge_url = bridge_url;
+        bridge.script(connection, statuses);
+        bridge
+    }
+
+    fn encrypted_completed_raw(plaintext: &str) -> String {
+        // Matches the all-zero key used by `sample_connection`
+        let iv = [1u8; 12];
+        let ciphertext = encrypt(&[0; 32], &iv, plaintext.as_bytes()).unwrap();
+        serde_json::json!({
+            "status": "completed",
+            "response": {
+                "iv": base64_encode(&iv),
+                "payload": base64_encode(&ciphertext),
+            },
+        })
+        .to_string()
     }
 
     fn poll_once(connection: &BridgeConnection) -> Result<Status> {
@@ -3959,6 +8111,363 @@ mod tests {
             .starts_with("https://custom.example.com/verify?t=wld"));
     }
 
+    fn query_pairs(url: &str) -> Vec<(String, String)> {
+        url::Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+
+    #[test]
+    fn test_deep_link_url_matches_connect_url_parameters() {
+        let mut plain = sample_connection(None);
+        plain.environment = Environment::Staging;
+
+        let mut full = sample_connection(Some("idkitsample://callback?step=proof".to_string()));
+        full.bridge_url = BridgeUrl::new(
+            "https://bridge.example.com",
+            &AppId::new("app_staging_test").unwrap(),
+        )
+        .unwrap();
+        full.invite_code = Some("ABCD1234".to_string());
+        full.override_connect_base_url = Some("https://custom.example.com/verify".to_string());
+
+        for connection in [plain, full] {
+            let deep_link = connection.deep_link_url();
+            assert!(deep_link.starts_with("worldapp://verify?t=wld"));
+            assert_eq!(
+                query_pairs(&deep_link),
+                query_pairs(&connection.connect_url())
+            );
+        }
+    }
+
+    /// Connection with a distinctive key, so leaks are easy to spot
+    fn connection_with_key(key: [u8; 32]) -> (BridgeConnection, String) {
+        let mut connection = sample_connection(None);
+        #[cfg(feature = "native-crypto")]
+        {
+            connection.key = crate::crypto::CryptoKey::new(key, [0; 12]);
+        }
+        connection.key_bytes = SecretBytes::new(key);
+        (connection, base64_encode(&key))
+    }
+
+    #[test]
+    fn test_connect_url_redacted_replaces_only_key() {
+        let (connection, key_b64) = connection_with_key([0x5a; 32]);
+
+        let redacted = connection.connect_url_redacted();
+        assert!(!redacted.contains(&key_b64));
+
+        let expected: Vec<_> = query_pairs(&connection.connect_url())
+            .into_iter()
+            .map(|(name, value)| {
+                if name == "k" {
+                    assert_eq!(value, key_b64);
+                    (name, "REDACTED".to_string())
+                } else {
+                    (name, value)
+                }
+            })
+            .collect();
+        assert_eq!(query_pairs(&redacted), expected);
+    }
+
+    #[test]
+    fn test_connect_url_parses_default_bridge_url() {
+        // Encodes to `+` and `/`, which must survive percent-encoding
+        let (connection, _) = connection_with_key([0xfb; 32]);
+        let url = connection.connect_url();
+
+        let parsed = ConnectUrl::parse(&url).unwrap();
+        assert_eq!(parsed, connection.typed_connect_url());
+        assert_eq!(parsed.base_url, "https://world.org/verify");
+        assert_eq!(parsed.request_id, connection.request_id());
+        assert_eq!(parsed.key, vec![0xfb; 32]);
+        assert_eq!(parsed.bridge_url, None);
+        assert_eq!(parsed.to_string(), url);
+        assert!(!format!("{parsed:?}").contains(&base64_encode(&parsed.key)));
+
+        let deep_link = ConnectUrl::parse(&connection.deep_link_url()).unwrap();
+        assert_eq!(deep_link.base_url, DEEP_LINK_BASE_URL);
+        assert_eq!(deep_link.to_string(), connection.deep_link_url());
+    }
+
+    #[test]
+    fn test_connect_url_parses_custom_bridge_url() {
+        let mut connection = sample_connection(Some("idkitsample://callback?x=1".to_string()));
+        connection.bridge_url = BridgeUrl::new_strict("https://bridge.example.com").unwrap();
+        let url = connection.connect_url();
+        assert!(url.contains("&b=https%3A%2F%2Fbridge.example.com"));
+
+        let parsed = ConnectUrl::parse(&url).unwrap();
+        assert_eq!(parsed.bridge_url, Some(connection.bridge_url.clone()));
+        assert_eq!(
+            parsed.return_to.as_deref(),
+            Some("idkitsample://callback?x=1")
+        );
+        assert_eq!(parsed.to_string(), url);
+
+        for malformed in [
+            "https://world.org/verify",
+            "not a url?t=wld",
+            "https://world.org/verify?i=abc&k=AAAA",
+            "https://world.org/verify?t=wld&i=abc&k=AAAA",
+            "https://world.org/verify?t=wld&i=a%2Fb&k=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D",
+            "https://world.org/verify?t=wld&i=abc&k=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D&b=http%3A%2F%2Fbridge.example.com",
+        ] {
+            assert!(
+                matches!(ConnectUrl::parse(malformed), Err(Error::InvalidConfiguration(_))),
+                "{malformed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_debug_output_never_contains_key() {
+        let (connection, key_b64) = connection_with_key([0x5a; 32]);
+
+        let debug = format!("{connection:?}");
+        assert!(debug.contains(connection.request_id()));
+        assert!(debug.contains(connection.bridge_url.as_str()));
+        assert!(!debug.contains(&key_b64));
+        assert!(!debug.contains("90, 90, 90"));
+
+        let bundle = connection.support_bundle(SupportBundleKey::IncludeSensitiveKey);
+        assert_eq!(bundle.key.as_deref(), Some(key_b64.as_str()));
+        assert!(!format!("{bundle:?}").contains(&key_b64));
+
+        assert_eq!(format!("{:?}", connection.key_bytes), "REDACTED");
+        #[cfg(feature = "native-crypto")]
+        assert!(!format!("{:?}", connection.key).contains("90, 90, 90"));
+    }
+
+    /// Minimal QR reader for the test below: reads format info, unmasks and
+    /// de-interleaves the data codewords. The function-pattern layout and the
+    /// block structure come from ISO/IEC 18004 rather than from `qrcode`, so
+    /// the test shares no layout code with the encoder it checks. Error
+    /// correction is not applied, since the matrix is read without damage.
+    #[cfg(feature = "qr")]
+    fn read_qr_data(matrix: &[Vec<bool>]) -> (i16, Vec<u8>) {
+        // Error correction codewords per block and block counts for versions
+        // 1 to 10, by level L, M, Q, H (ISO/IEC 18004 table 9)
+        const ECC_PER_BLOCK: [[usize; 10]; 4] = [
+            [7, 10, 15, 20, 26, 18, 20, 24, 30, 18],
+            [10, 16, 26, 18, 24, 16, 18, 22, 22, 26],
+            [13, 22, 18, 26, 18, 24, 18, 22, 20, 24],
+            [17, 28, 22, 16, 22, 28, 26, 26, 24, 28],
+        ];
+        const BLOCKS: [[usize; 10]; 4] = [
+            [1, 1, 1, 1, 1, 2, 2, 2, 2, 4],
+            [1, 1, 1, 2, 2, 4, 4, 4, 5, 5],
+            [1, 1, 2, 2, 4, 4, 6, 6, 8, 8],
+            [1, 1, 2, 4, 4, 4, 5, 6, 8, 8],
+        ];
+
+        let width = i16::try_from(matrix.len()).unwrap();
+        let version_number = (width - 17) / 4;
+        assert!(
+            (1..=10).contains(&version_number),
+            "the test's connect URLs fit in version 10, got {version_number}"
+        );
+        let module =
+            |x: i16, y: i16| matrix[usize::try_from(y).unwrap()][usize::try_from(x).unwrap()];
+
+        // Format info sits around the top-left finder pattern, most significant bit first
+        let format = (0..=5)
+            .map(|x| (x, 8))
+            .chain([(7, 8), (8, 8), (8, 7)])
+            .chain((0..=5).rev().map(|y| (8, y)))
+            .fold(0u16, |acc, (x, y)| (acc << 1) | u16::from(module(x, y)))
+            ^ 0x5412;
+        // Levels are encoded as L = 01, M = 00, Q = 11, H = 10
+        let ec_level = usize::from((format >> 13) ^ 1);
+        let mask = |x: i16, y: i16| match (format >> 10) & 0b111 {
+            0 => (x + y) % 2 == 0,
+            1 => y % 2 == 0,
+            2 => x % 3 == 0,
+            3 => (x + y) % 3 == 0,
+            4 => (y / 2 + x / 3) % 2 == 0,
+            5 => (x * y) % 2 + (x * y) % 3 == 0,
+            6 => ((x * y) % 2 + (x * y) % 3) % 2 == 0,
+            _ => ((x + y) % 2 + (x * y) % 3) % 2 == 0,
+        };
+
+        let is_function = qr_function_modules(version_number);
+
+        // Data modules zigzag up and down two-column strips from the right
+        let mut bits = Vec::new();
+        let mut right = width - 1;
+        let mut upward = true;
+        while right > 0 {
+            if right == 6 {
+                right -= 1;
+            }
+            for i in 0..width {
+                let y = if upward { width - 1 - i } else { i };
+                for x in [right, right - 1] {
+                    if !is_function(x, y) {
+                        bits.push(module(x, y) ^ mask(x, y));
+                    }
+                }
+            }
+            upward = !upward;
+            right -= 2;
+        }
+        let codewords: Vec<u8> = bits
+            .chunks_exact(8)
+            .map(|byte| byte.iter().fold(0, |acc, &bit| (acc << 1) | u8::from(bit)))
+            .collect();
+
+        // Data codewords are interleaved one from each block in turn. Blocks
+        // share the codewords evenly; the last ones get one more each.
+        let index = usize::try_from(version_number - 1).unwrap();
+        let blocks = BLOCKS[ec_level][index];
+        let data_len = codewords.len() - blocks * ECC_PER_BLOCK[ec_level][index];
+        let long_blocks = data_len % blocks;
+        let block_lens: Vec<usize> = (0..blocks)
+            .map(|block| data_len / blocks + usize::from(block >= blocks - long_blocks))
+            .collect();
+        let mut block_data: Vec<Vec<u8>> = vec![Vec::new(); blocks];
+        let mut interleaved = codewords.iter();
+        for position in 0..=data_len / blocks {
+            for (block, &len) in block_data.iter_mut().zip(&block_lens) {
+                if position < len {
+                    block.push(*interleaved.next().unwrap());
+                }
+            }
+        }
+        (version_number, block_data.concat())
+    }
+
+    /// Returns whether a module of a QR code of `version_number` belongs to a
+    /// function pattern rather than to the data
+    #[cfg(feature = "qr")]
+    fn qr_function_modules(version_number: i16) -> impl Fn(i16, i16) -> bool {
+        let width = version_number * 4 + 17;
+        // Alignment patterns are centered on every pair of these coordinates,
+        // except the three that overlap the finder patterns
+        let alignment: Vec<i16> = if version_number == 1 {
+            Vec::new()
+        } else {
+            let count = version_number / 7 + 2;
+            let step = (version_number * 4 + count * 2 + 1) / (count * 2 - 2) * 2;
+            std::iter::once(6)
+                .chain((0..count - 1).rev().map(|i| width - 7 - i * step))
+                .collect()
+        };
+
+        move |x, y| {
+            // Finder patterns, their separators and the format info next to them
+            let finder = (x < 9 && (y < 9 || y >= width - 8)) || (x >= width - 8 && y < 9);
+            let timing = x == 6 || y == 6;
+            let version_info = version_number >= 7
+                && ((x >= width - 11 && x < width - 8 && y < 6)
+                    || (y >= width - 11 && y < width - 8 && x < 6));
+            let alignment_pattern = alignment.iter().any(|&cx| {
+                alignment.iter().any(|&cy| {
+                    let overlaps_finder =
+                        (cx == 6 && (cy == 6 || cy == width - 7)) || (cx == width - 7 && cy == 6);
+                    !overlaps_finder && (x - cx).abs() <= 2 && (y - cy).abs() <= 2
+                })
+            });
+            finder || timing || version_info || alignment_pattern
+        }
+    }
+
+    /// Parses the numeric, alphanumeric and byte segments of QR data codewords
+    #[cfg(feature = "qr")]
+    fn parse_qr_segments(version_number: i16, data: &[u8]) -> String {
+        const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+        let mut reader = data
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |bit| (byte >> bit) & 1 == 1));
+        let mut read = |count: usize| {
+            (0..count).fold(0usize, |acc, _| {
+                (acc << 1) | usize::from(reader.next().unwrap_or(false))
+            })
+        };
+        let size_class = match version_number {
+            1..=9 => 0,
+            10..=26 => 1,
+            _ => 2,
+        };
+
+        let mut decoded = Vec::new();
+        loop {
+            match read(4) {
+                0 => break,
+                0b0001 => {
+                    let mut count = read([10, 12, 14][size_class]);
+                    while count > 0 {
+                        let digits = count.min(3);
+                        let value = read([4, 7, 10][digits - 1]);
+                        decoded.extend(format!("{value:0digits$}").bytes());
+                        count -= digits;
+                    }
+                }
+                0b0010 => {
+                    let mut count = read([9, 11, 13][size_class]);
+                    while count >= 2 {
+                        let value = read(11);
+                        decoded.extend([ALPHANUMERIC[value / 45], ALPHANUMERIC[value % 45]]);
+                        count -= 2;
+                    }
+                    if count == 1 {
+                        decoded.push(ALPHANUMERIC[read(6)]);
+                    }
+                }
+                0b0100 => {
+                    let count = read([8, 16, 16][size_class]);
+                    decoded.extend((0..count).map(|_| u8::try_from(read(8)).unwrap()));
+                }
+                mode => panic!("unsupported QR mode {mode:#06b}"),
+            }
+        }
+        String::from_utf8(decoded).unwrap()
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_connect_url_qr_matrix_decodes_to_connect_url() {
+        let mut plain = sample_connection(None);
+        plain.environment = Environment::Staging;
+        let mut full = sample_connection(Some("idkitsample://callback?step=proof".to_string()));
+        full.invite_code = Some("ABCD1234".to_string());
+
+        for connection in [plain, full] {
+            let matrix = connection.connect_url_qr_matrix().unwrap();
+            assert!(matrix.iter().all(|row| row.len() == matrix.len()));
+            let (version, data) = read_qr_data(&matrix);
+            assert_eq!(parse_qr_segments(version, &data), connection.connect_url());
+        }
+    }
+
+    #[cfg(feature = "qr")]
+    #[test]
+    fn test_connect_url_qr_svg() {
+        let connection = sample_connection(None);
+
+        let svg = connection.connect_url_qr_svg(256).unwrap();
+        assert!(svg.contains("<svg"));
+        let width: u32 = svg
+            .split("width=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(width >= 256);
+
+        assert!(matches!(
+            connection.connect_url_qr_svg(0),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
     #[test]
     fn test_sandbox_environment_serializes_to_snake_case() {
         assert_eq!(Environment::Sandbox.to_string(), "sandbox");
@@ -3975,10 +8484,3053 @@ mod tests {
 
         // Non-completed statuses don't carry a decrypted payload, so the report
         // leaves `response_payload` empty.
-        connection.bridge_url = serve_bridge_response(r#"{"status":"initialized"}"#.to_string());
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
         let status = poll_once(&connection).unwrap();
         assert_eq!(status, Status::WaitingForConnection);
 
         assert_eq!(connection.get_debug_report().response_payload, None);
     }
+
+    #[test]
+    fn test_poll_rejects_placeholder_v1_proof() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Completed(serde_json::json!({
+                "proof": format!("0x{}", "0".repeat(512)),
+                "merkle_root": format!("0x{}", "0".repeat(64)),
+                "nullifier_hash": format!("0x{}", "0".repeat(64)),
+                "verification_level": "orb",
+            }))],
+        );
+
+        let err = poll_once(&connection).unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(_)));
+    }
+
+    #[test]
+    fn test_poll_accepts_non_placeholder_v1_proof() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Completed(serde_json::json!({
+                "proof": "0x1a2b",
+                "merkle_root": "0x2c3d",
+                "nullifier_hash": "0x3e4f",
+                "verification_level": "orb",
+            }))],
+        );
+
+        let status = poll_once(&connection).unwrap();
+        assert!(matches!(status, Status::Confirmed(_)));
+    }
+
+    #[test]
+    fn test_acknowledge_sends_delete_once() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [completed.clone()]);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            assert!(matches!(
+                connection.poll_for_status().await.unwrap(),
+                Status::Confirmed(_)
+            ));
+            connection.acknowledge().await.unwrap();
+
+            // The bridge forgets acknowledged requests, so serve the response
+            // again. Re-polling and re-acknowledging must not hit the ack
+            // endpoint again.
+            bridge.script(&connection, [completed]);
+            assert!(matches!(
+                connection.poll_for_status().await.unwrap(),
+                Status::Confirmed(_)
+            ));
+            connection.acknowledge().await.unwrap();
+        });
+
+        let request_id = connection.request_id();
+        assert_eq!(
+            bridge.requests(),
+            vec![
+                format!("GET /response/{request_id} HTTP/1.1"),
+                format!("DELETE /response/{request_id} HTTP/1.1"),
+                format!("GET /response/{request_id} HTTP/1.1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_acknowledgments_send_delete_once() {
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Retrieved]);
+
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let (first, second) = tokio::join!(connection.acknowledge(), connection.acknowledge());
+            first.unwrap();
+            second.unwrap();
+        });
+
+        assert_eq!(
+            bridge.requests(),
+            [format!(
+                "DELETE /response/{} HTTP/1.1",
+                connection.request_id()
+            )]
+        );
+        assert!(connection.acknowledged.load(Ordering::Acquire));
+    }
+
+    fn sample_create_params(bridge_url: BridgeUrl) -> BridgeConnectionParams {
+        let sig = "0x".to_string() + &"00".repeat(64) + "1b";
+        let rp_context = RpContext::new(
+            "rp_1234567890abcdef",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            1_700_000_000,
+            1_700_003_600,
+            &sig,
+        )
+        .unwrap();
+        BridgeConnectionParams {
+            app_id: AppId::new("app_staging_test").unwrap(),
+            package_name: "idkit_test".to_string(),
+            package_version: "1.0.0".to_string(),
+            kind: RequestKind::Uniqueness {
+                action: crate::Action::new("my-action").unwrap(),
+            },
+            constraints: None,
+            rp_context,
+            action_description: None,
+            legacy_verification_level: VerificationLevel::Orb,
+            legacy_signal: String::new(),
+            bridge_url: Some(bridge_url),
+            allow_legacy_proofs: false,
+            require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
+            override_connect_base_url: None,
+            return_to: None,
+            return_to_schemes: Vec::new(),
+            environment: None,
+            identity_attributes: None,
+            locale: None,
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    fn sample_request_config(bridge_url: BridgeUrl) -> IDKitRequestConfig {
+        let bridge_url_string = bridge_url.as_str().to_string();
+        let sample = sample_create_params(bridge_url);
+        IDKitRequestConfig {
+            app_id: sample.app_id.as_str().to_string(),
+            package_name: sample.package_name,
+            package_version: sample.package_version,
+            action: "my-action".to_string(),
+            rp_context: Arc::new(sample.rp_context),
+            action_description: None,
+            bridge_url: Some(bridge_url_string),
+            allow_legacy_proofs: false,
+            require_user_presence: None,
+            override_connect_base_url: None,
+            return_to: None,
+            environment: None,
+            connect_url_mode: None,
+            connect_timeout_ms: None,
+            request_timeout_ms: None,
+            normalize_constraints: None,
+            compress_payload: None,
+            cancel_on_drop: None,
+            single_consumption: None,
+            check_constraints: None,
+            session_ttl_ms: None,
+            locale: None,
+            return_to_schemes: None,
+        }
+    }
+
+    #[test]
+    fn test_create_with_client_uses_injected_client() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        let params = sample_create_params(bridge_url);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-idkit-test", "injected".parse().unwrap());
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let status = runtime.block_on(async {
+            let connection = BridgeConnection::builder(params)
+                .client(client)
+                .create()
+                .await
+                .unwrap();
+            connection.poll_for_status().await.unwrap()
+        });
+        assert_eq!(status, Status::WaitingForConnection);
+
+        let requests = bridge.raw_requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /request "));
+        assert!(requests[1].starts_with("GET /response/"));
+        for request in &requests {
+            assert!(request.to_lowercase().contains("x-idkit-test: injected"));
+        }
+    }
+
+    #[test]
+    fn test_invite_code_creation_goes_through_the_transport() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        // The first code collides, the retry gets a fresh one
+        bridge.script_create([(409, "{}".to_string())]);
+        let params = sample_create_params(bridge_url);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-idkit-test", "injected".parse().unwrap());
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let connection = runtime
+            .block_on(
+                BridgeConnection::builder(params)
+                    .transport(Box::new(ReqwestTransport::new(client)))
+                    .invite_code()
+                    .create(),
+            )
+            .unwrap();
+        let code = connection.invite_code.clone().unwrap();
+        assert_eq!(
+            connection.request_id(),
+            crate::crypto::hkdf_invite_index_hex(&code)
+        );
+        let status = runtime.block_on(connection.poll_for_status()).unwrap();
+        assert_eq!(status, Status::WaitingForConnection);
+
+        let requests = bridge.raw_requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].starts_with("POST /request "));
+        assert!(requests[1].starts_with("POST /request "));
+        assert!(requests[2].starts_with("GET /response/"));
+        for request in &requests {
+            assert!(request.to_lowercase().contains("x-idkit-test: injected"));
+        }
+    }
+
+    #[test]
+    fn test_invite_codes_need_transport_support() {
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(InMemoryTransport::default()))
+                    .invite_code()
+                    .create(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::BridgeError(_)), "{err:?}");
+    }
+
+    fn create_against(status: u16, body: &str) -> Error {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(status, body.to_string())]);
+        let params = sample_create_params(bridge_url);
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(BridgeConnection::create(params))
+            .expect_err("bridge rejection should fail create")
+    }
+
+    fn create_with_precheck_against(portal_status: u16, portal_body: &str) -> Result<String> {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(200, r#"{"request_id":"req_after_precheck"}"#.to_string())]);
+        let portal = CannedServer::start([(portal_status, portal_body)]).unwrap();
+        let precheck = crate::portal::PrecheckOptions {
+            endpoint: Some(format!("{}/api/v2/precheck", portal.url())),
+            ..crate::portal::PrecheckOptions::default()
+        };
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url))
+                    .precheck(precheck)
+                    .create(),
+            )
+            .map(|connection| connection.request_id().to_string())
+    }
+
+    #[test]
+    fn test_create_with_precheck() {
+        // Exists: creation proceeds
+        let request_id =
+            create_with_precheck_against(200, r#"{"action":{"max_verifications":1}}"#).unwrap();
+        assert_eq!(request_id, "req_after_precheck");
+
+        // Missing: fails before the bridge is contacted
+        let err = create_with_precheck_against(404, "").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidConfiguration(msg) if msg == "action 'my-action' not found for app_staging_test"
+        ));
+
+        // Portal down: best-effort precheck does not block creation
+        let request_id = create_with_precheck_against(503, "").unwrap();
+        assert_eq!(request_id, "req_after_precheck");
+    }
+
+    #[test]
+    fn test_builder_combines_creation_options() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(503, String::new())]);
+        let portal = CannedServer::start([(200, r#"{"action":{"max_verifications":1}}"#)]).unwrap();
+        let precheck = crate::portal::PrecheckOptions {
+            endpoint: Some(format!("{}/api/v2/precheck", portal.url())),
+            cache_ttl: std::time::Duration::ZERO,
+            ..crate::portal::PrecheckOptions::default()
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-idkit-test", "injected".parse().unwrap());
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+
+        let connection = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url))
+                    .client(client)
+                    .retry(fast_retry())
+                    .health_check()
+                    .precheck(precheck)
+                    .invite_code()
+                    .create(),
+            )
+            .unwrap();
+        assert!(connection.invite_code.is_some());
+
+        // Health check, then the retried invite-code creation, all through the
+        // injected client
+        let requests = bridge.raw_requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].starts_with("GET /health "));
+        assert!(requests[1].starts_with("POST /request "));
+        assert!(requests[2].starts_with("POST /request "));
+        assert_eq!(
+            requests[1].split("\r\n\r\n").nth(1),
+            requests[2].split("\r\n\r\n").nth(1)
+        );
+        for request in &requests {
+            assert!(request.to_lowercase().contains("x-idkit-test: injected"));
+        }
+    }
+
+    #[test]
+    fn test_builder_rejects_resuming_an_invite_code_request() {
+        let previous = sample_connection(None).to_resumable();
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .earlier_attempt(&previous)
+                    .invite_code()
+                    .create(),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidConfiguration(msg) if msg == "Invite-code requests cannot resume an earlier attempt"
+        ));
+    }
+
+    fn create_with_retry_against(
+        responses: Vec<(u16, String)>,
+        retry: CreateRetryConfig,
+    ) -> (Result<BridgeConnection>, Vec<String>) {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create(responses);
+        let result = tokio::runtime::Runtime::new().unwrap().block_on(
+            BridgeConnection::builder(sample_create_params(bridge_url))
+                .client(reqwest::Client::new())
+                .retry(retry)
+                .create(),
+        );
+        (result, bridge.raw_requests())
+    }
+
+    fn fast_retry() -> CreateRetryConfig {
+        CreateRetryConfig {
+            initial_backoff: std::time::Duration::from_millis(10),
+            ..CreateRetryConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_create_retries_gateway_errors_with_same_payload() {
+        let (result, requests) = create_with_retry_against(
+            vec![
+                (503, String::new()),
+                (502, String::new()),
+                (200, r#"{"request_id":"req_third_attempt"}"#.to_string()),
+            ],
+            fast_retry(),
+        );
+
+        assert_eq!(result.unwrap().request_id(), "req_third_attempt");
+        assert_eq!(requests.len(), 3);
+        let body = |request: &String| request.split("\r\n\r\n").nth(1).unwrap().to_string();
+        assert_eq!(body(&requests[0]), body(&requests[1]));
+        assert_eq!(body(&requests[1]), body(&requests[2]));
+    }
+
+    #[test]
+    fn test_create_does_not_retry_client_errors() {
+        let (result, requests) = create_with_retry_against(
+            vec![
+                (429, String::new()),
+                (200, r#"{"request_id":"req_unused"}"#.to_string()),
+            ],
+            fast_retry(),
+        );
+
+        assert_eq!(result.unwrap_err().bridge_status(), Some(429));
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[test]
+    fn test_create_stops_after_max_attempts() {
+        let (result, requests) = create_with_retry_against(
+            vec![
+                (504, String::new()),
+                (504, String::new()),
+                (504, String::new()),
+            ],
+            CreateRetryConfig {
+                max_attempts: 2,
+                ..fast_retry()
+            },
+        );
+
+        assert_eq!(result.unwrap_err().bridge_status(), Some(504));
+        assert_eq!(requests.len(), 2);
+    }
+
+    #[test]
+    fn test_create_retries_connection_errors() {
+        // Nothing listens on a dropped listener's port
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let bridge_url = BridgeUrl::new(
+            format!("http://{addr}"),
+            &AppId::new("app_staging_test").unwrap(),
+        )
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url))
+                    .client(reqwest::Client::new())
+                    .retry(fast_retry())
+                    .create(),
+            )
+            .unwrap_err();
+
+        assert!(matches!(err, Error::Http(ref e) if e.is_connect()));
+        // Two backoffs of 10ms and 20ms
+        assert!(started.elapsed() >= std::time::Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_create_rejects_zero_attempts() {
+        let (result, requests) = create_with_retry_against(
+            Vec::new(),
+            CreateRetryConfig {
+                max_attempts: 0,
+                ..fast_retry()
+            },
+        );
+
+        assert!(matches!(result, Err(Error::InvalidConfiguration(_))));
+        assert!(requests.is_empty());
+    }
+
+    fn idempotency_key_header(request: &str) -> Option<&str> {
+        request.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case(IDEMPOTENCY_KEY_HEADER)
+                .then(|| value.trim())
+        })
+    }
+
+    #[test]
+    fn test_create_with_idempotency_key_adopts_existing_request() {
+        let existing = r#"{"request_id":"req_existing"}"#.to_string();
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(200, existing.clone()), (409, existing)]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let first = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url.clone()))
+                    .client(reqwest::Client::new())
+                    .create(),
+            )
+            .unwrap();
+        let key = first.idempotency_key().unwrap().to_string();
+        assert!(uuid::Uuid::parse_str(&key).is_ok());
+
+        // A restarted backend retries under the persisted state
+        let retried = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url))
+                    .earlier_attempt(&first.to_resumable())
+                    .create(),
+            )
+            .unwrap();
+        assert_eq!(retried.request_id(), "req_existing");
+        assert_eq!(retried.idempotency_key(), Some(key.as_str()));
+        // The adopted request stays decryptable with the reused key material
+        assert_eq!(retried.key_bytes.expose(), first.key_bytes.expose());
+        assert_eq!(retried.encrypted_request.iv, first.encrypted_request.iv);
+
+        let requests = bridge.raw_requests();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(idempotency_key_header(request), Some(key.as_str()));
+        }
+
+        // The key survives a resume
+        assert_eq!(
+            BridgeConnection::from_resumable(first.to_resumable())
+                .unwrap()
+                .idempotency_key(),
+            Some(key.as_str())
+        );
+    }
+
+    #[test]
+    fn test_create_retry_after_lost_response_adopts_request() {
+        let (result, requests) = create_with_retry_against(
+            vec![
+                (504, String::new()),
+                (409, r#"{"request_id":"req_first_attempt"}"#.to_string()),
+            ],
+            fast_retry(),
+        );
+
+        assert_eq!(result.unwrap().request_id(), "req_first_attempt");
+        let key = idempotency_key_header(&requests[0]).unwrap();
+        assert_eq!(idempotency_key_header(&requests[1]), Some(key));
+
+        // Every creation gets its own key
+        let (result, _) = create_with_retry_against(
+            vec![(200, r#"{"request_id":"req_other"}"#.to_string())],
+            fast_retry(),
+        );
+        assert_ne!(result.unwrap().idempotency_key(), Some(key));
+    }
+
+    #[test]
+    fn test_create_with_idempotency_key_rejects_empty_key() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut previous = sample_connection(None).to_resumable();
+        previous.idempotency_key = Some(" ".to_string());
+        let err = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .earlier_attempt(&previous)
+                    .create(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+
+        previous.idempotency_key = Some("key".to_string());
+        previous.app_id = "app_staging_other".to_string();
+        let err = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .earlier_attempt(&previous)
+                    .create(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+    }
+
+    #[derive(Default)]
+    struct InMemoryBridge {
+        requests: std::collections::HashMap<String, EncryptedPayload>,
+        create_attempts: usize,
+        create_failures: std::collections::VecDeque<u16>,
+        poll_responses: std::collections::VecDeque<std::result::Result<BridgePollResponse, u16>>,
+        /// Tokio time of each poll, which a paused clock makes exact
+        polled_at: Vec<tokio::time::Instant>,
+        acknowledged: Vec<String>,
+        acknowledge_failures: std::collections::VecDeque<u16>,
+    }
+
+    impl InMemoryBridge {
+        fn create(&mut self, payload: &EncryptedPayload) -> Result<String> {
+            self.create_attempts += 1;
+            if let Some(status) = self.create_failures.pop_front() {
+                return Err(in_memory_error(status, BridgeEndpoint::Request));
+            }
+            let request_id = format!("req_{}", self.requests.len());
+            self.requests.insert(request_id.clone(), payload.clone());
+            Ok(request_id)
+        }
+
+        fn poll(&mut self, request_id: &str) -> Result<BridgePollResponse> {
+            self.polled_at.push(tokio::time::Instant::now());
+            if !self.requests.contains_key(request_id) {
+                return Err(in_memory_error(404, BridgeEndpoint::Response));
+            }
+            self.poll_responses
+                .pop_front()
+                .unwrap_or_else(|| {
+                    Ok(BridgePollResponse {
+                        status: "initialized".to_string(),
+                        response: None,
+                    })
+                })
+                .map_err(|status| in_memory_error(status, BridgeEndpoint::Response))
+        }
+
+        fn acknowledge(&mut self, request_id: &str) -> Result<()> {
+            if let Some(status) = self.acknowledge_failures.pop_front() {
+                return Err(in_memory_error(status, BridgeEndpoint::Response));
+            }
+            if self.requests.remove(request_id).is_none() {
+                return Err(in_memory_error(404, BridgeEndpoint::Response));
+            }
+            self.acknowledged.push(request_id.to_string());
+            Ok(())
+        }
+    }
+
+    fn in_memory_error(status: u16, endpoint: BridgeEndpoint) -> Error {
+        Error::BridgeHttp {
+            status,
+            endpoint,
+            body: String::new(),
+        }
+    }
+
+    /// Bridge kept in memory. Failures are scripted as HTTP status codes; a
+    /// poll with nothing scripted reports the request as `initialized`.
+    #[derive(Clone, Default)]
+    struct InMemoryTransport(Arc<Mutex<InMemoryBridge>>);
+
+    impl InMemoryTransport {
+        fn bridge(&self) -> std::sync::MutexGuard<'_, InMemoryBridge> {
+            self.0.lock().unwrap()
+        }
+    }
+
+    impl BridgeTransport for InMemoryTransport {
+        fn create_request<'a>(
+            &'a self,
+            _bridge_url: &'a BridgeUrl,
+            payload: &'a EncryptedPayload,
+        ) -> TransportFuture<'a, String> {
+            let result = self.bridge().create(payload);
+            Box::pin(std::future::ready(result))
+        }
+
+        fn poll<'a>(
+            &'a self,
+            _bridge_url: &'a BridgeUrl,
+            request_id: &'a str,
+            _max_response_bytes: usize,
+        ) -> TransportFuture<'a, BridgePollResponse> {
+            let result = self.bridge().poll(request_id);
+            Box::pin(std::future::ready(result))
+        }
+
+        fn acknowledge<'a>(
+            &'a self,
+            _bridge_url: &'a BridgeUrl,
+            request_id: &'a str,
+        ) -> TransportFuture<'a, ()> {
+            let result = self.bridge().acknowledge(request_id);
+            Box::pin(std::future::ready(result))
+        }
+    }
+
+    #[test]
+    fn test_wait_for_proof_polls_faster_once_retrieved() {
+        let transport = InMemoryTransport::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        let connection = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(transport.clone()))
+                    .create(),
+            )
+            .unwrap();
+
+        let iv = [7u8; 12];
+        let plaintext =
+            serde_json::to_vec(&BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1)).unwrap();
+        let ciphertext = encrypt(connection.key_bytes.expose(), &iv, &plaintext).unwrap();
+        let status = |status: &str| {
+            Ok(BridgePollResponse {
+                status: status.to_string(),
+                response: None,
+            })
+        };
+        transport.bridge().poll_responses.extend([
+            status("initialized"),
+            status("initialized"),
+            status("retrieved"),
+            status("retrieved"),
+            Ok(BridgePollResponse {
+                status: "completed".to_string(),
+                response: Some(EncryptedPayload {
+                    iv: base64_encode(&iv),
+                    payload: base64_encode(&ciphertext),
+                    compressed: false,
+                }),
+            }),
+        ]);
+
+        let config = PollConfig {
+            interval: std::time::Duration::from_secs(3),
+            awaiting_confirmation_interval: std::time::Duration::from_millis(500),
+            ..PollConfig::default()
+        };
+        runtime
+            .block_on(connection.wait_for_proof_with_config(config))
+            .unwrap();
+
+        let polled_at = transport.bridge().polled_at.clone();
+        let gaps: Vec<_> = polled_at.windows(2).map(|w| w[1] - w[0]).collect();
+        let ms = std::time::Duration::from_millis;
+        assert_eq!(gaps, [ms(3000), ms(3000), ms(500), ms(500)]);
+    }
+
+    #[test]
+    fn test_wait_for_proof_acknowledges_unless_disabled() {
+        let transport = InMemoryTransport::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        let connect = || {
+            let connection = runtime
+                .block_on(
+                    BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                        .transport(Box::new(transport.clone()))
+                        .create(),
+                )
+                .unwrap();
+            let iv = [7u8; 12];
+            let plaintext =
+                serde_json::to_vec(&BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                    .unwrap();
+            let ciphertext = encrypt(connection.key_bytes.expose(), &iv, &plaintext).unwrap();
+            transport
+                .bridge()
+                .poll_responses
+                .push_back(Ok(BridgePollResponse {
+                    status: "completed".to_string(),
+                    response: Some(EncryptedPayload {
+                        iv: base64_encode(&iv),
+                        payload: base64_encode(&ciphertext),
+                        compressed: false,
+                    }),
+                }));
+            connection
+        };
+
+        let acked = connect();
+        runtime.block_on(acked.wait_for_proof()).unwrap();
+        assert_eq!(transport.bridge().acknowledged, [acked.request_id()]);
+
+        let unacked = connect();
+        unacked.set_auto_ack(false);
+        runtime.block_on(unacked.wait_for_proof()).unwrap();
+        assert_eq!(transport.bridge().acknowledged.len(), 1);
+
+        // A failed acknowledgment is logged, and the proof is still returned
+        let failed = connect();
+        transport.bridge().acknowledge_failures.push_back(500);
+        runtime.block_on(failed.wait_for_proof()).unwrap();
+        assert_eq!(transport.bridge().acknowledged.len(), 1);
+        assert!(!failed.acknowledged.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_poll_schedule_intervals() {
+        let schedule = PollConfig::default().schedule();
+        assert_eq!(
+            schedule.interval_after(&Status::WaitingForConnection),
+            std::time::Duration::from_secs(1)
+        );
+        assert_eq!(
+            schedule.interval_after(&Status::AwaitingConfirmation),
+            std::time::Duration::from_millis(750)
+        );
+
+        let fast = PollConfig {
+            interval: std::time::Duration::from_millis(200),
+            ..PollConfig::default()
+        }
+        .schedule();
+        assert_eq!(
+            fast.interval_after(&Status::AwaitingConfirmation),
+            std::time::Duration::from_millis(200)
+        );
+
+        let fixed = PollSchedule::fixed(std::time::Duration::from_secs(2));
+        assert_eq!(
+            fixed.interval_after(&Status::AwaitingConfirmation),
+            fixed.interval_after(&Status::WaitingForConnection)
+        );
+
+        assert!(PollConfig {
+            awaiting_confirmation_interval: std::time::Duration::ZERO,
+            ..PollConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_create_and_poll_through_custom_transport() {
+        let transport = InMemoryTransport::default();
+        transport.bridge().create_failures.push_back(503);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let connection = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(transport.clone()))
+                    .create(),
+            )
+            .unwrap();
+
+        // The gateway error was retried and the bridge got the encrypted request
+        assert_eq!(connection.request_id(), "req_0");
+        assert_eq!(transport.bridge().create_attempts, 2);
+        let stored = transport.bridge().requests["req_0"].clone();
+        assert_eq!(stored.payload, connection.encrypted_request.payload);
+        assert_eq!(stored.iv, connection.encrypted_request.iv);
+
+        assert!(matches!(
+            runtime.block_on(connection.poll_for_status()).unwrap(),
+            Status::WaitingForConnection
+        ));
+
+        // The connection decrypts what the transport hands back
+        let iv = [7u8; 12];
+        let plaintext =
+            serde_json::to_vec(&BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1)).unwrap();
+        let ciphertext = encrypt(connection.key_bytes.expose(), &iv, &plaintext).unwrap();
+        transport
+            .bridge()
+            .poll_responses
+            .push_back(Ok(BridgePollResponse {
+                status: "completed".to_string(),
+                response: Some(EncryptedPayload {
+                    iv: base64_encode(&iv),
+                    payload: base64_encode(&ciphertext),
+                    compressed: false,
+                }),
+            }));
+        let Status::Confirmed(result) = runtime.block_on(connection.poll_for_status()).unwrap()
+        else {
+            panic!("expected a confirmed status");
+        };
+        assert_eq!(result.protocol_version, "3.0");
+
+        runtime.block_on(connection.acknowledge()).unwrap();
+        assert_eq!(transport.bridge().acknowledged, ["req_0"]);
+        assert!(matches!(
+            runtime.block_on(connection.poll_for_status()).unwrap(),
+            Status::Failed(AppError::RequestNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_compress_payload_threshold() {
+        let json = |len: usize| {
+            let mut payload = br#"{"action":""#.to_vec();
+            payload.resize(len - 2, b'a');
+            payload.extend_from_slice(br#""}"#);
+            payload
+        };
+
+        assert_eq!(
+            compress_payload(&json(PAYLOAD_COMPRESSION_THRESHOLD - 1)),
+            None
+        );
+        assert_eq!(compress_payload(&json(PAYLOAD_COMPRESSION_THRESHOLD)), None);
+
+        let above = json(PAYLOAD_COMPRESSION_THRESHOLD + 1);
+        let compressed = compress_payload(&above).unwrap();
+        assert!(compressed.len() < above.len());
+        assert_eq!(
+            decompress_payload(&compressed, DEFAULT_MAX_RESPONSE_BYTES),
+            Some(above)
+        );
+
+        // Random bytes do not shrink, so they are sent uncompressed
+        let mut random = vec![0u8; PAYLOAD_COMPRESSION_THRESHOLD * 2];
+        getrandom::getrandom(&mut random).unwrap();
+        assert_eq!(compress_payload(&random), None);
+    }
+
+    #[test]
+    fn test_create_compresses_only_large_request_payloads() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let create = |action_description: Option<String>, compress_payload: bool| {
+            let transport = InMemoryTransport::default();
+            let mut params = sample_create_params(BridgeUrl::default());
+            params.action_description = action_description;
+            params.compress_payload = compress_payload;
+            let connection = runtime
+                .block_on(
+                    BridgeConnection::builder(params)
+                        .transport(Box::new(transport.clone()))
+                        .create(),
+                )
+                .unwrap();
+            let stored = transport.bridge().requests["req_0"].clone();
+            (connection, stored)
+        };
+
+        let (_, small) = create(None, true);
+        assert!(!small.compressed);
+        assert!(serde_json::to_value(&small)
+            .unwrap()
+            .get("compressed")
+            .is_none());
+
+        // Compression is opt-in
+        let description = "Vote for the next feature. ".repeat(200);
+        let (_, uncompressed) = create(Some(description.clone()), false);
+        assert!(!uncompressed.compressed);
+
+        let (connection, large) = create(Some(description), true);
+        assert!(large.compressed);
+        assert_eq!(
+            serde_json::to_value(&large).unwrap()["compressed"],
+            serde_json::json!(true)
+        );
+
+        let ciphertext = base64_decode(&large.payload).unwrap();
+        let deflated = decrypt(
+            connection.key_bytes.expose(),
+            &base64_decode(&large.iv).unwrap(),
+            &ciphertext,
+        )
+        .unwrap();
+        let plaintext = decompress_payload(&deflated, usize::MAX - 1).unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(&plaintext).unwrap();
+        assert_eq!(payload, connection.request_payload);
+        assert!(plaintext.len() > PAYLOAD_COMPRESSION_THRESHOLD);
+        assert!(ciphertext.len() < PAYLOAD_COMPRESSION_THRESHOLD);
+
+        // Support bundles carry the flag and still decrypt
+        let bundle = connection.support_bundle(SupportBundleKey::IncludeSensitiveKey);
+        assert!(bundle.compressed);
+        assert_eq!(bundle.decrypt_and_validate().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_decrypt_bridge_response_inflates_compressed_responses() {
+        let connection = sample_connection(None);
+        let iv = [1u8; 12];
+        let sealed_compressed = |plaintext: &[u8]| EncryptedPayload {
+            iv: base64_encode(&iv),
+            payload: base64_encode(
+                &encrypt(
+                    &[0; 32],
+                    &iv,
+                    &miniz_oxide::deflate::compress_to_vec(plaintext, 6),
+                )
+                .unwrap(),
+            ),
+            compressed: true,
+        };
+
+        let orb = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1);
+        let decrypted = connection
+            .decrypt_bridge_response(&sealed_compressed(&serde_json::to_vec(&orb).unwrap()))
+            .unwrap();
+        assert!(matches!(
+            decrypted,
+            BridgeResponse::ResponseV1 { response, .. } if response.proof == orb.proof
+        ));
+
+        // Flagged as compressed but not deflate data
+        let mut not_deflate = sealed_compressed(b"");
+        not_deflate.payload = base64_encode(&encrypt(&[0; 32], &iv, b"{}").unwrap());
+        assert!(matches!(
+            connection.decrypt_bridge_response(&not_deflate),
+            Err(Error::ResponseDecryption {
+                kind: DecryptionFailureKind::PlaintextNotJson,
+                ..
+            })
+        ));
+
+        // A small deflate stream cannot expand past the response cap
+        let bomb = vec![b' '; DEFAULT_MAX_RESPONSE_BYTES * 4];
+        assert!(matches!(
+            connection.decrypt_bridge_response(&sealed_compressed(&bomb)),
+            Err(Error::BridgeError(message)) if message == "response too large"
+        ));
+    }
+
+    #[test]
+    fn test_custom_transport_errors_keep_status_mapping() {
+        let transport = InMemoryTransport::default();
+        transport.bridge().create_failures.push_back(400);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let err = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(transport.clone()))
+                    .create(),
+            )
+            .unwrap_err();
+        assert_eq!(err.bridge_status(), Some(400));
+        assert_eq!(transport.bridge().create_attempts, 1);
+
+        let connection = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(transport.clone()))
+                    .create(),
+            )
+            .unwrap();
+        transport
+            .bridge()
+            .poll_responses
+            .extend([Err(429), Err(500)]);
+        assert!(matches!(
+            runtime.block_on(connection.poll_for_status()).unwrap(),
+            Status::Failed(AppError::RateLimited)
+        ));
+        assert_eq!(
+            runtime
+                .block_on(connection.poll_for_status())
+                .unwrap_err()
+                .bridge_status(),
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn test_create_surfaces_bridge_http_status() {
+        let err = create_against(400, r#"{"error":"malformed payload"}"#);
+        assert_eq!(err.bridge_status(), Some(400));
+        assert!(matches!(
+            &err,
+            Error::BridgeHttp {
+                status: 400,
+                endpoint: BridgeEndpoint::Request,
+                body,
+            } if body == r#"{"error":"malformed payload"}"#
+        ));
+
+        let err = create_against(429, "");
+        assert!(matches!(
+            err,
+            Error::BridgeHttp {
+                status: 429,
+                endpoint: BridgeEndpoint::Request,
+                ..
+            }
+        ));
+        assert!(err.to_string().ends_with("no error details"));
+    }
+
+    #[test]
+    fn test_poll_surfaces_bridge_http_status() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Raw(500, "upstream down".to_string())],
+        );
+
+        let err = poll_once(&connection).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::BridgeHttp {
+                status: 500,
+                endpoint: BridgeEndpoint::Response,
+                body,
+            } if body == "upstream down"
+        ));
+    }
+
+    #[test]
+    fn test_bridge_http_error_drops_oversized_body() {
+        let body = "x".repeat(MAX_ERROR_BODY_BYTES + 1);
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Raw(500, body)]);
+
+        let err = poll_once(&connection).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::BridgeHttp { status: 500, body, .. } if body.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_poll_maps_missing_request_to_request_not_found() {
+        for status in [404, 410] {
+            let mut connection = sample_connection(None);
+            let _bridge = mock_bridge_for(&mut connection, [MockStatus::HttpError(status)]);
+
+            assert_eq!(
+                poll_once(&connection).unwrap(),
+                Status::Failed(AppError::RequestNotFound),
+                "status {status}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_poll_maps_too_many_requests_to_rate_limited() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Raw(429, "slow down".to_string())],
+        );
+
+        assert_eq!(
+            poll_once(&connection).unwrap(),
+            Status::Failed(AppError::RateLimited)
+        );
+    }
+
+    #[test]
+    fn test_rate_limited_round_trips_through_json() {
+        assert_eq!(
+            serde_json::to_string(&AppError::RateLimited).unwrap(),
+            r#""rate_limited""#
+        );
+        assert_eq!(
+            serde_json::from_str::<AppError>(r#""rate_limited""#).unwrap(),
+            AppError::RateLimited
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_poll_reports_rate_limited_as_failed() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::HttpError(429)]);
+
+        let status =
+            poll_status_once_blocking(&tokio::runtime::Runtime::new().unwrap(), &connection, false);
+        let StatusWrapper::Failed { error, .. } = status else {
+            panic!("expected a failed status, got {status:?}");
+        };
+        assert_eq!(error, AppError::RateLimited);
+        assert_eq!(error.to_string(), "Too many requests");
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_builder_orb_creates_orb_request() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(
+            200,
+            r#"{"request_id":"64e0ec6b-b4ca-47cc-8f70-504a95189e26"}"#.to_string(),
+        )]);
+        let config = sample_request_config(bridge_url);
+
+        let request = request(config).orb(Some("user-123".to_string())).unwrap();
+
+        assert_eq!(request.request_id(), "64e0ec6b-b4ca-47cc-8f70-504a95189e26");
+        assert_eq!(bridge.requests().len(), 1);
+        let payload = &request.inner.request_payload;
+        assert_eq!(payload["verification_level"], "orb");
+        assert_eq!(
+            payload["signal"],
+            crate::crypto::hash_signal(&Signal::from_string("user-123"))
+        );
+        // Orb-only requests are legacy requests without a constraint tree
+        assert!(payload
+            .get("proof_request")
+            .is_none_or(serde_json::Value::is_null));
+        assert!(request.inner.constraints.is_none());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_create_from_constraints() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(
+            200,
+            r#"{"request_id":"64e0ec6b-b4ca-47cc-8f70-504a95189e26"}"#.to_string(),
+        )]);
+        let config = sample_request_config(bridge_url);
+        let constraints = ConstraintNode::any(vec![
+            ConstraintNode::item(CredentialRequest::new(CredentialType::ProofOfHuman, None)),
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None)),
+        ]);
+
+        let request = IDKitRequestWrapper::create_from_constraints(
+            config,
+            Arc::new(constraints),
+            Some("user-123".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(request.request_id(), "64e0ec6b-b4ca-47cc-8f70-504a95189e26");
+        assert_eq!(bridge.requests().len(), 1);
+        let derived: Vec<_> = request
+            .inner
+            .constraints
+            .as_ref()
+            .unwrap()
+            .collect_items()
+            .iter()
+            .map(|item| (item.credential_type, item.effective_signal().cloned()))
+            .collect();
+        let signal = Some(Signal::from_string("user-123".to_string()));
+        assert_eq!(
+            derived,
+            [
+                (CredentialType::ProofOfHuman, signal.clone()),
+                (CredentialType::Passport, signal),
+            ]
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_cancel_on_drop_deletes_before_runtime_shutdown() {
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(
+            200,
+            r#"{"request_id":"64e0ec6b-b4ca-47cc-8f70-504a95189e26"}"#.to_string(),
+        )]);
+        let config = IDKitRequestConfig {
+            cancel_on_drop: Some(true),
+            ..sample_request_config(bridge_url)
+        };
+
+        let request = request(config).preset(Preset::orb_legacy(None)).unwrap();
+        drop(request);
+
+        // The wrapper blocks on its own runtime, so the DELETE is already sent
+        let requests = bridge.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1],
+            "DELETE /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26 HTTP/1.1"
+        );
+    }
+
+    /// Accepts every connection to the returned bridge URL and never answers.
+    fn serve_hanging_bridge() -> BridgeUrl {
+        let server = CannedServer::hanging().unwrap();
+        BridgeUrl::new(server.url(), &AppId::new("app_staging_test").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_http_timeouts_report_timeout() {
+        let timeouts = HttpTimeouts {
+            connect: std::time::Duration::from_secs(1),
+            request: std::time::Duration::from_millis(200),
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let bridge_url = serve_hanging_bridge();
+
+        let mut connection = sample_connection(None);
+        connection.bridge_url = bridge_url.clone();
+        connection.transport = Arc::new(ReqwestTransport::new(timeouts.build_client().unwrap()));
+        let started = std::time::Instant::now();
+        assert!(matches!(
+            runtime.block_on(connection.poll_for_status()),
+            Err(Error::Timeout)
+        ));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+        let err = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(bridge_url))
+                    .client(timeouts.build_client().unwrap())
+                    .retry(CreateRetryConfig::no_retry())
+                    .create(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout));
+    }
+
+    #[test]
+    fn test_health_check_reports_version_and_latency() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_health([
+            (200, r#"{"status":"ok","version":"1.4.2"}"#.to_string()),
+            (200, "OK".to_string()),
+        ]);
+        let client = reqwest::Client::new();
+
+        let health = runtime
+            .block_on(health_check(&bridge_url, &client))
+            .unwrap();
+        assert_eq!(health.version.as_deref(), Some("1.4.2"));
+        assert!(health.latency < std::time::Duration::from_secs(5));
+
+        let health = runtime
+            .block_on(health_check(&bridge_url, &client))
+            .unwrap();
+        assert_eq!(health.version, None);
+
+        assert!(bridge
+            .requests()
+            .iter()
+            .all(|request| request.starts_with("GET /health ")));
+    }
+
+    #[test]
+    fn test_health_check_rejects_servers_that_are_not_bridges() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_health([
+            (404, String::new()),
+            (200, "<!doctype html><title>Welcome</title>".to_string()),
+            (200, "[1, 2, 3]".to_string()),
+            (503, "maintenance".to_string()),
+        ]);
+        let client = reqwest::Client::new();
+
+        for _ in 0..3 {
+            assert!(matches!(
+                runtime.block_on(health_check(&bridge_url, &client)),
+                Err(Error::BridgeUnhealthy {
+                    kind: BridgeHealthFailureKind::NotABridge
+                })
+            ));
+        }
+        assert!(matches!(
+            runtime.block_on(health_check(&bridge_url, &client)),
+            Err(Error::BridgeHttp {
+                status: 503,
+                endpoint: BridgeEndpoint::Health,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_health_check_classifies_connection_failures() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let app_id = AppId::new("app_staging_test").unwrap();
+        let client = HttpTimeouts::default().build_client().unwrap();
+        let kind_for = |bridge_url: String| {
+            let bridge_url = BridgeUrl::new(bridge_url, &app_id).unwrap();
+            match runtime.block_on(health_check(&bridge_url, &client)) {
+                Err(Error::BridgeUnhealthy { kind }) => kind,
+                other => panic!("unexpected health check result: {other:?}"),
+            }
+        };
+
+        // Nothing listens on a dropped listener's port
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        assert_eq!(
+            kind_for(format!("http://{addr}")),
+            BridgeHealthFailureKind::Connect
+        );
+
+        // A plain HTTP server cannot complete a TLS handshake
+        let plain = CannedServer::start([(200, "")]).unwrap();
+        assert_eq!(
+            kind_for(plain.url().replacen("http://", "https://", 1)),
+            BridgeHealthFailureKind::Tls
+        );
+
+        assert_eq!(
+            kind_for("https://bridge.idkit-test.invalid".to_string()),
+            BridgeHealthFailureKind::Dns
+        );
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_request_timeout_ms() {
+        let config = IDKitRequestConfig {
+            request_timeout_ms: Some(100),
+            ..sample_request_config(serve_hanging_bridge())
+        };
+        assert_eq!(
+            IDKitConfig::Request(config.clone()).http_timeouts(),
+            HttpTimeouts {
+                request: std::time::Duration::from_millis(100),
+                ..HttpTimeouts::default()
+            }
+        );
+
+        let Err(err) = request(config).preset(Preset::orb_legacy(None)) else {
+            panic!("expected the hanging bridge to time out");
+        };
+        assert!(matches!(err, crate::error::IdkitError::Timeout));
+    }
+
+    #[test]
+    fn test_wait_for_proof_stops_on_missing_request() {
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Initialized, MockStatus::HttpError(404)],
+        );
+
+        let started = std::time::Instant::now();
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_config(PollConfig {
+                interval: std::time::Duration::from_millis(10),
+                timeout: std::time::Duration::from_mins(1),
+                ..PollConfig::default()
+            }))
+            .unwrap_err();
+
+        assert!(matches!(err, Error::AppError(AppError::RequestNotFound)));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(bridge.requests().len(), 2);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_poll_separates_expired_from_network_failures() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let poll_with = |bridge_url: BridgeUrl| {
+            let mut connection = sample_connection(None);
+            connection.bridge_url = bridge_url;
+            poll_status_once_blocking(&runtime, &connection, false)
+        };
+        let poll_mock = |status: u16| {
+            let mut connection = sample_connection(None);
+            let _bridge = mock_bridge_for(&mut connection, [MockStatus::HttpError(status)]);
+            poll_status_once_blocking(&runtime, &connection, false)
+        };
+
+        assert!(matches!(
+            poll_mock(410),
+            StatusWrapper::Failed {
+                error: AppError::RequestNotFound,
+                retry_after_secs: None
+            }
+        ));
+
+        assert!(matches!(
+            poll_mock(500),
+            StatusWrapper::NetworkingError {
+                error: AppError::ConnectionFailed
+            }
+        ));
+
+        // Nothing listens on a dropped listener's port
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let refused = BridgeUrl::new(
+            format!("http://{addr}"),
+            &AppId::new("app_staging_test").unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            poll_with(refused),
+            StatusWrapper::NetworkingError {
+                error: AppError::ConnectionFailed
+            }
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_bridge_http_status_maps_to_ffi_errors() {
+        let malformed = create_against(400, "bad");
+        assert_eq!(to_app_error(&malformed), AppError::MalformedRequest);
+        assert!(!is_networking_error(&malformed));
+
+        let rate_limited = create_against(429, "slow down");
+        assert_eq!(to_app_error(&rate_limited), AppError::RateLimited);
+        assert!(is_networking_error(&rate_limited));
+
+        match crate::error::IdkitError::from(rate_limited) {
+            crate::error::IdkitError::BridgeHttpError {
+                status_code,
+                endpoint,
+                body,
+            } => {
+                assert_eq!(status_code, 429);
+                assert_eq!(endpoint, "request");
+                assert_eq!(body, "slow down");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    fn confirmed_result(mut responses: serde_json::Value) -> IDKitResult {
+        serde_json::from_value(serde_json::json!({
+            "protocol_version": "4.0",
+            "nonce": "0x01",
+            "responses": responses.take(),
+            "user_presence_completed": false,
+            "environment": "production",
+        }))
+        .unwrap()
+    }
+
+    fn v4_item(identifier: &str) -> serde_json::Value {
+        serde_json::json!({
+            "identifier": identifier,
+            "issuer_schema_id": 1,
+            "proof": ["0x01", "0x02", "0x03", "0x04", "0x05"],
+            "nullifier": "0x06",
+            "expires_at_min": 1_735_689_600,
+        })
+    }
+
+    #[test]
+    fn test_status_json_round_trips_each_variant() {
+        let cases = [
+            (
+                Status::WaitingForConnection,
+                serde_json::json!({"type": "waiting_for_connection"}),
+            ),
+            (
+                Status::AwaitingConfirmation,
+                serde_json::json!({"type": "awaiting_confirmation"}),
+            ),
+            (
+                Status::Failed(AppError::UserRejected),
+                serde_json::json!({"type": "failed", "error": "user_rejected"}),
+            ),
+            (
+                Status::Failed(AppError::Other("new_code".to_string())),
+                serde_json::json!({"type": "failed", "error": "new_code"}),
+            ),
+        ];
+        for (status, expected) in cases {
+            let json = serde_json::to_value(&status).unwrap();
+            assert_eq!(json, expected);
+            assert_eq!(serde_json::from_value::<Status>(json).unwrap(), status);
+        }
+
+        let result = confirmed_result(serde_json::json!([v4_item("proof_of_human")]));
+        let confirmed = Status::Confirmed(result.clone());
+        let json = serde_json::to_value(&confirmed).unwrap();
+        assert_eq!(json["type"], "confirmed");
+        assert_eq!(json["result"], serde_json::to_value(&result).unwrap());
+        assert_eq!(serde_json::from_value::<Status>(json).unwrap(), confirmed);
+
+        assert!(
+            serde_json::from_value::<Status>(serde_json::json!({"type": "confirmed"})).is_err()
+        );
+    }
+
+    #[test]
+    fn test_satisfied_branch_reports_preferred_and_fallback_credentials() {
+        let mut connection = connection_with_encrypted_request();
+        connection.constraints = Some(ConstraintNode::any(vec![
+            ConstraintNode::item(CredentialRequest::new(CredentialType::ProofOfHuman, None)),
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None)),
+        ]));
+
+        let preferred = confirmed_result(serde_json::json!([v4_item("proof_of_human")]));
+        assert_eq!(
+            connection.satisfied_branch(&preferred),
+            Some(SatisfiedBranch {
+                credential: CredentialType::ProofOfHuman,
+                priority_index: 0,
+                was_preferred: true,
+            })
+        );
+        let fallback = confirmed_result(serde_json::json!([v4_item("passport")]));
+        assert_eq!(
+            connection.satisfied_branch(&fallback),
+            Some(SatisfiedBranch {
+                credential: CredentialType::Passport,
+                priority_index: 1,
+                was_preferred: false,
+            })
+        );
+
+        // Constraints survive a resume
+        assert_eq!(
+            resumed(&connection).satisfied_branch(&fallback),
+            connection.satisfied_branch(&fallback)
+        );
+
+        // Nothing to report without constraints
+        assert_eq!(sample_connection(None).satisfied_branch(&preferred), None);
+    }
+
+    #[test]
+    fn test_wait_for_result_returns_proof_and_branch() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [completed]);
+        connection.constraints = Some(ConstraintNode::item(CredentialRequest::new(
+            CredentialType::ProofOfHuman,
+            None,
+        )));
+
+        let verified = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_result())
+            .unwrap();
+        assert!(matches!(
+            verified.proof.responses.as_slice(),
+            [ResponseItem::V3 { .. }]
+        ));
+        // A legacy proof names no credential of the constraint tree
+        assert_eq!(verified.branch, None);
+    }
+
+    #[test]
+    fn test_wait_for_proof_detailed_reports_credential() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [completed]);
+
+        let outcome = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_detailed())
+            .unwrap();
+        assert!(matches!(
+            outcome.result.proof.responses.as_slice(),
+            [ResponseItem::V3 { .. }]
+        ));
+        assert_eq!(outcome.result.branch, None);
+        // Legacy proofs carry a verification level, not a credential
+        assert_eq!(outcome.credential, None);
+        assert!(!outcome.user_presence_required);
+        assert!(outcome
+            .completed_at
+            .is_some_and(|completed_at| completed_at > 0));
+
+        // Without constraints the credential is read from the proof itself
+        let proof = confirmed_result(serde_json::json!([v4_item("passport")]));
+        let outcome = connection.verification_outcome(proof, std::time::Duration::ZERO);
+        let [ResponseItem::V4 { identifier, .. }] = outcome.result.proof.responses.as_slice()
+        else {
+            panic!("expected a single V4 response");
+        };
+        assert_eq!(
+            outcome.credential.map(|credential| credential.to_string()),
+            Some(identifier.clone())
+        );
+    }
+
+    #[test]
+    fn test_single_consumption_hands_out_proof_once() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [completed]);
+        let connection = Arc::new(connection.with_single_consumption(true));
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let results = runtime.block_on(async {
+            let racers = [connection.clone(), connection.clone()]
+                .map(|connection| tokio::spawn(async move { connection.wait_for_proof().await }));
+            let mut results = Vec::new();
+            for racer in racers {
+                results.push(racer.await.unwrap());
+            }
+            results
+        });
+
+        let proofs: Vec<_> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .collect();
+        assert_eq!(proofs.len(), 1);
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Err(Error::ProofAlreadyConsumed))));
+        // Peeking stays possible after the proof was consumed
+        assert_eq!(connection.peek_proof().as_ref(), Some(proofs[0]));
+    }
+
+    #[test]
+    fn test_status_history_records_only_transitions() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Initialized,
+                MockStatus::Initialized,
+                MockStatus::Retrieved,
+                MockStatus::Retrieved,
+                completed,
+            ],
+        );
+        assert!(connection.status_history().is_empty());
+        assert_eq!(connection.time_to_connection(), None);
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            for _ in 0..6 {
+                connection.poll_for_status().await.unwrap();
+            }
+        });
+
+        let history = connection.status_history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].status, Status::WaitingForConnection);
+        assert_eq!(history[1].status, Status::AwaitingConfirmation);
+        assert!(matches!(history[2].status, Status::Confirmed(_)));
+        assert!(history
+            .windows(2)
+            .all(|w| w[0].observed_at <= w[1].observed_at));
+        assert!(connection.time_to_connection().is_some());
+        assert!(connection.time_to_confirmation().is_some());
+    }
+
+    #[test]
+    fn test_status_history_durations() {
+        let connection = sample_connection(None);
+        *connection.status_history.lock().unwrap() = vec![
+            StatusTransition {
+                status: Status::AwaitingConfirmation,
+                observed_at: 1_700_000_010,
+            },
+            StatusTransition {
+                status: Status::Failed(AppError::UserRejected),
+                observed_at: 1_700_000_025,
+            },
+        ];
+        // The request was already retrieved when first polled
+        assert_eq!(connection.time_to_connection(), None);
+        assert_eq!(connection.time_to_confirmation(), None);
+
+        connection.status_history.lock().unwrap()[1] = StatusTransition {
+            status: Status::Confirmed(confirmed_result(serde_json::json!([]))),
+            observed_at: 1_700_000_025,
+        };
+        assert_eq!(
+            connection.time_to_confirmation(),
+            Some(std::time::Duration::from_secs(15))
+        );
+    }
+
+    /// Gives spawned drop tasks time to reach the mock bridge.
+    async fn settle(bridge: &MockBridge, expected: usize) {
+        for _ in 0..50 {
+            if bridge.requests().len() >= expected {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    #[test]
+    fn test_cancel_on_drop_deletes_unfinished_request() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let mut connection = sample_connection(None).with_cancel_on_drop(true);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        runtime.block_on(async {
+            drop(connection);
+            settle(&bridge, 1).await;
+        });
+        assert_eq!(
+            bridge.requests(),
+            ["DELETE /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26 HTTP/1.1"]
+        );
+
+        // An explicit close cancels once and disarms the drop
+        let mut connection = sample_connection(None).with_cancel_on_drop(true);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        runtime.block_on(async {
+            connection.close().await.unwrap();
+            drop(connection);
+            settle(&bridge, 2).await;
+        });
+        assert_eq!(bridge.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_on_drop_skips_finished_and_disabled_requests() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+
+        // A terminal status means there is nothing left to cancel
+        let mut connection = sample_connection(None).with_cancel_on_drop(true);
+        let bridge = mock_bridge_for(&mut connection, [completed]);
+        runtime.block_on(async {
+            assert!(matches!(
+                connection.poll_for_status().await.unwrap(),
+                Status::Confirmed(_)
+            ));
+            drop(connection);
+            settle(&bridge, 2).await;
+        });
+        assert_eq!(bridge.requests().len(), 1);
+
+        // Off by default
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        runtime.block_on(async {
+            drop(connection);
+            settle(&bridge, 1).await;
+        });
+        assert!(bridge.requests().is_empty());
+
+        // Outside a runtime the drop sends nothing and does not panic
+        let mut connection = sample_connection(None).with_cancel_on_drop(true);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        drop(connection);
+        runtime.block_on(settle(&bridge, 1));
+        assert!(bridge.requests().is_empty());
+    }
+
+    #[test]
+    fn test_verify_confirmed_proof_accepts_satisfying_credentials() {
+        let mut accepted = sample_connection(None);
+        accepted.constraints = Some(ConstraintNode::any(vec![
+            ConstraintNode::item(CredentialRequest::new(CredentialType::ProofOfHuman, None)),
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None)),
+        ]));
+        accepted.legacy_verification_level = Some(VerificationLevel::Document);
+
+        accepted
+            .verify_confirmed_proof(&confirmed_result(serde_json::json!([v4_item("passport")])))
+            .unwrap();
+
+        // Legacy proofs are checked against the legacy verification level
+        let legacy = confirmed_result(serde_json::json!([{
+            "identifier": "orb",
+            "signal_hash": "0x00",
+            "proof": "0x01",
+            "merkle_root": "0x02",
+            "nullifier": "0x03",
+        }]));
+        accepted.verify_confirmed_proof(&legacy).unwrap();
+    }
+
+    #[test]
+    fn test_verify_confirmed_proof_rejects_unrequested_credentials() {
+        let mut accepted = sample_connection(None);
+        accepted.constraints = Some(ConstraintNode::all(vec![
+            ConstraintNode::item(CredentialRequest::new(CredentialType::ProofOfHuman, None)),
+            ConstraintNode::item(CredentialRequest::new(CredentialType::Passport, None)),
+        ]));
+        accepted.legacy_verification_level = Some(VerificationLevel::SecureDocument);
+
+        for responses in [
+            serde_json::json!([v4_item("selfie")]),
+            serde_json::json!([v4_item("passport")]),
+            serde_json::json!([]),
+        ] {
+            let err = accepted
+                .verify_confirmed_proof(&confirmed_result(responses))
+                .unwrap_err();
+            assert!(matches!(err, Error::InvalidProof(_)));
+        }
+        let Err(Error::InvalidProof(message)) = accepted
+            .verify_confirmed_proof(&confirmed_result(serde_json::json!([v4_item("passport")])))
+        else {
+            panic!("expected unsatisfied constraints to be rejected");
+        };
+        assert_eq!(
+            message,
+            "received passport proof but constraints require proof_of_human"
+        );
+
+        let legacy = confirmed_result(serde_json::json!([{
+            "identifier": "document",
+            "signal_hash": "0x00",
+            "proof": "0x01",
+            "merkle_root": "0x02",
+            "nullifier": "0x03",
+        }]));
+        assert!(matches!(
+            accepted.verify_confirmed_proof(&legacy),
+            Err(Error::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn test_poll_checks_confirmed_proof_against_constraints() {
+        let poll_legacy = |level, requested, check| {
+            let proof = serde_json::to_value(BridgeResponseV1::test_fixture(level, 1)).unwrap();
+            let mut connection = sample_connection(None).with_constraint_check(check);
+            let _bridge = mock_bridge_for(&mut connection, [MockStatus::Completed(proof)]);
+            connection.legacy_verification_level = requested;
+            poll_once(&connection)
+        };
+
+        // Matching proof
+        assert!(matches!(
+            poll_legacy(VerificationLevel::Orb, Some(VerificationLevel::Orb), true),
+            Ok(Status::Confirmed(_))
+        ));
+
+        // Mismatching proof
+        let Err(Error::InvalidProof(message)) = poll_legacy(
+            VerificationLevel::Device,
+            Some(VerificationLevel::Orb),
+            true,
+        ) else {
+            panic!("expected a device proof to be rejected for an orb request");
+        };
+        assert_eq!(message, "received device proof but constraints require orb");
+
+        // A rejected proof ends the request
+        let proof =
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Device, 1))
+                .unwrap();
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Completed(proof)]);
+        connection.legacy_verification_level = Some(VerificationLevel::Orb);
+        assert!(poll_once(&connection).is_err());
+        assert!(connection.finished.load(Ordering::Acquire));
+        assert!(matches!(
+            connection
+                .status_history()
+                .last()
+                .map(|transition| &transition.status),
+            Some(Status::Failed(AppError::UnexpectedResponse))
+        ));
+
+        // Nothing to check against
+        assert!(matches!(
+            poll_legacy(VerificationLevel::Device, None, true),
+            Ok(Status::Confirmed(_))
+        ));
+
+        // Check turned off
+        assert!(matches!(
+            poll_legacy(
+                VerificationLevel::Device,
+                Some(VerificationLevel::Orb),
+                false
+            ),
+            Ok(Status::Confirmed(_))
+        ));
+    }
+
+    #[test]
+    fn test_action_field_matches_string_action() {
+        let constraints =
+            ConstraintNode::item(CredentialRequest::new(CredentialType::ProofOfHuman, None));
+
+        let mut string_params = sample_create_params(BridgeUrl::default());
+        string_params.constraints = Some(constraints.clone());
+        let string_payload = build_request_payload(&string_params, false).unwrap();
+
+        let mut field_params = sample_create_params(BridgeUrl::default());
+        field_params.constraints = Some(constraints);
+        field_params.kind = RequestKind::UniquenessField {
+            action: crate::crypto::encode_action_field("my-action"),
+        };
+        let field_payload = build_request_payload(&field_params, false).unwrap();
+
+        let string_action = string_payload.proof_request.unwrap().action;
+        assert!(string_action.is_some());
+        assert_eq!(string_action, field_payload.proof_request.unwrap().action);
+        assert_eq!(
+            field_params.kind.action(),
+            Some(crate::crypto::encode_action_field("my-action").to_string())
+        );
+    }
+
+    #[test]
+    fn test_action_field_rejects_legacy_proofs() {
+        let mut params = sample_create_params(BridgeUrl::default());
+        params.allow_legacy_proofs = true;
+        params.kind = RequestKind::UniquenessField {
+            action: crate::crypto::encode_action_field("my-action"),
+        };
+
+        assert!(matches!(
+            build_request_payload(&params, false),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
+    fn collect_status_stream(
+        connection: &BridgeConnection,
+        deadline: Option<std::time::Duration>,
+    ) -> Vec<Result<Status>> {
+        use futures_util::StreamExt;
+
+        let options = StatusStreamOptions {
+            initial_interval: std::time::Duration::from_millis(1),
+            max_interval: std::time::Duration::from_millis(4),
+            deadline,
+            mode: PollMode::Interval,
+        };
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.status_stream(options).collect())
+    }
+
+    #[test]
+    fn test_status_stream_yields_transitions_until_terminal() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Initialized,
+                MockStatus::Initialized,
+                MockStatus::Retrieved,
+                MockStatus::Retrieved,
+                completed,
+            ],
+        );
+
+        let statuses = collect_status_stream(&connection, None);
+
+        assert_eq!(statuses.len(), 3);
+        assert!(matches!(statuses[0], Ok(Status::WaitingForConnection)));
+        assert!(matches!(statuses[1], Ok(Status::AwaitingConfirmation)));
+        assert!(matches!(statuses[2], Ok(Status::Confirmed(_))));
+        assert_eq!(bridge.requests().len(), 5);
+    }
+
+    #[test]
+    fn test_status_stream_ends_after_failure() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Retrieved,
+                MockStatus::Completed(serde_json::json!({"error_code": "user_rejected"})),
+            ],
+        );
+
+        let statuses = collect_status_stream(&connection, None);
+
+        assert_eq!(statuses.len(), 2);
+        assert!(matches!(statuses[0], Ok(Status::AwaitingConfirmation)));
+        assert!(matches!(
+            statuses[1],
+            Ok(Status::Failed(AppError::UserRejected))
+        ));
+    }
+
+    #[test]
+    fn test_status_stream_times_out_after_deadline() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+
+        let statuses =
+            collect_status_stream(&connection, Some(std::time::Duration::from_millis(50)));
+
+        assert_eq!(statuses.len(), 2);
+        assert!(matches!(statuses[0], Ok(Status::WaitingForConnection)));
+        assert!(matches!(statuses[1], Err(Error::Timeout)));
+    }
+
+    fn connection_with_encrypted_request() -> BridgeConnection {
+        let mut connection = sample_connection(None);
+        connection.request_payload = serde_json::json!({
+            "app_id": "app_test",
+            "signal": "0x00c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a4",
+            "proof_request": {
+                "requests": [{ "type": "proof_of_human", "signal": "0x75736572" }],
+            },
+        });
+        let iv = [2u8; 12];
+        let ciphertext = encrypt(
+            connection.key_bytes.expose(),
+            &iv,
+            connection.request_payload.to_string().as_bytes(),
+        )
+        .unwrap();
+        connection.encrypted_request = EncryptedPayload {
+            iv: base64_encode(&iv),
+            payload: base64_encode(&ciphertext),
+            compressed: false,
+        };
+        connection
+    }
+
+    #[test]
+    fn test_support_bundle_omits_key_by_default() {
+        let connection = connection_with_encrypted_request();
+        let bundle = connection.support_bundle(SupportBundleKey::default());
+        let json: serde_json::Value = serde_json::from_str(&bundle.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["schema_version"], SUPPORT_BUNDLE_SCHEMA_VERSION);
+        assert_eq!(json["request_id"], connection.request_id());
+        assert_eq!(json["iv"], connection.encrypted_request.iv);
+        assert!(json.get("key").is_none());
+        assert!(json.get("WARNING_CONTAINS_KEY").is_none());
+        assert_eq!(json["redacted_payload"]["signal"], REDACTED);
+        assert_eq!(
+            json["redacted_payload"]["proof_request"]["requests"][0]["signal"],
+            REDACTED
+        );
+        assert_eq!(json["redacted_payload"]["app_id"], "app_test");
+
+        assert!(matches!(
+            bundle.decrypt_and_validate(),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_support_bundle_with_key_round_trips() {
+        let connection = connection_with_encrypted_request();
+        let json = connection
+            .support_bundle(SupportBundleKey::IncludeSensitiveKey)
+            .to_json()
+            .unwrap();
+        assert!(json.contains("\"WARNING_CONTAINS_KEY\""));
+
+        let bundle = SupportBundle::from_json(&json).unwrap();
+        assert_eq!(
+            bundle.decrypt_and_validate().unwrap(),
+            connection.request_payload
+        );
+    }
+
+    #[test]
+    fn test_support_bundle_rejects_mismatched_payload() {
+        let connection = connection_with_encrypted_request();
+        let mut bundle = connection.support_bundle(SupportBundleKey::IncludeSensitiveKey);
+        bundle.redacted_payload["app_id"] = serde_json::json!("app_other");
+
+        assert!(matches!(
+            bundle.decrypt_and_validate(),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_support_bundle_caps_inflated_payload() {
+        let connection = connection_with_encrypted_request();
+        let mut bundle = connection.support_bundle(SupportBundleKey::IncludeSensitiveKey);
+        let bomb = miniz_oxide::deflate::compress_to_vec(
+            &vec![b' '; MAX_SUPPORT_BUNDLE_PAYLOAD_BYTES + 1],
+            6,
+        );
+        let ciphertext = encrypt(
+            connection.key_bytes.expose(),
+            &base64_decode(&bundle.iv).unwrap(),
+            &bomb,
+        )
+        .unwrap();
+        bundle.ciphertext = base64_encode(&ciphertext);
+        bundle.compressed = true;
+
+        let Err(Error::InvalidConfiguration(message)) = bundle.decrypt_and_validate() else {
+            panic!("expected the oversized payload to be rejected");
+        };
+        assert!(message.contains("too large"), "{message}");
+    }
+
+    #[test]
+    fn test_poll_rejects_oversized_response() {
+        let body = format!(
+            r#"{{"status":"initialized","padding":"{}"}}"#,
+            "a".repeat(2048)
+        );
+        let mut connection = sample_connection(None).with_max_response_bytes(1024);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Raw(200, body)]);
+
+        let err = poll_once(&connection).unwrap_err();
+        assert!(matches!(err, Error::BridgeError(ref message) if message == "response too large"));
+    }
+
+    #[test]
+    fn test_poll_rejects_deeply_nested_response() {
+        let depth = 10_000;
+        let nested = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        // Unknown fields are skipped without recursing
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Raw(
+                    200,
+                    format!(r#"{{"status":"initialized","padding":{nested}}}"#),
+                ),
+                MockStatus::Raw(
+                    200,
+                    encrypted_completed_raw(&format!(r#"{{"legacy_responses":{nested}}}"#)),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            poll_once(&connection).unwrap(),
+            Status::WaitingForConnection
+        );
+        // The decrypted payload hits serde_json's recursion limit instead of the stack
+        assert!(matches!(
+            poll_once(&connection),
+            Err(Error::ResponseDecryption {
+                kind: DecryptionFailureKind::PlaintextNotJson,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_bridge_response_classifies_failures() {
+        let connection = sample_connection(None);
+        let iv = [1u8; 12];
+        let sealed = |key: &[u8; 32], plaintext: &[u8]| EncryptedPayload {
+            iv: base64_encode(&iv),
+            payload: base64_encode(&encrypt(key, &iv, plaintext).unwrap()),
+            compressed: false,
+        };
+        let kind = |encrypted: &EncryptedPayload| match connection
+            .decrypt_bridge_response(encrypted)
+            .unwrap_err()
+        {
+            Error::ResponseDecryption { request_id, kind } => {
+                assert_eq!(request_id, connection.request_id());
+                kind
+            }
+            other => panic!("expected a decryption failure, got {other:?}"),
+        };
+
+        let orb =
+            serde_json::to_vec(&BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1)).unwrap();
+        assert!(connection
+            .decrypt_bridge_response(&sealed(&[0; 32], &orb))
+            .is_ok());
+
+        let mut not_base64 = sealed(&[0; 32], &orb);
+        not_base64.payload.insert(0, '!');
+        let mut short_iv = sealed(&[0; 32], &orb);
+        short_iv.iv = base64_encode(&[1u8; 8]);
+        let truncated = EncryptedPayload {
+            iv: base64_encode(&iv),
+            payload: base64_encode(&[0u8; 15]),
+            compressed: false,
+        };
+        for malformed in [not_base64, short_iv, truncated] {
+            assert_eq!(kind(&malformed), DecryptionFailureKind::MalformedCiphertext);
+        }
+
+        // Encrypted for another key, as after regenerating the QR code
+        assert_eq!(
+            kind(&sealed(&[9; 32], &orb)),
+            DecryptionFailureKind::KeyMismatchSuspected
+        );
+        let mut tampered = sealed(&[0; 32], &orb);
+        tampered.iv = base64_encode(&[2u8; 12]);
+        assert_eq!(kind(&tampered), DecryptionFailureKind::KeyMismatchSuspected);
+
+        assert_eq!(
+            kind(&sealed(&[0; 32], b"<html>bad gateway</html>")),
+            DecryptionFailureKind::PlaintextNotJson
+        );
+    }
+
+    #[test]
+    fn test_wait_for_proof_retries_malformed_ciphertext_once() {
+        let malformed = MockStatus::Raw(
+            200,
+            serde_json::json!({
+                "status": "completed",
+                "response": { "iv": "!!", "payload": "!!" },
+            })
+            .to_string(),
+        );
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let config = PollConfig {
+            interval: std::time::Duration::from_millis(10),
+            ..PollConfig::default()
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [malformed.clone(), completed.clone()]);
+        runtime
+            .block_on(connection.wait_for_proof_with_config(config))
+            .unwrap();
+        // Polled twice, then acknowledged
+        assert_eq!(bridge.requests().len(), 3);
+
+        // A second corrupted response ends the wait
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(
+            &mut connection,
+            [malformed.clone(), malformed, completed.clone()],
+        );
+        let err = runtime
+            .block_on(connection.wait_for_proof_with_config(config))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ResponseDecryption {
+                kind: DecryptionFailureKind::MalformedCiphertext,
+                ..
+            }
+        ));
+        assert_eq!(bridge.requests().len(), 2);
+
+        // A key mismatch is terminal right away
+        let iv = [1u8; 12];
+        let wrong_key = MockStatus::Raw(
+            200,
+            serde_json::json!({
+                "status": "completed",
+                "response": {
+                    "iv": base64_encode(&iv),
+                    "payload": base64_encode(&encrypt(&[9; 32], &iv, b"{}").unwrap()),
+                },
+            })
+            .to_string(),
+        );
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [wrong_key, completed]);
+        let err = runtime
+            .block_on(connection.wait_for_proof_with_config(config))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("create a new request and show its QR code"));
+        assert_eq!(bridge.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_poll_config_validation() {
+        let ms = std::time::Duration::from_millis;
+
+        assert!(PollConfig::default().validate().is_ok());
+        assert!(PollConfig {
+            interval: ms(1000),
+            timeout: ms(5000),
+            ..PollConfig::default()
+        }
+        .validate()
+        .is_ok());
+        assert!(matches!(
+            PollConfig {
+                interval: ms(0),
+                timeout: ms(5000),
+                ..PollConfig::default()
+            }
+            .validate(),
+            Err(Error::InvalidConfiguration(_))
+        ));
+        assert!(matches!(
+            PollConfig {
+                interval: ms(5000),
+                timeout: ms(5000),
+                ..PollConfig::default()
+            }
+            .validate(),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_long_poll_request_configuration() {
+        let bridge_url = BridgeUrl::default();
+        assert_eq!(
+            response_url(&bridge_url, "abc", None).unwrap().as_str(),
+            "https://bridge.worldcoin.org/response/abc"
+        );
+        assert_eq!(
+            response_url(&bridge_url, "abc", Some(std::time::Duration::from_secs(25)))
+                .unwrap()
+                .as_str(),
+            "https://bridge.worldcoin.org/response/abc?wait=25"
+        );
+        assert_eq!(
+            long_poll_timeout(std::time::Duration::from_secs(25)),
+            std::time::Duration::from_secs(30)
+        );
+
+        let secs = std::time::Duration::from_secs;
+        assert!(PollConfig::default().long_poll(secs(25)).validate().is_ok());
+        for max_wait in [std::time::Duration::from_millis(500), secs(15 * 60)] {
+            assert!(matches!(
+                PollConfig::default().long_poll(max_wait).validate(),
+                Err(Error::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_status_stream_falls_back_when_long_poll_is_ignored() {
+        use futures_util::StreamExt;
+
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        // The mock answers at once, as a bridge without long-poll support would
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Initialized,
+                MockStatus::Initialized,
+                MockStatus::Initialized,
+                completed,
+            ],
+        );
+
+        let options = StatusStreamOptions {
+            initial_interval: std::time::Duration::from_millis(1),
+            max_interval: std::time::Duration::from_millis(4),
+            deadline: None,
+            mode: PollMode::LongPoll {
+                max_wait: std::time::Duration::from_secs(20),
+            },
+        };
+        let statuses: Vec<_> = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.status_stream(options).collect());
+        assert!(matches!(
+            statuses.as_slice(),
+            [Ok(Status::WaitingForConnection), Ok(Status::Confirmed(_))]
+        ));
+
+        assert_eq!(
+            bridge.requests(),
+            [
+                "GET /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26?wait=20 HTTP/1.1",
+                "GET /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26?wait=20 HTTP/1.1",
+                "GET /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26 HTTP/1.1",
+                "GET /response/64e0ec6b-b4ca-47cc-8f70-504a95189e26 HTTP/1.1",
+            ]
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct CapturedTrace(Arc<Mutex<Vec<String>>>);
+
+    #[cfg(feature = "tracing")]
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for CapturedTrace {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            use std::fmt::Write as _;
+
+            let mut line = format!("span {}", attrs.metadata().name());
+            attrs.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    let _ = write!(line, " {}={value:?}", field.name());
+                },
+            );
+            self.0.lock().unwrap().push(line);
+        }
+
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            use std::fmt::Write as _;
+
+            let mut line = String::from("event");
+            event.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    let _ = write!(line, " {}={value:?}", field.name());
+                },
+            );
+            self.0.lock().unwrap().push(line);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_records_lifecycle_without_secrets() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let proof = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1);
+        let (bridge_url, bridge) = start_mock_bridge();
+        bridge.script_create([(
+            200,
+            r#"{"request_id":"64e0ec6b-b4ca-47cc-8f70-504a95189e26"}"#.to_string(),
+        )]);
+        let params = sample_create_params(bridge_url.clone());
+        // The completion is encrypted for the fixed test key, so the trace can
+        // be checked for its ciphertext
+        let completed = encrypted_completed_raw(&serde_json::to_string(&proof).unwrap());
+        let mut fixed_key = sample_connection(None);
+        fixed_key.bridge_url = bridge_url;
+        bridge.script(&fixed_key, [MockStatus::Raw(200, completed.clone())]);
+
+        let captured = CapturedTrace::default();
+        let subscriber = tracing_subscriber::registry().with(captured.clone());
+        let created = tracing::subscriber::with_default(subscriber, || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let created = BridgeConnection::create(params).await.unwrap();
+                let status = fixed_key.poll_for_status().await.unwrap();
+                assert!(matches!(status, Status::Confirmed(_)));
+                created
+            })
+        });
+
+        let lines = captured.0.lock().unwrap().join("\n");
+        for expected in [
+            "span idkit.create app_id=app_staging_test",
+            "request payload encrypted",
+            "bridge request created",
+            "span idkit.poll request_id=64e0ec6b-b4ca-47cc-8f70-504a95189e26",
+            "response decrypted",
+        ] {
+            assert!(
+                lines.contains(expected),
+                "missing {expected:?} in:\n{lines}"
+            );
+        }
+        let key = base64_encode(created.key_bytes.expose());
+        for secret in [
+            key.as_str(),
+            &proof.proof,
+            &proof.nullifier_hash,
+            &completed,
+        ] {
+            assert!(!lines.contains(secret), "trace leaked {secret:?}");
+        }
+    }
+
+    #[test]
+    fn test_wait_for_proof_with_config_honors_interval() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(
+            &mut connection,
+            [
+                MockStatus::Initialized,
+                MockStatus::Initialized,
+                MockStatus::Retrieved,
+                completed,
+            ],
+        );
+        let interval = std::time::Duration::from_millis(50);
+
+        let started = std::time::Instant::now();
+        let result = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_config(PollConfig {
+                interval,
+                timeout: std::time::Duration::from_secs(10),
+                ..PollConfig::default()
+            }))
+            .unwrap();
+
+        // Four polls are separated by three full intervals, then acknowledged
+        assert!(started.elapsed() >= interval * 3);
+        assert_eq!(bridge.requests().len(), 5);
+        assert_eq!(result.action.as_deref(), Some("test-action"));
+    }
+
+    #[test]
+    fn test_wait_timeout_is_capped_at_session_ttl() {
+        let mut connection = sample_connection(None);
+        let long = std::time::Duration::from_mins(5);
+        let short = std::time::Duration::from_secs(10);
+
+        // Unknown creation time leaves the caller's timeout alone
+        assert_eq!(connection.effective_wait_timeout(long), long);
+
+        connection.created_at = Some(current_unix_seconds().unwrap());
+        let connection = connection.with_session_ttl(std::time::Duration::from_mins(1));
+        let capped = connection.effective_wait_timeout(long);
+        assert!(capped <= std::time::Duration::from_mins(1));
+        assert!(capped >= std::time::Duration::from_secs(59));
+        assert_eq!(connection.effective_wait_timeout(short), short);
+
+        // The bridge's own TTL is the upper bound
+        let connection = connection.with_session_ttl(std::time::Duration::from_hours(1));
+        assert_eq!(connection.session_ttl, BRIDGE_REQUEST_TTL);
+        assert_eq!(
+            connection.session_expires_at(),
+            connection
+                .created_at
+                .map(|created_at| created_at + BRIDGE_REQUEST_TTL.as_secs())
+        );
+    }
+
+    #[test]
+    fn test_clock_stepping_backwards_is_clamped() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let clock = Arc::new(MockClock::new(1_700_000_100));
+        let mut connection = sample_connection(None).with_clock(clock.clone());
+        let _bridge = mock_bridge_for(
+            &mut connection,
+            [MockStatus::Initialized, MockStatus::Retrieved, completed],
+        );
+        connection.created_at = Some(1_700_000_000);
+
+        assert_eq!(
+            poll_once(&connection).unwrap(),
+            Status::WaitingForConnection
+        );
+        // An NTP correction steps the clock back past the creation time
+        clock.rewind(std::time::Duration::from_mins(10));
+        assert_eq!(
+            poll_once(&connection).unwrap(),
+            Status::AwaitingConfirmation
+        );
+        clock.advance(std::time::Duration::from_mins(2));
+        assert!(matches!(
+            poll_once(&connection).unwrap(),
+            Status::Confirmed(_)
+        ));
+
+        let observed: Vec<u64> = connection
+            .status_history()
+            .iter()
+            .map(|transition| transition.observed_at)
+            .collect();
+        assert_eq!(observed, [1_700_000_100, 1_700_000_100, 1_700_000_100]);
+        assert_eq!(
+            connection.time_to_connection(),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(
+            connection.time_to_confirmation(),
+            Some(std::time::Duration::ZERO)
+        );
+
+        // Waits never outlast the TTL, however far the clock went back
+        clock.rewind(std::time::Duration::from_hours(1));
+        assert_eq!(
+            connection.effective_wait_timeout(std::time::Duration::from_hours(2)),
+            BRIDGE_REQUEST_TTL
+        );
+        // ...and stop once it passed
+        clock.advance(std::time::Duration::from_hours(2));
+        assert_eq!(
+            connection.effective_wait_timeout(std::time::Duration::from_hours(2)),
+            std::time::Duration::ZERO
+        );
+        assert_eq!(
+            connection
+                .verification_outcome(
+                    confirmed_result(serde_json::json!([])),
+                    std::time::Duration::ZERO
+                )
+                .completed_at,
+            Some(1_700_003_220)
+        );
+    }
+
+    #[test]
+    fn test_wait_for_proof_stops_once_session_ttl_passes() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        connection.created_at = Some(current_unix_seconds().unwrap() - 60);
+        let connection = connection.with_session_ttl(std::time::Duration::from_secs(1));
+
+        let started = std::time::Instant::now();
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_timeout(std::time::Duration::from_mins(5)))
+            .unwrap_err();
+
+        assert!(matches!(err, Error::Timeout));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_wait_for_proof_with_config_rejects_invalid_config() {
+        let connection = sample_connection(None);
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_config(PollConfig {
+                interval: std::time::Duration::ZERO,
+                timeout: std::time::Duration::from_secs(1),
+                ..PollConfig::default()
+            }))
+            .unwrap_err();
+
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_wait_for_proof_surfaces_pending_inclusion_proof() {
+        // The bridge keeps serving World App's one response
+        let pending = MockStatus::Completed(serde_json::json!({
+            "error_code": "inclusion_proof_pending",
+        }));
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Retrieved, pending]);
+
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_config(PollConfig {
+                interval: std::time::Duration::from_millis(10),
+                timeout: std::time::Duration::from_secs(10),
+                ..PollConfig::default()
+            }))
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::InclusionPending { retry_after_hint } if retry_after_hint == INCLUSION_PENDING_RETRY_HINT
+        ));
+        // The failed request is finished, so nothing is polled or acknowledged after it
+        assert_eq!(bridge.requests().len(), 2);
+        assert!(connection.finished.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_wait_for_proof_with_cancel_returns_promptly() {
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let trigger = cancel.clone();
+
+        let started = std::time::Instant::now();
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async {
+                tokio::spawn(async move {
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    trigger.cancel();
+                });
+                connection.wait_for_proof_with_cancel(cancel).await
+            })
+            .unwrap_err();
+
+        // Cancelled while sleeping out the default 1s interval after the first poll
+        assert!(matches!(err, Error::Cancelled));
+        assert!(started.elapsed() < std::time::Duration::from_millis(900));
+        assert_eq!(bridge.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_wait_for_proof_with_cancel_skips_polling_when_already_cancelled() {
+        let mut connection = sample_connection(None);
+        let bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        let cancel = tokio_util::sync::CancellationToken::new();
+        cancel.cancel();
+
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(connection.wait_for_proof_with_cancel(cancel))
+            .unwrap_err();
+
+        assert!(matches!(err, Error::Cancelled));
+        assert!(bridge.requests().is_empty());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_cancel_handle_cancels_blocking_wait_from_another_thread() {
+        let mut connection = sample_connection(None);
+        let _bridge = mock_bridge_for(&mut connection, [MockStatus::Initialized]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = CancelHandle::new();
+        let trigger = handle.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            trigger.cancel();
+        });
+
+        let err = wait_for_proof_blocking(&runtime, &connection, true, &handle).unwrap_err();
+
+        assert!(handle.is_cancelled());
+        assert!(matches!(err, crate::error::IdkitError::Cancelled));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_blocking_wait_surfaces_pending_inclusion_proof() {
+        let transport = InMemoryTransport::default();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        let connection = runtime
+            .block_on(
+                BridgeConnection::builder(sample_create_params(BridgeUrl::default()))
+                    .transport(Box::new(transport.clone()))
+                    .create(),
+            )
+            .unwrap();
+        let completed = |plaintext: serde_json::Value| {
+            let iv = [7u8; 12];
+            let ciphertext = encrypt(
+                connection.key_bytes.expose(),
+                &iv,
+                plaintext.to_string().as_bytes(),
+            )
+            .unwrap();
+            Ok(BridgePollResponse {
+                status: "completed".to_string(),
+                response: Some(EncryptedPayload {
+                    iv: base64_encode(&iv),
+                    payload: base64_encode(&ciphertext),
+                    compressed: false,
+                }),
+            })
+        };
+        let pending = completed(serde_json::json!({ "error_code": "inclusion_proof_pending" }));
+
+        transport.bridge().poll_responses.push_back(pending);
+        let err = wait_for_proof_blocking(&runtime, &connection, false, &CancelHandle::new())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::IdkitError::InclusionPending { retry_after_secs }
+                if retry_after_secs == INCLUSION_PENDING_RETRY_HINT.as_secs()
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_failed_status_wrapper_carries_retry_hint() {
+        assert!(matches!(
+            StatusWrapper::from(Status::Failed(AppError::InclusionProofPending)),
+            StatusWrapper::Failed {
+                error: AppError::InclusionProofPending,
+                retry_after_secs: Some(secs),
+            } if secs == INCLUSION_PENDING_RETRY_HINT.as_secs()
+        ));
+        assert!(matches!(
+            StatusWrapper::from(Status::Failed(AppError::UserRejected)),
+            StatusWrapper::Failed {
+                error: AppError::UserRejected,
+                retry_after_secs: None,
+            }
+        ));
+    }
+
+    fn resumed(connection: &BridgeConnection) -> BridgeConnection {
+        let json = serde_json::to_string(&connection.to_resumable()).unwrap();
+        BridgeConnection::from_resumable(serde_json::from_str(&json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_resumable_round_trip_preserves_connect_url() {
+        let mut connection = connection_with_encrypted_request()
+            .with_max_response_bytes(4096)
+            .with_session_ttl(std::time::Duration::from_millis(1500));
+        connection.return_to = Some("idkitsample://callback".to_string());
+        connection.invite_code = Some("ABC123".to_string());
+        connection.code_expires_at = Some(1_700_000_900);
+        connection.acknowledged.store(true, Ordering::Release);
+
+        let resumed = resumed(&connection);
+
+        assert_eq!(resumed.connect_url(), connection.connect_url());
+        assert_eq!(resumed.request_id(), connection.request_id());
+        assert_eq!(resumed.code_expires_at, connection.code_expires_at);
+        assert_eq!(resumed.max_response_bytes, 4096);
+        assert_eq!(resumed.session_ttl, std::time::Duration::from_millis(1500));
+        assert!(resumed.acknowledged.load(Ordering::Acquire));
+        assert_eq!(
+            serde_json::to_value(resumed.to_resumable()).unwrap(),
+            serde_json::to_value(connection.to_resumable()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fixture_round_trip_rebuilds_connection() {
+        let mut connection = connection_with_encrypted_request();
+        connection.constraints = Some(ConstraintNode::item(CredentialRequest::new(
+            CredentialType::ProofOfHuman,
+            Some(Signal::from_string("user")),
+        )));
+        connection.return_to = Some("idkitsample://callback".to_string());
+
+        let fixture = connection.fixture();
+        let json = serde_json::to_string(&fixture).unwrap();
+        assert!(!json.contains(&base64_encode(connection.key_bytes.expose())));
+
+        let rebuilt = BridgeConnection::from_fixture(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(
+            serde_json::to_value(rebuilt.fixture()).unwrap(),
+            serde_json::to_value(&fixture).unwrap()
+        );
+        assert_eq!(rebuilt.key_bytes.expose(), &FIXTURE_KEY);
+        let request = decrypt(
+            &FIXTURE_KEY,
+            &base64_decode(&rebuilt.encrypted_request.iv).unwrap(),
+            &base64_decode(&rebuilt.encrypted_request.payload).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&request).unwrap(),
+            connection.request_payload
+        );
+
+        // The same fixture always rebuilds the same connection
+        let again = BridgeConnection::from_fixture(fixture).unwrap();
+        assert_eq!(again.connect_url(), rebuilt.connect_url());
+        assert_eq!(
+            again.encrypted_request.payload,
+            rebuilt.encrypted_request.payload
+        );
+    }
+
+    #[test]
+    fn test_resumed_connection_decrypts_completed_response() {
+        let completed = MockStatus::Completed(
+            serde_json::to_value(BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1))
+                .unwrap(),
+        );
+        let mut connection = connection_with_encrypted_request();
+        // Local bridge URLs are only accepted for staging apps
+        connection.app_id = "app_staging_test".to_string();
+        let _bridge = mock_bridge_for(&mut connection, [completed]);
+
+        let resumed = resumed(&connection);
+
+        let status = poll_once(&resumed).unwrap();
+        assert!(matches!(status, Status::Confirmed(_)));
+        assert_eq!(status, poll_once(&connection).unwrap());
+    }
+
+    #[test]
+    fn test_resumable_debug_redacts_key() {
+        let mut connection = connection_with_encrypted_request();
+        connection.key_bytes = SecretBytes::new([0xab; 32]);
+        let state = connection.to_resumable();
+        let key = base64_encode(&[0xab; 32]);
+        assert!(serde_json::to_string(&state).unwrap().contains(&key));
+
+        let debug = format!("{state:?}");
+        assert!(debug.contains(connection.request_id()));
+        assert!(debug.contains("key: \"REDACTED\""));
+        assert!(!debug.contains(&key));
+        assert!(!debug.contains("request_payload"));
+    }
+
+    #[test]
+    fn test_from_resumable_rejects_bad_key() {
+        let mut state =
+            serde_json::to_value(connection_with_encrypted_request().to_resumable()).unwrap();
+        state["key"] = serde_json::json!(base64_encode(&[0u8; 16]));
+
+        assert!(matches!(
+            BridgeConnection::from_resumable(serde_json::from_value(state).unwrap()),
+            Err(Error::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_resume_polls_existing_request_from_id_and_key() {
+        let bridge = MockBridge::start().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let app_id = AppId::new("app_staging_test").unwrap();
+        let connection = runtime
+            .block_on(BridgeConnection::create(sample_create_params(
+                bridge.bridge_url(&app_id).unwrap(),
+            )))
+            .unwrap();
+
+        let resumed = BridgeConnection::resume(
+            &app_id,
+            connection.request_id(),
+            connection.key_bytes.expose().to_vec(),
+            Some(bridge.bridge_url(&app_id).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(resumed.connect_url(), connection.connect_url());
+
+        let proof = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 3);
+        bridge.script(
+            &connection,
+            [
+                MockStatus::Retrieved,
+                MockStatus::Completed(serde_json::to_value(&proof).unwrap()),
+            ],
+        );
+        assert_eq!(
+            runtime.block_on(resumed.poll_for_status()).unwrap(),
+            Status::AwaitingConfirmation
+        );
+        // The legacy signal is unknown, so a legacy proof cannot be matched
+        assert!(matches!(
+            runtime.block_on(resumed.poll_for_status()),
+            Err(Error::InvalidConfiguration(_))
+        ));
+
+        // Resuming never creates a second request
+        let posts = bridge
+            .requests()
+            .iter()
+            .filter(|line| line.starts_with("POST /request"))
+            .count();
+        assert_eq!(posts, 1);
+    }
+
+    #[test]
+    fn test_resume_rejects_bad_request_id_and_key() {
+        let app_id = AppId::new("app_staging_test").unwrap();
+        for (request_id, key) in [
+            ("", vec![0u8; 32]),
+            ("../request", vec![0u8; 32]),
+            ("64e0ec6b-b4ca-47cc-8f70-504a95189e26", vec![0u8; 16]),
+        ] {
+            assert!(matches!(
+                BridgeConnection::resume(&app_id, request_id, key, None),
+                Err(Error::InvalidConfiguration(_))
+            ));
+        }
+    }
 }
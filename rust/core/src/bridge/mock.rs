@@ -0,0 +1,586 @@
+//! In-process bridge for end-to-end tests
+//!
+//! [`MockBridge`] serves `POST /request`, `GET /response/:id` and
+//! `DELETE /response/:id` on a loopback port. A [`BridgeConnection`] created
+//! with [`MockBridge::bridge_url`] runs its real HTTP code against it, so
+//! polling, retries and acknowledgement are exercised end to end.
+//!
+//! Tests script the statuses each request reports with
+//! [`MockBridge::script`]. Completed responses are encrypted with the
+//! connection's key, as World App would. [`MockBridge::script_create`] and
+//! [`MockBridge::script_health`] script the other endpoints, e.g. to reject
+//! creation or to answer the health check like a server that is not a bridge.
+//!
+//! [`CapturingTransport`] skips the network altogether and keeps the sealed
+//! payload, for tests and benchmarks that only measure request creation, such
+//! as those built on [`large_signal_params`].
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::{
+    BridgeConnection, BridgeConnectionParams, BridgePollResponse, BridgeTransport,
+    EncryptedPayload, RequestKind, TransportFuture,
+};
+use crate::crypto::{base64_encode, encrypt, generate_nonce};
+use crate::types::{
+    Action, AppId, BridgeUrl, CredentialRequest, CredentialType, RpContext, Signal,
+    VerificationLevel,
+};
+use crate::ConstraintNode;
+
+/// A status the mock reports when a request is polled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockStatus {
+    /// World App has not picked up the request yet
+    Initialized,
+    /// World App has retrieved the request
+    Retrieved,
+    /// World App responded with this plaintext. It is encrypted with the
+    /// request's key before it is served.
+    Completed(serde_json::Value),
+    /// The poll fails with this HTTP status
+    HttpError(u16),
+    /// The poll is answered with this HTTP status and body verbatim, e.g. a
+    /// malformed body or a response encrypted with another key
+    Raw(u16, String),
+}
+
+struct MockRequest {
+    key: Option<Vec<u8>>,
+    /// Statuses still to be served, one per poll
+    statuses: VecDeque<MockStatus>,
+    /// Served once `statuses` runs out
+    last: MockStatus,
+}
+
+impl MockRequest {
+    const fn initialized() -> Self {
+        Self {
+            key: None,
+            statuses: VecDeque::new(),
+            last: MockStatus::Initialized,
+        }
+    }
+
+    fn next_status(&mut self) -> MockStatus {
+        if let Some(status) = self.statuses.pop_front() {
+            self.last = status;
+        }
+        self.last.clone()
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: HashMap<String, MockRequest>,
+    /// Answers to `POST /request` still to be served, before requests are
+    /// created as usual
+    create_responses: VecDeque<(u16, String)>,
+    /// Answers to `GET /health` still to be served, before the mock reports
+    /// itself healthy
+    health_responses: VecDeque<(u16, String)>,
+    /// Request lines received, e.g. `GET /response/abc?wait=20`
+    log: Vec<String>,
+    /// Raw requests received: request line, headers and body
+    raw_log: Vec<String>,
+}
+
+/// A bridge served from a background thread on `127.0.0.1`
+///
+/// Requests created through it report `initialized` until scripted. The
+/// server stops when the mock is dropped.
+pub struct MockBridge {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl MockBridge {
+    /// Binds a free loopback port and starts serving
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound
+    pub fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let server_state = state.clone();
+        let server_shutdown = shutdown.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    // A client that hangs up mid-request only loses its own answer
+                    let _ = serve(stream, &server_state);
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown,
+        })
+    }
+
+    /// Returns the mock's base URL, e.g. `http://127.0.0.1:41234`
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns the mock's URL for `app_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless `app_id` is a staging app, since only those
+    /// may use a loopback bridge
+    pub fn bridge_url(&self, app_id: &AppId) -> crate::Result<BridgeUrl> {
+        BridgeUrl::new(self.url(), app_id)
+    }
+
+    /// Queues the statuses `connection`'s request reports, one per poll
+    ///
+    /// The last status keeps being served once the queue runs out. Replaces
+    /// any statuses queued earlier for the same request.
+    pub fn script(
+        &self,
+        connection: &BridgeConnection,
+        statuses: impl IntoIterator<Item = MockStatus>,
+    ) {
+        let key = connection.key_bytes.expose().to_vec();
+        let statuses = statuses.into_iter().collect();
+        let mut state = self.lock();
+        let request = state
+            .requests
+            .entry(connection.request_id.clone())
+            .or_insert_with(MockRequest::initialized);
+        request.key = Some(key);
+        request.statuses = statuses;
+        drop(state);
+    }
+
+    /// Queues the answers to `POST /request`, one per call, as HTTP status
+    /// and body
+    ///
+    /// A successful answer with a `request_id` creates that request. Once the
+    /// queue runs out, requests are created as usual.
+    pub fn script_create(&self, responses: impl IntoIterator<Item = (u16, String)>) {
+        self.lock().create_responses.extend(responses);
+    }
+
+    /// Queues the answers to `GET /health`, one per call, as HTTP status and
+    /// body
+    ///
+    /// Once the queue runs out, the mock reports itself healthy.
+    pub fn script_health(&self, responses: impl IntoIterator<Item = (u16, String)>) {
+        self.lock().health_responses.extend(responses);
+    }
+
+    /// Returns the request lines received so far, in order
+    #[must_use]
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().log.clone()
+    }
+
+    /// Returns the raw requests received so far, in order
+    ///
+    /// Each holds the request line, the headers and the body.
+    #[must_use]
+    pub fn raw_requests(&self) -> Vec<String> {
+        self.lock().raw_log.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockBridge {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wakes the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+impl std::fmt::Debug for MockBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockBridge")
+            .field("addr", &self.addr)
+            .field(
+                "requests",
+                &self.state.lock().map_or(0, |state| state.requests.len()),
+            )
+            .field("shutdown", &self.shutdown.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+/// Reads one HTTP request from `stream` and answers it
+fn serve(stream: TcpStream, state: &Mutex<MockState>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut raw = String::new();
+    reader.read_line(&mut raw)?;
+    let request_line = raw.trim_end().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        raw.push_str(&header);
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    raw.push_str(&String::from_utf8_lossy(&body));
+
+    let mut guard = state.lock().unwrap_or_else(PoisonError::into_inner);
+    guard.log.push(request_line.clone());
+    guard.raw_log.push(raw);
+    let (status, response) = route(&mut guard, &request_line, &body);
+    drop(guard);
+
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{response}",
+        response.len()
+    )?;
+    stream.flush()
+}
+
+/// Returns the HTTP status and body for one request
+fn route(state: &mut MockState, request_line: &str, body: &[u8]) -> (u16, String) {
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    // `?wait=` is accepted but every poll is answered straight away
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+
+    match (method, path) {
+        ("GET", "/health") => state
+            .health_responses
+            .pop_front()
+            .unwrap_or_else(|| (200, serde_json::json!({ "status": "ok" }).to_string())),
+        ("POST", "/request") => {
+            if let Some((status, response)) = state.create_responses.pop_front() {
+                let created = serde_json::from_str::<serde_json::Value>(&response)
+                    .ok()
+                    .and_then(|body| body["request_id"].as_str().map(str::to_string));
+                if let Some(request_id) = created.filter(|_| (200..300).contains(&status)) {
+                    state
+                        .requests
+                        .entry(request_id)
+                        .or_insert_with(MockRequest::initialized);
+                }
+                return (status, response);
+            }
+            // Invite-code mode picks its own id; URL/QR mode leaves it to the bridge
+            let request_id = serde_json::from_slice::<serde_json::Value>(body)
+                .ok()
+                .and_then(|body| body["request_id"].as_str().map(str::to_string))
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            state
+                .requests
+                .entry(request_id.clone())
+                .or_insert_with(MockRequest::initialized);
+            (
+                201,
+                serde_json::json!({ "request_id": request_id }).to_string(),
+            )
+        }
+        (method, path) if path.starts_with("/response/") => {
+            let request_id = &path["/response/".len()..];
+            match method {
+                "GET" => state
+                    .requests
+                    .get_mut(request_id)
+                    .map_or_else(|| (404, "{}".to_string()), poll_response),
+                "DELETE" => match state.requests.remove(request_id) {
+                    Some(_) => (200, "{}".to_string()),
+                    None => (404, "{}".to_string()),
+                },
+                _ => (405, "{}".to_string()),
+            }
+        }
+        _ => (404, "{}".to_string()),
+    }
+}
+
+fn poll_response(request: &mut MockRequest) -> (u16, String) {
+    let body = match request.next_status() {
+        MockStatus::Initialized => serde_json::json!({ "status": "initialized" }),
+        MockStatus::Retrieved => serde_json::json!({ "status": "retrieved" }),
+        MockStatus::Completed(plaintext) => {
+            let Some(key) = &request.key else {
+                return (500, "{}".to_string());
+            };
+            let Ok(nonce) = generate_nonce() else {
+                return (500, "{}".to_string());
+            };
+            let Ok(ciphertext) = encrypt(key, &nonce, plaintext.to_string().as_bytes()) else {
+                return (500, "{}".to_string());
+            };
+            serde_json::json!({
+                "status": "completed",
+                "response": {
+                    "iv": base64_encode(&nonce),
+                    "payload": base64_encode(&ciphertext),
+                },
+            })
+        }
+        MockStatus::HttpError(status) => return (status, "{}".to_string()),
+        MockStatus::Raw(status, body) => return (status, body),
+    };
+    (200, body.to_string())
+}
+
+/// Size of the signal carried by [`large_signal_params`]
+pub const LARGE_SIGNAL_BYTES: usize = 2 * 1024 * 1024;
+
+/// Keeps the payload of the created request instead of sending it anywhere
+///
+/// Clones share the captured payload. Polls fail, since no response is ever
+/// sent to the request.
+#[derive(Debug, Clone, Default)]
+pub struct CapturingTransport(Arc<Mutex<Option<EncryptedPayload>>>);
+
+impl CapturingTransport {
+    /// Returns the payload of the last request created through this transport
+    #[must_use]
+    pub fn payload(&self) -> Option<EncryptedPayload> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl BridgeTransport for CapturingTransport {
+    fn create_request<'a>(
+        &'a self,
+        _bridge_url: &'a BridgeUrl,
+        payload: &'a EncryptedPayload,
+    ) -> TransportFuture<'a, String> {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(payload.clone());
+        Box::pin(std::future::ready(Ok(
+            "64e0ec6b-b4ca-47cc-8f70-504a95189e26".to_string(),
+        )))
+    }
+
+    fn poll<'a>(
+        &'a self,
+        _bridge_url: &'a BridgeUrl,
+        _request_id: &'a str,
+        _max_response_bytes: usize,
+    ) -> TransportFuture<'a, BridgePollResponse> {
+        Box::pin(std::future::ready(Err(crate::Error::BridgeError(
+            "no response is ever sent to this request".to_string(),
+        ))))
+    }
+
+    fn acknowledge<'a>(
+        &'a self,
+        _bridge_url: &'a BridgeUrl,
+        _request_id: &'a str,
+    ) -> TransportFuture<'a, ()> {
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+/// Returns [`LARGE_SIGNAL_BYTES`] pseudo-random bytes, which keep the payload
+/// from deflating to almost nothing
+#[must_use]
+pub fn large_signal() -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..LARGE_SIGNAL_BYTES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state.to_le_bytes()[0]
+        })
+        .collect()
+}
+
+/// Returns params for a staging uniqueness request whose single credential
+/// carries [`large_signal`]
+///
+/// # Panics
+///
+/// Never; the fixed RP context and IDs are valid.
+#[must_use]
+pub fn large_signal_params(compress_payload: bool) -> BridgeConnectionParams {
+    let sig = "0x".to_string() + &"00".repeat(64) + "1b";
+    let rp_context = RpContext::new(
+        "rp_1234567890abcdef",
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+        1_700_000_000,
+        1_700_003_600,
+        &sig,
+    )
+    .expect("fixed RP context is valid");
+    BridgeConnectionParams {
+        app_id: AppId::new("app_staging_test").expect("fixed app ID is valid"),
+        package_name: "idkit_test".to_string(),
+        package_version: "1.0.0".to_string(),
+        kind: RequestKind::Uniqueness {
+            action: Action::new("my-action").expect("fixed action is valid"),
+        },
+        constraints: Some(ConstraintNode::item(CredentialRequest::new(
+            CredentialType::ProofOfHuman,
+            Some(Signal::from_bytes(large_signal())),
+        ))),
+        rp_context,
+        action_description: None,
+        legacy_verification_level: VerificationLevel::Orb,
+        legacy_signal: String::new(),
+        bridge_url: Some(BridgeUrl::default()),
+        allow_legacy_proofs: false,
+        require_user_presence: false,
+        normalize_constraints: true,
+        compress_payload,
+        override_connect_base_url: None,
+        return_to: None,
+        return_to_schemes: Vec::new(),
+        environment: None,
+        identity_attributes: None,
+        locale: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::{BridgeConnectionParams, PollConfig, RequestKind};
+    use crate::types::{BridgeResponseV1, RpContext, VerificationLevel};
+    use crate::{Error, Status};
+
+    fn orb_params(bridge: &MockBridge) -> BridgeConnectionParams {
+        let sig = "0x".to_string() + &"00".repeat(64) + "1b";
+        let rp_context = RpContext::new(
+            "rp_1234567890abcdef",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            1_700_000_000,
+            1_700_003_600,
+            &sig,
+        )
+        .unwrap();
+        let app_id = AppId::new("app_staging_test").unwrap();
+        BridgeConnectionParams {
+            bridge_url: Some(bridge.bridge_url(&app_id).unwrap()),
+            app_id,
+            package_name: "idkit_test".to_string(),
+            package_version: "1.0.0".to_string(),
+            kind: RequestKind::Uniqueness {
+                action: crate::Action::new("my-action").unwrap(),
+            },
+            constraints: None,
+            rp_context,
+            action_description: None,
+            legacy_verification_level: VerificationLevel::Orb,
+            legacy_signal: String::new(),
+            allow_legacy_proofs: false,
+            require_user_presence: false,
+            normalize_constraints: true,
+            compress_payload: false,
+            override_connect_base_url: None,
+            return_to: None,
+            return_to_schemes: Vec::new(),
+            environment: None,
+            identity_attributes: None,
+            locale: None,
+        }
+    }
+
+    #[test]
+    fn test_mock_bridge_scripts_status_progression() {
+        let bridge = MockBridge::start().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let connection = runtime
+            .block_on(BridgeConnection::create(orb_params(&bridge)))
+            .unwrap();
+
+        let proof = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 1);
+        bridge.script(
+            &connection,
+            [
+                MockStatus::Initialized,
+                MockStatus::Retrieved,
+                MockStatus::Completed(serde_json::to_value(&proof).unwrap()),
+            ],
+        );
+
+        assert_eq!(
+            runtime.block_on(connection.poll_for_status()).unwrap(),
+            Status::WaitingForConnection
+        );
+        assert_eq!(
+            runtime.block_on(connection.poll_for_status()).unwrap(),
+            Status::AwaitingConfirmation
+        );
+        let Status::Confirmed(result) = runtime.block_on(connection.poll_for_status()).unwrap()
+        else {
+            panic!("expected a confirmed status");
+        };
+        assert_eq!(result.responses.len(), 1);
+
+        let requests = bridge.requests();
+        assert_eq!(requests[0], "POST /request HTTP/1.1");
+        let request_id = connection.request_id();
+        assert!(requests[1..]
+            .iter()
+            .all(|line| line.contains(&format!("/response/{request_id}"))));
+    }
+
+    #[test]
+    fn test_mock_bridge_serves_wait_for_proof_and_http_errors() {
+        let bridge = MockBridge::start().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let connection = runtime
+            .block_on(BridgeConnection::create(orb_params(&bridge)))
+            .unwrap();
+
+        let proof = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 2);
+        bridge.script(
+            &connection,
+            [
+                MockStatus::Retrieved,
+                MockStatus::Completed(serde_json::to_value(&proof).unwrap()),
+            ],
+        );
+        let config = PollConfig {
+            interval: std::time::Duration::from_millis(10),
+            ..PollConfig::default()
+        };
+        let result = runtime
+            .block_on(connection.wait_for_proof_with_config(config))
+            .unwrap();
+        assert_eq!(result.responses.len(), 1);
+
+        bridge.script(&connection, [MockStatus::HttpError(503)]);
+        assert!(matches!(
+            runtime.block_on(connection.poll_for_status()),
+            Err(Error::BridgeHttp { status: 503, .. })
+        ));
+    }
+}
@@ -8,7 +8,7 @@
 //! The underlying protocol types use string identifiers and lifetimes,
 //! which allows them to be decoupled (potentially replaced by an external crate).
 
-use crate::types::{CredentialRequest, CredentialType};
+use crate::types::{CredentialRequest, CredentialType, Signal};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::collections::HashSet;
@@ -17,7 +17,25 @@ use world_id_primitives::{
 };
 
 #[cfg(feature = "ffi")]
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Deepest operator nesting [`ConstraintNode::validate`] accepts
+pub const MAX_CONSTRAINT_DEPTH: usize = 4;
+
+/// Largest node count, operators and credentials alike, [`ConstraintNode::validate`] accepts
+pub const MAX_CONSTRAINT_NODES: usize = 24;
+
+/// Which part of a constraint tree a response satisfied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+pub struct SatisfiedBranch {
+    /// Highest-priority returned credential that satisfies the constraints
+    pub credential: CredentialType,
+    /// Position of `credential` in [`ConstraintNode::priority_order`]
+    pub priority_index: u32,
+    /// Whether `credential` is the most preferred credential of the request
+    pub was_preferred: bool,
+}
 
 /// A node in the constraint tree
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +63,23 @@ pub enum ConstraintNode {
         /// Child constraints to evaluate and include when satisfied
         enumerate: Vec<Self>,
     },
+
+    /// A NOT node - satisfied only when the child is not satisfied
+    ///
+    /// Negation only contributes to boolean evaluation; it never names a
+    /// satisfying credential on its own.
+    Not {
+        /// Child constraint to negate
+        not: Box<Self>,
+    },
+
+    /// A threshold node - at least `at_least` children must be satisfied
+    AtLeast {
+        /// Minimum number of children that must be satisfied
+        at_least: usize,
+        /// Child constraints (priority ordered)
+        of: Vec<Self>,
+    },
 }
 
 impl ConstraintNode {
@@ -66,6 +101,23 @@ impl ConstraintNode {
         Self::Enumerate { enumerate: nodes }
     }
 
+    /// Creates a Not constraint negating the given node
+    #[must_use]
+    pub fn negate(node: Self) -> Self {
+        Self::Not {
+            not: Box::new(node),
+        }
+    }
+
+    /// Creates an `AtLeast` constraint requiring `threshold` of the given nodes
+    #[must_use]
+    pub fn at_least(threshold: usize, nodes: Vec<Self>) -> Self {
+        Self::AtLeast {
+            at_least: threshold,
+            of: nodes,
+        }
+    }
+
     /// Creates an item node from a `CredentialRequest`
     #[must_use]
     pub fn item(request: CredentialRequest) -> Self {
@@ -80,6 +132,10 @@ impl ConstraintNode {
             Self::Any { any } => any.iter().any(|node| node.evaluate(available)),
             Self::All { all } => all.iter().all(|node| node.evaluate(available)),
             Self::Enumerate { enumerate } => enumerate.iter().any(|node| node.evaluate(available)),
+            Self::Not { not } => !not.evaluate(available),
+            Self::AtLeast { at_least, of } => {
+                of.iter().filter(|node| node.evaluate(available)).count() >= *at_least
+            }
         }
     }
 
@@ -89,6 +145,9 @@ impl ConstraintNode {
     /// For All nodes, returns None if not all satisfied, or attempts to find a single credential.
     /// For Enumerate nodes, returns the first satisfiable child in evaluation order.
     /// For Item nodes, returns the credential type if available.
+    /// For `AtLeast` nodes, returns None unless the threshold is met, then the first
+    /// satisfying child in priority order.
+    /// Not nodes never name a satisfying credential and always return None.
     #[must_use]
     pub fn first_satisfying(&self, available: &HashSet<CredentialType>) -> Option<CredentialType> {
         match self {
@@ -132,6 +191,14 @@ impl ConstraintNode {
                 }
                 None
             }
+            Self::AtLeast { of, .. } => {
+                if !self.evaluate(available) {
+                    return None;
+                }
+
+                of.iter().find_map(|node| node.first_satisfying(available))
+            }
+            Self::Not { .. } => None,
         }
     }
 
@@ -163,6 +230,84 @@ impl ConstraintNode {
                     node.collect_credential_types_recursive(result);
                 }
             }
+            Self::Not { not } => not.collect_credential_types_recursive(result),
+            Self::AtLeast { of, .. } => {
+                for node in of {
+                    node.collect_credential_types_recursive(result);
+                }
+            }
+        }
+    }
+
+    /// Returns the credential types in the order World App would try them,
+    /// without duplicates
+    ///
+    /// Children are visited left to right, so earlier `any` and `at_least`
+    /// branches come first. Credentials under a `Not` node are never
+    /// presented and are left out.
+    #[must_use]
+    pub fn priority_order(&self) -> Vec<CredentialType> {
+        let mut order = Vec::new();
+        self.push_priority_order(&mut order);
+        order
+    }
+
+    /// Returns the only credential this tree asks for, if it reduces to one
+    ///
+    /// That is the case for a lone leaf, and for `any` or `all` nodes with a
+    /// single child that reduces to one credential. UIs can skip the
+    /// credential chooser for such requests.
+    #[must_use]
+    pub fn single_credential(&self) -> Option<CredentialType> {
+        match self {
+            Self::Item(item) => Some(item.credential_type),
+            Self::Any { any: children } | Self::All { all: children } => {
+                match children.as_slice() {
+                    [child] => child.single_credential(),
+                    _ => None,
+                }
+            }
+            Self::Enumerate { .. } | Self::Not { .. } | Self::AtLeast { .. } => None,
+        }
+    }
+
+    /// Returns which branch the `returned` credentials satisfy, or `None` if
+    /// they don't satisfy this tree
+    ///
+    /// An app asking for `any(orb, passport)` can use `was_preferred` to tell
+    /// whether the user answered with the first choice.
+    #[must_use]
+    pub fn satisfied_branch(&self, returned: &HashSet<CredentialType>) -> Option<SatisfiedBranch> {
+        let credential = self.first_satisfying(returned)?;
+        let index = self
+            .priority_order()
+            .iter()
+            .position(|candidate| *candidate == credential)?;
+        Some(SatisfiedBranch {
+            credential,
+            priority_index: u32::try_from(index).ok()?,
+            was_preferred: index == 0,
+        })
+    }
+
+    fn push_priority_order(&self, order: &mut Vec<CredentialType>) {
+        match self {
+            Self::Item(item) => {
+                if !order.contains(&item.credential_type) {
+                    order.push(item.credential_type);
+                }
+            }
+            Self::Any { any: children }
+            | Self::All { all: children }
+            | Self::Enumerate {
+                enumerate: children,
+            }
+            | Self::AtLeast { of: children, .. } => {
+                for child in children {
+                    child.push_priority_order(order);
+                }
+            }
+            Self::Not { .. } => {}
         }
     }
 
@@ -176,6 +321,89 @@ impl ConstraintNode {
             Self::Enumerate { enumerate } => {
                 enumerate.iter().flat_map(Self::collect_items).collect()
             }
+            Self::Not { not } => not.collect_items(),
+            Self::AtLeast { of, .. } => of.iter().flat_map(Self::collect_items).collect(),
+        }
+    }
+
+    /// Returns true if any credential leaf requires a user presence check
+    ///
+    /// Leaves under a `Not` node are ignored, since they are never presented.
+    #[must_use]
+    pub fn requires_user_presence(&self) -> bool {
+        match self {
+            Self::Item(item) => item.require_user_presence == Some(true),
+            Self::Any { any: children }
+            | Self::All { all: children }
+            | Self::Enumerate {
+                enumerate: children,
+            }
+            | Self::AtLeast { of: children, .. } => {
+                children.iter().any(Self::requires_user_presence)
+            }
+            Self::Not { .. } => false,
+        }
+    }
+
+    /// Sets `signal` on every credential leaf that has no signal of its own
+    #[must_use]
+    pub fn with_default_signal(self, signal: &Signal) -> Self {
+        let fill = |nodes: Vec<Self>| {
+            nodes
+                .into_iter()
+                .map(|node| node.with_default_signal(signal))
+                .collect()
+        };
+        match self {
+            Self::Item(mut item) => {
+                if item.effective_signal().is_none() && !signal.is_empty() {
+                    item.signal = Some(signal.clone());
+                }
+                Self::Item(item)
+            }
+            Self::Any { any } => Self::Any { any: fill(any) },
+            Self::All { all } => Self::All { all: fill(all) },
+            Self::Enumerate { enumerate } => Self::Enumerate {
+                enumerate: fill(enumerate),
+            },
+            Self::Not { not } => Self::Not {
+                not: Box::new(not.with_default_signal(signal)),
+            },
+            Self::AtLeast { at_least, of } => Self::AtLeast {
+                at_least,
+                of: fill(of),
+            },
+        }
+    }
+
+    /// Collapses every `Any` or `All` node with a single child into that child
+    ///
+    /// A one-option `any` and a bare credential mean the same thing, but some
+    /// World App versions render the wrapper as a picker with a single entry.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let normalize = |nodes: Vec<Self>| nodes.into_iter().map(Self::normalized).collect();
+        match self {
+            Self::Any { any: mut nodes } | Self::All { all: mut nodes } if nodes.len() == 1 => {
+                nodes.remove(0).normalized()
+            }
+            Self::Item(item) => Self::Item(item),
+            Self::Any { any } => Self::Any {
+                any: normalize(any),
+            },
+            Self::All { all } => Self::All {
+                all: normalize(all),
+            },
+            Self::Enumerate { enumerate } => Self::Enumerate {
+                enumerate: normalize(enumerate),
+            },
+            Self::Not { not } => Self::Not {
+                not: Box::new(not.normalized()),
+            },
+            Self::AtLeast { at_least, of } => Self::AtLeast {
+                at_least,
+                of: normalize(of),
+            },
         }
     }
 
@@ -183,8 +411,98 @@ impl ConstraintNode {
     ///
     /// # Errors
     ///
-    /// Returns an error if the tree is invalid (e.g., empty Any/All/Enumerate nodes)
+    /// Returns an error if the tree is invalid (e.g., empty Any/All/Enumerate nodes),
+    /// or nests deeper than [`MAX_CONSTRAINT_DEPTH`] or has more than
+    /// [`MAX_CONSTRAINT_NODES`] nodes
     pub fn validate(&self) -> crate::Result<()> {
+        self.validate_max_depth(MAX_CONSTRAINT_DEPTH)?;
+        self.validate_max_nodes(MAX_CONSTRAINT_NODES)?;
+        self.validate_user_presence_consistency()?;
+        self.validate_structure()
+    }
+
+    /// Rejects trees that both require and explicitly waive user presence for
+    /// the same credential type
+    fn validate_user_presence_consistency(&self) -> crate::Result<()> {
+        let mut seen: Vec<(CredentialType, bool)> = Vec::new();
+        for item in self.collect_items() {
+            let Some(required) = item.require_user_presence else {
+                continue;
+            };
+            match seen
+                .iter()
+                .find(|(credential_type, _)| *credential_type == item.credential_type)
+            {
+                Some((_, earlier)) if *earlier != required => {
+                    return Err(crate::Error::InvalidConfiguration(format!(
+                        "Conflicting require_user_presence settings for {}",
+                        item.credential_type
+                    )));
+                }
+                Some(_) => {}
+                None => seen.push((item.credential_type, required)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that no path through the tree crosses more than `max` operator
+    /// nodes. A bare credential has depth 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree is nested deeper than `max`
+    pub fn validate_max_depth(&self, max: usize) -> crate::Result<()> {
+        let depth = self.depth();
+        if depth > max {
+            return Err(crate::Error::InvalidConfiguration(format!(
+                "Constraint tree is nested {depth} levels deep, the limit is {max}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the tree has at most `max` nodes, counting operators and
+    /// credentials alike
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree has more than `max` nodes
+    pub fn validate_max_nodes(&self, max: usize) -> crate::Result<()> {
+        let count = self.node_count();
+        if count > max {
+            return Err(crate::Error::InvalidConfiguration(format!(
+                "Constraint tree has {count} nodes, the limit is {max}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn children(&self) -> &[Self] {
+        match self {
+            Self::Item(_) => &[],
+            Self::Any { any: children }
+            | Self::All { all: children }
+            | Self::Enumerate {
+                enumerate: children,
+            }
+            | Self::AtLeast { of: children, .. } => children,
+            Self::Not { not } => std::slice::from_ref(not.as_ref()),
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Self::Item(_) => 0,
+            _ => 1 + self.children().iter().map(Self::depth).max().unwrap_or(0),
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        1 + self.children().iter().map(Self::node_count).sum::<usize>()
+    }
+
+    fn validate_structure(&self) -> crate::Result<()> {
         match self {
             Self::Item(_) => Ok(()),
             Self::Any { any } => {
@@ -194,7 +512,7 @@ impl ConstraintNode {
                     ));
                 }
                 for node in any {
-                    node.validate()?;
+                    node.validate_structure()?;
                 }
                 Ok(())
             }
@@ -205,7 +523,7 @@ impl ConstraintNode {
                     ));
                 }
                 for node in all {
-                    node.validate()?;
+                    node.validate_structure()?;
                 }
                 Ok(())
             }
@@ -216,7 +534,25 @@ impl ConstraintNode {
                     ));
                 }
                 for node in enumerate {
-                    node.validate()?;
+                    node.validate_structure()?;
+                }
+                Ok(())
+            }
+            Self::Not { not } => not.validate_structure(),
+            Self::AtLeast { at_least, of } => {
+                if *at_least == 0 {
+                    return Err(crate::Error::InvalidConfiguration(
+                        "AtLeast constraint threshold must be at least 1".to_string(),
+                    ));
+                }
+                if *at_least > of.len() {
+                    return Err(crate::Error::InvalidConfiguration(format!(
+                        "AtLeast constraint threshold {at_least} exceeds its {} children",
+                        of.len()
+                    )));
+                }
+                for node in of {
+                    node.validate_structure()?;
                 }
                 Ok(())
             }
@@ -228,19 +564,51 @@ impl ConstraintNode {
     // ─────────────────────────────────────────────────────────────────────────
 
     /// Converts this node to a protocol constraint node (expression form)
-    fn to_protocol_node(&self) -> ProtocolNode<'static> {
-        match self {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree contains a Not or `AtLeast` node, which the protocol
+    /// cannot express
+    fn to_protocol_node(&self) -> crate::Result<ProtocolNode<'static>> {
+        Ok(match self {
             Self::Item(item) => ProtocolNode::Type(Cow::Owned(item.credential_type.to_string())),
-            Self::Any { any } => ProtocolNode::Expr(ProtocolExpr::Any {
-                any: any.iter().map(Self::to_protocol_node).collect(),
-            }),
-            Self::All { all } => ProtocolNode::Expr(ProtocolExpr::All {
-                all: all.iter().map(Self::to_protocol_node).collect(),
-            }),
-            Self::Enumerate { enumerate } => ProtocolNode::Expr(ProtocolExpr::Enumerate {
-                enumerate: enumerate.iter().map(Self::to_protocol_node).collect(),
-            }),
-        }
+            _ => ProtocolNode::Expr(self.to_protocol_expr()?),
+        })
+    }
+
+    /// Converts a non-item node to a protocol constraint expression
+    fn to_protocol_expr(&self) -> crate::Result<ProtocolExpr<'static>> {
+        Ok(match self {
+            // If a constraint tree is only one item, we convert it to any(item)
+            Self::Item(item) => ProtocolExpr::Any {
+                any: vec![ProtocolNode::Type(Cow::Owned(
+                    item.credential_type.to_string(),
+                ))],
+            },
+            Self::Any { any } => ProtocolExpr::Any {
+                any: Self::to_protocol_nodes(any)?,
+            },
+            Self::All { all } => ProtocolExpr::All {
+                all: Self::to_protocol_nodes(all)?,
+            },
+            Self::Enumerate { enumerate } => ProtocolExpr::Enumerate {
+                enumerate: Self::to_protocol_nodes(enumerate)?,
+            },
+            Self::Not { .. } => {
+                return Err(crate::Error::InvalidConfiguration(
+                    "Not constraints can only be evaluated locally and cannot be sent to World App"
+                        .to_string(),
+                ))
+            }
+            Self::AtLeast { .. } => return Err(crate::Error::InvalidConfiguration(
+                "AtLeast constraints can only be evaluated locally and cannot be sent to World App"
+                    .to_string(),
+            )),
+        })
+    }
+
+    fn to_protocol_nodes(nodes: &[Self]) -> crate::Result<Vec<ProtocolNode<'static>>> {
+        nodes.iter().map(Self::to_protocol_node).collect()
     }
 
     /// Converts constraint tree to protocol types
@@ -250,7 +618,8 @@ impl ConstraintNode {
     ///
     /// # Errors
     ///
-    /// Returns an error if any `CredentialRequest` cannot be converted to protocol format
+    /// Returns an error if any `CredentialRequest` cannot be converted to protocol format,
+    /// or if the tree contains a Not or `AtLeast` node
     pub fn to_protocol(&self) -> crate::Result<(Vec<RequestItem>, ProtocolExpr<'static>)> {
         // Extract unique request items and convert to protocol
         let items = self.collect_items();
@@ -260,23 +629,7 @@ impl ConstraintNode {
             .collect::<crate::Result<Vec<_>>>()?;
 
         // Build constraint expression
-        let expr = match self {
-            // If a constraint tree is only one item, we convert it to any(item)
-            Self::Item(item) => ProtocolExpr::Any {
-                any: vec![ProtocolNode::Type(Cow::Owned(
-                    item.credential_type.to_string(),
-                ))],
-            },
-            Self::Any { any } => ProtocolExpr::Any {
-                any: any.iter().map(Self::to_protocol_node).collect(),
-            },
-            Self::All { all } => ProtocolExpr::All {
-                all: all.iter().map(Self::to_protocol_node).collect(),
-            },
-            Self::Enumerate { enumerate } => ProtocolExpr::Enumerate {
-                enumerate: enumerate.iter().map(Self::to_protocol_node).collect(),
-            },
-        };
+        let expr = self.to_protocol_expr()?;
 
         Ok((protocol_items, expr))
     }
@@ -288,7 +641,8 @@ impl ConstraintNode {
     ///
     /// # Errors
     ///
-    /// Returns an error if any `CredentialRequest` cannot be converted to protocol format
+    /// Returns an error if any `CredentialRequest` cannot be converted to protocol format,
+    /// or if the tree contains a Not or `AtLeast` node
     pub fn to_protocol_top_level(
         &self,
     ) -> crate::Result<(Vec<RequestItem>, Option<ProtocolExpr<'static>>)> {
@@ -302,15 +656,7 @@ impl ConstraintNode {
         // Single item doesn't need constraint expression
         let expr = match self {
             Self::Item(_) => None, // Just one credential, no constraint needed
-            Self::Any { any } => Some(ProtocolExpr::Any {
-                any: any.iter().map(Self::to_protocol_node).collect(),
-            }),
-            Self::All { all } => Some(ProtocolExpr::All {
-                all: all.iter().map(Self::to_protocol_node).collect(),
-            }),
-            Self::Enumerate { enumerate } => Some(ProtocolExpr::Enumerate {
-                enumerate: enumerate.iter().map(Self::to_protocol_node).collect(),
-            }),
+            _ => Some(self.to_protocol_expr()?),
         };
 
         Ok((protocol_items, expr))
@@ -353,6 +699,47 @@ impl ConstraintNode {
         Arc::new(Self::enumerate(core_nodes))
     }
 
+    /// Creates a "not" (negation) constraint node
+    #[must_use]
+    #[uniffi::constructor(name = "not")]
+    pub fn ffi_not(node: Arc<Self>) -> Arc<Self> {
+        Arc::new(Self::negate((*node).clone()))
+    }
+
+    /// Creates an "at least" (threshold) constraint node
+    #[must_use]
+    #[uniffi::constructor(name = "at_least")]
+    pub fn ffi_at_least(threshold: u32, nodes: Vec<Arc<Self>>) -> Arc<Self> {
+        let core_nodes = nodes.iter().map(|n| (**n).clone()).collect();
+        Arc::new(Self::at_least(threshold as usize, core_nodes))
+    }
+
+    /// Validates the constraint tree, so callers can check it before creating a request
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the tree is invalid (e.g., an empty
+    /// group or an "at least" threshold above its child count)
+    #[uniffi::method(name = "validate")]
+    pub fn ffi_validate(&self) -> std::result::Result<(), crate::error::IdkitError> {
+        Ok(self.validate()?)
+    }
+
+    /// Returns the credential types in the order World App would try them,
+    /// without duplicates
+    #[must_use]
+    #[uniffi::method(name = "priority_order")]
+    pub fn ffi_priority_order(&self) -> Vec<CredentialType> {
+        self.priority_order()
+    }
+
+    /// Returns the only credential this tree asks for, if it reduces to one
+    #[must_use]
+    #[uniffi::method(name = "single_credential")]
+    pub fn ffi_single_credential(&self) -> Option<CredentialType> {
+        self.single_credential()
+    }
+
     /// Serializes a constraint node to JSON
     ///
     /// # Errors
@@ -376,6 +763,92 @@ impl ConstraintNode {
     }
 }
 
+/// Fluent builder for constraint trees, for bindings where nesting `ConstraintNode`
+/// constructors by hand is awkward.
+///
+/// Each builder collects a group of requirements. Nested groups are built with their
+/// own builder and attached with `any_of` / `all_of`. At the top level, multiple
+/// requirements are combined with "all" semantics.
+#[cfg(feature = "ffi")]
+#[derive(uniffi::Object, Default)]
+pub struct ConstraintsBuilder {
+    nodes: Mutex<Vec<ConstraintNode>>,
+}
+
+#[cfg(feature = "ffi")]
+impl ConstraintsBuilder {
+    fn push(self: Arc<Self>, node: ConstraintNode) -> Arc<Self> {
+        self.nodes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(node);
+        self
+    }
+
+    /// Copies the collected requirements, leaving the builder reusable
+    fn to_nodes(&self) -> Vec<ConstraintNode> {
+        self.nodes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[cfg(feature = "ffi")]
+#[uniffi::export]
+#[allow(clippy::needless_pass_by_value)]
+impl ConstraintsBuilder {
+    /// Creates an empty builder
+    #[must_use]
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Adds a single credential requirement to this group
+    #[must_use]
+    pub fn require_credential(self: Arc<Self>, request: Arc<CredentialRequest>) -> Arc<Self> {
+        self.push(ConstraintNode::item((*request).clone()))
+    }
+
+    /// Adds an "any" (OR) node built from the requirements collected in `group`
+    #[must_use]
+    pub fn any_of(self: Arc<Self>, group: Arc<Self>) -> Arc<Self> {
+        let nodes = group.to_nodes();
+        self.push(ConstraintNode::any(nodes))
+    }
+
+    /// Adds an "all" (AND) node built from the requirements collected in `group`
+    #[must_use]
+    pub fn all_of(self: Arc<Self>, group: Arc<Self>) -> Arc<Self> {
+        let nodes = group.to_nodes();
+        self.push(ConstraintNode::all(nodes))
+    }
+
+    /// Builds and validates the constraint tree
+    ///
+    /// A single requirement is returned as-is; several are combined with "all".
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfiguration` if the builder or any nested group is empty
+    pub fn build(&self) -> std::result::Result<Arc<ConstraintNode>, crate::error::IdkitError> {
+        let mut nodes = self.to_nodes();
+        let node = match nodes.len() {
+            0 => {
+                return Err(crate::Error::InvalidConfiguration(
+                    "Constraints builder must have at least one requirement".to_string(),
+                )
+                .into())
+            }
+            1 => nodes.remove(0),
+            _ => ConstraintNode::all(nodes),
+        };
+        node.validate()?;
+        Ok(Arc::new(node))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +869,160 @@ mod tests {
         CredentialRequest::new(CredentialType::Mnc, None)
     }
 
+    #[test]
+    fn test_single_credential() {
+        let leaf = ConstraintNode::item(passport_item());
+        assert_eq!(leaf.single_credential(), Some(CredentialType::Passport));
+        assert_eq!(
+            ConstraintNode::any(vec![ConstraintNode::all(vec![leaf.clone()])]).single_credential(),
+            Some(CredentialType::Passport)
+        );
+
+        let choice = ConstraintNode::any(vec![leaf.clone(), ConstraintNode::item(mnc_item())]);
+        assert_eq!(choice.single_credential(), None);
+        assert_eq!(
+            ConstraintNode::negate(leaf.clone()).single_credential(),
+            None
+        );
+        assert_eq!(
+            ConstraintNode::enumerate(vec![leaf]).single_credential(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_priority_order_flattens_nested_tree() {
+        let node = ConstraintNode::all(vec![
+            ConstraintNode::any(vec![
+                ConstraintNode::item(passport_item()),
+                ConstraintNode::all(vec![
+                    ConstraintNode::item(mnc_item()),
+                    ConstraintNode::item(passport_item()),
+                ]),
+            ]),
+            ConstraintNode::negate(ConstraintNode::item(selfie_item())),
+            ConstraintNode::at_least(
+                1,
+                vec![
+                    ConstraintNode::item(poh_item()),
+                    ConstraintNode::item(mnc_item()),
+                ],
+            ),
+        ]);
+
+        assert_eq!(
+            node.priority_order(),
+            [
+                CredentialType::Passport,
+                CredentialType::Mnc,
+                CredentialType::ProofOfHuman,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_satisfied_branch() {
+        let node = ConstraintNode::any(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::all(vec![
+                ConstraintNode::item(passport_item()),
+                ConstraintNode::item(selfie_item()),
+            ]),
+        ]);
+
+        let branch = |returned: &[CredentialType]| {
+            node.satisfied_branch(&returned.iter().copied().collect())
+        };
+        assert_eq!(
+            branch(&[CredentialType::ProofOfHuman]),
+            Some(SatisfiedBranch {
+                credential: CredentialType::ProofOfHuman,
+                priority_index: 0,
+                was_preferred: true,
+            })
+        );
+        assert_eq!(
+            branch(&[CredentialType::Selfie, CredentialType::Passport]),
+            Some(SatisfiedBranch {
+                credential: CredentialType::Passport,
+                priority_index: 1,
+                was_preferred: false,
+            })
+        );
+        // Half of the `all` branch satisfies nothing
+        assert_eq!(branch(&[CredentialType::Passport]), None);
+    }
+
+    #[test]
+    fn test_bare_and_attributed_leaves_deserialize_side_by_side() {
+        let node: ConstraintNode = serde_json::from_value(serde_json::json!({
+            "any": [
+                {"type": "proof_of_human"},
+                {"type": "selfie", "require_user_presence": true, "genesis_issued_at_min": 100},
+            ]
+        }))
+        .unwrap();
+
+        let items = node.collect_items();
+        assert_eq!(items[0].require_user_presence, None);
+        assert_eq!(items[1].require_user_presence, Some(true));
+        assert_eq!(items[1].genesis_issued_at_min, Some(100));
+        assert!(node.requires_user_presence());
+
+        // Bare leaves serialize exactly as before
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(
+            json["any"][0],
+            serde_json::json!({"type": "proof_of_human"})
+        );
+        assert_eq!(json["any"][1]["require_user_presence"], true);
+    }
+
+    #[test]
+    fn test_normalized_collapses_single_child_wrappers() {
+        // Both the wrapped and the bare form deserialize
+        let wrapped: ConstraintNode = serde_json::from_value(serde_json::json!({
+            "all": [{"any": [{"type": "proof_of_human"}]}, {"type": "passport"}]
+        }))
+        .unwrap();
+        let bare: ConstraintNode = serde_json::from_value(serde_json::json!({
+            "all": [{"type": "proof_of_human"}, {"type": "passport"}]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(wrapped.normalized()).unwrap(),
+            serde_json::to_value(&bare).unwrap()
+        );
+        assert!(matches!(
+            ConstraintNode::all(vec![ConstraintNode::any(vec![ConstraintNode::item(
+                poh_item()
+            )])])
+            .normalized(),
+            ConstraintNode::Item(_)
+        ));
+
+        // Enumerate, Not and AtLeast keep their shape
+        let enumerate = ConstraintNode::enumerate(vec![ConstraintNode::item(poh_item())]);
+        assert!(matches!(
+            enumerate.normalized(),
+            ConstraintNode::Enumerate { enumerate } if enumerate.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_attributed_leaf_satisfies_like_bare_leaf() {
+        let attributed = ConstraintNode::item(poh_item().with_user_presence(true));
+        let available: HashSet<CredentialType> = [CredentialType::ProofOfHuman].into();
+
+        assert!(attributed.evaluate(&available));
+        assert_eq!(
+            attributed.first_satisfying(&available),
+            Some(CredentialType::ProofOfHuman)
+        );
+        assert!(attributed.validate().is_ok());
+    }
+
     #[test]
     fn test_item_node() {
         let node = ConstraintNode::item(poh_item());
@@ -507,6 +1134,33 @@ mod tests {
         assert_eq!(node.first_satisfying(&available), Some(CredentialType::Mnc));
     }
 
+    #[test]
+    fn test_with_default_signal_keeps_leaf_signals() {
+        let own = CredentialRequest::new(
+            CredentialType::Passport,
+            Some(Signal::from_string("own".to_string())),
+        );
+        let node = ConstraintNode::any(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::all(vec![
+                ConstraintNode::item(own),
+                ConstraintNode::item(mnc_item()),
+            ]),
+        ])
+        .with_default_signal(&Signal::from_string("shared".to_string()));
+
+        let signals: Vec<_> = node
+            .collect_items()
+            .iter()
+            .map(|item| item.effective_signal().and_then(Signal::as_str))
+            .collect();
+        assert_eq!(signals, [Some("shared"), Some("own"), Some("shared")]);
+
+        let node = ConstraintNode::item(poh_item())
+            .with_default_signal(&Signal::from_string(String::new()));
+        assert!(node.collect_items()[0].effective_signal().is_none());
+    }
+
     #[test]
     fn test_face_orb_example() {
         let node = ConstraintNode::any(vec![
@@ -593,6 +1247,74 @@ mod tests {
         assert!(invalid_enumerate.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_rejects_conflicting_user_presence() {
+        let leaf = |credential_type, presence: Option<bool>| {
+            let request = CredentialRequest::new(credential_type, None);
+            ConstraintNode::item(match presence {
+                Some(required) => request.with_user_presence(required),
+                None => request,
+            })
+        };
+
+        let conflicting = ConstraintNode::any(vec![
+            leaf(CredentialType::ProofOfHuman, Some(true)),
+            ConstraintNode::all(vec![
+                leaf(CredentialType::Passport, None),
+                leaf(CredentialType::ProofOfHuman, Some(false)),
+            ]),
+        ]);
+        assert!(matches!(
+            conflicting.validate(),
+            Err(crate::Error::InvalidConfiguration(msg)) if msg.contains("proof_of_human")
+        ));
+
+        // Repeating a setting, leaving one unset, or differing across types is fine
+        let consistent = ConstraintNode::any(vec![
+            leaf(CredentialType::ProofOfHuman, Some(true)),
+            leaf(CredentialType::ProofOfHuman, Some(true)),
+            leaf(CredentialType::ProofOfHuman, None),
+            leaf(CredentialType::Passport, Some(false)),
+        ]);
+        assert!(consistent.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validation_limits() {
+        let nested = |depth: usize| {
+            (0..depth).fold(ConstraintNode::item(poh_item()), |node, _| {
+                ConstraintNode::all(vec![node])
+            })
+        };
+        assert!(nested(MAX_CONSTRAINT_DEPTH).validate().is_ok());
+        assert!(matches!(
+            nested(MAX_CONSTRAINT_DEPTH + 1).validate(),
+            Err(crate::Error::InvalidConfiguration(msg)) if msg.contains("levels deep")
+        ));
+        assert!(nested(2).validate_max_depth(2).is_ok());
+        assert!(nested(2).validate_max_depth(1).is_err());
+
+        // One `any` plus the given number of leaves
+        let wide = |leaves: usize| {
+            ConstraintNode::any(
+                (0..leaves)
+                    .map(|_| ConstraintNode::item(poh_item()))
+                    .collect(),
+            )
+        };
+        assert!(wide(MAX_CONSTRAINT_NODES - 1).validate().is_ok());
+        assert!(matches!(
+            wide(MAX_CONSTRAINT_NODES).validate(),
+            Err(crate::Error::InvalidConfiguration(msg)) if msg.contains("25 nodes")
+        ));
+        assert!(ConstraintNode::item(poh_item())
+            .validate_max_nodes(1)
+            .is_ok());
+        assert!(ConstraintNode::negate(ConstraintNode::item(poh_item()))
+            .validate_max_nodes(1)
+            .is_err());
+    }
+
     #[test]
     fn test_serialization() {
         let node = ConstraintNode::any(vec![
@@ -684,4 +1406,337 @@ mod tests {
         assert!(nested_json.contains("passport"));
         assert!(nested_json.contains("mnc"));
     }
+
+    #[test]
+    fn test_not_node() {
+        // proof_of_human AND NOT selfie
+        let node = ConstraintNode::all(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::negate(ConstraintNode::item(selfie_item())),
+        ]);
+
+        let mut available = HashSet::new();
+        available.insert(CredentialType::ProofOfHuman);
+
+        assert!(node.evaluate(&available));
+        // The Not branch never names a credential, so the item wins
+        assert_eq!(
+            node.first_satisfying(&available),
+            Some(CredentialType::ProofOfHuman)
+        );
+
+        available.insert(CredentialType::Selfie);
+        assert!(!node.evaluate(&available));
+        assert_eq!(node.first_satisfying(&available), None);
+    }
+
+    #[test]
+    fn test_not_node_first_satisfying_is_none() {
+        let node = ConstraintNode::negate(ConstraintNode::item(selfie_item()));
+        let available = HashSet::new();
+
+        assert!(node.evaluate(&available));
+        assert_eq!(node.first_satisfying(&available), None);
+    }
+
+    #[test]
+    fn test_not_node_collect_and_validate() {
+        let node = ConstraintNode::all(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::negate(ConstraintNode::item(selfie_item())),
+        ]);
+
+        let credentials = node.collect_credential_types();
+        assert_eq!(credentials.len(), 2);
+        assert!(credentials.contains(&CredentialType::Selfie));
+        assert!(node.validate().is_ok());
+
+        let invalid = ConstraintNode::negate(ConstraintNode::any(vec![]));
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_not_node_serialization() {
+        let node = ConstraintNode::all(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::negate(ConstraintNode::item(selfie_item())),
+        ]);
+
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["all"][1]["not"]["type"], "selfie");
+
+        let deserialized: ConstraintNode = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            &deserialized,
+            ConstraintNode::All { all } if matches!(&all[1], ConstraintNode::Not { .. })
+        ));
+        assert_eq!(
+            serde_json::to_value(&deserialized).unwrap(),
+            serde_json::to_value(&node).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_not_node_rejected_by_protocol() {
+        let node = ConstraintNode::all(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::negate(ConstraintNode::item(selfie_item())),
+        ]);
+
+        assert!(node.to_protocol().is_err());
+        assert!(node.to_protocol_top_level().is_err());
+    }
+
+    fn document_at_least(threshold: usize) -> ConstraintNode {
+        ConstraintNode::at_least(
+            threshold,
+            vec![
+                ConstraintNode::item(passport_item()),
+                ConstraintNode::item(mnc_item()),
+                ConstraintNode::item(selfie_item()),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_at_least_node_boundaries() {
+        let node = document_at_least(2);
+
+        let mut available = HashSet::new();
+        available.insert(CredentialType::Mnc);
+
+        // k - 1 satisfied
+        assert!(!node.evaluate(&available));
+        assert_eq!(node.first_satisfying(&available), None);
+
+        // exactly k satisfied
+        available.insert(CredentialType::Selfie);
+        assert!(node.evaluate(&available));
+        assert_eq!(node.first_satisfying(&available), Some(CredentialType::Mnc));
+
+        // all satisfied - priority order wins
+        available.insert(CredentialType::Passport);
+        assert!(node.evaluate(&available));
+        assert_eq!(
+            node.first_satisfying(&available),
+            Some(CredentialType::Passport)
+        );
+    }
+
+    #[test]
+    fn test_at_least_node_validation() {
+        assert!(document_at_least(1).validate().is_ok());
+        assert!(document_at_least(3).validate().is_ok());
+        assert!(document_at_least(0).validate().is_err());
+        assert!(document_at_least(4).validate().is_err());
+        assert!(
+            ConstraintNode::at_least(1, vec![ConstraintNode::any(vec![])])
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_at_least_node_collects_children() {
+        let node = document_at_least(2);
+
+        assert_eq!(node.collect_credential_types().len(), 3);
+        assert_eq!(node.collect_items().len(), 3);
+    }
+
+    #[test]
+    fn test_at_least_node_serialization() {
+        let node =
+            ConstraintNode::all(vec![ConstraintNode::item(poh_item()), document_at_least(2)]);
+
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["all"][1]["at_least"], 2);
+        assert_eq!(json["all"][1]["of"][0]["type"], "passport");
+
+        let deserialized: ConstraintNode = serde_json::from_value(json.clone()).unwrap();
+        assert!(matches!(
+            &deserialized,
+            ConstraintNode::All { all } if matches!(&all[1], ConstraintNode::AtLeast { at_least: 2, of } if of.len() == 3)
+        ));
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), json);
+    }
+
+    #[test]
+    fn test_at_least_node_rejected_by_protocol() {
+        assert!(document_at_least(2).to_protocol().is_err());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_constraints_builder_matches_manual_construction() {
+        let builder = ConstraintsBuilder::new()
+            .require_credential(Arc::new(poh_item()))
+            .any_of(
+                ConstraintsBuilder::new()
+                    .require_credential(Arc::new(passport_item()))
+                    .all_of(
+                        ConstraintsBuilder::new()
+                            .require_credential(Arc::new(mnc_item()))
+                            .require_credential(Arc::new(selfie_item())),
+                    ),
+            );
+        let built = builder.build().unwrap();
+
+        let manual = ConstraintNode::all(vec![
+            ConstraintNode::item(poh_item()),
+            ConstraintNode::any(vec![
+                ConstraintNode::item(passport_item()),
+                ConstraintNode::all(vec![
+                    ConstraintNode::item(mnc_item()),
+                    ConstraintNode::item(selfie_item()),
+                ]),
+            ]),
+        ]);
+
+        assert_eq!(built.to_json().unwrap(), manual.to_json().unwrap());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_constraints_builder_single_requirement_is_bare_item() {
+        let built = ConstraintsBuilder::new()
+            .require_credential(Arc::new(poh_item()))
+            .build()
+            .unwrap();
+
+        assert!(matches!(*built, ConstraintNode::Item(_)));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_constraints_builder_rejects_empty_groups() {
+        let empty = ConstraintsBuilder::new().build();
+        assert!(matches!(
+            empty,
+            Err(crate::error::IdkitError::InvalidConfiguration { .. })
+        ));
+
+        let empty_group = ConstraintsBuilder::new()
+            .require_credential(Arc::new(poh_item()))
+            .any_of(ConstraintsBuilder::new())
+            .build();
+        assert!(matches!(
+            empty_group,
+            Err(crate::error::IdkitError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_validate_delegates_to_core() {
+        let item = ConstraintNode::ffi_item(Arc::new(poh_item().with_user_presence(true)));
+        assert!(item.ffi_validate().is_ok());
+        assert!(ConstraintNode::ffi_any(vec![item.clone()])
+            .ffi_validate()
+            .is_ok());
+
+        assert!(matches!(
+            ConstraintNode::ffi_any(vec![]).ffi_validate(),
+            Err(crate::error::IdkitError::InvalidConfiguration { .. })
+        ));
+        assert!(matches!(
+            ConstraintNode::ffi_at_least(2, vec![item]).ffi_validate(),
+            Err(crate::error::IdkitError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_matches_protocol_evaluate() {
+        use crate::test_utils::{arbitrary_constraint_tree, credential_subsets, XorShift};
+
+        let mut rng = XorShift(0x5eed_1dc0_ffee_2024);
+        let subsets = credential_subsets();
+
+        for _ in 0..500 {
+            let node = arbitrary_constraint_tree(&mut rng, 3);
+            let (_, expr) = node.to_protocol().unwrap();
+
+            for available in &subsets {
+                let has_type = |identifier: &str| {
+                    available
+                        .iter()
+                        .any(|credential| credential.as_ref() == identifier)
+                };
+                assert_eq!(
+                    node.evaluate(available),
+                    expr.evaluate(&has_type),
+                    "divergence for {} with {available:?}",
+                    serde_json::to_string(&node).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_satisfying_matches_protocol_selection() {
+        use crate::test_utils::{arbitrary_constraint_tree, credential_subsets, XorShift};
+        use std::str::FromStr;
+        use world_id_primitives::{
+            rp::RpId, FieldElement, OprfKeyId, ProofRequest, ProofType, RequestVersion,
+        };
+
+        let signature =
+            alloy_primitives::Signature::from_str(&format!("0x{}1b", "00".repeat(64))).unwrap();
+        let mut rng = XorShift(0x0dd_ba11_cafe_f00d);
+        let subsets = credential_subsets();
+
+        let mut checked = 0;
+        while checked < 500 {
+            let node = ConstraintNode::any(
+                (0..=rng.below(3))
+                    .map(|_| arbitrary_constraint_tree(&mut rng, 2))
+                    .collect(),
+            );
+            // The protocol returns its selection in request order. A repeated
+            // credential lists every request for it, including those in
+            // branches it did not pick, so only trees with distinct leaves
+            // keep the order of the branch that was selected.
+            if node.collect_items().len() != node.collect_credential_types().len() {
+                continue;
+            }
+            checked += 1;
+            let (requests, expr) = node.to_protocol().unwrap();
+            let request = ProofRequest {
+                id: "req".to_string(),
+                version: RequestVersion::V1,
+                proof_type: ProofType::Uniqueness,
+                session_id: None,
+                action: None,
+                created_at: 1_700_000_000,
+                expires_at: 1_700_003_600,
+                rp_id: RpId::new(1),
+                oprf_key_id: OprfKeyId::new(ruint::aliases::U160::from(1)),
+                signature,
+                nonce: FieldElement::from_str(&format!("0x{}01", "00".repeat(31))).unwrap(),
+                requests,
+                constraints: Some(expr),
+            };
+
+            for available in &subsets {
+                let schema_ids = available
+                    .iter()
+                    .map(CredentialType::issuer_schema_id)
+                    .collect();
+                let selection = request.credentials_to_prove(&schema_ids);
+
+                match node.first_satisfying(available) {
+                    Some(credential) => {
+                        let selection = selection.expect("protocol must select when core does");
+                        assert_eq!(
+                            selection[0].identifier,
+                            credential.as_ref(),
+                            "divergence for {} with {available:?}",
+                            serde_json::to_string(&node).unwrap()
+                        );
+                    }
+                    None => assert!(selection.is_none()),
+                }
+            }
+        }
+    }
 }
@@ -3,6 +3,7 @@
 use crate::Result;
 use ruint::aliases::U256;
 use tiny_keccak::{Hasher, Keccak};
+use world_id_primitives::FieldElement;
 
 // ============================================================================
 // AES-256-GCM encryption (unified implementation for native and WASM)
@@ -10,7 +11,7 @@ use tiny_keccak::{Hasher, Keccak};
 #[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
 use {
     aes_gcm::{
-        aead::{Aead, KeyInit},
+        aead::{Aead, AeadInPlace, KeyInit},
         Aes256Gcm, Nonce,
     },
     getrandom::getrandom,
@@ -69,6 +70,33 @@ pub fn encrypt(key: &[u8], nonce: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
         .map_err(|_| Error::Crypto("Encryption failed".to_string()))
 }
 
+/// Encrypts `buffer` in place using AES-256-GCM, appending the tag
+///
+/// Produces the same output as [`encrypt`] without allocating a second
+/// buffer for the ciphertext, which matters for large payloads.
+///
+/// # Errors
+///
+/// Returns an error if encryption fails
+#[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
+pub fn encrypt_in_place(key: &[u8], nonce: &[u8], buffer: &mut Vec<u8>) -> Result<()> {
+    use crate::Error;
+
+    if key.len() != 32 {
+        return Err(Error::Crypto("Key must be 32 bytes".to_string()));
+    }
+    let nonce_array: [u8; 12] = nonce
+        .try_into()
+        .map_err(|_| Error::Crypto("Nonce must be 12 bytes".to_string()))?;
+
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|_| Error::Crypto("Invalid key length".to_string()))?;
+
+    cipher
+        .encrypt_in_place(&Nonce::from(nonce_array), b"", buffer)
+        .map_err(|_| Error::Crypto("Encryption failed".to_string()))
+}
+
 /// Generates a random AES-GCM nonce (12 bytes).
 ///
 /// Used by the invite-code path, which derives the AES key from the user-typed
@@ -291,8 +319,10 @@ pub(crate) use invite_code::{generate_invite_code, hkdf_invite_index_hex, hkdf_i
 // ============================================================================
 
 /// Cryptographic key wrapper for encryption/decryption
+///
+/// `Debug` output omits the key so it cannot leak into logs.
 #[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct CryptoKey {
     /// AES-256 key (32 bytes)
     pub key: [u8; 32],
@@ -319,6 +349,54 @@ impl CryptoKey {
     }
 }
 
+#[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
+impl std::fmt::Debug for CryptoKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CryptoKey")
+            .field("key", &SecretBytes::REDACTED)
+            .field("nonce", &hex::encode(self.nonce))
+            .finish()
+    }
+}
+
+/// Key material that prints as `REDACTED` instead of its bytes.
+///
+/// Holding keys in this wrapper keeps an accidental `{:?}` of a struct that
+/// contains them from leaking the key into logs.
+#[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct SecretBytes(Vec<u8>);
+
+#[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
+impl SecretBytes {
+    /// Placeholder printed, and substituted into URLs, in place of the key
+    pub(crate) const REDACTED: &'static str = "REDACTED";
+
+    #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+    pub(crate) fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// Returns the raw key bytes. Never log the result.
+    #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+    pub(crate) fn expose(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
+impl std::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(Self::REDACTED)
+    }
+}
+
+/// Encodes an action string as the field element the protocol proves over
+#[must_use]
+pub fn encode_action_field(action: &str) -> FieldElement {
+    FieldElement::from_arbitrary_raw_bytes(action.as_bytes())
+}
+
 /// Hashes a value to a field element using Keccak256
 ///
 /// The output is shifted right by 8 bits to fit within the field prime
@@ -358,6 +436,30 @@ pub fn hash_signal(signal: &crate::Signal) -> String {
     format!("{hash:#066x}")
 }
 
+/// Computes the signal hash committed to a proof request, if any.
+///
+/// Returns `None` when `signal` is absent or empty: an empty signal is not
+/// sent to World App, so there is no hash to match against. Use
+/// [`hash_signal`] to hash a signal unconditionally, e.g. the legacy v3
+/// signal, where `""` is hashed like any other string.
+#[must_use]
+pub fn compute_signal_hash(signal: Option<&crate::Signal>) -> Option<String> {
+    signal.filter(|signal| !signal.is_empty()).map(hash_signal)
+}
+
+/// Computes the World ID 3.0 external nullifier for `action` in `app_id`
+///
+/// Matches the on-chain derivation: `hashToField(abi.encodePacked(hashToField(app_id), action))`,
+/// formatted as a 0x-prefixed hex string. The action is hashed as given; pass
+/// a [`crate::Action`] to hash its normalized form.
+#[must_use]
+pub fn compute_external_nullifier(app_id: &crate::AppId, action: impl AsRef<str>) -> String {
+    let app_id_hash = hash_to_field(app_id.as_str().as_bytes());
+    let mut packed = app_id_hash.to_be_bytes::<32>().to_vec();
+    packed.extend_from_slice(action.as_ref().as_bytes());
+    format!("{:#066x}", hash_to_field(&packed))
+}
+
 /// Base64 encodes bytes
 #[must_use]
 pub fn base64_encode(input: &[u8]) -> String {
@@ -408,6 +510,16 @@ pub fn base64_decode(input: &str) -> Result<Vec<u8>> {
     Ok(STANDARD.decode(input)?)
 }
 
+/// Base64 URL-safe decodes a string (no padding), the inverse of [`base64_url_encode`]
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid unpadded URL-safe base64
+pub fn base64_url_decode(input: &str) -> Result<Vec<u8>> {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    Ok(URL_SAFE_NO_PAD.decode(input)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,6 +551,10 @@ mod tests {
 
         let decrypted = decrypt(&key_bytes, &nonce_bytes, &ciphertext).unwrap();
         assert_eq!(decrypted.as_slice(), plaintext);
+
+        let mut buffer = plaintext.to_vec();
+        encrypt_in_place(&key_bytes, &nonce_bytes, &mut buffer).unwrap();
+        assert_eq!(buffer, ciphertext);
     }
 
     #[test]
@@ -482,6 +598,73 @@ mod tests {
         assert_eq!(hash_signal(&Signal::from_string(signal)), expected);
     }
 
+    #[test]
+    fn test_compute_external_nullifier() {
+        let app_id = crate::AppId::new("app_staging_45068dca85829d2fd90e2dd6f0bff997").unwrap();
+        assert_eq!(
+            compute_external_nullifier(&app_id, "test-action"),
+            "0x00da2c9cf89e9d5e5bfcdd2ecaa4834f0cb638021d680dc817253ea55fe508f4"
+        );
+
+        let app_id = crate::AppId::new("app_10719845a0977ef63ebe8eb9edb890ad").unwrap();
+        assert_eq!(
+            compute_external_nullifier(&app_id, ""),
+            "0x0017c3dce3971bb22227acea18f278ae082d2a6380b2e415689c5c49aa27ef6e"
+        );
+    }
+
+    #[test]
+    fn test_external_nullifier_of_normalized_action() {
+        let app_id = crate::AppId::new("app_staging_45068dca85829d2fd90e2dd6f0bff997").unwrap();
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+
+        // The same visible action hashes differently until it is normalized
+        assert_ne!(
+            compute_external_nullifier(&app_id, composed),
+            compute_external_nullifier(&app_id, decomposed)
+        );
+        assert_eq!(
+            compute_external_nullifier(&app_id, crate::Action::new(decomposed).unwrap()),
+            compute_external_nullifier(&app_id, composed)
+        );
+        assert_eq!(
+            compute_external_nullifier(&app_id, crate::Action::new("test-action").unwrap()),
+            compute_external_nullifier(&app_id, "test-action")
+        );
+    }
+
+    #[test]
+    fn test_compute_signal_hash_skips_empty_signals() {
+        use crate::Signal;
+
+        assert_eq!(compute_signal_hash(None), None);
+        assert_eq!(compute_signal_hash(Some(&Signal::from_string(""))), None);
+        assert_eq!(
+            compute_signal_hash(Some(&Signal::from_bytes(Vec::new()))),
+            None
+        );
+
+        // Whitespace is a real signal, not an empty one
+        let whitespace = Signal::from_string(" ");
+        assert_eq!(
+            compute_signal_hash(Some(&whitespace)),
+            Some(hash_signal(&whitespace))
+        );
+
+        let one_byte = Signal::from_bytes(vec![0x00]);
+        assert_eq!(
+            compute_signal_hash(Some(&one_byte)),
+            Some(hash_signal(&one_byte))
+        );
+
+        // Explicit hashing keeps hashing the empty string for legacy v3 parity
+        assert_eq!(
+            hash_signal(&Signal::from_string("")),
+            format!("{:#066x}", hash_to_field(b""))
+        );
+    }
+
     #[test]
     fn test_base64_encode_decode() {
         let input = b"Hello, World!";
@@ -490,6 +673,21 @@ mod tests {
         assert_eq!(decoded.as_slice(), input);
     }
 
+    #[test]
+    fn test_base64_url_uses_url_safe_alphabet() {
+        // Encodes to `+/+/` in the standard alphabet
+        let input = [0xfb, 0xff, 0xbf, 0xfb, 0xff, 0xbf];
+        assert_eq!(base64_encode(&input), "+/+/+/+/");
+        assert_eq!(base64_url_encode(&input), "-_-_-_-_");
+        assert_eq!(base64_url_decode("-_-_-_-_").unwrap(), input);
+
+        // No padding, and the standard alphabet is rejected
+        assert_eq!(base64_url_encode(b"ab"), "YWI");
+        assert_eq!(base64_url_decode("YWI").unwrap(), b"ab");
+        assert!(base64_url_decode("YWI=").is_err());
+        assert!(base64_url_decode("+/+/").is_err());
+    }
+
     #[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
     mod invite_code_tests {
         use super::super::invite_code::{
@@ -16,6 +16,20 @@ pub enum Error {
     #[error("Bridge error: {0}")]
     BridgeError(String),
 
+    /// Bridge answered with a non-success HTTP status
+    #[error(
+        "Bridge /{endpoint} failed with status {status}: {}",
+        display_body(body)
+    )]
+    BridgeHttp {
+        /// HTTP status code returned by the bridge
+        status: u16,
+        /// Bridge endpoint that was called
+        endpoint: BridgeEndpoint,
+        /// Raw response body, possibly empty
+        body: String,
+    },
+
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
@@ -48,18 +62,212 @@ pub enum Error {
     #[error("Request timed out")]
     Timeout,
 
+    /// Wait was cancelled by the caller
+    #[error("Request was cancelled")]
+    Cancelled,
+
+    /// A single-consumption request already handed out its proof
+    #[error("Proof was already consumed")]
+    ProofAlreadyConsumed,
+
+    /// The user's identity is not in the Merkle tree yet
+    #[error(
+        "Inclusion proof is still pending, retry in about {} minutes",
+        retry_after_hint.as_secs() / 60
+    )]
+    InclusionPending {
+        /// Suggested delay before asking the user to try again
+        retry_after_hint: std::time::Duration,
+    },
+
     /// Invalid proof
     #[error("Invalid proof: {0}")]
     InvalidProof(String),
 
+    /// A completed bridge response could not be decrypted
+    #[error(
+        "Could not decrypt the response to request {request_id} ({kind}): {}",
+        kind.recovery_hint()
+    )]
+    ResponseDecryption {
+        /// Request whose response failed
+        request_id: String,
+        /// Where in the decryption pipeline it failed
+        kind: DecryptionFailureKind,
+    },
+
+    /// A bridge health check did not reach a working bridge
+    #[error("Bridge health check failed ({kind}): {}", kind.recovery_hint())]
+    BridgeUnhealthy {
+        /// Why the bridge could not be confirmed
+        kind: BridgeHealthFailureKind,
+    },
+
     /// HTTP request error
-    #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+    #[cfg(any(feature = "bridge", feature = "bridge-wasm", feature = "verification"))]
     #[error(transparent)]
     Http(#[from] reqwest::Error),
 }
 
+impl Error {
+    /// Returns the HTTP status code if the bridge rejected the call.
+    #[must_use]
+    pub const fn bridge_status(&self) -> Option<u16> {
+        match self {
+            Self::BridgeHttp { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+fn display_body(body: &str) -> &str {
+    if body.is_empty() {
+        "no error details"
+    } else {
+        body
+    }
+}
+
+/// Stage at which decrypting a completed bridge response failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+pub enum DecryptionFailureKind {
+    /// The authentication tag did not verify. The response was most likely
+    /// encrypted for a different key, e.g. because the QR code was regenerated.
+    KeyMismatchSuspected,
+    /// The IV or ciphertext is not valid base64 or has the wrong length
+    MalformedCiphertext,
+    /// The response decrypted but is not a bridge response this SDK can parse
+    PlaintextNotJson,
+}
+
+impl DecryptionFailureKind {
+    /// Returns the snake-case code exposed to bindings.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::KeyMismatchSuspected => "key_mismatch_suspected",
+            Self::MalformedCiphertext => "malformed_ciphertext",
+            Self::PlaintextNotJson => "plaintext_not_json",
+        }
+    }
+
+    /// Returns `true` if polling again may succeed. Only a corrupted
+    /// transfer can heal; a wrong key or unreadable payload stays that way.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::MalformedCiphertext)
+    }
+
+    /// Tells integrators what to do about the failure.
+    #[must_use]
+    pub const fn recovery_hint(self) -> &'static str {
+        match self {
+            Self::KeyMismatchSuspected => {
+                "the response was encrypted for another key; create a new request and show its QR code"
+            }
+            Self::MalformedCiphertext => {
+                "the response was corrupted in transit; polling again may succeed"
+            }
+            Self::PlaintextNotJson => {
+                "World App sent a response this SDK cannot read; update IDKit"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DecryptionFailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Reason a bridge health check failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+pub enum BridgeHealthFailureKind {
+    /// The bridge host name did not resolve
+    Dns,
+    /// The TLS handshake failed, e.g. because of an untrusted certificate
+    Tls,
+    /// The bridge host could not be connected to
+    Connect,
+    /// The bridge did not answer in time
+    Timeout,
+    /// A server answered, but not like a bridge
+    NotABridge,
+}
+
+impl BridgeHealthFailureKind {
+    /// Returns the snake-case code exposed to bindings.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dns => "dns",
+            Self::Tls => "tls",
+            Self::Connect => "connect",
+            Self::Timeout => "timeout",
+            Self::NotABridge => "not_a_bridge",
+        }
+    }
+
+    /// Returns `true` if checking again may succeed without changing the
+    /// bridge URL or its deployment.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Connect | Self::Timeout)
+    }
+
+    /// Tells integrators what to do about the failure.
+    #[must_use]
+    pub const fn recovery_hint(self) -> &'static str {
+        match self {
+            Self::Dns => "check the host name of the bridge URL",
+            Self::Tls => "check that the bridge serves a valid certificate for its host name",
+            Self::Connect => "check that the bridge is running and reachable from this network",
+            Self::Timeout => "the bridge is slow to answer; check its load or try again",
+            Self::NotABridge => "the URL points to a server that is not a World ID bridge",
+        }
+    }
+}
+
+impl std::fmt::Display for BridgeHealthFailureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Bridge endpoints whose HTTP failures are reported in [`Error::BridgeHttp`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeEndpoint {
+    /// `/request`, used to create a request
+    Request,
+    /// `/response/{id}`, used to poll and acknowledge a response
+    Response,
+    /// `/health`, used to check that a bridge is reachable
+    Health,
+}
+
+impl BridgeEndpoint {
+    /// Returns the endpoint name without its leading slash.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Request => "request",
+            Self::Response => "response",
+            Self::Health => "health",
+        }
+    }
+}
+
+impl std::fmt::Display for BridgeEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Errors returned by the World App
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Error, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Error, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
 #[serde(rename_all = "snake_case")]
 pub enum AppError {
@@ -128,6 +336,9 @@ pub enum AppError {
 
     /// Nullifier was already used
     #[error("Nullifier was already used")]
+    // `from_code` also accepts the shorter `nullifier_replay`; without the
+    // alias that code would deserialize as `Other`
+    #[serde(alias = "nullifier_replay")]
     NullifierReplayed,
 
     /// RP reused a signature nonce
@@ -164,13 +375,34 @@ pub enum AppError {
     #[serde(alias = "identity_attribute_mismatch")]
     IdentityAttributesNotMatched,
 
+    /// The bridge no longer knows the request: it expired or its response was
+    /// already consumed
+    #[error("Request not found or expired")]
+    RequestNotFound,
+
+    /// The bridge or World App rejected the call for sending too many requests
+    #[error("Too many requests")]
+    RateLimited,
+
     /// Generic error
     #[error("An error occurred")]
-    #[serde(other)]
     GenericError,
+
+    /// Error code this version does not know yet, kept verbatim
+    #[error("World App error: {0}")]
+    #[serde(untagged)]
+    Other(String),
 }
 
 impl AppError {
+    /// Returns `true` if the user is expected to succeed by trying again
+    /// later with a new request, e.g. once a newly verified identity lands in
+    /// the Merkle tree.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::InclusionProofPending)
+    }
+
     /// Parses a raw World App/bridge error code.
     #[must_use]
     pub fn from_code(code: &str) -> Self {
@@ -202,7 +434,10 @@ impl AppError {
             "identity_attributes_not_matched" | "identity_attribute_mismatch" => {
                 Self::IdentityAttributesNotMatched
             }
-            _ => Self::GenericError,
+            "request_not_found" => Self::RequestNotFound,
+            "rate_limited" => Self::RateLimited,
+            "generic_error" => Self::GenericError,
+            other => Self::Other(other.to_string()),
         }
     }
 }
@@ -239,6 +474,14 @@ pub enum IdkitError {
     #[error("Bridge error: {details}")]
     BridgeError { details: String },
 
+    /// Bridge answered with a non-success HTTP status
+    #[error("Bridge /{endpoint} failed with status {status_code}: {body}")]
+    BridgeHttpError {
+        status_code: u16,
+        endpoint: String,
+        body: String,
+    },
+
     /// Application-level error
     #[error("App error: {details}")]
     AppError { details: String },
@@ -254,6 +497,32 @@ pub enum IdkitError {
     /// Request timed out
     #[error("Request timed out")]
     Timeout,
+
+    /// Wait was cancelled by the caller
+    #[error("Request was cancelled")]
+    Cancelled,
+
+    /// A single-consumption request already handed out its proof
+    #[error("Proof was already consumed")]
+    ProofAlreadyConsumed,
+
+    /// The user's identity is not in the Merkle tree yet
+    #[error("Inclusion proof is still pending, retry in {retry_after_secs}s")]
+    InclusionPending { retry_after_secs: u64 },
+
+    /// A completed bridge response could not be decrypted
+    #[error(
+        "Could not decrypt the response to request {request_id} ({kind}): {}",
+        kind.recovery_hint()
+    )]
+    ResponseDecryption {
+        request_id: String,
+        kind: DecryptionFailureKind,
+    },
+
+    /// A bridge health check did not reach a working bridge
+    #[error("Bridge health check failed ({kind}): {}", kind.recovery_hint())]
+    BridgeUnhealthy { kind: BridgeHealthFailureKind },
 }
 
 #[cfg(feature = "ffi")]
@@ -273,13 +542,31 @@ impl From<Error> for IdkitError {
             },
             Error::InvalidProof(message) => Self::InvalidProof { details: message },
             Error::BridgeError(message) => Self::BridgeError { details: message },
+            Error::BridgeHttp {
+                status,
+                endpoint,
+                body,
+            } => Self::BridgeHttpError {
+                status_code: status,
+                endpoint: endpoint.to_string(),
+                body,
+            },
             Error::AppError(app_err) => Self::AppError {
                 details: app_err.to_string(),
             },
             Error::UnexpectedResponse => Self::UnexpectedResponse,
             Error::ConnectionFailed => Self::ConnectionFailed,
             Error::Timeout => Self::Timeout,
-            #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+            Error::Cancelled => Self::Cancelled,
+            Error::ProofAlreadyConsumed => Self::ProofAlreadyConsumed,
+            Error::InclusionPending { retry_after_hint } => Self::InclusionPending {
+                retry_after_secs: retry_after_hint.as_secs(),
+            },
+            Error::ResponseDecryption { request_id, kind } => {
+                Self::ResponseDecryption { request_id, kind }
+            }
+            Error::BridgeUnhealthy { kind } => Self::BridgeUnhealthy { kind },
+            #[cfg(any(feature = "bridge", feature = "bridge-wasm", feature = "verification"))]
             Error::Http(err) => Self::BridgeError {
                 details: format!("HTTP error: {err}"),
             },
@@ -299,9 +586,33 @@ impl From<IdkitError> for Error {
             | IdkitError::UrlError { details }
             | IdkitError::BridgeError { details }
             | IdkitError::AppError { details } => Self::BridgeError(details),
+            IdkitError::BridgeHttpError {
+                status_code,
+                endpoint,
+                body,
+            } => Self::BridgeHttp {
+                status: status_code,
+                endpoint: if endpoint == BridgeEndpoint::Request.as_str() {
+                    BridgeEndpoint::Request
+                } else if endpoint == BridgeEndpoint::Health.as_str() {
+                    BridgeEndpoint::Health
+                } else {
+                    BridgeEndpoint::Response
+                },
+                body,
+            },
             IdkitError::UnexpectedResponse => Self::UnexpectedResponse,
             IdkitError::ConnectionFailed => Self::ConnectionFailed,
             IdkitError::Timeout => Self::Timeout,
+            IdkitError::Cancelled => Self::Cancelled,
+            IdkitError::ProofAlreadyConsumed => Self::ProofAlreadyConsumed,
+            IdkitError::InclusionPending { retry_after_secs } => Self::InclusionPending {
+                retry_after_hint: std::time::Duration::from_secs(retry_after_secs),
+            },
+            IdkitError::ResponseDecryption { request_id, kind } => {
+                Self::ResponseDecryption { request_id, kind }
+            }
+            IdkitError::BridgeUnhealthy { kind } => Self::BridgeUnhealthy { kind },
         }
     }
 }
@@ -0,0 +1,165 @@
+//! HTTP helpers shared by the bridge and verification clients
+
+use crate::error::{Error, Result};
+use std::sync::OnceLock;
+
+/// Default cap on response bodies read from the bridge or the Developer Portal
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+fn too_large() -> Error {
+    Error::BridgeError("response too large".to_string())
+}
+
+/// Timeouts applied by the HTTP client that talks to the bridge or the
+/// Developer Portal
+///
+/// A timeout is reported as `Error::Timeout`. WASM builds rely on the
+/// browser's own limits, which `fetch` does not let us configure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpTimeouts {
+    /// Limit for establishing a connection
+    pub connect: std::time::Duration,
+    /// Limit for a whole call, from sending it to reading the body
+    pub request: std::time::Duration,
+}
+
+impl Default for HttpTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: std::time::Duration::from_secs(10),
+            request: std::time::Duration::from_secs(30),
+        }
+    }
+}
+
+impl HttpTimeouts {
+    /// Builds an HTTP client applying these timeouts, for use with
+    /// `BridgeConnectionBuilder::client` or `verify_proof_with_client`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client cannot be built
+    pub fn build_client(&self) -> Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .user_agent(format!("idkit-core/{}", env!("CARGO_PKG_VERSION")));
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = builder.connect_timeout(self.connect).timeout(self.request);
+        Ok(builder.build()?)
+    }
+}
+
+/// Returns the process-wide default client, building it on first use with
+/// the default [`HttpTimeouts`].
+///
+/// `reqwest::Client` pools connections internally and is cheap to clone, so
+/// sharing a single instance lets every bridge connection and portal call
+/// reuse sockets.
+pub(crate) fn default_client() -> Result<reqwest::Client> {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+    if let Some(client) = CLIENT.get() {
+        return Ok(client.clone());
+    }
+
+    let client = HttpTimeouts::default().build_client()?;
+
+    Ok(CLIENT.get_or_init(|| client).clone())
+}
+
+/// Reads a response body, failing once it grows past `limit` bytes.
+///
+/// Native builds stop reading as soon as the limit is crossed. In the browser
+/// `fetch` buffers the body itself, so WASM builds can only check the
+/// `Content-Length` header and the buffered length.
+pub(crate) async fn read_capped_body(response: reqwest::Response, limit: usize) -> Result<Vec<u8>> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(too_large());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut response = response;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let body = response.bytes().await?;
+        if body.len() > limit {
+            return Err(too_large());
+        }
+        Ok(body.to_vec())
+    }
+}
+
+/// Parses a capped JSON body, relying on `serde_json`'s recursion limit to
+/// reject deeply nested input.
+#[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+pub(crate) async fn read_capped_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+    limit: usize,
+) -> Result<T> {
+    let body = read_capped_body(response, limit).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Fails if a decrypted payload exceeds `limit` bytes.
+#[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+pub(crate) fn ensure_within_limit(payload: &[u8], limit: usize) -> Result<()> {
+    if payload.len() > limit {
+        return Err(too_large());
+    }
+    Ok(())
+}
+
+// Tests borrow the tokio runtime pulled in by the `bridge` feature
+#[cfg(all(test, feature = "bridge"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::server::{CannedResponse, CannedServer};
+
+    /// Serves `body` with chunked encoding, so no `Content-Length` is sent.
+    fn serve_chunked(body: Vec<u8>) -> String {
+        CannedServer::start([CannedResponse::new(200, body).chunked()])
+            .unwrap()
+            .url()
+    }
+
+    fn read_from(url: &str, limit: usize) -> Result<Vec<u8>> {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let response = reqwest::get(url).await?;
+            read_capped_body(response, limit).await
+        })
+    }
+
+    #[test]
+    fn test_read_capped_body_within_limit() {
+        let url = serve_chunked(vec![b'a'; 1000]);
+        assert_eq!(read_from(&url, 1000).unwrap().len(), 1000);
+    }
+
+    #[test]
+    fn test_read_capped_body_stops_streamed_body_over_limit() {
+        let url = serve_chunked(vec![b'a'; 4096]);
+        assert!(matches!(
+            read_from(&url, 1000),
+            Err(Error::BridgeError(ref message)) if message == "response too large"
+        ));
+    }
+
+    #[test]
+    fn test_ensure_within_limit() {
+        assert!(ensure_within_limit(&[0; 16], 16).is_ok());
+        assert!(ensure_within_limit(&[0; 17], 16).is_err());
+    }
+}
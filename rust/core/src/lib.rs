@@ -10,31 +10,66 @@
 #![allow(clippy::missing_const_for_fn)]
 #![cfg_attr(target_arch = "wasm32", allow(clippy::future_not_send))]
 
+/// Emits a `tracing` debug event with the `tracing` feature, and nothing
+/// without it. Only pass identifiers, sizes and status codes, never payloads
+/// or key material.
+#[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
 #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
 pub mod bridge;
 pub mod constraints;
 pub mod crypto;
 pub mod error;
+#[cfg(any(feature = "bridge", feature = "bridge-wasm", feature = "verification"))]
+pub mod http;
+#[cfg(feature = "axum")]
+pub mod middleware;
+#[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+pub mod portal;
 pub mod preset;
 #[cfg(feature = "rp-signature")]
 pub mod rp_signature;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 pub mod types;
+#[cfg(feature = "verification")]
+pub mod verification;
 
 #[cfg(feature = "wasm-bindings")]
 pub mod wasm_bindings;
 
+#[cfg(feature = "bridge")]
+pub use bridge::{
+    health_check, BridgeHealth, PollConfig, PollMode, PollSchedule, StatusStreamOptions,
+};
 #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
-pub use bridge::{BridgeConnection, Status};
+pub use bridge::{
+    BridgeConnection, BridgeConnectionBuilder, BridgeTransport, Clock, ConnectUrl,
+    CreateRetryConfig, HttpTimeouts, ReqwestTransport, ResumableConnection, Status,
+    StatusTransition, SupportBundle, SupportBundleKey, SystemClock, VerificationOutcome,
+    VerifiedResult, BRIDGE_REQUEST_TTL, INCLUSION_PENDING_RETRY_HINT,
+};
 #[cfg(all(any(feature = "bridge", feature = "bridge-wasm"), feature = "ffi"))]
 pub use bridge::{IDKitBuilder, IDKitRequestConfig};
-pub use constraints::ConstraintNode;
+#[cfg(feature = "ffi")]
+pub use constraints::ConstraintsBuilder;
+pub use constraints::{ConstraintNode, SatisfiedBranch};
 #[cfg(any(feature = "native-crypto", feature = "wasm-crypto"))]
 pub use crypto::CryptoKey;
-pub use error::{Error, Result};
+pub use error::{BridgeEndpoint, BridgeHealthFailureKind, DecryptionFailureKind, Error, Result};
 pub use preset::Preset;
+#[cfg(feature = "bridge")]
+pub use tokio_util::sync::CancellationToken;
 pub use types::{
-    AppId, BridgeResponseV1, BridgeUrl, CredentialRequest, CredentialType, IDKitResult,
-    IntegrityBundle, IntegritySignatureFormat, ResponseItem, RpContext, Signal, VerificationLevel,
+    Action, AppId, BridgeResponseV1, BridgeUrl, CredentialRequest, CredentialType, Environment,
+    IDKitResult, IntegrityBundle, IntegritySignatureFormat, ProofResponseExt, RequestVersionExt,
+    ResponseItem, RpContext, Signal, VerificationLevel,
 };
 
 // UniFFI scaffolding for core types
@@ -0,0 +1,683 @@
+//! Axum middleware that admits only requests carrying a verified World ID proof
+//!
+//! [`WorldIdLayer`] reads an [`IDKitResult`] from each request, checks that it
+//! answers the configured action, verifies it with the Developer Portal and
+//! rejects nullifiers it has already seen. Handlers receive the outcome as a
+//! [`VerifiedWorldId`] request extension:
+//!
+//! ```ignore
+//! let layer = WorldIdLayer::new(
+//!     AppId::new("app_staging_1234")?,
+//!     "vote",
+//!     VerifyOptions::new("rp_1234567890abcdef")?,
+//!     Arc::new(InMemoryNullifierStore::default()),
+//! );
+//! let app = Router::new()
+//!     .route("/vote", post(|Extension(id): Extension<VerifiedWorldId>| async move { id.nullifier_hash }))
+//!     .layer(layer);
+//! ```
+//!
+//! Rejected requests get a JSON body with a `code` and a `detail`, the same
+//! shape the Developer Portal uses.
+
+use crate::{
+    error::{Error, Result},
+    http::default_client,
+    types::{AppId, IDKitResult, ResponseItem},
+    verification::verify_proof_with_client,
+};
+use axum::{
+    body::Body,
+    http::{HeaderName, Request, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use ruint::aliases::U256;
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Default cap on a request body read to find the proof
+pub const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Where [`WorldIdLayer`] looks for the proof on a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofSource {
+    /// A header whose value is the JSON-encoded `IDKitResult`
+    Header(HeaderName),
+    /// A top-level field of a JSON request body. The body is buffered and
+    /// handed on to the inner service unchanged.
+    JsonField(String),
+}
+
+impl Default for ProofSource {
+    fn default() -> Self {
+        Self::JsonField("proof".to_string())
+    }
+}
+
+/// How [`WorldIdLayer`] finds and verifies proofs
+#[derive(Debug, Clone)]
+pub struct VerifyOptions {
+    /// RP ID the proofs are verified for
+    pub rp_id: String,
+    /// Verify endpoint override, see
+    /// [`crate::verification::verify_proof_with_endpoint`]
+    pub endpoint: Option<String>,
+    /// Client used to reach the Developer Portal (default: the crate's shared
+    /// client with [`HttpTimeouts`](crate::http::HttpTimeouts))
+    pub client: reqwest::Client,
+    /// Where the proof is read from (default: the `proof` body field)
+    pub source: ProofSource,
+    /// Largest request body read when the proof is in the body
+    pub max_body_bytes: usize,
+}
+
+impl VerifyOptions {
+    /// Creates options that verify against the production portal
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default HTTP client cannot be built
+    pub fn new(rp_id: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            rp_id: rp_id.into(),
+            endpoint: None,
+            client: default_client()?,
+            source: ProofSource::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+        })
+    }
+}
+
+/// Future returned by [`NullifierStore::insert_all`]
+pub type NullifierFuture<'a> = Pin<Box<dyn Future<Output = Result<bool>> + Send + 'a>>;
+
+/// Remembers which nullifiers have been used for an action
+///
+/// Back this with a unique index in the RP's database so that a proof cannot
+/// be replayed against another server instance or after a restart, and
+/// insert a proof's nullifiers in one transaction.
+pub trait NullifierStore: Send + Sync {
+    /// Records every nullifier in `nullifiers` as used for `action` of `app_id`
+    ///
+    /// Resolves to `false` if any of them was already recorded or appears
+    /// twice, and then records none of them, so a rejected proof does not use
+    /// up its other credentials. Must be atomic: of two concurrent calls
+    /// sharing a nullifier, only one may succeed.
+    fn insert_all<'a>(
+        &'a self,
+        app_id: &'a AppId,
+        action: &'a str,
+        nullifiers: &'a [String],
+    ) -> NullifierFuture<'a>;
+}
+
+/// Process-local [`NullifierStore`], for tests and single-instance servers
+#[derive(Debug, Default)]
+pub struct InMemoryNullifierStore {
+    seen: Mutex<HashSet<(String, String, String)>>,
+}
+
+impl NullifierStore for InMemoryNullifierStore {
+    fn insert_all<'a>(
+        &'a self,
+        app_id: &'a AppId,
+        action: &'a str,
+        nullifiers: &'a [String],
+    ) -> NullifierFuture<'a> {
+        let keys: HashSet<_> = nullifiers
+            .iter()
+            .map(|nullifier| {
+                (
+                    app_id.as_str().to_string(),
+                    action.to_string(),
+                    nullifier.clone(),
+                )
+            })
+            .collect();
+        let inserted = self
+            .seen
+            .lock()
+            .map_err(|_| Error::InvalidConfiguration("nullifier store lock poisoned".into()))
+            .map(|mut seen| {
+                let fresh = keys.len() == nullifiers.len() && seen.is_disjoint(&keys);
+                if fresh {
+                    seen.extend(keys);
+                }
+                fresh
+            });
+        Box::pin(async move { inserted })
+    }
+}
+
+/// Request extension added by [`WorldIdLayer`] once a proof is accepted
+///
+/// Describes the first credential of the proof; the nullifiers of all its
+/// credentials are recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedWorldId {
+    /// Nullifier of the accepted proof, unique per user and action, as
+    /// `0x`-prefixed 32-byte lowercase hex
+    pub nullifier_hash: String,
+    /// Credential identifier the proof was made with (e.g. `proof_of_human`)
+    pub credential: String,
+}
+
+/// Tower layer that verifies a World ID proof before calling the inner service
+#[derive(Clone)]
+pub struct WorldIdLayer {
+    gate: Arc<Gate>,
+}
+
+impl WorldIdLayer {
+    /// Creates a layer that accepts proofs for `action` of `app_id`
+    ///
+    /// Nullifiers are recorded in `nullifier_store` after the portal accepts
+    /// the proof, so a rejected proof never uses one up.
+    #[must_use]
+    pub fn new(
+        app_id: AppId,
+        action: impl Into<String>,
+        verify_options: VerifyOptions,
+        nullifier_store: Arc<dyn NullifierStore>,
+    ) -> Self {
+        Self {
+            gate: Arc::new(Gate {
+                app_id,
+                action: action.into(),
+                options: verify_options,
+                store: nullifier_store,
+            }),
+        }
+    }
+}
+
+impl std::fmt::Debug for WorldIdLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldIdLayer")
+            .field("app_id", &self.gate.app_id)
+            .field("action", &self.gate.action)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for WorldIdLayer {
+    type Service = WorldIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        WorldIdService {
+            inner,
+            gate: self.gate.clone(),
+        }
+    }
+}
+
+/// Service produced by [`WorldIdLayer`]
+#[derive(Clone)]
+pub struct WorldIdService<S> {
+    inner: S,
+    gate: Arc<Gate>,
+}
+
+impl<S> Service<Request<Body>> for WorldIdService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = std::result::Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        // Call the instance that was driven to readiness, leave a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let gate = self.gate.clone();
+        Box::pin(async move {
+            match gate.admit(request).await {
+                Ok(request) => inner.call(request).await,
+                Err(rejection) => Ok(rejection.into_response()),
+            }
+        })
+    }
+}
+
+struct Gate {
+    app_id: AppId,
+    action: String,
+    options: VerifyOptions,
+    store: Arc<dyn NullifierStore>,
+}
+
+impl Gate {
+    async fn admit(&self, request: Request<Body>) -> std::result::Result<Request<Body>, Rejection> {
+        let (mut request, result) = self.extract(request).await?;
+        let credentials = self.check(&result)?;
+
+        verify_proof_with_client(
+            &result,
+            &self.options.rp_id,
+            self.options.endpoint.as_deref(),
+            &self.options.client,
+        )
+        .await
+        .map_err(|error| match error {
+            Error::InvalidProof(detail) => Rejection::invalid_proof(detail),
+            other => Rejection::new(
+                StatusCode::BAD_GATEWAY,
+                "verification_unavailable",
+                other.to_string(),
+            ),
+        })?;
+
+        let nullifiers: Vec<String> = credentials
+            .iter()
+            .map(|credential| credential.nullifier_hash.clone())
+            .collect();
+        let inserted = self
+            .store
+            .insert_all(&self.app_id, &self.action, &nullifiers)
+            .await
+            .map_err(|error| {
+                Rejection::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "nullifier_store_error",
+                    error.to_string(),
+                )
+            })?;
+        if !inserted {
+            return Err(Rejection::new(
+                StatusCode::CONFLICT,
+                "duplicate_nullifier",
+                "this proof has already been used for this action".to_string(),
+            ));
+        }
+
+        if let Some(verified) = credentials.into_iter().next() {
+            request.extensions_mut().insert(verified);
+        }
+        Ok(request)
+    }
+
+    async fn extract(
+        &self,
+        request: Request<Body>,
+    ) -> std::result::Result<(Request<Body>, IDKitResult), Rejection> {
+        match &self.options.source {
+            ProofSource::Header(name) => {
+                let value = request
+                    .headers()
+                    .get(name)
+                    .ok_or_else(|| Rejection::missing_proof(format!("missing {name} header")))?;
+                let result = serde_json::from_slice(value.as_bytes())
+                    .map_err(|e| Rejection::invalid_proof(format!("malformed proof: {e}")))?;
+                Ok((request, result))
+            }
+            ProofSource::JsonField(field) => {
+                let (parts, body) = request.into_parts();
+                let bytes = axum::body::to_bytes(body, self.options.max_body_bytes)
+                    .await
+                    .map_err(|e| Rejection::missing_proof(format!("unreadable body: {e}")))?;
+                let mut json: serde_json::Value = serde_json::from_slice(&bytes)
+                    .map_err(|_| Rejection::missing_proof("body is not JSON".to_string()))?;
+                let proof = json
+                    .get_mut(field)
+                    .map(serde_json::Value::take)
+                    .ok_or_else(|| Rejection::missing_proof(format!("missing `{field}` field")))?;
+                let result = serde_json::from_value(proof)
+                    .map_err(|e| Rejection::invalid_proof(format!("malformed proof: {e}")))?;
+                Ok((Request::from_parts(parts, Body::from(bytes)), result))
+            }
+        }
+    }
+
+    /// Local checks that need no network: the proof answers this action and
+    /// every credential in it carries a well-formed uniqueness nullifier
+    fn check(&self, result: &IDKitResult) -> std::result::Result<Vec<VerifiedWorldId>, Rejection> {
+        if result.action.as_deref() != Some(self.action.as_str()) {
+            return Err(Rejection::invalid_proof(format!(
+                "proof is not for action {}",
+                self.action
+            )));
+        }
+
+        if result.responses.is_empty() {
+            return Err(Rejection::invalid_proof(
+                "no credentials returned".to_string(),
+            ));
+        }
+
+        result
+            .responses
+            .iter()
+            .map(|item| match item {
+                ResponseItem::V4 {
+                    identifier,
+                    nullifier,
+                    ..
+                }
+                | ResponseItem::V3 {
+                    identifier,
+                    nullifier,
+                    ..
+                } => Ok(VerifiedWorldId {
+                    nullifier_hash: canonical_nullifier(nullifier)?,
+                    credential: identifier.clone(),
+                }),
+                ResponseItem::Session { .. } => Err(Rejection::invalid_proof(
+                    "session proofs carry no action nullifier".to_string(),
+                )),
+            })
+            .collect()
+    }
+}
+
+/// Rewrites a hex nullifier as `0x`-prefixed 32-byte lowercase hex
+///
+/// Replay checks compare nullifiers as text, so every encoding of the same
+/// value (case, padding, prefix) must map to the same string.
+fn canonical_nullifier(nullifier: &str) -> std::result::Result<String, Rejection> {
+    let digits = nullifier
+        .strip_prefix("0x")
+        .or_else(|| nullifier.strip_prefix("0X"))
+        .unwrap_or(nullifier);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Rejection::invalid_proof(format!(
+            "malformed nullifier {nullifier}"
+        )));
+    }
+    U256::from_str_radix(digits, 16)
+        .map(|value| format!("{value:#066x}"))
+        .map_err(|_| Rejection::invalid_proof(format!("nullifier {nullifier} is out of range")))
+}
+
+/// Response sent instead of calling the inner service
+#[derive(Debug)]
+struct Rejection {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+}
+
+impl Rejection {
+    const fn new(status: StatusCode, code: &'static str, detail: String) -> Self {
+        Self {
+            status,
+            code,
+            detail,
+        }
+    }
+
+    const fn missing_proof(detail: String) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "missing_proof", detail)
+    }
+
+    const fn invalid_proof(detail: String) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "invalid_proof", detail)
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "code": self.code, "detail": self.detail });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::server::CannedServer;
+    use crate::CredentialType;
+    use axum::{routing::post, Extension, Router};
+    use tower::ServiceExt;
+
+    const RP_ID: &str = "rp_1234567890abcdef";
+
+    /// Answers each portal call with the next canned `(status, body)`
+    fn serve_portal(responses: Vec<(u16, &str)>) -> (String, CannedServer) {
+        let portal = CannedServer::start(responses).unwrap();
+        (format!("{}/api/v4/verify", portal.url()), portal)
+    }
+
+    fn sample_result(action: &str, seed: u64) -> IDKitResult {
+        IDKitResult::new(
+            "4.0",
+            "0x01",
+            Some(action.to_string()),
+            None,
+            vec![ResponseItem::test_fixture(
+                CredentialType::ProofOfHuman,
+                seed,
+            )],
+            false,
+            "production",
+        )
+    }
+
+    fn app(endpoint: &str, source: ProofSource) -> Router {
+        let mut options = VerifyOptions::new(RP_ID).unwrap();
+        options.endpoint = Some(endpoint.to_string());
+        options.source = source;
+        let layer = WorldIdLayer::new(
+            AppId::new("app_staging_1234").unwrap(),
+            "vote",
+            options,
+            Arc::new(InMemoryNullifierStore::default()),
+        );
+        Router::new()
+            .route(
+                "/vote",
+                post(
+                    |Extension(id): Extension<VerifiedWorldId>, body: String| async move {
+                        format!("{} {} {}", id.credential, id.nullifier_hash, body.len())
+                    },
+                ),
+            )
+            .layer(layer)
+    }
+
+    fn body_request(result: &IDKitResult) -> Request<Body> {
+        let body = serde_json::json!({ "choice": 2, "proof": result });
+        Request::post("/vote")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn send(app: &Router, request: Request<Body>) -> (StatusCode, String) {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let response = app.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        })
+    }
+
+    fn error_code(body: &str) -> String {
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        json["code"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_layer_admits_verified_proof_and_rejects_replay() {
+        let (endpoint, portal) = serve_portal(vec![
+            (200, r#"{"success":true}"#),
+            (200, r#"{"success":true}"#),
+        ]);
+        let app = app(&endpoint, ProofSource::default());
+        let result = sample_result("vote", 1);
+        let ResponseItem::V4 { nullifier, .. } = &result.responses[0] else {
+            unreachable!()
+        };
+        let sent_body_len = serde_json::json!({ "choice": 2, "proof": &result })
+            .to_string()
+            .len();
+
+        let (status, body) = send(&app, body_request(&result));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            format!(
+                "proof_of_human {} {sent_body_len}",
+                nullifier.to_lowercase()
+            )
+        );
+
+        let (status, body) = send(&app, body_request(&result));
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(error_code(&body), "duplicate_nullifier");
+
+        let request_lines: Vec<_> = portal
+            .requests()
+            .iter()
+            .map(|request| request.lines().next().unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(
+            request_lines,
+            vec![format!("POST /api/v4/verify/{RP_ID} HTTP/1.1"); 2]
+        );
+    }
+
+    #[test]
+    fn test_layer_rejects_replay_with_differently_written_nullifier() {
+        let (endpoint, _) = serve_portal(vec![
+            (200, r#"{"success":true}"#),
+            (200, r#"{"success":true}"#),
+        ]);
+        let app = app(&endpoint, ProofSource::default());
+        let result = sample_result("vote", 5);
+        let (status, _) = send(&app, body_request(&result));
+        assert_eq!(status, StatusCode::OK);
+
+        let mut replay = result;
+        let ResponseItem::V4 { nullifier, .. } = &mut replay.responses[0] else {
+            unreachable!()
+        };
+        *nullifier = format!("0X00{}", nullifier[2..].to_uppercase());
+        let (status, body) = send(&app, body_request(&replay));
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(error_code(&body), "duplicate_nullifier");
+    }
+
+    #[test]
+    fn test_layer_checks_every_credential() {
+        let (endpoint, _) = serve_portal(vec![
+            (200, r#"{"success":true}"#),
+            (200, r#"{"success":true}"#),
+            (200, r#"{"success":true}"#),
+        ]);
+        let app = app(&endpoint, ProofSource::default());
+        let first = sample_result("vote", 6);
+        let (status, _) = send(&app, body_request(&first));
+        assert_eq!(status, StatusCode::OK);
+
+        // The seen nullifier hides behind a fresh first credential
+        let mut combined = sample_result("vote", 7);
+        combined.responses.push(first.responses[0].clone());
+        let (status, body) = send(&app, body_request(&combined));
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(error_code(&body), "duplicate_nullifier");
+
+        // The rejected request did not use up its fresh credential
+        let (status, _) = send(&app, body_request(&sample_result("vote", 7)));
+        assert_eq!(status, StatusCode::OK);
+
+        // Rejected locally, before the portal is called
+        let mut with_session = sample_result("vote", 8);
+        with_session.responses.push(ResponseItem::Session {
+            identifier: "proof_of_human".to_string(),
+            signal_hash: None,
+            proof: Vec::new(),
+            session_nullifier: Vec::new(),
+            issuer_schema_id: 1,
+            expires_at_min: 0,
+        });
+        let (status, body) = send(&app, body_request(&with_session));
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(&body), "invalid_proof");
+    }
+
+    #[test]
+    fn test_canonical_nullifier() {
+        let canonical = format!("0x{}3e4f", "0".repeat(60));
+        for nullifier in ["0x3e4f", "0X3E4F", "3e4f", &canonical] {
+            assert_eq!(canonical_nullifier(nullifier).unwrap(), canonical);
+        }
+        for nullifier in ["", "0x", "0xnullifier", &format!("0x1{}", "0".repeat(64))] {
+            assert!(canonical_nullifier(nullifier).is_err());
+        }
+    }
+
+    #[test]
+    fn test_layer_rejects_proof_the_portal_refuses() {
+        let (endpoint, _) = serve_portal(vec![
+            (
+                400,
+                r#"{"code":"invalid_proof","detail":"proof does not verify"}"#,
+            ),
+            (200, r#"{"success":true}"#),
+        ]);
+        let app = app(&endpoint, ProofSource::default());
+        let result = sample_result("vote", 2);
+
+        let (status, body) = send(&app, body_request(&result));
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["code"], "invalid_proof");
+        assert_eq!(json["detail"], "proof does not verify");
+
+        // A refused proof does not use up its nullifier
+        let (status, _) = send(&app, body_request(&result));
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_layer_rejects_without_calling_portal() {
+        // Nothing listens here, so reaching the portal would fail with 502
+        let endpoint = "http://127.0.0.1:9/api/v4/verify";
+        let app = app(endpoint, ProofSource::default());
+
+        let (status, body) = send(&app, body_request(&sample_result("other-action", 3)));
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(&body), "invalid_proof");
+
+        let request = Request::post("/vote")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"choice":2}"#))
+            .unwrap();
+        let (status, body) = send(&app, request);
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(&body), "missing_proof");
+    }
+
+    #[test]
+    fn test_layer_reads_proof_from_header() {
+        let (endpoint, _) = serve_portal(vec![(200, r#"{"success":true}"#)]);
+        let header = HeaderName::from_static("x-world-id-proof");
+        let app = app(&endpoint, ProofSource::Header(header.clone()));
+        let result = sample_result("vote", 4);
+
+        let request = Request::post("/vote")
+            .header(header, serde_json::to_string(&result).unwrap())
+            .body(Body::from("plain"))
+            .unwrap();
+        let (status, body) = send(&app, request);
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with("proof_of_human "));
+        assert!(body.ends_with(" 5"));
+    }
+}
@@ -0,0 +1,349 @@
+//! Developer Portal action precheck
+//!
+//! A mistyped action only surfaces once the user has scanned the QR code and
+//! World App rejects the request. [`precheck_action`] asks the Developer
+//! Portal about the action up front so integrations can fail fast instead.
+
+use crate::{
+    bridge::current_unix_seconds,
+    error::{Error, Result},
+    http::{default_client, read_capped_json},
+    types::AppId,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+/// Default Developer Portal precheck endpoint. The app ID is appended as the
+/// last path segment.
+pub const DEFAULT_PRECHECK_ENDPOINT: &str = "https://developer.worldcoin.org/api/v2/precheck";
+
+/// Cap on precheck response bodies, which only carry action metadata
+const MAX_PRECHECK_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Action metadata reported by the Developer Portal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActionInfo {
+    /// Whether the action is registered for the app
+    pub exists: bool,
+    /// How many times a user may verify for this action, when limited
+    pub max_verifications: Option<u32>,
+    /// External nullifier the portal derived for the action
+    pub external_nullifier: Option<String>,
+}
+
+impl ActionInfo {
+    const fn missing() -> Self {
+        Self {
+            exists: false,
+            max_verifications: None,
+            external_nullifier: None,
+        }
+    }
+}
+
+/// Options for [`precheck_action`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecheckOptions {
+    /// Replaces [`DEFAULT_PRECHECK_ENDPOINT`]; the app ID is still appended
+    pub endpoint: Option<String>,
+    /// How long an existing action is remembered per app and action.
+    /// Missing actions and failures are never cached.
+    pub cache_ttl: Duration,
+    /// When creating a connection, only a definite "not found" answer fails
+    /// it; portal outages are ignored
+    pub best_effort: bool,
+}
+
+impl Default for PrecheckOptions {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            cache_ttl: Duration::from_mins(5),
+            best_effort: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PrecheckResponse {
+    #[serde(default)]
+    action: Option<PrecheckAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrecheckAction {
+    #[serde(default)]
+    max_verifications: Option<u32>,
+    #[serde(default)]
+    external_nullifier: Option<String>,
+}
+
+type CacheKey = (String, String, String);
+
+/// Existing actions keyed by (endpoint, app ID, action), with the Unix time
+/// at which each entry expires
+type Cache = HashMap<CacheKey, (ActionInfo, u64)>;
+
+/// Most actions remembered at once; the entry closest to expiring makes room
+const MAX_CACHED_ACTIONS: usize = 1024;
+
+fn cache() -> &'static Mutex<Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Returns the cached info for `key`, dropping the entry once it has expired
+fn cached(cache: &mut Cache, key: &CacheKey, now: u64) -> Option<ActionInfo> {
+    match cache.get(key) {
+        Some((info, expires_at)) if now < *expires_at => Some(info.clone()),
+        Some(_) => {
+            cache.remove(key);
+            None
+        }
+        None => None,
+    }
+}
+
+/// Caches `info` until `expires_at`, first evicting expired entries and, at
+/// [`MAX_CACHED_ACTIONS`], the entry closest to expiring
+fn remember(cache: &mut Cache, key: CacheKey, info: ActionInfo, expires_at: u64, now: u64) {
+    cache.retain(|_, (_, expires_at)| now < *expires_at);
+    if cache.len() >= MAX_CACHED_ACTIONS && !cache.contains_key(&key) {
+        let soonest = cache
+            .iter()
+            .min_by_key(|(_, (_, expires_at))| *expires_at)
+            .map(|(key, _)| key.clone());
+        if let Some(soonest) = soonest {
+            cache.remove(&soonest);
+        }
+    }
+    cache.insert(key, (info, expires_at));
+}
+
+/// Asks the Developer Portal whether `action` is registered for `app_id`.
+///
+/// A 404, or a response without action metadata, yields
+/// `ActionInfo { exists: false, .. }`. Existing actions are cached for
+/// `options.cache_ttl`.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidConfiguration` if `action` is empty,
+/// `Error::UnexpectedResponse` if the portal answers with any other
+/// non-success status, or an HTTP error if it cannot be reached.
+pub async fn precheck_action(
+    app_id: &AppId,
+    action: &str,
+    options: &PrecheckOptions,
+) -> Result<ActionInfo> {
+    if action.is_empty() {
+        return Err(Error::InvalidConfiguration("action cannot be empty".into()));
+    }
+
+    let endpoint = options
+        .endpoint
+        .as_deref()
+        .unwrap_or(DEFAULT_PRECHECK_ENDPOINT)
+        .trim_end_matches('/');
+    let key = (
+        endpoint.to_string(),
+        app_id.as_str().to_string(),
+        action.to_string(),
+    );
+    let now = current_unix_seconds()?;
+
+    if let Ok(mut cache) = cache().lock() {
+        if let Some(info) = cached(&mut cache, &key, now) {
+            return Ok(info);
+        }
+    }
+
+    let response = default_client()?
+        .post(format!("{endpoint}/{}", app_id.as_str()))
+        .json(&serde_json::json!({ "action": action }))
+        .send()
+        .await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(ActionInfo::missing());
+    }
+    if !response.status().is_success() {
+        return Err(Error::UnexpectedResponse);
+    }
+
+    let body: PrecheckResponse = read_capped_json(response, MAX_PRECHECK_RESPONSE_BYTES).await?;
+    let Some(metadata) = body.action else {
+        return Ok(ActionInfo::missing());
+    };
+    let info = ActionInfo {
+        exists: true,
+        max_verifications: metadata.max_verifications,
+        external_nullifier: metadata.external_nullifier,
+    };
+
+    let ttl = options.cache_ttl.as_secs();
+    if ttl > 0 {
+        if let Ok(mut cache) = cache().lock() {
+            remember(&mut cache, key, info.clone(), now.saturating_add(ttl), now);
+        }
+    }
+    Ok(info)
+}
+
+/// Runs [`precheck_action`] before a connection is created.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidConfiguration` if the portal reports the action as
+/// missing. Other precheck failures are returned unless
+/// `options.best_effort` is set.
+pub(crate) async fn ensure_action_exists(
+    app_id: &AppId,
+    action: &str,
+    options: &PrecheckOptions,
+) -> Result<()> {
+    match precheck_action(app_id, action, options).await {
+        Ok(info) if info.exists => Ok(()),
+        Ok(_) => Err(Error::InvalidConfiguration(format!(
+            "action '{action}' not found for {}",
+            app_id.as_str()
+        ))),
+        Err(_) if options.best_effort => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+// Tests borrow the tokio runtime pulled in by the `bridge` feature
+#[cfg(all(test, feature = "bridge"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::server::CannedServer;
+
+    /// Serves a single response, then stops listening.
+    fn serve_portal_response(status: u16, body: &str) -> String {
+        let portal = CannedServer::start([(status, body)]).unwrap();
+        format!("{}/api/v2/precheck", portal.url())
+    }
+
+    fn options(endpoint: String) -> PrecheckOptions {
+        PrecheckOptions {
+            endpoint: Some(endpoint),
+            ..PrecheckOptions::default()
+        }
+    }
+
+    fn precheck(action: &str, options: &PrecheckOptions) -> Result<ActionInfo> {
+        let app_id = AppId::new("app_staging_test").unwrap();
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(precheck_action(&app_id, action, options))
+    }
+
+    #[test]
+    fn test_precheck_existing_action_is_cached() {
+        let portal = options(serve_portal_response(
+            200,
+            r#"{"id":"app_staging_test","action":{"external_nullifier":"0x00ab","max_verifications":1}}"#,
+        ));
+
+        let info = precheck("my-action", &portal).unwrap();
+        assert_eq!(
+            info,
+            ActionInfo {
+                exists: true,
+                max_verifications: Some(1),
+                external_nullifier: Some("0x00ab".to_string()),
+            }
+        );
+
+        // The mock only answers once, so a second lookup must hit the cache
+        assert_eq!(precheck("my-action", &portal).unwrap(), info);
+    }
+
+    #[test]
+    fn test_cache_evicts_expired_entries_and_stays_bounded() {
+        let key = |i: usize| {
+            (
+                "endpoint".to_string(),
+                "app".to_string(),
+                format!("action-{i}"),
+            )
+        };
+        let info = ActionInfo {
+            exists: true,
+            max_verifications: None,
+            external_nullifier: None,
+        };
+        let mut cache = Cache::new();
+
+        remember(&mut cache, key(0), info.clone(), 10, 0);
+        assert_eq!(cached(&mut cache, &key(0), 9), Some(info.clone()));
+        assert_eq!(cached(&mut cache, &key(0), 10), None);
+        assert!(cache.is_empty());
+
+        // Expired entries are dropped when a new one is stored
+        remember(&mut cache, key(0), info.clone(), 10, 0);
+        remember(&mut cache, key(1), info.clone(), 30, 20);
+        assert_eq!(cache.len(), 1);
+
+        // At capacity, the entry closest to expiring makes room
+        for i in 2..=MAX_CACHED_ACTIONS {
+            remember(&mut cache, key(i), info.clone(), 100, 20);
+        }
+        assert_eq!(cache.len(), MAX_CACHED_ACTIONS);
+        remember(&mut cache, key(0), info.clone(), 100, 20);
+        assert_eq!(cache.len(), MAX_CACHED_ACTIONS);
+        assert!(!cache.contains_key(&key(1)));
+        assert_eq!(cached(&mut cache, &key(0), 20), Some(info));
+    }
+
+    #[test]
+    fn test_precheck_missing_action() {
+        let not_found = options(serve_portal_response(404, r#"{"code":"not_found"}"#));
+        assert!(!precheck("my-acton", &not_found).unwrap().exists);
+
+        let no_metadata = options(serve_portal_response(200, r#"{"action":null}"#));
+        assert!(!precheck("my-acton", &no_metadata).unwrap().exists);
+    }
+
+    #[test]
+    fn test_precheck_portal_down() {
+        let down = options(serve_portal_response(503, ""));
+        assert!(matches!(
+            precheck("my-action", &down),
+            Err(Error::UnexpectedResponse)
+        ));
+    }
+
+    #[test]
+    fn test_ensure_action_exists() {
+        let app_id = AppId::new("app_staging_test").unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let missing = options(serve_portal_response(404, ""));
+        let err = runtime
+            .block_on(ensure_action_exists(&app_id, "my-acton", &missing))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidConfiguration(msg) if msg == "action 'my-acton' not found for app_staging_test"
+        ));
+
+        let down = PrecheckOptions {
+            best_effort: false,
+            ..options(serve_portal_response(500, ""))
+        };
+        assert!(runtime
+            .block_on(ensure_action_exists(&app_id, "my-action", &down))
+            .is_err());
+
+        let down = options(serve_portal_response(500, ""));
+        runtime
+            .block_on(ensure_action_exists(&app_id, "my-action", &down))
+            .unwrap();
+    }
+}
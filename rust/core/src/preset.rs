@@ -209,35 +209,35 @@ impl Preset {
             Self::OrbLegacy { signal } => BridgeParams {
                 constraints: None,
                 legacy_verification_level: Some(VerificationLevel::Orb),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: None,
             },
             Self::SecureDocumentLegacy { signal } => BridgeParams {
                 constraints: None,
                 legacy_verification_level: Some(VerificationLevel::SecureDocument),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: None,
             },
             Self::DocumentLegacy { signal } => BridgeParams {
                 constraints: None,
                 legacy_verification_level: Some(VerificationLevel::Document),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: None,
             },
             Self::SelfieCheckLegacy { signal } => BridgeParams {
                 constraints: None,
                 legacy_verification_level: Some(VerificationLevel::Face),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: None,
             },
             Self::DeviceLegacy { signal } => BridgeParams {
                 constraints: None,
                 legacy_verification_level: Some(VerificationLevel::Device),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: None,
             },
@@ -247,7 +247,7 @@ impl Preset {
                     signal.clone().map(Signal::from_string),
                 ))),
                 legacy_verification_level: Some(VerificationLevel::Orb),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: Some(true),
             },
@@ -257,7 +257,7 @@ impl Preset {
                     signal.clone().map(Signal::from_string),
                 ))),
                 legacy_verification_level: Some(VerificationLevel::Document),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: Some(true),
             },
@@ -267,7 +267,7 @@ impl Preset {
                     signal.clone().map(Signal::from_string),
                 ))),
                 legacy_verification_level: Some(VerificationLevel::Document),
-                legacy_signal: signal,
+                legacy_signal: non_empty(signal),
                 identity_attributes: None,
                 allow_legacy_proofs_override: Some(true),
             },
@@ -292,7 +292,7 @@ impl Preset {
                 BridgeParams {
                     constraints: Some(constraints),
                     legacy_verification_level: Some(VerificationLevel::Document),
-                    legacy_signal,
+                    legacy_signal: non_empty(legacy_signal),
                     identity_attributes: Some(attributes),
                     allow_legacy_proofs_override: Some(true),
                 }
@@ -301,6 +301,13 @@ impl Preset {
     }
 }
 
+/// Treats an empty preset signal as no signal, so a proof is never bound to
+/// `""` by accident. Credential requests drop empty signals on their own.
+#[cfg(any(test, feature = "ffi", feature = "wasm-bindings"))]
+fn non_empty(signal: Option<String>) -> Option<String> {
+    signal.filter(|signal| !signal.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -365,6 +372,34 @@ mod tests {
         assert!(bridge_params.constraints.is_none());
     }
 
+    #[test]
+    fn presets_treat_empty_signal_as_no_signal() {
+        let empty = || Some(String::new());
+        let presets = [
+            Preset::orb_legacy(empty()),
+            Preset::secure_document_legacy(empty()),
+            Preset::document_legacy(empty()),
+            Preset::selfie_check_legacy(empty()),
+            Preset::device_legacy(empty()),
+            Preset::proof_of_human(empty()),
+            Preset::passport(empty()),
+            Preset::mnc(empty()),
+            Preset::identity_check(vec![IdentityAttribute::MinimumAge(18)], empty()),
+        ];
+
+        for preset in presets {
+            let bridge_params = preset.into_bridge_params();
+            assert_eq!(bridge_params.legacy_signal, None);
+            for item in bridge_params
+                .constraints
+                .iter()
+                .flat_map(ConstraintNode::collect_items)
+            {
+                assert_eq!(item.signal, None);
+            }
+        }
+    }
+
     #[test]
     fn proof_of_human_preset_builds_v4_constraint_with_legacy_orb_fallback() {
         let preset = Preset::proof_of_human(Some("poh-signal".to_string()));
@@ -5,7 +5,8 @@
 use crate::error::{Error, Result};
 use alloy::signers::{local::PrivateKeySigner, SignerSync};
 use getrandom::getrandom;
-use k256::ecdsa::SigningKey;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use std::str::FromStr;
 use world_id_primitives::{rp::compute_rp_signature_msg, FieldElement};
 
 // Default expiration time of an RP request - 5 minutes
@@ -25,6 +26,44 @@ pub struct RpSignature {
     pub expires_at: u64,
 }
 
+impl RpSignature {
+    /// Returns `true` once `now` (unix seconds) has reached `expires_at`.
+    #[must_use]
+    pub const fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Returns `created_at` as an RFC 3339 UTC timestamp, for logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is outside the representable range
+    pub fn created_at_iso8601(&self) -> Result<String> {
+        format_unix_timestamp(self.created_at)
+    }
+
+    /// Returns `expires_at` as an RFC 3339 UTC timestamp, for logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamp is outside the representable range
+    pub fn expires_at_iso8601(&self) -> Result<String> {
+        format_unix_timestamp(self.expires_at)
+    }
+}
+
+fn format_unix_timestamp(seconds: u64) -> Result<String> {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(|seconds| time::OffsetDateTime::from_unix_timestamp(seconds).ok())
+        .and_then(|timestamp| {
+            timestamp
+                .format(&time::format_description::well_known::Rfc3339)
+                .ok()
+        })
+        .ok_or_else(|| Error::InvalidConfiguration(format!("Invalid timestamp: {seconds}")))
+}
+
 /// Computes the RP signature for a proof request.
 ///
 /// This function:
@@ -37,13 +76,16 @@ pub struct RpSignature {
 /// # Arguments
 /// * `signing_key_hex` - Hex-encoded 32-byte private key (with or without 0x prefix)
 /// * `ttl` - Optional time-to-live in seconds (defaults to 300 = 5 minutes)
-/// * `action` - Optional action string hashed into a field element and appended to the message
+/// * `action` - Optional action string, normalized like [`crate::Action::new`] and then hashed
+///   into a field element and appended to the message
 ///
 /// # Returns
 /// `Result<RpSignature>` containing the signature, nonce, and timestamp
 ///
 /// # Errors
 /// Returns an error if:
+/// - `ttl` is zero, which would make the signature expire as it is created
+/// - `action` is not a valid action
 /// - The signing key is invalid hex or wrong length
 /// - Random number generation fails
 /// - System time is before UNIX epoch
@@ -53,44 +95,115 @@ pub fn compute_rp_signature(
     ttl: Option<u64>,
     action: Option<&str>,
 ) -> Result<RpSignature> {
-    // 1. Parse signing key
+    validate_ttl(ttl)?;
+    let signing_key = parse_signing_key(signing_key_hex)?;
+    let nonce = random_nonce()?;
+    let timestamp = current_timestamp()?;
+
+    // Saturate so an oversized caller-supplied TTL can't overflow into the past
+    let expiration_timestamp = timestamp.saturating_add(ttl.unwrap_or(DEFAULT_SIG_EXPIRATION));
+    // Requests carry the normalized action, so the signature must cover it
+    let action = action
+        .map(crate::Action::new)
+        .transpose()?
+        .map(|action| crate::crypto::encode_action_field(action.as_str()));
+
+    sign_rp_message(&signing_key, nonce, timestamp, expiration_timestamp, action)
+}
+
+/// Computes RP signatures for several actions at once.
+///
+/// Parses the signing key and reads the clock once, so every signature shares
+/// the same `created_at` and `expires_at`. Each one gets its own random nonce.
+/// The result is in the same order as `actions`.
+///
+/// # Arguments
+/// * `signing_key_hex` - Hex-encoded 32-byte private key (with or without 0x prefix)
+/// * `actions` - Actions already encoded as field elements
+/// * `ttl` - Optional time-to-live in seconds (defaults to 300 = 5 minutes)
+///
+/// # Errors
+/// Returns the same errors as [`compute_rp_signature`]. No signatures are
+/// returned if any of them fails.
+pub fn compute_rp_signatures(
+    signing_key_hex: &str,
+    actions: &[FieldElement],
+    ttl: Option<u64>,
+) -> Result<Vec<RpSignature>> {
+    validate_ttl(ttl)?;
+    let signing_key = parse_signing_key(signing_key_hex)?;
+    let timestamp = current_timestamp()?;
+    let expiration_timestamp = timestamp.saturating_add(ttl.unwrap_or(DEFAULT_SIG_EXPIRATION));
+
+    actions
+        .iter()
+        .map(|action| {
+            sign_rp_message(
+                &signing_key,
+                random_nonce()?,
+                timestamp,
+                expiration_timestamp,
+                Some(*action),
+            )
+        })
+        .collect()
+}
+
+fn validate_ttl(ttl: Option<u64>) -> Result<()> {
+    if ttl == Some(0) {
+        return Err(Error::InvalidConfiguration(
+            "Invalid TTL: must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_signing_key(signing_key_hex: &str) -> Result<SigningKey> {
     let hex_str = signing_key_hex
         .strip_prefix("0x")
         .unwrap_or(signing_key_hex);
-    let key_bytes =
-        hex::decode(hex_str).map_err(|e| Error::Crypto(format!("Invalid signing key hex: {e}")))?;
 
-    // Validate key length before passing to k256 (which panics on wrong length)
-    if key_bytes.len() != 32 {
+    // Validate key length before decoding, so odd-length and truncated keys get a precise
+    // message, and before passing to k256 (which panics on wrong length)
+    if !hex_str.len().is_multiple_of(2) {
         return Err(Error::Crypto(format!(
-            "Invalid signing key length: expected 32 bytes, got {}",
-            key_bytes.len()
+            "Invalid signing key hex: odd number of hex characters ({})",
+            hex_str.len()
+        )));
+    }
+    if hex_str.len() != 64 {
+        return Err(Error::Crypto(format!(
+            "Invalid signing key length: expected 64 hex characters (32 bytes), got {}",
+            hex_str.len()
         )));
     }
 
-    let signing_key = SigningKey::from_bytes(key_bytes.as_slice().into())
-        .map_err(|e| Error::Crypto(format!("Invalid signing key: {e}")))?;
+    let key_bytes =
+        hex::decode(hex_str).map_err(|e| Error::Crypto(format!("Invalid signing key hex: {e}")))?;
 
-    // 2. Generate random nonce
+    SigningKey::from_bytes(key_bytes.as_slice().into())
+        .map_err(|e| Error::Crypto(format!("Invalid signing key: {e}")))
+}
+
+fn random_nonce() -> Result<FieldElement> {
     let mut nonce_bytes = [0u8; 32];
     getrandom(&mut nonce_bytes)
         .map_err(|e| Error::Crypto(format!("Failed to generate random nonce: {e}")))?;
-    let nonce = FieldElement::from_arbitrary_raw_bytes(&nonce_bytes);
+    Ok(FieldElement::from_arbitrary_raw_bytes(&nonce_bytes))
+}
 
-    // 3. Get current timestamp
-    #[cfg(not(target_arch = "wasm32"))]
-    let timestamp = std::time::SystemTime::now()
+#[cfg(not(target_arch = "wasm32"))]
+fn current_timestamp() -> Result<u64> {
+    Ok(std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|e| Error::Crypto(format!("System time error: {e}")))?
-        .as_secs();
-
-    #[cfg(target_arch = "wasm32")]
-    let timestamp = (js_sys::Date::now() / 1000.0) as u64;
-
-    let expiration_timestamp = timestamp + ttl.unwrap_or(DEFAULT_SIG_EXPIRATION);
-    let action = action.map(|action| FieldElement::from_arbitrary_raw_bytes(action.as_bytes()));
+        .as_secs())
+}
 
-    sign_rp_message(&signing_key, nonce, timestamp, expiration_timestamp, action)
+#[cfg(target_arch = "wasm32")]
+#[allow(clippy::unnecessary_wraps)]
+fn current_timestamp() -> Result<u64> {
+    Ok((js_sys::Date::now() / 1000.0) as u64)
 }
 
 /// Deterministic RP signature computation.
@@ -123,11 +236,52 @@ pub fn sign_rp_message(
     })
 }
 
+/// Verifies an RP signature against the signer's public key.
+///
+/// Rebuilds the signed message from the signature's nonce and timestamps (plus
+/// `action`, if one was signed), recovers the public key from the EIP-191
+/// prefixed keccak hash and compares it to `expected_pubkey`.
+///
+/// # Arguments
+/// * `signature` - The signature to check
+/// * `expected_pubkey` - SEC1-encoded secp256k1 public key, compressed or uncompressed
+/// * `action` - The action passed to [`compute_rp_signature`], if any
+///
+/// # Returns
+/// `Ok(true)` if the signature was made by `expected_pubkey`, `Ok(false)` otherwise
+///
+/// # Errors
+/// Returns an error if the public key, signature or nonce cannot be parsed.
+pub fn verify_rp_signature(
+    signature: &RpSignature,
+    expected_pubkey: &[u8],
+    action: Option<&str>,
+) -> Result<bool> {
+    let expected = VerifyingKey::from_sec1_bytes(expected_pubkey)
+        .map_err(|e| Error::Crypto(format!("Invalid public key: {e}")))?;
+    let nonce = FieldElement::from_str(&signature.nonce)
+        .map_err(|e| Error::Crypto(format!("Invalid nonce: {e}")))?;
+    let sig = alloy_primitives::Signature::from_str(&signature.sig)
+        .map_err(|e| Error::Crypto(format!("Invalid signature: {e}")))?;
+
+    let action = action.map(crate::crypto::encode_action_field);
+    let msg = compute_rp_signature_msg(
+        *nonce,
+        signature.created_at,
+        signature.expires_at,
+        action.map(|action| *action),
+    );
+
+    // A tampered message usually recovers some other key, but can also fail outright
+    Ok(sig
+        .recover_from_msg(&msg)
+        .is_ok_and(|recovered| recovered == expected))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloy_primitives::{Address, Signature};
-    use std::str::FromStr;
 
     // Valid 32-byte (64 hex chars) test key
     const TEST_KEY: &str = "abababababababababababababababababababababababababababababababab";
@@ -175,6 +329,54 @@ mod tests {
         assert_eq!(result.expires_at - result.created_at, 600);
     }
 
+    #[test]
+    fn test_oversized_ttl_saturates() {
+        let result = compute_rp_signature(TEST_KEY, Some(u64::MAX), None).unwrap();
+
+        assert_eq!(result.expires_at, u64::MAX);
+        assert!(result.expires_at > result.created_at);
+    }
+
+    #[test]
+    fn test_zero_ttl_is_rejected() {
+        let err = compute_rp_signature(TEST_KEY, Some(0), None).unwrap_err();
+
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_invalid_action_is_rejected() {
+        let err = compute_rp_signature(TEST_KEY, None, Some("log\u{200B}in")).unwrap_err();
+
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+        assert!(compute_rp_signature(TEST_KEY, None, Some("cafe\u{301}")).is_ok());
+    }
+
+    #[test]
+    fn test_iso8601_timestamps() {
+        let signature = RpSignature {
+            sig: String::new(),
+            nonce: String::new(),
+            created_at: 1_700_000_000,
+            expires_at: 1_700_000_300,
+        };
+
+        assert_eq!(
+            signature.created_at_iso8601().unwrap(),
+            "2023-11-14T22:13:20Z"
+        );
+        assert_eq!(
+            signature.expires_at_iso8601().unwrap(),
+            "2023-11-14T22:18:20Z"
+        );
+
+        let out_of_range = RpSignature {
+            expires_at: u64::MAX,
+            ..signature
+        };
+        assert!(out_of_range.expires_at_iso8601().is_err());
+    }
+
     #[test]
     fn test_signature_is_65_bytes() {
         let result = compute_rp_signature(TEST_KEY, None, None).unwrap();
@@ -268,6 +470,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compute_rp_signature_odd_length_key() {
+        let odd = &TEST_KEY_0X[..TEST_KEY_0X.len() - 1];
+        match compute_rp_signature(odd, None, None).unwrap_err() {
+            Error::Crypto(msg) => {
+                assert!(msg.contains("odd number of hex characters (63)"), "{msg}");
+            }
+            _ => panic!("Expected Crypto error"),
+        }
+    }
+
+    #[test]
+    fn test_compute_rp_signature_too_long_key() {
+        let too_long = format!("{TEST_KEY_0X}ab");
+        match compute_rp_signature(&too_long, None, None).unwrap_err() {
+            Error::Crypto(msg) => {
+                assert!(
+                    msg.contains("expected 64 hex characters (32 bytes), got 66"),
+                    "{msg}"
+                );
+            }
+            _ => panic!("Expected Crypto error"),
+        }
+    }
+
     #[test]
     fn test_compute_rp_signature_accepts_key_without_prefix() {
         let result = compute_rp_signature(TEST_KEY, None, None);
@@ -330,4 +557,91 @@ mod tests {
 
         assert_eq!(recovered, expected_test_signer_address());
     }
+
+    fn test_public_key() -> Vec<u8> {
+        let key_bytes = hex::decode(TEST_KEY).unwrap();
+        let signing_key = SigningKey::from_bytes(key_bytes.as_slice().into()).unwrap();
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_verify_rp_signature_accepts_fresh_signature() {
+        let public_key = test_public_key();
+
+        let result = compute_rp_signature(TEST_KEY, None, None).unwrap();
+        assert!(verify_rp_signature(&result, &public_key, None).unwrap());
+
+        let result = compute_rp_signature(TEST_KEY, None, Some("test-action")).unwrap();
+        assert!(verify_rp_signature(&result, &public_key, Some("test-action")).unwrap());
+        assert!(!verify_rp_signature(&result, &public_key, None).unwrap());
+    }
+
+    #[test]
+    fn test_verify_rp_signature_rejects_tampered_signature() {
+        let public_key = test_public_key();
+        let result = compute_rp_signature(TEST_KEY, None, None).unwrap();
+
+        let extended = RpSignature {
+            expires_at: result.expires_at + 1,
+            ..result.clone()
+        };
+        assert!(!verify_rp_signature(&extended, &public_key, None).unwrap());
+
+        let other_nonce = RpSignature {
+            nonce: FieldElement::from(123_u64).to_string(),
+            ..result.clone()
+        };
+        assert!(!verify_rp_signature(&other_nonce, &public_key, None).unwrap());
+
+        let other_key = hex::decode("cd".repeat(32)).unwrap();
+        let other_key = SigningKey::from_bytes(other_key.as_slice().into()).unwrap();
+        let other_public_key = other_key.verifying_key().to_encoded_point(false);
+        assert!(!verify_rp_signature(&result, other_public_key.as_bytes(), None).unwrap());
+
+        assert!(matches!(
+            verify_rp_signature(&result, &[0x02; 5], None),
+            Err(Error::Crypto(_))
+        ));
+    }
+
+    #[test]
+    fn test_compute_rp_signatures_batch() {
+        let actions: Vec<FieldElement> = ["login", "vote", "claim"]
+            .into_iter()
+            .map(crate::crypto::encode_action_field)
+            .collect();
+        let signatures = compute_rp_signatures(TEST_KEY_0X, &actions, Some(600)).unwrap();
+
+        assert_eq!(signatures.len(), actions.len());
+        let nonces: std::collections::HashSet<_> =
+            signatures.iter().map(|sig| sig.nonce.as_str()).collect();
+        assert_eq!(nonces.len(), actions.len());
+        assert!(signatures.iter().all(|sig| {
+            sig.created_at == signatures[0].created_at && sig.expires_at == sig.created_at + 600
+        }));
+
+        let pubkey = test_public_key();
+        for (signature, action) in signatures.iter().zip(["login", "vote", "claim"]) {
+            assert!(verify_rp_signature(signature, &pubkey, Some(action)).unwrap());
+        }
+
+        assert!(compute_rp_signatures(TEST_KEY, &[], None)
+            .unwrap()
+            .is_empty());
+        assert!(compute_rp_signatures("abc", &actions, None).is_err());
+        assert!(compute_rp_signatures(TEST_KEY, &actions, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_rp_signature_is_expired() {
+        let result = compute_rp_signature(TEST_KEY, Some(60), None).unwrap();
+
+        assert!(!result.is_expired(result.created_at));
+        assert!(!result.is_expired(result.expires_at - 1));
+        assert!(result.is_expired(result.expires_at));
+    }
 }
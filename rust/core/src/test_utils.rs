@@ -0,0 +1,322 @@
+//! Deterministic fixtures for tests
+//!
+//! Hand-rolled values such as `"0x123"` stop parsing as soon as validation
+//! tightens. Every fixture here is structurally valid and derived only from
+//! its seed, so the same seed always yields the same value. Randomized tests
+//! draw constraint trees from a seeded [`XorShift`] for the same reason.
+//!
+//! [`server::CannedServer`] serves canned HTTP responses to code under test.
+//!
+//! Only available with the `test-utils` feature, so production builds cannot
+//! ship fixture generators.
+
+use crate::types::{
+    BridgeResponseV1, CredentialRequest, CredentialType, ResponseItem, VerificationLevel,
+};
+use crate::ConstraintNode;
+use ruint::aliases::{U160, U256};
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+use tiny_keccak::{Hasher, Keccak};
+use world_id_primitives::{
+    rp::RpId, FieldElement, Nullifier, OprfKeyId, ProofRequest, ProofResponse, ProofType,
+    RequestItem, RequestVersion, ResponseItem as ProtocolResponseItem, ZeroKnowledgeProof,
+};
+
+pub mod server;
+
+/// `created_at` of every fixture request. Fixture requests expire an hour later.
+pub const FIXTURE_CREATED_AT: u64 = 1_700_000_000;
+
+/// Credential requested by [`ProtocolFixture`] requests and proven by their responses
+const FIXTURE_CREDENTIAL: CredentialType = CredentialType::ProofOfHuman;
+
+/// Returns `len` pseudo-random bytes derived from `seed` and `label`.
+fn seeded_bytes(seed: u64, label: &str, len: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(len + 32);
+    let mut counter = 0u32;
+    while bytes.len() < len {
+        let mut hasher = Keccak::v256();
+        hasher.update(&seed.to_be_bytes());
+        hasher.update(label.as_bytes());
+        hasher.update(&counter.to_be_bytes());
+        let mut block = [0u8; 32];
+        hasher.finalize(&mut block);
+        bytes.extend_from_slice(&block);
+        counter += 1;
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+fn seeded_hex(seed: u64, label: &str, len: usize) -> String {
+    format!("0x{}", hex::encode(seeded_bytes(seed, label, len)))
+}
+
+fn seeded_field(seed: u64, label: &str) -> FieldElement {
+    FieldElement::from_arbitrary_raw_bytes(&seeded_bytes(seed, label, 32))
+}
+
+fn seeded_proof(seed: u64) -> ZeroKnowledgeProof {
+    let mut elements = [U256::ZERO; 5];
+    for (index, element) in elements.iter_mut().enumerate() {
+        *element = seeded_field(seed, &format!("proof{index}")).into();
+    }
+    ZeroKnowledgeProof::from_ethereum_representation(elements)
+}
+
+impl BridgeResponseV1 {
+    /// Returns a legacy (World ID 3.0) proof with a 256-byte proof and
+    /// 32-byte Merkle root and nullifier hash, all derived from `seed`
+    #[must_use]
+    pub fn test_fixture(verification_level: VerificationLevel, seed: u64) -> Self {
+        Self {
+            proof: seeded_hex(seed, "legacy_proof", 256),
+            merkle_root: seeded_hex(seed, "legacy_root", 32),
+            nullifier_hash: seeded_hex(seed, "legacy_nullifier", 32),
+            verification_level,
+        }
+    }
+}
+
+impl ResponseItem {
+    /// Returns a World ID 4.0 uniqueness proof for `credential`, derived from `seed`
+    #[must_use]
+    pub fn test_fixture(credential: CredentialType, seed: u64) -> Self {
+        Self::V4 {
+            identifier: credential.to_string(),
+            signal_hash: None,
+            issuer_schema_id: credential.issuer_schema_id(),
+            proof: seeded_proof(seed)
+                .as_ethereum_representation()
+                .map(|value| value.to_string())
+                .to_vec(),
+            nullifier: seeded_field(seed, "nullifier").to_string(),
+            expires_at_min: FIXTURE_CREATED_AT,
+        }
+    }
+}
+
+/// Deterministic fixtures for the protocol request and response types
+///
+/// `ProofResponse::test_fixture(seed)` answers `ProofRequest::test_fixture(seed)`
+/// and passes its `validate_response`.
+pub trait ProtocolFixture: Sized {
+    /// Returns the fixture for `seed`.
+    fn test_fixture(seed: u64) -> Self;
+}
+
+impl ProtocolFixture for ProofRequest {
+    fn test_fixture(seed: u64) -> Self {
+        let rp_id = RpId::new(u64::from_be_bytes(
+            seeded_bytes(seed, "rp_id", 8)
+                .try_into()
+                .expect("seeded_bytes returns the requested length"),
+        ));
+        Self {
+            id: format!("req_fixture_{seed}"),
+            version: RequestVersion::V1,
+            proof_type: ProofType::Uniqueness,
+            created_at: FIXTURE_CREATED_AT,
+            expires_at: FIXTURE_CREATED_AT + 3600,
+            rp_id,
+            oprf_key_id: OprfKeyId::new(U160::from(rp_id.into_inner())),
+            session_id: None,
+            action: Some(seeded_field(seed, "action")),
+            signature: alloy_primitives::Signature::new(
+                seeded_field(seed, "signature_r").into(),
+                seeded_field(seed, "signature_s").into(),
+                false,
+            ),
+            nonce: seeded_field(seed, "nonce"),
+            requests: vec![RequestItem::new(
+                FIXTURE_CREDENTIAL.to_string(),
+                FIXTURE_CREDENTIAL.issuer_schema_id(),
+                None,
+                None,
+                None,
+            )],
+            constraints: None,
+        }
+    }
+}
+
+impl ProtocolFixture for ProofResponse {
+    fn test_fixture(seed: u64) -> Self {
+        Self {
+            id: format!("req_fixture_{seed}"),
+            version: RequestVersion::V1,
+            session_id: None,
+            error: None,
+            responses: vec![ProtocolResponseItem {
+                identifier: FIXTURE_CREDENTIAL.to_string(),
+                issuer_schema_id: FIXTURE_CREDENTIAL.issuer_schema_id(),
+                proof: seeded_proof(seed),
+                nullifier: Some(Nullifier::new(seeded_field(seed, "nullifier"))),
+                session_nullifier: None,
+                expires_at_min: FIXTURE_CREATED_AT,
+            }],
+        }
+    }
+}
+
+/// Returns a sample Developer Portal `/verify` response body by name
+///
+/// The bodies are sanitized and follow the portal's `{ code, detail, attribute }`
+/// error shape. Each is served with the HTTP status below:
+///
+/// | Name | Status |
+/// | --- | --- |
+/// | `success` | 200 |
+/// | `invalid_proof` | 400 |
+/// | `max_verifications_reached` | 400 |
+/// | `invalid_merkle_root` | 400 |
+/// | `rate_limited` | 429 |
+/// | `server_error` (HTML) | 500 |
+///
+/// # Panics
+///
+/// Panics if `name` is not one of the fixtures above.
+#[must_use]
+pub fn portal_fixture(name: &str) -> &'static str {
+    match name {
+        "success" => include_str!("../tests/fixtures/portal/success.json"),
+        "invalid_proof" => include_str!("../tests/fixtures/portal/invalid_proof.json"),
+        "max_verifications_reached" => {
+            include_str!("../tests/fixtures/portal/max_verifications_reached.json")
+        }
+        "invalid_merkle_root" => include_str!("../tests/fixtures/portal/invalid_merkle_root.json"),
+        "rate_limited" => include_str!("../tests/fixtures/portal/rate_limited.json"),
+        "server_error" => include_str!("../tests/fixtures/portal/server_error.html"),
+        _ => panic!("unknown portal fixture: {name}"),
+    }
+}
+
+/// Minimal xorshift generator, so randomized tests are deterministic and
+/// reproducible from their seed
+#[derive(Debug, Clone)]
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    /// Returns the next pseudo-random value
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a pseudo-random index below `bound`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        let bound = u64::try_from(bound).expect("bound fits in u64");
+        usize::try_from(self.next_u64() % bound).expect("value below a usize bound")
+    }
+}
+
+/// Returns a random protocol-expressible constraint tree with at most `depth`
+/// levels of nesting and three children per node
+///
+/// Leaves request a credential without a signal. `Not` and `AtLeast` nodes
+/// are never generated, since the protocol cannot express them.
+pub fn arbitrary_constraint_tree(rng: &mut XorShift, depth: usize) -> ConstraintNode {
+    let credentials: Vec<CredentialType> = CredentialType::iter().collect();
+    let kind = if depth == 0 { 0 } else { rng.below(4) };
+    if kind == 0 {
+        let credential = credentials[rng.below(credentials.len())];
+        return ConstraintNode::item(CredentialRequest::new(credential, None));
+    }
+
+    let children = (0..=rng.below(3))
+        .map(|_| arbitrary_constraint_tree(rng, depth - 1))
+        .collect();
+    match kind {
+        1 => ConstraintNode::any(children),
+        2 => ConstraintNode::all(children),
+        _ => ConstraintNode::enumerate(children),
+    }
+}
+
+/// Returns every subset of the known credential types
+#[must_use]
+pub fn credential_subsets() -> Vec<HashSet<CredentialType>> {
+    let credentials: Vec<CredentialType> = CredentialType::iter().collect();
+    (0..1u32 << credentials.len())
+        .map(|mask| {
+            credentials
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, credential)| *credential)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProofRequestExt;
+
+    #[test]
+    fn test_fixtures_are_deterministic_per_seed() {
+        let legacy = BridgeResponseV1::test_fixture(VerificationLevel::Orb, 7);
+        assert_eq!(
+            legacy,
+            BridgeResponseV1::test_fixture(VerificationLevel::Orb, 7)
+        );
+        assert_ne!(
+            legacy.proof,
+            BridgeResponseV1::test_fixture(VerificationLevel::Orb, 8).proof
+        );
+
+        assert_eq!(
+            ResponseItem::test_fixture(CredentialType::Passport, 7),
+            ResponseItem::test_fixture(CredentialType::Passport, 7)
+        );
+        assert_ne!(
+            ResponseItem::test_fixture(CredentialType::Passport, 7),
+            ResponseItem::test_fixture(CredentialType::Passport, 8)
+        );
+    }
+
+    #[test]
+    fn test_fixtures_are_structurally_valid() {
+        let legacy = BridgeResponseV1::test_fixture(VerificationLevel::Device, 1);
+        assert_eq!(legacy.proof.len(), 2 + 512);
+        assert_eq!(legacy.merkle_root.len(), 2 + 64);
+        assert_eq!(legacy.nullifier_hash.len(), 2 + 64);
+        assert!(!legacy.is_placeholder());
+        let round_trip: BridgeResponseV1 =
+            serde_json::from_value(serde_json::to_value(&legacy).unwrap()).unwrap();
+        assert_eq!(round_trip.verification_level, VerificationLevel::Device);
+
+        let item = ResponseItem::test_fixture(CredentialType::ProofOfHuman, 1);
+        assert!(!item.has_default_proof());
+        let ResponseItem::V4 {
+            identifier, proof, ..
+        } = item
+        else {
+            panic!("expected a World ID 4.0 response item");
+        };
+        assert_eq!(identifier, "proof_of_human");
+        assert_eq!(proof.len(), 5);
+    }
+
+    #[test]
+    fn test_protocol_fixtures_validate() {
+        let request = ProofRequest::test_fixture(3);
+        request.validate_timestamps().unwrap();
+        let request =
+            ProofRequest::from_json_validated(&serde_json::to_string(&request).unwrap(), false)
+                .unwrap();
+
+        let response = ProofResponse::test_fixture(3);
+        let response: ProofResponse =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        request.validate_response(&response).unwrap();
+    }
+}
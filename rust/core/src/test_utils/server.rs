@@ -0,0 +1,185 @@
+//! Canned HTTP responses on a loopback port
+//!
+//! [`CannedServer`] answers one connection per response, in order, and
+//! records every request it reads. Unlike the bridge's `MockBridge` it knows
+//! nothing about any protocol, so tests can serve malformed bodies, arbitrary
+//! statuses, streamed bodies or an endpoint that never answers.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A response served by [`CannedServer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CannedResponse {
+    status: u16,
+    body: Vec<u8>,
+    chunked: bool,
+}
+
+impl CannedResponse {
+    /// Returns a JSON response with the given status and body
+    #[must_use]
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+            chunked: false,
+        }
+    }
+
+    /// Streams the body with chunked encoding, so no `Content-Length` is sent
+    #[must_use]
+    pub const fn chunked(mut self) -> Self {
+        self.chunked = true;
+        self
+    }
+}
+
+impl From<(u16, String)> for CannedResponse {
+    fn from((status, body): (u16, String)) -> Self {
+        Self::new(status, body)
+    }
+}
+
+impl From<(u16, &str)> for CannedResponse {
+    fn from((status, body): (u16, &str)) -> Self {
+        Self::new(status, body)
+    }
+}
+
+/// Serves canned responses from a background thread on `127.0.0.1`
+///
+/// The thread exits once every response has been served; dropping the
+/// server does not stop it early.
+#[derive(Debug)]
+pub struct CannedServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl CannedServer {
+    /// Binds a free loopback port and serves `responses`, one per connection
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound
+    pub fn start<R: Into<CannedResponse>>(
+        responses: impl IntoIterator<Item = R>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let log = requests.clone();
+        let responses: Vec<CannedResponse> = responses.into_iter().map(Into::into).collect();
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                // A client that hangs up mid-request only loses its own answer
+                let _ = serve(stream, &response, &log);
+            }
+        });
+
+        Ok(Self { addr, requests })
+    }
+
+    /// Binds a free loopback port that accepts every connection and never
+    /// answers, for exercising timeouts
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound
+    pub fn hanging() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        // Keeps every accepted stream open
+        std::thread::spawn(move || listener.incoming().collect::<Vec<_>>());
+        Ok(Self {
+            addr,
+            requests: Arc::default(),
+        })
+    }
+
+    /// Returns the server's base URL, e.g. `http://127.0.0.1:41234`
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Returns the raw requests received so far, in order
+    ///
+    /// Each holds the request line, the headers and the body.
+    #[must_use]
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Reads one HTTP request from `stream`, records it and writes `response`
+fn serve(
+    mut stream: TcpStream,
+    response: &CannedResponse,
+    log: &Mutex<Vec<String>>,
+) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 4096];
+    let head_len = loop {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break request.len();
+        }
+        request.extend_from_slice(&buffer[..read]);
+        if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        // Not HTTP, e.g. a TLS handshake. Answer anyway and let the client fail.
+        if !request[0].is_ascii_uppercase() {
+            break request.len();
+        }
+    };
+
+    let content_length = String::from_utf8_lossy(&request[..head_len])
+        .lines()
+        .filter_map(|header| header.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+    while request.len() < head_len + content_length {
+        let read = stream.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    log.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(String::from_utf8_lossy(&request).into_owned());
+
+    let status = response.status;
+    if response.chunked {
+        write!(
+            stream,
+            "HTTP/1.1 {status} Canned\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+        )?;
+        for chunk in response.body.chunks(256) {
+            write!(stream, "{:x}\r\n", chunk.len())?;
+            stream.write_all(chunk)?;
+            write!(stream, "\r\n")?;
+        }
+        write!(stream, "0\r\n\r\n")?;
+    } else {
+        write!(
+            stream,
+            "HTTP/1.1 {status} Canned\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n",
+            response.body.len()
+        )?;
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()
+}
@@ -1,12 +1,12 @@
 //! Core types for the `IDKit` protocol
 
 use serde::{Deserialize, Serialize};
-use world_id_primitives::rp::RpId;
+use world_id_primitives::{
+    rp::RpId, ConstraintExpr, ConstraintNode as ProtocolNode, FieldElement, ProofRequest,
+    ProofResponse, RequestItem, RequestVersion,
+};
 
-use std::{borrow::Cow, str::FromStr};
-
-#[cfg(feature = "ffi")]
-use std::sync::Arc;
+use std::{borrow::Cow, collections::HashMap, str::FromStr, sync::Arc};
 
 /// Credential types that can be requested
 #[derive(
@@ -69,13 +69,20 @@ impl CredentialType {
 /// Signals are used to create unique proofs. They can be:
 /// - UTF-8 strings (common case for off-chain usage)
 /// - Raw bytes (user handles any encoding, e.g., ABI encoding for on-chain use)
+///
+/// An empty signal (`""` or zero bytes) means "no signal": `CredentialRequest`
+/// treats it like `None`, so nothing is sent to World App and no signal hash
+/// is computed. Whitespace-only strings are not empty and are hashed as-is.
+///
+/// Byte signals are shared rather than copied when a signal, request or
+/// connection is cloned, so large signals stay cheap to pass around.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "ffi", derive(uniffi::Object))]
 pub enum Signal {
     /// UTF-8 string signal
     String(String),
     /// Raw bytes signal (user handles any encoding)
-    Bytes(Vec<u8>),
+    Bytes(Arc<[u8]>),
 }
 
 impl Signal {
@@ -92,7 +99,7 @@ impl Signal {
         let s = s.into();
 
         if let Some(bytes) = decode_prefixed_hex_signal(&s) {
-            return Self::Bytes(bytes);
+            return Self::Bytes(bytes.into());
         }
 
         Self::String(s)
@@ -104,7 +111,7 @@ impl Signal {
     /// The caller is responsible for any encoding.
     #[must_use]
     pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
-        Self::Bytes(bytes.into())
+        Self::Bytes(bytes.into().into())
     }
 
     /// Gets the raw bytes of the signal
@@ -143,6 +150,31 @@ impl Signal {
             Self::Bytes(_) => None,
         }
     }
+
+    /// Converts the signal to the protocol `RequestItem.signal` format.
+    ///
+    /// The protocol carries signals as raw bytes, serialized as `0x` hex on the
+    /// wire. Byte signals pass through unchanged; string signals become their
+    /// UTF-8 bytes, except valid `0x` hex strings, which are decoded so the
+    /// Authenticator hashes the same bytes as `crypto::hash_signal`.
+    #[must_use]
+    pub fn to_request_item_signal(&self) -> Vec<u8> {
+        self.hash_input_bytes().into_owned()
+    }
+
+    /// Returns true for `""` and zero-byte signals, which count as no signal
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::String(s) => s.is_empty(),
+            Self::Bytes(b) => b.is_empty(),
+        }
+    }
+}
+
+/// Drops empty signals so they behave exactly like an absent one
+fn non_empty_signal(signal: Option<Signal>) -> Option<Signal> {
+    signal.filter(|signal| !signal.is_empty())
 }
 
 fn decode_prefixed_hex_signal(s: &str) -> Option<Vec<u8>> {
@@ -204,7 +236,14 @@ impl Serialize for Signal {
     {
         match self {
             Self::String(s) => serializer.serialize_str(s),
-            Self::Bytes(b) => serializer.serialize_str(&format!("0x{}", hex::encode(b))),
+            Self::Bytes(b) => {
+                // Hex-encode straight into one buffer; byte signals can be large
+                let mut encoded = vec![b'0'; 2 + 2 * b.len()];
+                encoded[1] = b'x';
+                hex::encode_to_slice(b, &mut encoded[2..]).map_err(serde::ser::Error::custom)?;
+                let encoded = std::str::from_utf8(&encoded).map_err(serde::ser::Error::custom)?;
+                serializer.serialize_str(encoded)
+            }
         }
     }
 }
@@ -221,7 +260,7 @@ impl<'de> Deserialize<'de> for Signal {
         // JS-facing `Signal::from_string` path still treats "0x" as text to
         // match `hashSignal("0x")`.
         if let Some(bytes) = decode_serialized_hex_signal(&s) {
-            return Ok(Self::Bytes(bytes));
+            return Ok(Self::Bytes(bytes.into()));
         }
 
         // Else, treat as a UTF-8 string
@@ -241,7 +280,7 @@ pub struct CredentialRequest {
     pub credential_type: CredentialType,
 
     /// The signal to be included in the proof (unique per request)
-    /// If `None`, no signal is included in the proof
+    /// If `None` or empty, no signal is included in the proof
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signal: Option<Signal>,
 
@@ -253,6 +292,13 @@ pub struct CredentialRequest {
     /// Optional minimum expiration timestamp constraint for the proof
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at_min: Option<u64>,
+
+    /// Optional user presence (face auth) requirement for this credential
+    ///
+    /// The bridge protocol only supports user presence for a whole request, so
+    /// a leaf with `Some(true)` turns it on for the request it belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_user_presence: Option<bool>,
 }
 
 impl CredentialRequest {
@@ -261,9 +307,10 @@ impl CredentialRequest {
     pub fn new(credential_type: CredentialType, signal: Option<Signal>) -> Self {
         Self {
             credential_type,
-            signal,
+            signal: non_empty_signal(signal),
             genesis_issued_at_min: None,
             expires_at_min: None,
+            require_user_presence: None,
         }
     }
 
@@ -276,9 +323,10 @@ impl CredentialRequest {
     ) -> Self {
         Self {
             credential_type,
-            signal,
+            signal: non_empty_signal(signal),
             genesis_issued_at_min: Some(genesis_min),
             expires_at_min: None,
+            require_user_presence: None,
         }
     }
 
@@ -291,22 +339,39 @@ impl CredentialRequest {
     ) -> Self {
         Self {
             credential_type,
-            signal,
+            signal: non_empty_signal(signal),
             genesis_issued_at_min: None,
             expires_at_min: Some(expires_at_min),
+            require_user_presence: None,
         }
     }
 
+    /// Sets whether this credential requires a user presence check
+    #[must_use]
+    pub const fn with_user_presence(mut self, required: bool) -> Self {
+        self.require_user_presence = Some(required);
+        self
+    }
+
     /// Gets the signal bytes used by protocol proof requests.
     ///
     /// These are the bytes the protocol hashes into the proof. Keep this aligned
     /// with `crypto::hash_signal`, including `IDKit`'s `0x` string decoding
     /// semantics for address-shaped signals.
+    ///
+    /// Returns `None` for an absent or empty signal.
     #[must_use]
     pub fn signal_bytes(&self) -> Option<Vec<u8>> {
-        self.signal
-            .as_ref()
-            .map(|signal| signal.hash_input_bytes().into_owned())
+        self.effective_signal().map(Signal::to_request_item_signal)
+    }
+
+    /// Gets the signal, treating an empty one as absent.
+    ///
+    /// Deserialized requests can still carry `""`, so read the signal through
+    /// this rather than the `signal` field.
+    #[must_use]
+    pub fn effective_signal(&self) -> Option<&Signal> {
+        self.signal.as_ref().filter(|signal| !signal.is_empty())
     }
 
     /// Converts to a protocol `RequestItem`
@@ -330,6 +395,26 @@ impl CredentialRequest {
             self.expires_at_min,
         ))
     }
+
+    /// Serializes the request item to JSON
+    ///
+    /// This is the format the `UniFFI` and WASM bindings produce as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes a request item from JSON produced by any binding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON deserialization fails
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
 }
 
 // UniFFI exports for CredentialRequest
@@ -391,12 +476,39 @@ impl CredentialRequest {
         ))
     }
 
+    /// Returns a copy of this item with its user presence requirement set
+    #[must_use]
+    #[uniffi::method(name = "with_user_presence")]
+    pub fn ffi_with_user_presence(&self, required: bool) -> Arc<Self> {
+        Arc::new(self.clone().with_user_presence(required))
+    }
+
     /// Gets the signal as raw bytes from a request item
     #[must_use]
     pub fn get_signal_bytes(&self) -> Option<Vec<u8>> {
         self.signal_bytes()
     }
 
+    /// Gets the signal if it was given as a string
+    ///
+    /// Returns `None` for byte signals and for an absent or empty signal.
+    #[must_use]
+    pub fn signal_as_string(&self) -> Option<String> {
+        self.effective_signal()
+            .and_then(Signal::as_str)
+            .map(str::to_string)
+    }
+
+    /// Whether the signal was given as raw bytes
+    ///
+    /// `Signal::from_string` stores valid `0x` hex as bytes, so such signals
+    /// report `true` here.
+    #[must_use]
+    pub fn signal_is_bytes(&self) -> bool {
+        self.effective_signal()
+            .is_some_and(|signal| signal.as_str().is_none())
+    }
+
     /// Gets the credential type
     #[must_use]
     pub fn credential_type(&self) -> CredentialType {
@@ -420,9 +532,9 @@ impl CredentialRequest {
     /// # Errors
     ///
     /// Returns an error if JSON serialization fails
-    pub fn to_json(&self) -> std::result::Result<String, crate::error::IdkitError> {
-        serde_json::to_string(&self)
-            .map_err(|e| crate::error::IdkitError::from(crate::Error::from(e)))
+    #[uniffi::method(name = "to_json")]
+    pub fn ffi_to_json(&self) -> std::result::Result<String, crate::error::IdkitError> {
+        Ok(self.to_json()?)
     }
 
     /// Deserializes a request item from JSON
@@ -432,9 +544,7 @@ impl CredentialRequest {
     /// Returns an error if JSON deserialization fails
     #[uniffi::constructor(name = "from_json")]
     pub fn ffi_from_json(json: &str) -> std::result::Result<Arc<Self>, crate::error::IdkitError> {
-        serde_json::from_str(json)
-            .map(Arc::new)
-            .map_err(|e| crate::error::IdkitError::from(crate::Error::from(e)))
+        Ok(Arc::new(Self::from_json(json)?))
     }
 }
 
@@ -610,6 +720,111 @@ impl<'de> Deserialize<'de> for BridgeResponseV1 {
     }
 }
 
+impl BridgeResponseV1 {
+    /// Returns true if any of the proof, merkle root or nullifier hash is a
+    /// placeholder: all zeros, or empty.
+    ///
+    /// Placeholders are valid hex and pass structural checks, but can never come from a
+    /// real proof, so they must be rejected before being surfaced to the caller.
+    /// [`ResponseItem::has_default_proof`] applies the same rule to World ID 3.0 items.
+    #[must_use]
+    pub fn is_placeholder(&self) -> bool {
+        is_placeholder_legacy_proof(&self.proof, &self.merkle_root, &self.nullifier_hash)
+    }
+
+    /// Parses a legacy proof from JSON, accepting only its exact serialized shape
+    ///
+    /// Plain deserialization ignores unknown keys and reads `credential_type` in place
+    /// of `verification_level`. This rejects both, listing the unexpected keys and
+    /// pointing at the right helper when the JSON is another `IDKit` type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProof`](crate::Error::InvalidProof) if the JSON has keys
+    /// other than the proof fields, or a JSON error if it does not match their types
+    pub fn from_json_strict(json: &str) -> crate::Result<Self> {
+        /// Mirror of the serialized form with no aliases or extra keys.
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Strict {
+            proof: String,
+            merkle_root: String,
+            nullifier_hash: String,
+            verification_level: VerificationLevel,
+        }
+
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if let serde_json::Value::Object(fields) = &value {
+            let unexpected: Vec<&str> = fields
+                .keys()
+                .map(String::as_str)
+                .filter(|key| !STRICT_PROOF_FIELDS.contains(key))
+                .collect();
+            if !unexpected.is_empty() {
+                let mut message = format!("unexpected keys: {}", unexpected.join(", "));
+                if let Some(hint) = foreign_proof_shape_hint(fields) {
+                    message.push_str("; ");
+                    message.push_str(hint);
+                }
+                return Err(crate::Error::InvalidProof(message));
+            }
+        }
+
+        let strict: Strict = serde_json::from_value(value)?;
+        Ok(Self {
+            proof: strict.proof,
+            merkle_root: strict.merkle_root,
+            nullifier_hash: strict.nullifier_hash,
+            verification_level: strict.verification_level,
+        })
+    }
+}
+
+/// Keys of a serialized [`BridgeResponseV1`]
+const STRICT_PROOF_FIELDS: [&str; 4] = [
+    "proof",
+    "merkle_root",
+    "nullifier_hash",
+    "verification_level",
+];
+
+/// Recognizes JSON that belongs to another `IDKit` type and names the helper for it
+fn foreign_proof_shape_hint(
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> Option<&'static str> {
+    if fields.contains_key("identifier") || fields.contains_key("issuer_schema_id") {
+        Some(
+            "this looks like a ResponseItem from IDKitResult.responses; parse the whole result with idkit_result_from_json instead",
+        )
+    } else if fields.contains_key("credential_type") {
+        Some(
+            "this looks like an ISuccessResult, which names the verification level credential_type; rename it to verification_level or use proof_from_json",
+        )
+    } else {
+        None
+    }
+}
+
+/// Whether a hex value carries no non-zero digit
+///
+/// An empty value (`""` or `"0x"`) counts as zero: like an all-zero one, it
+/// can never come from a real proof.
+fn is_zero_hex(value: &str) -> bool {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    digits.chars().all(|c| c == '0')
+}
+
+/// Whether any part of a World ID 3.0 proof is a placeholder value
+fn is_placeholder_legacy_proof(proof: &str, merkle_root: &str, nullifier: &str) -> bool {
+    [proof, merkle_root, nullifier].into_iter().any(is_zero_hex)
+}
+
+/// Whether a World ID 4.0 proof is the default all-zero proof, which is
+/// structurally valid but can never verify
+pub(crate) fn is_default_proof(proof: &[String]) -> bool {
+    proof.iter().all(|value| is_zero_hex(value))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Unified Response Types (World ID 4.0)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -714,6 +929,27 @@ pub enum ResponseItem {
     },
 }
 
+impl ResponseItem {
+    /// Returns true if the item carries the default all-zero proof.
+    ///
+    /// A default proof is structurally valid but can never verify, so a successful
+    /// item carrying one indicates a broken or placeholder response. World ID 3.0
+    /// items follow [`BridgeResponseV1::is_placeholder`], so a zero or empty merkle
+    /// root or nullifier also counts.
+    #[must_use]
+    pub fn has_default_proof(&self) -> bool {
+        match self {
+            Self::V4 { proof, .. } | Self::Session { proof, .. } => is_default_proof(proof),
+            Self::V3 {
+                proof,
+                merkle_root,
+                nullifier,
+                ..
+            } => is_placeholder_legacy_proof(proof, merkle_root, nullifier),
+        }
+    }
+}
+
 /// This is the top-level result returned from a proof request flow.
 /// It contains the protocol version and an array of credential responses.
 /// For session proofs, it also contains the `session_id`.
@@ -843,6 +1079,32 @@ pub fn idkit_result_from_json(
     serde_json::from_str(json).map_err(|e| crate::error::IdkitError::from(crate::Error::from(e)))
 }
 
+#[cfg(feature = "ffi")]
+/// Deserializes a legacy proof from JSON, ignoring unknown keys
+///
+/// # Errors
+///
+/// Returns an error if JSON deserialization fails
+#[uniffi::export]
+pub fn proof_from_json(
+    json: &str,
+) -> std::result::Result<BridgeResponseV1, crate::error::IdkitError> {
+    serde_json::from_str(json).map_err(|e| crate::error::IdkitError::from(crate::Error::from(e)))
+}
+
+#[cfg(feature = "ffi")]
+/// Deserializes a legacy proof from JSON, rejecting unknown keys and field aliases
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not exactly a serialized proof
+#[uniffi::export]
+pub fn proof_from_json_strict(
+    json: &str,
+) -> std::result::Result<BridgeResponseV1, crate::error::IdkitError> {
+    BridgeResponseV1::from_json_strict(json).map_err(crate::error::IdkitError::from)
+}
+
 /// Application ID for World ID
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AppId(String);
@@ -869,6 +1131,19 @@ impl AppId {
         self.0.starts_with("app_staging_")
     }
 
+    /// Returns the environment this app ID belongs to
+    ///
+    /// `app_staging_` IDs are staging apps, all others production apps. The
+    /// sandbox environment has no app ID prefix of its own.
+    #[must_use]
+    pub fn environment(&self) -> Environment {
+        if self.is_staging() {
+            Environment::Staging
+        } else {
+            Environment::Production
+        }
+    }
+
     /// Returns the raw app ID string
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -882,6 +1157,199 @@ impl std::fmt::Display for AppId {
     }
 }
 
+/// Maximum length of an [`Action`], in characters
+pub const MAX_ACTION_LENGTH: usize = 200;
+
+/// Validated, NFC-normalized action identifier
+///
+/// Actions are hashed byte for byte into the nullifier, so an accent typed as
+/// a combining character, a trailing space or a zero-width space each yield a
+/// different nullifier than the action the backend verifies. Build actions
+/// with the same constructor on the frontend and the backend so both hash the
+/// same bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Action(String);
+
+impl Action {
+    /// Normalizes `action` to NFC and validates it
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidConfiguration` if the action is empty, longer
+    /// than [`MAX_ACTION_LENGTH`] characters, starts or ends with whitespace,
+    /// or contains control or invisible characters
+    pub fn new(action: impl AsRef<str>) -> crate::Result<Self> {
+        Self::normalize(action.as_ref(), false)
+    }
+
+    /// Like [`Action::new`], but also lowercases the action so that `Login`
+    /// and `login` are the same action
+    ///
+    /// # Errors
+    ///
+    /// See [`Action::new`]
+    pub fn new_lowercase(action: impl AsRef<str>) -> crate::Result<Self> {
+        Self::normalize(action.as_ref(), true)
+    }
+
+    fn normalize(action: &str, lowercase: bool) -> crate::Result<Self> {
+        use unicode_normalization::UnicodeNormalization;
+
+        let invalid = |reason: String| crate::Error::InvalidConfiguration(reason);
+        // Lowercasing can decompose characters, so normalize last
+        let action: String = if lowercase {
+            action.to_lowercase().nfc().collect()
+        } else {
+            action.nfc().collect()
+        };
+
+        if action.is_empty() {
+            return Err(invalid("action cannot be empty".to_string()));
+        }
+        if action.chars().count() > MAX_ACTION_LENGTH {
+            return Err(invalid(format!(
+                "action must be at most {MAX_ACTION_LENGTH} characters"
+            )));
+        }
+        if action.starts_with(char::is_whitespace) || action.ends_with(char::is_whitespace) {
+            return Err(invalid(
+                "action cannot start or end with whitespace".to_string(),
+            ));
+        }
+        if let Some(c) = action.chars().find(|&c| c.is_control() || is_invisible(c)) {
+            return Err(invalid(format!(
+                "action contains the control or invisible character U+{:04X}",
+                u32::from(c)
+            )));
+        }
+
+        Ok(Self(action))
+    }
+
+    /// Returns the normalized action
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the normalized action as an owned string
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// Characters that render as nothing but still change the action's bytes
+const fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+    )
+}
+
+impl AsRef<str> for Action {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for Action {
+    type Error = crate::Error;
+
+    fn try_from(action: String) -> crate::Result<Self> {
+        Self::new(action)
+    }
+}
+
+impl TryFrom<&str> for Action {
+    type Error = crate::Error;
+
+    fn try_from(action: &str) -> crate::Result<Self> {
+        Self::new(action)
+    }
+}
+
+impl From<Action> for String {
+    fn from(action: Action) -> Self {
+        action.0
+    }
+}
+
+#[cfg(feature = "ffi")]
+/// Normalizes an action the way requests do, see [`Action::new`]
+///
+/// Sign and verify with the returned action so every party hashes the same
+/// bytes. `lowercase` also folds case, see [`Action::new_lowercase`].
+///
+/// # Errors
+///
+/// Returns an error if the action is invalid
+#[uniffi::export]
+#[allow(clippy::needless_pass_by_value)] // uniffi requires owned types
+pub fn normalize_action(
+    action: String,
+    lowercase: bool,
+) -> std::result::Result<String, crate::error::IdkitError> {
+    let action = if lowercase {
+        Action::new_lowercase(&action)?
+    } else {
+        Action::new(&action)?
+    };
+    Ok(action.into_string())
+}
+
+/// World environment a request targets
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Default,
+    strum::AsRefStr,
+    strum::Display,
+)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Environment {
+    #[default]
+    Production,
+    Staging,
+    Sandbox,
+}
+
+impl Environment {
+    #[must_use]
+    pub const fn connect_base_url(self) -> &'static str {
+        match self {
+            Self::Production => "https://world.org/verify",
+            Self::Staging => "https://staging.world.org/verify",
+            Self::Sandbox => "https://sandbox.world.org/verify",
+        }
+    }
+}
+
 /// Bridge URL for connecting to the World App
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BridgeUrl(String);
@@ -890,6 +1358,19 @@ impl BridgeUrl {
     /// Default bridge URL
     pub const DEFAULT: &'static str = "https://bridge.worldcoin.org";
 
+    /// Returns the default bridge URL for an environment
+    ///
+    /// All environments currently share the same bridge; they only differ in
+    /// the connect URL, see [`Environment::connect_base_url`].
+    #[must_use]
+    pub fn for_environment(env: Environment) -> Self {
+        match env {
+            Environment::Production | Environment::Staging | Environment::Sandbox => {
+                Self::default()
+            }
+        }
+    }
+
     /// Creates a new bridge URL with validation based on app context
     ///
     /// For staging apps (`app_staging_*`), allows localhost/127.0.0.1 with relaxed rules.
@@ -918,7 +1399,7 @@ impl BridgeUrl {
     }
 
     /// Internal validation with explicit staging flag
-    fn validated(url: String, is_staging: bool) -> crate::Result<Self> {
+    pub(crate) fn validated(url: String, is_staging: bool) -> crate::Result<Self> {
         let parsed = url::Url::parse(&url).map_err(|e| {
             crate::Error::InvalidConfiguration(format!("Failed to parse Bridge URL: {e}"))
         })?;
@@ -964,6 +1445,26 @@ impl BridgeUrl {
         &self.0
     }
 
+    /// Returns true if this is the hosted bridge at [`Self::DEFAULT`]
+    ///
+    /// A trailing slash or different letter case in the host still counts as the default.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        match (url::Url::parse(&self.0), url::Url::parse(Self::DEFAULT)) {
+            (Ok(url), Ok(default)) => url == default,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this URL points at a developer machine
+    ///
+    /// These are the loopback, private-network and `*.local` hosts that only
+    /// staging apps (`app_staging_*`) may use.
+    #[must_use]
+    pub fn is_staging(&self) -> bool {
+        url::Url::parse(&self.0).is_ok_and(|url| is_dev_host(url.host().as_ref()))
+    }
+
     /// Joins a path to the bridge URL
     ///
     /// # Errors
@@ -1069,7 +1570,7 @@ impl RpContext {
 
         #[cfg(target_arch = "wasm32")]
         let now = (js_sys::Date::now() / 1000.0) as u64;
-        if created_at > now + CLOCK_SKEW_ALLOWANCE_SECS {
+        if created_at > now.saturating_add(CLOCK_SKEW_ALLOWANCE_SECS) {
             return Err(crate::Error::InvalidConfiguration(
                 "created_at cannot be in the future".to_string(),
             ));
@@ -1152,7 +1653,16 @@ impl RpContext {
 
 /// Verification level (for backward compatibility)
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::AsRefStr, strum::Display,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum::AsRefStr,
+    strum::Display,
+    strum::EnumString,
 )]
 #[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
 #[serde(rename_all = "snake_case")]
@@ -1170,6 +1680,264 @@ pub enum VerificationLevel {
     SecureDocument,
 }
 
+impl VerificationLevel {
+    /// Returns the legacy credentials that satisfy this level, strongest
+    /// first. Orb credentials satisfy every level.
+    #[must_use]
+    pub fn to_credentials(self) -> Vec<Self> {
+        match self {
+            Self::Orb => vec![Self::Orb],
+            Self::Face => vec![Self::Orb, Self::Face],
+            Self::Device => vec![Self::Orb, Self::Device],
+            Self::Document => vec![Self::Orb, Self::SecureDocument, Self::Document],
+            Self::SecureDocument => vec![Self::Orb, Self::SecureDocument],
+        }
+    }
+}
+
+/// Protocol request versions this crate can build and parse
+const SUPPORTED_REQUEST_VERSIONS: &[RequestVersion] = &[RequestVersion::V1];
+
+/// Version negotiation helpers for the protocol [`RequestVersion`]
+pub trait RequestVersionExt: Sized {
+    /// Returns every request version this crate supports.
+    fn supported() -> &'static [Self];
+
+    /// Returns `true` if `version` is a supported request version number.
+    fn is_supported(version: u8) -> bool;
+
+    /// Returns the supported request version with the given number.
+    fn from_supported(version: u8) -> Option<Self>;
+}
+
+impl RequestVersionExt for RequestVersion {
+    fn supported() -> &'static [Self] {
+        SUPPORTED_REQUEST_VERSIONS
+    }
+
+    fn is_supported(version: u8) -> bool {
+        Self::from_supported(version).is_some()
+    }
+
+    fn from_supported(version: u8) -> Option<Self> {
+        SUPPORTED_REQUEST_VERSIONS
+            .iter()
+            .copied()
+            .find(|supported| *supported as u8 == version)
+    }
+}
+
+/// Deserializes a [`RequestVersion`], rejecting versions outside [`RequestVersionExt::supported`].
+#[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+pub(crate) fn deserialize_request_version<'de, D>(
+    deserializer: D,
+) -> std::result::Result<RequestVersion, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let version = u8::deserialize(deserializer)?;
+    RequestVersion::from_supported(version)
+        .ok_or_else(|| serde::de::Error::custom(format!("unsupported request version {version}")))
+}
+
+/// Timestamp checks for the protocol [`ProofRequest`]
+///
+/// The request's `created_at`/`expires_at` are copied from the signed
+/// [`RpContext`], so they are validated rather than recomputed here.
+pub trait ProofRequestExt: Sized {
+    /// Checks that the request expires strictly after it was created.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::Error::InvalidConfiguration`] if `expires_at <= created_at`.
+    fn validate_timestamps(&self) -> crate::Result<()>;
+
+    /// Checks that every credential identifier referenced by the constraints
+    /// has a matching request item, so the request is satisfiable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::Error::InvalidConfiguration`] listing the referenced
+    /// identifiers that have no request item.
+    fn validate_constraint_references(&self) -> crate::Result<()>;
+
+    /// Parses a request from JSON and validates its timestamps and constraint
+    /// references.
+    ///
+    /// Pass `allow_unknown_references` to skip the constraint reference check,
+    /// e.g. for requests naming credentials this SDK does not know yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::Error::Json`] if the JSON is malformed, or
+    /// [`crate::Error::InvalidConfiguration`] if validation fails.
+    fn from_json_validated(json: &str, allow_unknown_references: bool) -> crate::Result<Self>;
+}
+
+impl ProofRequestExt for ProofRequest {
+    fn validate_timestamps(&self) -> crate::Result<()> {
+        if self.expires_at <= self.created_at {
+            return Err(crate::Error::InvalidConfiguration(format!(
+                "Invalid proof request expiry: expires_at ({}) must be greater than created_at ({})",
+                self.expires_at, self.created_at
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_constraint_references(&self) -> crate::Result<()> {
+        let Some(constraints) = &self.constraints else {
+            return Ok(());
+        };
+        let mut referenced = std::collections::BTreeSet::new();
+        collect_constraint_identifiers(constraints, &mut referenced);
+
+        let missing: Vec<&str> = referenced
+            .into_iter()
+            .filter(|identifier| {
+                !self
+                    .requests
+                    .iter()
+                    .any(|item| item.identifier == *identifier)
+            })
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::Error::InvalidConfiguration(format!(
+                "Constraints reference credentials with no matching request: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    fn from_json_validated(json: &str, allow_unknown_references: bool) -> crate::Result<Self> {
+        let request = Self::from_json(json)?;
+        request.validate_timestamps()?;
+        if !allow_unknown_references {
+            request.validate_constraint_references()?;
+        }
+        Ok(request)
+    }
+}
+
+fn collect_constraint_identifiers<'a>(
+    expr: &'a ConstraintExpr<'_>,
+    identifiers: &mut std::collections::BTreeSet<&'a str>,
+) {
+    let (ConstraintExpr::All { all: nodes }
+    | ConstraintExpr::Any { any: nodes }
+    | ConstraintExpr::Enumerate { enumerate: nodes }) = expr;
+    for node in nodes {
+        match node {
+            ProtocolNode::Type(identifier) => {
+                identifiers.insert(identifier);
+            }
+            ProtocolNode::Expr(child) => collect_constraint_identifiers(child, identifiers),
+        }
+    }
+}
+
+/// Error reported for a requested credential that World App did not return
+pub const CREDENTIAL_NOT_RETURNED: &str = "credential_not_returned";
+
+/// Summaries of the credentials and nullifiers in a protocol [`ProofResponse`]
+///
+/// The protocol reports errors for the whole response rather than per
+/// credential, so failures are derived by comparing the response with the
+/// originally requested items.
+pub trait ProofResponseExt {
+    /// Returns the number of credentials proven in the response.
+    fn success_count(&self) -> usize;
+
+    /// Returns `(identifier, error)` for each requested credential that was not proven.
+    ///
+    /// The error is the response-level error when present, otherwise
+    /// [`CREDENTIAL_NOT_RETURNED`].
+    fn failed_credentials<'a>(&'a self, requested: &'a [RequestItem]) -> Vec<(&'a str, &'a str)>;
+
+    /// Returns the number of requested credentials that were not proven.
+    fn failure_count(&self, requested: &[RequestItem]) -> usize {
+        self.failed_credentials(requested).len()
+    }
+
+    /// Returns the uniqueness nullifier of every proven credential.
+    ///
+    /// Items without a nullifier (session proofs) are skipped, and a response
+    /// with an error has none.
+    fn nullifiers(&self) -> Vec<&FieldElement>;
+
+    /// Returns the uniqueness nullifier of every proven credential, keyed by
+    /// credential identifier, e.g. for storing them for sybil resistance.
+    ///
+    /// Skips the same items as [`ProofResponseExt::nullifiers`].
+    fn nullifiers_by_identifier(&self) -> HashMap<&str, &FieldElement>;
+
+    /// Returns the uniqueness nullifier proven for `identifier`, if any.
+    fn nullifier_for(&self, identifier: &str) -> Option<&FieldElement>;
+}
+
+impl ProofResponseExt for ProofResponse {
+    fn success_count(&self) -> usize {
+        self.successful_credentials().len()
+    }
+
+    fn failed_credentials<'a>(&'a self, requested: &'a [RequestItem]) -> Vec<(&'a str, &'a str)> {
+        let error = self.error.as_deref();
+        requested
+            .iter()
+            .filter(|item| {
+                error.is_some()
+                    || !self
+                        .responses
+                        .iter()
+                        .any(|response| response.identifier == item.identifier)
+            })
+            .map(|item| {
+                (
+                    item.identifier.as_str(),
+                    error.unwrap_or(CREDENTIAL_NOT_RETURNED),
+                )
+            })
+            .collect()
+    }
+
+    fn nullifiers(&self) -> Vec<&FieldElement> {
+        if self.error.is_some() {
+            return Vec::new();
+        }
+        self.responses
+            .iter()
+            .filter_map(|item| item.nullifier.as_ref())
+            .map(|nullifier| &nullifier.inner)
+            .collect()
+    }
+
+    fn nullifiers_by_identifier(&self) -> HashMap<&str, &FieldElement> {
+        if self.error.is_some() {
+            return HashMap::new();
+        }
+        self.responses
+            .iter()
+            .filter_map(|item| {
+                let nullifier = item.nullifier.as_ref()?;
+                Some((item.identifier.as_str(), &nullifier.inner))
+            })
+            .collect()
+    }
+
+    fn nullifier_for(&self, identifier: &str) -> Option<&FieldElement> {
+        if self.error.is_some() {
+            return None;
+        }
+        self.responses
+            .iter()
+            .find(|item| item.identifier == identifier)
+            .and_then(|item| item.nullifier.as_ref())
+            .map(|nullifier| &nullifier.inner)
+    }
+}
+
 // UniFFI helper function for CredentialType
 #[cfg(feature = "ffi")]
 /// Gets the string representation of a credential type
@@ -1179,9 +1947,45 @@ pub fn credential_to_string(credential: &CredentialType) -> String {
     credential.to_string()
 }
 
+// UniFFI helper function for VerificationLevel
+#[cfg(feature = "ffi")]
+/// Gets the legacy credentials that satisfy a verification level, strongest first
+#[must_use]
+#[uniffi::export]
+pub fn verification_level_credentials(level: VerificationLevel) -> Vec<VerificationLevel> {
+    level.to_credentials()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::ProtocolFixture;
+
+    #[test]
+    fn test_supported_request_versions() {
+        assert_eq!(RequestVersion::supported(), &[RequestVersion::V1]);
+        assert!(RequestVersion::is_supported(1));
+        assert!(!RequestVersion::is_supported(0));
+        assert!(!RequestVersion::is_supported(2));
+    }
+
+    #[cfg(any(feature = "bridge", feature = "bridge-wasm"))]
+    #[test]
+    fn test_deserialize_request_version_uses_supported_list() {
+        #[derive(Deserialize)]
+        struct Versioned {
+            #[serde(deserialize_with = "deserialize_request_version")]
+            version: RequestVersion,
+        }
+
+        let parsed: Versioned = serde_json::from_str(r#"{"version":1}"#).unwrap();
+        assert_eq!(parsed.version, RequestVersion::V1);
+
+        let err = serde_json::from_str::<Versioned>(r#"{"version":2}"#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("unsupported request version 2"));
+    }
 
     #[test]
     fn test_app_id_validation() {
@@ -1190,6 +1994,49 @@ mod tests {
         assert!(AppId::new("invalid").is_err());
     }
 
+    #[test]
+    fn test_action_normalization() {
+        // "café" with a combining acute accent normalizes to the composed form
+        let decomposed = "cafe\u{301}";
+        let action = Action::new(decomposed).unwrap();
+        assert_eq!(action.as_str(), "caf\u{e9}");
+        assert_eq!(action, Action::new("caf\u{e9}").unwrap());
+
+        // Case is kept unless asked for
+        assert_ne!(Action::new("Login").unwrap(), Action::new("login").unwrap());
+        assert_eq!(Action::new_lowercase("Login").unwrap().as_str(), "login");
+        assert_eq!(
+            Action::new_lowercase("CAFE\u{301}").unwrap().as_str(),
+            "caf\u{e9}"
+        );
+
+        assert!(Action::new("a".repeat(MAX_ACTION_LENGTH)).is_ok());
+        assert!(Action::new("\u{e9}".repeat(MAX_ACTION_LENGTH)).is_ok());
+        for invalid in [
+            String::new(),
+            "a".repeat(MAX_ACTION_LENGTH + 1),
+            "login ".to_string(),
+            "\tlogin".to_string(),
+            "log\nin".to_string(),
+            "log\u{200B}in".to_string(),
+            "\u{FEFF}login".to_string(),
+        ] {
+            assert!(
+                matches!(
+                    Action::new(&invalid),
+                    Err(crate::Error::InvalidConfiguration(_))
+                ),
+                "{invalid:?} should be rejected"
+            );
+        }
+
+        // Deserializing validates and normalizes too
+        let action: Action = serde_json::from_str(&format!("\"{decomposed}\"")).unwrap();
+        assert_eq!(action.as_str(), "caf\u{e9}");
+        assert!(serde_json::from_str::<Action>("\"\"").is_err());
+        assert_eq!(String::from(action), "caf\u{e9}");
+    }
+
     #[test]
     fn test_app_id_staging() {
         let staging = AppId::new("app_staging_123").unwrap();
@@ -1199,6 +2046,23 @@ mod tests {
         assert!(!prod.is_staging());
     }
 
+    #[test]
+    fn test_app_id_environment() {
+        let staging = AppId::new("app_staging_123").unwrap();
+        assert_eq!(staging.environment(), Environment::Staging);
+
+        let prod = AppId::new("app_123").unwrap();
+        assert_eq!(prod.environment(), Environment::Production);
+
+        for env in [
+            Environment::Production,
+            Environment::Staging,
+            Environment::Sandbox,
+        ] {
+            assert_eq!(BridgeUrl::for_environment(env), BridgeUrl::default());
+        }
+    }
+
     #[test]
     fn test_request_item_creation() {
         let item = CredentialRequest::new(
@@ -1244,6 +2108,36 @@ mod tests {
         assert_eq!(decoded, bytes);
     }
 
+    #[test]
+    fn test_to_request_item_signal_for_both_variants() {
+        assert_eq!(
+            Signal::String("my_signal".to_string()).to_request_item_signal(),
+            b"my_signal"
+        );
+        // Hex strings are decoded, matching `crypto::hash_signal`
+        assert_eq!(
+            Signal::String("0xdead".to_string()).to_request_item_signal(),
+            vec![0xde, 0xad]
+        );
+        assert_eq!(
+            Signal::from_bytes([0x00, 0xff]).to_request_item_signal(),
+            vec![0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn test_protocol_item_signal_is_hex_on_the_wire() {
+        for (signal, expected) in [
+            (Signal::from_string("hi"), "0x6869"),
+            (Signal::from_bytes([0xab, 0xcd]), "0xabcd"),
+        ] {
+            let item = CredentialRequest::new(CredentialType::ProofOfHuman, Some(signal))
+                .to_protocol_item()
+                .unwrap();
+            assert_eq!(serde_json::to_value(&item).unwrap()["signal"], expected);
+        }
+    }
+
     #[test]
     fn test_request_item_with_string_signal() {
         // Test creating request item with string signal
@@ -1278,6 +2172,70 @@ mod tests {
         assert_eq!(item.signal_bytes(), None);
     }
 
+    #[test]
+    fn test_request_item_treats_empty_signal_as_absent() {
+        for empty in [Signal::from_string(""), Signal::from_bytes(Vec::new())] {
+            let item = CredentialRequest::new(CredentialType::ProofOfHuman, Some(empty));
+            assert_eq!(item.signal, None);
+            assert_eq!(item.signal_bytes(), None);
+            assert_eq!(item.to_protocol_item().unwrap().signal, None);
+        }
+
+        // Deserialized requests bypass the constructors but read the same way
+        let item: CredentialRequest =
+            serde_json::from_value(serde_json::json!({"type": "proof_of_human", "signal": ""}))
+                .unwrap();
+        assert_eq!(item.effective_signal(), None);
+        assert_eq!(item.signal_bytes(), None);
+    }
+
+    #[test]
+    fn test_request_item_keeps_whitespace_and_single_byte_signals() {
+        let whitespace =
+            CredentialRequest::new(CredentialType::ProofOfHuman, Some(Signal::from_string(" ")));
+        assert_eq!(whitespace.signal_bytes(), Some(b" ".to_vec()));
+
+        let one_byte =
+            CredentialRequest::new(CredentialType::ProofOfHuman, Some(Signal::from_bytes([7])));
+        assert_eq!(one_byte.signal_bytes(), Some(vec![7]));
+    }
+
+    #[test]
+    fn test_request_item_json_round_trip() {
+        let item = CredentialRequest::with_genesis_min(
+            CredentialType::ProofOfHuman,
+            Some(Signal::from_string("user-123")),
+            1_700_000_000,
+        )
+        .with_user_presence(true);
+        let json = item.to_json().unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"proof_of_human","signal":"user-123","genesis_issued_at_min":1700000000,"require_user_presence":true}"#
+        );
+        assert_eq!(json, serde_json::to_string(&item).unwrap());
+
+        let parsed = CredentialRequest::from_json(&json).unwrap();
+        assert_eq!(parsed.to_json().unwrap(), json);
+        assert_eq!(parsed.signal_bytes(), item.signal_bytes());
+    }
+
+    #[test]
+    fn test_request_item_json_keeps_byte_signals() {
+        let item = CredentialRequest::new(
+            CredentialType::Passport,
+            Some(Signal::from_bytes(vec![0x48, 0x65, 0x6c, 0x6c, 0x6f])),
+        );
+        let json = item.to_json().unwrap();
+        assert_eq!(json, r#"{"type":"passport","signal":"0x48656c6c6f"}"#);
+
+        let parsed = CredentialRequest::from_json(&json).unwrap();
+        assert_eq!(parsed.signal, Some(Signal::from_bytes(b"Hello".to_vec())));
+        assert_eq!(parsed.signal_bytes(), Some(b"Hello".to_vec()));
+
+        assert!(CredentialRequest::from_json(r#"{"signal":"x"}"#).is_err());
+    }
+
     #[test]
     fn test_signal_serialization() {
         // Test string signal serialization
@@ -1311,7 +2269,7 @@ mod tests {
 
         // Test empty 0x-prefixed hex deserialization preserves serialized bytes
         let signal: Signal = serde_json::from_str(r#""0x""#).unwrap();
-        assert_eq!(signal, Signal::Bytes(Vec::new()));
+        assert_eq!(signal, Signal::from_bytes(Vec::new()));
 
         // Test non-prefixed hex is treated as a plain string (not bytes)
         // This prevents ambiguity with strings like "cafe" or "deadbeef"
@@ -1556,6 +2514,37 @@ mod tests {
         assert!(BridgeUrl::new("http://localhost:3000", &prod_app).is_err());
     }
 
+    #[test]
+    fn test_bridge_url_predicates() {
+        let prod_app = AppId::new("app_123").unwrap();
+        let staging_app = AppId::new("app_staging_123").unwrap();
+
+        let default = BridgeUrl::default();
+        assert!(default.is_default());
+        assert!(!default.is_staging());
+        for equivalent in [
+            "https://bridge.worldcoin.org/",
+            "https://BRIDGE.worldcoin.org",
+        ] {
+            assert!(BridgeUrl::new(equivalent, &prod_app).unwrap().is_default());
+        }
+
+        for dev in [
+            "http://localhost:3000",
+            "http://127.0.0.1:8080",
+            "http://192.168.1.20:8000",
+            "http://laptop.local:8000",
+        ] {
+            let url = BridgeUrl::new(dev, &staging_app).unwrap();
+            assert!(url.is_staging(), "{dev}");
+            assert!(!url.is_default(), "{dev}");
+        }
+
+        let custom = BridgeUrl::new("https://bridge.example.com", &prod_app).unwrap();
+        assert!(!custom.is_default());
+        assert!(!custom.is_staging());
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // IdentityAttribute serialization / deserialization
     // ─────────────────────────────────────────────────────────────────────────
@@ -1633,4 +2622,299 @@ mod tests {
         );
         assert_eq!(attrs[4], IdentityAttribute::MinimumAge(18));
     }
+
+    #[test]
+    fn test_bridge_response_v1_is_placeholder() {
+        let mut response = BridgeResponseV1 {
+            proof: format!("0x{}", "0".repeat(512)),
+            merkle_root: format!("0x{}", "0".repeat(64)),
+            nullifier_hash: "0x0".to_string(),
+            verification_level: VerificationLevel::Orb,
+        };
+        assert!(response.is_placeholder());
+
+        response.proof = "0x1a2b".to_string();
+        response.merkle_root = "0x2c3d".to_string();
+        assert!(response.is_placeholder());
+
+        response.nullifier_hash = "0x3e4f".to_string();
+        assert!(!response.is_placeholder());
+
+        // Empty values carry no proof either
+        for empty in ["", "0x"] {
+            response.merkle_root = empty.to_string();
+            assert!(response.is_placeholder(), "{empty:?}");
+        }
+    }
+
+    #[test]
+    fn test_bridge_response_v1_from_json_strict() {
+        let json = r#"{"proof":"0x1a","merkle_root":"0x2b","nullifier_hash":"0x3c","verification_level":"orb"}"#;
+        let strict = BridgeResponseV1::from_json_strict(json).unwrap();
+        assert_eq!(
+            strict,
+            serde_json::from_str::<BridgeResponseV1>(json).unwrap()
+        );
+
+        // Lenient parsing still ignores extra keys
+        let extra = r#"{"proof":"0x1a","merkle_root":"0x2b","nullifier_hash":"0x3c","verification_level":"orb","zeta":1,"alpha":2}"#;
+        assert!(serde_json::from_str::<BridgeResponseV1>(extra).is_ok());
+        let Err(crate::Error::InvalidProof(message)) = BridgeResponseV1::from_json_strict(extra)
+        else {
+            panic!("expected unexpected keys to be rejected");
+        };
+        assert_eq!(message, "unexpected keys: alpha, zeta");
+
+        // Type-coerced values are rejected rather than converted
+        let coerced = r#"{"proof":26,"merkle_root":"0x2b","nullifier_hash":"0x3c","verification_level":"orb"}"#;
+        assert!(matches!(
+            BridgeResponseV1::from_json_strict(coerced),
+            Err(crate::Error::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_bridge_response_v1_from_json_strict_suggests_helper() {
+        let response_item = r#"{"identifier":"orb","issuer_schema_id":1,"proof":"0x1a","merkle_root":"0x2b","nullifier_hash":"0x3c","verification_level":"orb"}"#;
+        let Err(crate::Error::InvalidProof(message)) =
+            BridgeResponseV1::from_json_strict(response_item)
+        else {
+            panic!("expected a ResponseItem to be rejected");
+        };
+        assert!(message.starts_with("unexpected keys: identifier, issuer_schema_id;"));
+        assert!(message.contains("idkit_result_from_json"));
+
+        let success_result = r#"{"proof":"0x1a","merkle_root":"0x2b","nullifier_hash":"0x3c","credential_type":"orb"}"#;
+        assert!(serde_json::from_str::<BridgeResponseV1>(success_result).is_ok());
+        let Err(crate::Error::InvalidProof(message)) =
+            BridgeResponseV1::from_json_strict(success_result)
+        else {
+            panic!("expected an ISuccessResult to be rejected");
+        };
+        assert!(message.starts_with("unexpected keys: credential_type;"));
+        assert!(message.contains("proof_from_json"));
+    }
+
+    #[test]
+    fn test_response_item_has_default_proof() {
+        let mut item = ResponseItem::V4 {
+            identifier: "proof_of_human".to_string(),
+            signal_hash: None,
+            issuer_schema_id: 1,
+            proof: vec!["0".to_string(); 5],
+            nullifier: "0x1234".to_string(),
+            expires_at_min: 1_735_689_600,
+        };
+        assert!(item.has_default_proof());
+
+        if let ResponseItem::V4 { proof, .. } = &mut item {
+            proof[4] = "0x2c3d".to_string();
+        }
+        assert!(!item.has_default_proof());
+
+        let mut legacy = ResponseItem::V3 {
+            identifier: "orb".to_string(),
+            signal_hash: "0x00".to_string(),
+            proof: format!("0x{}", "0".repeat(512)),
+            merkle_root: "0x2c3d".to_string(),
+            nullifier: "0x3e4f".to_string(),
+        };
+        assert!(legacy.has_default_proof());
+
+        // A zero root or nullifier is a placeholder too, as for BridgeResponseV1
+        if let ResponseItem::V3 {
+            proof, nullifier, ..
+        } = &mut legacy
+        {
+            *proof = "0x1a2b".to_string();
+            *nullifier = "0x0".to_string();
+        }
+        assert!(legacy.has_default_proof());
+        if let ResponseItem::V3 {
+            merkle_root,
+            nullifier,
+            ..
+        } = &mut legacy
+        {
+            *nullifier = "0x3e4f".to_string();
+            *merkle_root = "0x".to_string();
+        }
+        assert!(legacy.has_default_proof());
+        if let ResponseItem::V3 { merkle_root, .. } = &mut legacy {
+            *merkle_root = "0x2c3d".to_string();
+        }
+        assert!(!legacy.has_default_proof());
+    }
+
+    fn requested_items() -> Vec<RequestItem> {
+        ["orb", "document", "secure_document"]
+            .into_iter()
+            .enumerate()
+            .map(|(index, identifier)| {
+                RequestItem::new(identifier.to_string(), index as u64 + 1, None, None, None)
+            })
+            .collect()
+    }
+
+    fn proof_response(error: Option<&str>, identifiers: &[&str]) -> ProofResponse {
+        let mut response = ProofResponse::test_fixture(0);
+        let template = response.responses.remove(0);
+        response.error = error.map(str::to_string);
+        response.responses = identifiers
+            .iter()
+            .enumerate()
+            .map(|(index, identifier)| world_id_primitives::ResponseItem {
+                identifier: (*identifier).to_string(),
+                issuer_schema_id: index as u64 + 1,
+                ..template.clone()
+            })
+            .collect();
+        response
+    }
+
+    #[test]
+    fn test_proof_response_summary_for_mixed_response() {
+        let requested = requested_items();
+        let response = proof_response(None, &["orb", "secure_document"]);
+
+        assert_eq!(response.success_count(), 2);
+        assert_eq!(response.failure_count(&requested), 1);
+        assert_eq!(
+            response.failed_credentials(&requested),
+            vec![("document", CREDENTIAL_NOT_RETURNED)]
+        );
+    }
+
+    #[test]
+    fn test_proof_response_summary_with_response_error() {
+        let requested = requested_items();
+        let response = proof_response(Some("credential_unavailable"), &[]);
+
+        assert_eq!(response.success_count(), 0);
+        assert_eq!(response.failure_count(&requested), 3);
+        assert!(response
+            .failed_credentials(&requested)
+            .iter()
+            .all(|(_, error)| *error == "credential_unavailable"));
+    }
+
+    #[test]
+    fn test_proof_response_nullifiers() {
+        let item = |identifier: &str, nullifier: Option<u8>| {
+            let mut item = serde_json::json!({
+                "identifier": identifier,
+                "issuer_schema_id": 1,
+                "proof": "0".repeat(320),
+                "expires_at_min": 1_735_689_600,
+            });
+            if let Some(nullifier) = nullifier {
+                item["nullifier"] = format!("nil_{}{nullifier:02x}", "0".repeat(62)).into();
+            }
+            item
+        };
+        let response: ProofResponse = serde_json::from_value(serde_json::json!({
+            "id": "req_nullifiers",
+            "version": 1,
+            "responses": [item("orb", Some(1)), item("selfie", None), item("passport", Some(2))],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.nullifiers(),
+            vec![&FieldElement::from(1u64), &FieldElement::from(2u64)]
+        );
+
+        assert_eq!(
+            response.nullifiers_by_identifier(),
+            HashMap::from([
+                ("orb", &FieldElement::from(1u64)),
+                ("passport", &FieldElement::from(2u64)),
+            ])
+        );
+        assert_eq!(
+            response.nullifier_for("passport"),
+            Some(&FieldElement::from(2u64))
+        );
+        assert_eq!(response.nullifier_for("selfie"), None);
+        assert_eq!(response.nullifier_for("document"), None);
+
+        let mut errored = response;
+        errored.error = Some("credential_unavailable".to_string());
+        assert!(errored.nullifiers().is_empty());
+        assert!(errored.nullifiers_by_identifier().is_empty());
+        assert_eq!(errored.nullifier_for("orb"), None);
+    }
+
+    #[test]
+    fn test_verification_level_to_credentials() {
+        assert_eq!(
+            VerificationLevel::Device.to_credentials(),
+            vec![VerificationLevel::Orb, VerificationLevel::Device]
+        );
+        assert_eq!(
+            VerificationLevel::Orb.to_credentials(),
+            vec![VerificationLevel::Orb]
+        );
+        for level in [
+            VerificationLevel::Face,
+            VerificationLevel::Document,
+            VerificationLevel::SecureDocument,
+        ] {
+            let credentials = level.to_credentials();
+            assert_eq!(credentials.first(), Some(&VerificationLevel::Orb));
+            assert_eq!(credentials.last(), Some(&level));
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_signal_accessors_keep_signal_kind() {
+        let string = CredentialRequest::with_string_signal(
+            CredentialType::ProofOfHuman,
+            Some("user-123".to_string()),
+        );
+        assert_eq!(string.signal_as_string(), Some("user-123".to_string()));
+        assert!(!string.signal_is_bytes());
+
+        let abi_encoded = [0u8; 31].into_iter().chain([42]).collect::<Vec<_>>();
+        let bytes = CredentialRequest::ffi_new(
+            CredentialType::ProofOfHuman,
+            Some(Arc::new(Signal::from_bytes(abi_encoded.clone()))),
+        );
+        assert_eq!(bytes.signal_as_string(), None);
+        assert!(bytes.signal_is_bytes());
+        assert_eq!(bytes.get_signal_bytes(), Some(abi_encoded));
+
+        let none = CredentialRequest::ffi_new(CredentialType::ProofOfHuman, None);
+        assert_eq!(none.signal_as_string(), None);
+        assert!(!none.signal_is_bytes());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_request_item_json_matches_core() {
+        let core = CredentialRequest::new(
+            CredentialType::ProofOfHuman,
+            Some(Signal::from_bytes([0xde, 0xad])),
+        );
+        let ffi = CredentialRequest::ffi_new(
+            CredentialType::ProofOfHuman,
+            Some(Arc::new(Signal::from_bytes([0xde, 0xad]))),
+        );
+        let json = core.to_json().unwrap();
+        assert_eq!(ffi.ffi_to_json().unwrap(), json);
+
+        let parsed = CredentialRequest::ffi_from_json(&json).unwrap();
+        assert_eq!(parsed.get_signal_bytes(), Some(vec![0xde, 0xad]));
+        assert_eq!(parsed.ffi_to_json().unwrap(), json);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_ffi_verification_level_credentials() {
+        assert_eq!(
+            verification_level_credentials(VerificationLevel::Device),
+            vec![VerificationLevel::Orb, VerificationLevel::Device]
+        );
+    }
 }
@@ -0,0 +1,372 @@
+//! Server-side verification of `IDKit` results against the Developer Portal
+//!
+//! The bridge only transports proofs; an RP backend must still forward the
+//! [`IDKitResult`] to the Developer Portal, which checks the proofs on-chain
+//! and enforces nullifier uniqueness.
+
+use crate::{
+    error::{Error, Result},
+    http::{default_client, read_capped_body, DEFAULT_MAX_RESPONSE_BYTES},
+    types::{BridgeResponseV1, IDKitResult, ResponseItem},
+    Signal,
+};
+use serde::Deserialize;
+use std::str::FromStr;
+use world_id_primitives::rp::RpId;
+
+/// Default Developer Portal verify endpoint. The RP ID is appended as the
+/// last path segment.
+pub const DEFAULT_VERIFY_ENDPOINT: &str = "https://developer.worldcoin.org/api/v4/verify";
+
+/// Error body returned by the Developer Portal on a rejected verification
+#[derive(Debug, Default, Deserialize)]
+struct VerifyErrorResponse {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    detail: Option<String>,
+}
+
+/// Verifies an `IDKitResult` with the Developer Portal.
+///
+/// World ID 4.0 verifies against `/api/v4/verify/{rp_id}`, which takes the
+/// whole result rather than the single proof, `app_id`, action and signal of
+/// the World ID 3.0 `/api/v2/verify/{app_id}` endpoint: the result already
+/// carries the action and each response's signal hash, and RPs are keyed by
+/// their RP ID. Legacy proofs can still be shaped for the v2 endpoint with
+/// [`BridgeResponseV1::to_portal_verify_body`].
+///
+/// # Errors
+///
+/// See [`verify_proof_with_client`].
+pub async fn verify_proof(result: &IDKitResult, rp_id: &str) -> Result<()> {
+    verify_proof_with_endpoint(result, rp_id, None).await
+}
+
+/// Verifies an `IDKitResult` against a custom verify endpoint.
+///
+/// `endpoint` replaces [`DEFAULT_VERIFY_ENDPOINT`]; the RP ID is still
+/// appended to it. Useful for staging portals and self-hosted proxies.
+/// Requests go through the shared client, with the default
+/// [`HttpTimeouts`](crate::http::HttpTimeouts).
+///
+/// # Errors
+///
+/// See [`verify_proof_with_client`].
+pub async fn verify_proof_with_endpoint(
+    result: &IDKitResult,
+    rp_id: &str,
+    endpoint: Option<&str>,
+) -> Result<()> {
+    verify_proof_with_client(result, rp_id, endpoint, &default_client()?).await
+}
+
+/// Verifies an `IDKitResult` using the given HTTP client.
+///
+/// Pass a client configured with a timeout to bound how long the portal may take.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidConfiguration` if `rp_id` is not a valid RP ID,
+/// `Error::InvalidProof` if any response carries a placeholder proof,
+/// which is rejected before contacting the portal, or with the portal's detail
+/// string if the proof is rejected. Returns `Error::Timeout` if the client timed out, or another error if
+/// the request could not be sent.
+pub async fn verify_proof_with_client(
+    result: &IDKitResult,
+    rp_id: &str,
+    endpoint: Option<&str>,
+    client: &reqwest::Client,
+) -> Result<()> {
+    // Parsing keeps anything but an `rp_` ID out of the URL path
+    let rp_id = RpId::from_str(rp_id).map_err(|_| {
+        Error::InvalidConfiguration("Invalid RP ID: must start with 'rp_'".to_string())
+    })?;
+    if result.responses.iter().any(ResponseItem::has_default_proof) {
+        return Err(Error::InvalidProof(
+            "result carries a placeholder all-zero proof".into(),
+        ));
+    }
+
+    let endpoint = endpoint.unwrap_or(DEFAULT_VERIFY_ENDPOINT);
+    let url = format!("{}/{rp_id}", endpoint.trim_end_matches('/'));
+
+    let response = client
+        .post(url)
+        .json(result)
+        .send()
+        .await
+        .map_err(map_request_error)?;
+
+    if response.status().is_success() {
+        return Ok(());
+    }
+
+    let status = response.status();
+    let body = read_capped_body(response, DEFAULT_MAX_RESPONSE_BYTES).await?;
+    let error: VerifyErrorResponse = serde_json::from_slice(&body).unwrap_or_default();
+
+    Err(Error::InvalidProof(
+        error
+            .detail
+            .or(error.code)
+            .unwrap_or_else(|| format!("verification failed with status {status}")),
+    ))
+}
+
+impl BridgeResponseV1 {
+    /// Shapes this World ID 3.0 proof as the body of the legacy Developer
+    /// Portal `/verify` endpoint
+    ///
+    /// `signal` is hashed with [`crate::crypto::hash_signal`]; an empty
+    /// signal yields the hash of `""`, as World ID 3.0 expects.
+    #[must_use]
+    pub fn to_portal_verify_body(&self, action: &str, signal: &[u8]) -> serde_json::Value {
+        serde_json::json!({
+            "nullifier_hash": self.nullifier_hash,
+            "merkle_root": self.merkle_root,
+            "proof": self.proof,
+            "verification_level": self.verification_level,
+            "action": action,
+            "signal_hash": crate::crypto::hash_signal(&Signal::from_bytes(signal)),
+        })
+    }
+}
+
+fn map_request_error(error: reqwest::Error) -> Error {
+    if error.is_timeout() {
+        Error::Timeout
+    } else {
+        Error::Http(error)
+    }
+}
+
+// Tests borrow the tokio runtime pulled in by the `bridge` feature
+#[cfg(all(test, feature = "bridge"))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const VERIFY_PATH: &str = "/api/v4/verify/rp_1234567890abcdef";
+
+    fn sample_result() -> IDKitResult {
+        serde_json::from_value(serde_json::json!({
+            "protocol_version": "4.0",
+            "nonce": "0x01",
+            "action": "my-action",
+            "responses": [],
+            "user_presence_completed": false,
+            "environment": "production",
+        }))
+        .unwrap()
+    }
+
+    /// Verifies `sample_result` against a portal that answers with `response`,
+    /// returning the outcome and the requests the portal received.
+    fn verify_against(
+        response: ResponseTemplate,
+        client: &reqwest::Client,
+    ) -> (Result<()>, Vec<wiremock::Request>) {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let portal = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path(VERIFY_PATH))
+                .respond_with(response)
+                .mount(&portal)
+                .await;
+
+            let endpoint = format!("{}/api/v4/verify", portal.uri());
+            let result = verify_proof_with_client(
+                &sample_result(),
+                "rp_1234567890abcdef",
+                Some(&endpoint),
+                client,
+            )
+            .await;
+            (result, portal.received_requests().await.unwrap_or_default())
+        })
+    }
+
+    /// Serves a Developer Portal fixture the way the portal does
+    fn portal_response(status: u16, fixture: &str) -> ResponseTemplate {
+        let content_type = if fixture == "server_error" {
+            "text/html"
+        } else {
+            "application/json"
+        };
+        ResponseTemplate::new(status)
+            .set_body_raw(crate::test_utils::portal_fixture(fixture), content_type)
+    }
+
+    #[test]
+    fn test_verify_success_posts_result_to_rp_endpoint() {
+        let (result, requests) = verify_against(
+            ResponseTemplate::new(200).set_body_raw(r#"{"success":true}"#, "application/json"),
+            &reqwest::Client::new(),
+        );
+        result.unwrap();
+
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = requests[0].body_json().unwrap();
+        assert_eq!(body["protocol_version"], "4.0");
+        assert_eq!(body["action"], "my-action");
+    }
+
+    #[test]
+    fn test_verify_rejection_maps_detail_to_invalid_proof() {
+        let (result, _) = verify_against(
+            ResponseTemplate::new(400).set_body_raw(
+                r#"{"code":"invalid_proof","detail":"The provided proof is invalid."}"#,
+                "application/json",
+            ),
+            &reqwest::Client::new(),
+        );
+
+        let err = result.unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidProof(ref detail) if detail == "The provided proof is invalid.")
+        );
+    }
+
+    #[test]
+    fn test_verify_rejection_falls_back_to_code() {
+        let (result, _) = verify_against(
+            ResponseTemplate::new(400)
+                .set_body_raw(r#"{"code":"invalid_proof"}"#, "application/json"),
+            &reqwest::Client::new(),
+        );
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(ref detail) if detail == "invalid_proof"));
+    }
+
+    #[test]
+    fn test_verify_timeout() {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let (result, _) = verify_against(
+            portal_response(200, "success").set_delay(Duration::from_secs(2)),
+            &client,
+        );
+
+        assert!(matches!(result.unwrap_err(), Error::Timeout));
+    }
+
+    #[test]
+    fn test_verify_maps_portal_fixtures() {
+        let (result, requests) =
+            verify_against(portal_response(200, "success"), &reqwest::Client::new());
+        result.unwrap();
+        assert_eq!(requests.len(), 1);
+
+        // Rejections surface the portal's detail, then its code, then the status
+        for (status, fixture, expected) in [
+            (
+                400,
+                "invalid_proof",
+                "The provided proof is invalid and it cannot be verified. Please check all inputs and try again.",
+            ),
+            (
+                400,
+                "max_verifications_reached",
+                "This person has already verified for this action.",
+            ),
+            (
+                400,
+                "invalid_merkle_root",
+                "The provided Merkle root is invalid. User appears to be unverified.",
+            ),
+            (429, "rate_limited", "rate_limit_exceeded"),
+            (
+                500,
+                "server_error",
+                "verification failed with status 500 Internal Server Error",
+            ),
+        ] {
+            let (result, requests) =
+                verify_against(portal_response(status, fixture), &reqwest::Client::new());
+            // Rejections are final; nothing is retried
+            assert_eq!(requests.len(), 1, "{fixture}");
+            let err = result.unwrap_err();
+            assert!(
+                matches!(err, Error::InvalidProof(ref detail) if detail == expected),
+                "{fixture}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_portal_verify_body_shape() {
+        let proof = BridgeResponseV1::test_fixture(crate::VerificationLevel::Document, 1);
+
+        let body = proof.to_portal_verify_body("my-action", b"hello");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "nullifier_hash": proof.nullifier_hash,
+                "merkle_root": proof.merkle_root,
+                "proof": proof.proof,
+                "verification_level": "document",
+                "action": "my-action",
+                "signal_hash": crate::crypto::hash_signal(&Signal::from_string("hello")),
+            })
+        );
+
+        // World ID 3.0 hashes an absent signal as ""
+        assert_eq!(
+            proof.to_portal_verify_body("my-action", b"")["signal_hash"],
+            "0x00c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a4"
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_placeholder_proof_before_sending() {
+        let mut result = sample_result();
+        result.responses.push(crate::ResponseItem::V3 {
+            identifier: "orb".to_string(),
+            signal_hash: "0x00".to_string(),
+            proof: format!("0x{}", "0".repeat(512)),
+            merkle_root: format!("0x{}", "0".repeat(64)),
+            nullifier: "0x0".to_string(),
+        });
+
+        // The endpoint is unroutable, so only an early rejection avoids a network error
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(verify_proof_with_endpoint(
+                &result,
+                "rp_1234567890abcdef",
+                Some("http://127.0.0.1:9/api/v4/verify"),
+            ))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidProof(ref detail) if detail.contains("placeholder")));
+    }
+
+    #[test]
+    fn test_verify_rejects_empty_rp_id() {
+        let err = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(verify_proof(&sample_result(), ""))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_verify_rejects_malformed_rp_id() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for rp_id in ["app_1234567890abcdef", "rp_12/../../v2/verify", "rp_zz?x=1"] {
+            let err = runtime
+                .block_on(verify_proof_with_endpoint(
+                    &sample_result(),
+                    rp_id,
+                    Some("http://127.0.0.1:9/api/v4/verify"),
+                ))
+                .unwrap_err();
+            assert!(matches!(err, Error::InvalidConfiguration(_)), "{rp_id}");
+        }
+    }
+}
@@ -127,6 +127,19 @@ impl CredentialRequestWasm {
     pub fn to_json(&self) -> Result<JsValue, JsValue> {
         serde_wasm_bindgen::to_value(&self.0).map_err(|e| JsValue::from_str(&e.to_string()))
     }
+
+    /// Creates a request item from the object `toJSON()` returns, or from the
+    /// JSON the Rust core and native bindings produce once parsed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object is not a valid request item
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: JsValue) -> Result<Self, JsValue> {
+        serde_wasm_bindgen::from_value(json)
+            .map(Self)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 }
 
 /// WASM wrapper for `BridgeResponseV1` (legacy proof format)
@@ -165,6 +178,32 @@ impl IDKitProof {
     pub fn to_json(&self) -> Result<JsValue, JsValue> {
         serde_wasm_bindgen::to_value(&self.0).map_err(|e| JsValue::from_str(&e.to_string()))
     }
+
+    /// Creates a proof from JSON, ignoring unknown keys
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization fails
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: JsValue) -> Result<Self, JsValue> {
+        serde_wasm_bindgen::from_value(json)
+            .map(Self)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Creates a proof from JSON, rejecting unknown keys and field aliases
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is not exactly a serialized proof
+    #[wasm_bindgen(js_name = fromJSONStrict)]
+    pub fn from_json_strict(json: JsValue) -> Result<Self, JsValue> {
+        let value: serde_json::Value =
+            serde_wasm_bindgen::from_value(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        crate::BridgeResponseV1::from_json_strict(&value.to_string())
+            .map(Self)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 }
 
 /// Bridge encryption for secure communication between client and bridge
@@ -272,6 +311,27 @@ impl RpContextWasm {
     }
 }
 
+/// Normalizes an action the way requests do
+///
+/// Sign and verify with the returned action so every party hashes the same
+/// bytes. `lowercase` also folds case.
+///
+/// # Errors
+///
+/// Returns an error if the action is empty, too long, starts or ends with
+/// whitespace, or contains control or invisible characters
+#[wasm_bindgen(js_name = normalizeAction)]
+pub fn normalize_action_wasm(action: &str, lowercase: Option<bool>) -> Result<String, JsValue> {
+    let action = if lowercase.unwrap_or(false) {
+        crate::Action::new_lowercase(action)
+    } else {
+        crate::Action::new(action)
+    };
+    action
+        .map(crate::Action::into_string)
+        .map_err(|e| JsValue::from_str(&format!("Invalid action: {e}")))
+}
+
 /// Hashes a Signal (string or `Uint8Array`) to a signal hash
 ///
 /// This is the same encoding used internally when constructing proof requests.
@@ -298,6 +358,134 @@ pub fn hash_signal_wasm(signal: JsValue) -> Result<String, JsValue> {
     Err(JsValue::from_str("Signal must be a string or Uint8Array"))
 }
 
+/// Computes the World ID 3.0 external nullifier for an action in an app
+///
+/// Returns the same 0x-prefixed hex string the on-chain contract derives.
+///
+/// # Errors
+///
+/// Returns an error if `app_id` is not a valid app ID
+#[wasm_bindgen(js_name = externalNullifier)]
+pub fn external_nullifier_wasm(app_id: &str, action: &str) -> Result<String, JsValue> {
+    let app_id = crate::AppId::new(app_id)
+        .map_err(|e| JsValue::from_str(&format!("Invalid app_id: {e}")))?;
+    Ok(crate::crypto::compute_external_nullifier(&app_id, action))
+}
+
+/// A single `{ type, value }` entry accepted by `abiEncodePacked`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AbiSignalValue {
+    #[serde(rename = "type")]
+    kind: String,
+    value: serde_json::Value,
+}
+
+fn parse_abi_address(value: &str) -> Result<alloy_primitives::Address, String> {
+    let hex = value
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("Invalid address {value:?}: expected 0x-prefixed hex"))?;
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid address {value:?}: expected 20 bytes of hex"
+        ));
+    }
+    // Mixed-case addresses carry an EIP-55 checksum, which must match
+    let is_mixed_case =
+        hex.bytes().any(|b| b.is_ascii_lowercase()) && hex.bytes().any(|b| b.is_ascii_uppercase());
+    if is_mixed_case {
+        return alloy_primitives::Address::parse_checksummed(value, None)
+            .map_err(|_| format!("Invalid address {value:?}: checksum mismatch"));
+    }
+    value
+        .parse()
+        .map_err(|e| format!("Invalid address {value:?}: {e}"))
+}
+
+fn encode_abi_value(entry: &AbiSignalValue) -> Result<Vec<u8>, String> {
+    use alloy::sol_types::SolValue;
+
+    let kind = entry.kind.as_str();
+    let as_str = || {
+        entry
+            .value
+            .as_str()
+            .ok_or_else(|| format!("Value for {kind} must be a string"))
+    };
+
+    match kind {
+        "address" => Ok(parse_abi_address(as_str()?)?.abi_encode_packed()),
+        "uint256" => {
+            let value = match &entry.value {
+                serde_json::Value::Number(n) => n
+                    .as_u64()
+                    .map(alloy_primitives::U256::from)
+                    .ok_or_else(|| {
+                        format!("Invalid uint256 {n}: expected a non-negative integer")
+                    })?,
+                serde_json::Value::String(s) => s
+                    .parse::<alloy_primitives::U256>()
+                    .map_err(|e| format!("Invalid uint256 {s:?}: {e}"))?,
+                _ => return Err("Value for uint256 must be a string or number".to_string()),
+            };
+            Ok(value.abi_encode_packed())
+        }
+        "bytes32" => {
+            let value = as_str()?;
+            let bytes = value
+                .strip_prefix("0x")
+                .and_then(|hex| hex::decode(hex).ok())
+                .filter(|bytes| bytes.len() == 32)
+                .ok_or_else(|| {
+                    format!("Invalid bytes32 {value:?}: expected 0x-prefixed 32-byte hex")
+                })?;
+            Ok(alloy_primitives::B256::from_slice(&bytes).abi_encode_packed())
+        }
+        "string" => Ok(as_str()?.abi_encode_packed()),
+        other => Err(format!(
+            "Unsupported ABI type {other:?}: expected address, uint256, bytes32, or string"
+        )),
+    }
+}
+
+fn abi_encode_packed_values(values: &[AbiSignalValue]) -> Result<Vec<u8>, String> {
+    values.iter().try_fold(Vec::new(), |mut packed, entry| {
+        packed.extend(encode_abi_value(entry)?);
+        Ok(packed)
+    })
+}
+
+/// ABI-encodes an address as a signal, matching `abi.encodePacked(address)`.
+///
+/// # Errors
+///
+/// Returns an error if `addr` is not a 0x-prefixed 20-byte hex string, or if a
+/// mixed-case address fails its EIP-55 checksum
+#[wasm_bindgen(js_name = abiEncodeAddress)]
+pub fn abi_encode_address(addr: &str) -> Result<Vec<u8>, JsValue> {
+    parse_abi_address(addr)
+        .map(|address| alloy::sol_types::SolValue::abi_encode_packed(&address))
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Packs `[{ type, value }, ...]` per Solidity's `abi.encodePacked`.
+///
+/// Supported types are `address`, `uint256` (decimal or 0x-hex string, or a
+/// safe integer), `bytes32`, and `string`. The result can be passed to
+/// `CredentialRequestWasm.withBytes`.
+///
+/// # Errors
+///
+/// Returns an error if `values` is not an array of `{ type, value }` objects,
+/// if a type is unsupported, or if a value is malformed
+#[wasm_bindgen(js_name = abiEncodePacked)]
+pub fn abi_encode_packed(
+    #[wasm_bindgen(unchecked_param_type = "AbiSignalValue[]")] values: JsValue,
+) -> Result<Vec<u8>, JsValue> {
+    let values: Vec<AbiSignalValue> = serde_wasm_bindgen::from_value(values)?;
+    abi_encode_packed_values(&values).map_err(|e| JsValue::from_str(&e))
+}
+
 #[derive(Deserialize)]
 struct ProofResponseToIDKitResultOptions {
     nonce: String,
@@ -389,7 +577,7 @@ pub fn compute_rp_signature_message_wasm(
     let nonce = nonce
         .parse::<FieldElement>()
         .map_err(|e| JsValue::from_str(&format!("Invalid nonce: {e}")))?;
-    let action = action.map(|action| FieldElement::from_arbitrary_raw_bytes(action.as_bytes()));
+    let action = action.as_deref().map(crate::crypto::encode_action_field);
 
     Ok(compute_rp_signature_msg(
         *nonce,
@@ -399,6 +587,59 @@ pub fn compute_rp_signature_message_wasm(
     ))
 }
 
+/// Which branch of a request's constraints a proof satisfied
+#[wasm_bindgen(js_name = SatisfiedBranch)]
+pub struct SatisfiedBranchWasm(crate::SatisfiedBranch);
+
+#[wasm_bindgen(js_class = SatisfiedBranch)]
+impl SatisfiedBranchWasm {
+    /// Gets the credential type that satisfied the constraints
+    #[must_use]
+    #[wasm_bindgen(getter)]
+    pub fn credential(&self) -> String {
+        self.0.credential.to_string()
+    }
+
+    /// Gets the credential's position in the request's priority order
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = priorityIndex)]
+    pub fn priority_index(&self) -> u32 {
+        self.0.priority_index
+    }
+
+    /// Returns true if the credential is the request's most preferred one
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = wasPreferred)]
+    pub fn was_preferred(&self) -> bool {
+        self.0.was_preferred
+    }
+
+    /// Converts to JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting object properties fails
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let obj = js_sys::Object::new();
+        js_sys::Reflect::set(&obj, &"credential".into(), &self.credential().into())?;
+        js_sys::Reflect::set(&obj, &"priorityIndex".into(), &self.0.priority_index.into())?;
+        js_sys::Reflect::set(&obj, &"wasPreferred".into(), &self.0.was_preferred.into())?;
+        Ok(obj.into())
+    }
+}
+
+fn satisfied_branch_of(
+    connection: &crate::BridgeConnection,
+    result: JsValue,
+) -> Result<Option<SatisfiedBranchWasm>, JsValue> {
+    let result: crate::IDKitResult = serde_wasm_bindgen::from_value(result)
+        .map_err(|e| JsValue::from_str(&format!("Invalid IDKitResult: {e}")))?;
+    Ok(connection
+        .satisfied_branch(&result)
+        .map(SatisfiedBranchWasm))
+}
+
 // RP Signature wrapper for WASM
 #[wasm_bindgen(js_name = RpSignature)]
 pub struct RpSignatureWasm {
@@ -523,6 +764,37 @@ pub fn compute_rp_signature_wasm(
     }
 }
 
+/// Asks the Developer Portal whether `action` is registered for `app_id`
+///
+/// Resolves to an `ActionInfo`. Useful for validating an action in a form
+/// before any request is created; existing actions are cached for five minutes.
+///
+/// # Example
+/// ```javascript
+/// import { precheckAction } from '@worldcoin/idkit-core'
+///
+/// const { exists } = await precheckAction("app_staging_123", "my-action")
+/// ```
+#[wasm_bindgen(js_name = precheckAction, unchecked_return_type = "Promise<ActionInfo>")]
+pub fn precheck_action(
+    app_id: String,
+    action: String,
+    endpoint: Option<String>,
+) -> js_sys::Promise {
+    future_to_promise(async move {
+        let app_id = crate::AppId::new(&app_id)
+            .map_err(|e| JsValue::from_str(&format!("Invalid app_id: {e}")))?;
+        let options = crate::portal::PrecheckOptions {
+            endpoint,
+            ..crate::portal::PrecheckOptions::default()
+        };
+        let info = crate::portal::precheck_action(&app_id, &action, &options)
+            .await
+            .map_err(|e| JsValue::from_str(&format!("Action precheck failed: {e}")))?;
+        serde_wasm_bindgen::to_value(&info).map_err(|e| JsValue::from_str(&e.to_string()))
+    })
+}
+
 /// Encodes data to base64
 #[must_use]
 #[wasm_bindgen(js_name = base64Encode)]
@@ -541,6 +813,24 @@ pub fn base64_decode(data: &str) -> Result<Vec<u8>, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Base64 decode failed: {e}")))
 }
 
+/// Encodes data to URL-safe base64 without padding
+#[must_use]
+#[wasm_bindgen(js_name = base64UrlEncode)]
+pub fn base64_url_encode(data: &[u8]) -> String {
+    crate::crypto::base64_url_encode(data)
+}
+
+/// Decodes URL-safe base64 without padding
+///
+/// # Errors
+///
+/// Returns an error if decoding fails
+#[wasm_bindgen(js_name = base64UrlDecode)]
+pub fn base64_url_decode(data: &str) -> Result<Vec<u8>, JsValue> {
+    crate::crypto::base64_url_decode(data)
+        .map_err(|e| JsValue::from_str(&format!("Base64url decode failed: {e}")))
+}
+
 /// Internal enum to store builder configuration (WASM)
 enum IDKitConfigWasm {
     Request {
@@ -556,6 +846,8 @@ enum IDKitConfigWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Vec<String>,
     },
     CreateSession {
         app_id: String,
@@ -568,6 +860,8 @@ enum IDKitConfigWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Vec<String>,
     },
     ProveSession {
         session_id: String,
@@ -581,6 +875,8 @@ enum IDKitConfigWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Vec<String>,
     },
 }
 
@@ -602,6 +898,8 @@ impl IDKitConfigWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes,
                 package_name,
                 package_version,
             } => {
@@ -618,7 +916,8 @@ impl IDKitConfigWasm {
                     package_name: package_name.clone(),
                     package_version: package_version.clone(),
                     kind: crate::bridge::RequestKind::Uniqueness {
-                        action: action.clone(),
+                        action: crate::Action::new(action)
+                            .map_err(|e| JsValue::from_str(&format!("Invalid action: {e}")))?,
                     },
                     constraints,
                     rp_context: rp_context.clone(),
@@ -629,15 +928,19 @@ impl IDKitConfigWasm {
                     bridge_url,
                     allow_legacy_proofs: *allow_legacy_proofs,
                     require_user_presence: *require_user_presence,
+                    normalize_constraints: true,
+                    compress_payload: false,
 
                     override_connect_base_url: override_connect_base_url.clone(),
                     return_to: return_to.clone(),
+                    return_to_schemes: return_to_schemes.clone(),
                     environment: environment.as_deref().map(|e| match e {
                         "staging" => crate::bridge::Environment::Staging,
                         "sandbox" => crate::bridge::Environment::Sandbox,
                         _ => crate::bridge::Environment::Production,
                     }),
                     identity_attributes: None,
+                    locale: locale.clone(),
                 })
             }
             Self::CreateSession {
@@ -649,6 +952,8 @@ impl IDKitConfigWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes,
                 package_name,
                 package_version,
             } => {
@@ -674,15 +979,19 @@ impl IDKitConfigWasm {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: *require_user_presence,
+                    normalize_constraints: true,
+                    compress_payload: false,
 
                     override_connect_base_url: override_connect_base_url.clone(),
                     return_to: return_to.clone(),
+                    return_to_schemes: return_to_schemes.clone(),
                     environment: environment.as_deref().map(|e| match e {
                         "staging" => crate::bridge::Environment::Staging,
                         "sandbox" => crate::bridge::Environment::Sandbox,
                         _ => crate::bridge::Environment::Production,
                     }),
                     identity_attributes: None,
+                    locale: locale.clone(),
                 })
             }
             Self::ProveSession {
@@ -695,6 +1004,8 @@ impl IDKitConfigWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes,
                 package_name,
                 package_version,
             } => {
@@ -722,15 +1033,19 @@ impl IDKitConfigWasm {
                     bridge_url,
                     allow_legacy_proofs: false,
                     require_user_presence: *require_user_presence,
+                    normalize_constraints: true,
+                    compress_payload: false,
 
                     override_connect_base_url: override_connect_base_url.clone(),
                     return_to: return_to.clone(),
+                    return_to_schemes: return_to_schemes.clone(),
                     environment: environment.as_deref().map(|e| match e {
                         "staging" => crate::bridge::Environment::Staging,
                         "sandbox" => crate::bridge::Environment::Sandbox,
                         _ => crate::bridge::Environment::Production,
                     }),
                     identity_attributes: None,
+                    locale: locale.clone(),
                 })
             }
         }
@@ -769,6 +1084,24 @@ fn validate_v1_preset_support(preset: &Preset) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Checks a constraint tree from JS before any request is built, so an
+/// invalid tree, e.g. one that both requires and waives user presence (face
+/// auth) for a credential type, is rejected naming that credential type
+/// instead of failing later
+fn validate_constraints(constraints: &ConstraintNode) -> Result<(), String> {
+    constraints
+        .validate()
+        .map_err(|e| format!("Invalid constraints: {e}"))
+}
+
+/// Deserializes and validates a constraint tree passed from JS
+fn parse_constraints(constraints_json: JsValue) -> Result<ConstraintNode, JsValue> {
+    let constraints: ConstraintNode = serde_wasm_bindgen::from_value(constraints_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid constraints: {e}")))?;
+    validate_constraints(&constraints).map_err(|e| JsValue::from_str(&e))?;
+    Ok(constraints)
+}
+
 /// Unified builder for creating `IDKit` requests and sessions (WASM)
 #[wasm_bindgen(js_name = IDKitBuilder)]
 pub struct IDKitBuilderWasm {
@@ -794,6 +1127,8 @@ impl IDKitBuilderWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Option<Vec<String>>,
     ) -> Self {
         Self {
             config: IDKitConfigWasm::Request {
@@ -809,6 +1144,8 @@ impl IDKitBuilderWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes: return_to_schemes.unwrap_or_default(),
             },
         }
     }
@@ -827,6 +1164,8 @@ impl IDKitBuilderWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Option<Vec<String>>,
     ) -> Self {
         Self {
             config: IDKitConfigWasm::CreateSession {
@@ -840,6 +1179,8 @@ impl IDKitBuilderWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes: return_to_schemes.unwrap_or_default(),
             },
         }
     }
@@ -859,6 +1200,8 @@ impl IDKitBuilderWasm {
         override_connect_base_url: Option<String>,
         return_to: Option<String>,
         environment: Option<String>,
+        locale: Option<String>,
+        return_to_schemes: Option<Vec<String>>,
     ) -> Self {
         Self {
             config: IDKitConfigWasm::ProveSession {
@@ -873,6 +1216,8 @@ impl IDKitBuilderWasm {
                 override_connect_base_url,
                 return_to,
                 environment,
+                locale,
+                return_to_schemes: return_to_schemes.unwrap_or_default(),
             },
         }
     }
@@ -887,8 +1232,7 @@ impl IDKitBuilderWasm {
     /// Returns an error if constraints are invalid or payload construction fails.
     #[wasm_bindgen(js_name = nativePayload)]
     pub fn native_payload(self, constraints_json: JsValue) -> Result<JsValue, JsValue> {
-        let constraints: ConstraintNode = serde_wasm_bindgen::from_value(constraints_json)
-            .map_err(|e| JsValue::from_str(&format!("Invalid constraints: {e}")))?;
+        let constraints = parse_constraints(constraints_json)?;
 
         let params = self.config.to_params(Some(constraints))?;
 
@@ -1040,13 +1384,12 @@ impl IDKitBuilderWasm {
     pub fn constraints(self, constraints_json: JsValue) -> js_sys::Promise {
         let config = self.config;
         future_to_promise(async move {
-            let constraints: ConstraintNode = serde_wasm_bindgen::from_value(constraints_json)
-                .map_err(|e| JsValue::from_str(&format!("Invalid constraints: {e}")))?;
+            let constraints = parse_constraints(constraints_json)?;
 
             let params = config.to_params(Some(constraints))?;
             let connection = crate::bridge::BridgeConnection::create(params)
                 .await
-                .map_err(|e| JsValue::from_str(&format!("Failed: {e}")))?;
+                .map_err(|e| bridge_error_to_js("Failed", &e))?;
 
             Ok(JsValue::from(IDKitRequest {
                 inner: Rc::new(connection),
@@ -1064,7 +1407,7 @@ impl IDKitBuilderWasm {
             let params = config.to_params_from_preset(preset)?;
             let connection = crate::bridge::BridgeConnection::create(params)
                 .await
-                .map_err(|e| JsValue::from_str(&format!("Failed: {e}")))?;
+                .map_err(|e| bridge_error_to_js("Failed", &e))?;
 
             Ok(JsValue::from(IDKitRequest {
                 inner: Rc::new(connection),
@@ -1077,13 +1420,12 @@ impl IDKitBuilderWasm {
     pub fn constraints_with_invite_code(self, constraints_json: JsValue) -> js_sys::Promise {
         let config = self.config;
         future_to_promise(async move {
-            let constraints: ConstraintNode = serde_wasm_bindgen::from_value(constraints_json)
-                .map_err(|e| JsValue::from_str(&format!("Invalid constraints: {e}")))?;
+            let constraints = parse_constraints(constraints_json)?;
 
             let params = config.to_params(Some(constraints))?;
             let connection = crate::bridge::BridgeConnection::create_for_invite_code(params)
                 .await
-                .map_err(|e| JsValue::from_str(&format!("Failed: {e}")))?;
+                .map_err(|e| bridge_error_to_js("Failed", &e))?;
 
             Ok(JsValue::from(IDKitInviteCodeRequest {
                 inner: Rc::new(connection),
@@ -1102,7 +1444,7 @@ impl IDKitBuilderWasm {
             let params = config.to_params_from_preset(preset)?;
             let connection = crate::bridge::BridgeConnection::create_for_invite_code(params)
                 .await
-                .map_err(|e| JsValue::from_str(&format!("Failed: {e}")))?;
+                .map_err(|e| bridge_error_to_js("Failed", &e))?;
 
             Ok(JsValue::from(IDKitInviteCodeRequest {
                 inner: Rc::new(connection),
@@ -1128,6 +1470,8 @@ pub fn request(
     override_connect_base_url: Option<String>,
     return_to: Option<String>,
     environment: Option<String>,
+    locale: Option<String>,
+    return_to_schemes: Option<Vec<String>>,
 ) -> IDKitBuilderWasm {
     IDKitBuilderWasm::new(
         app_id,
@@ -1142,6 +1486,8 @@ pub fn request(
         override_connect_base_url,
         return_to,
         environment,
+        locale,
+        return_to_schemes,
     )
 }
 
@@ -1163,6 +1509,8 @@ pub fn create_session(
     override_connect_base_url: Option<String>,
     return_to: Option<String>,
     environment: Option<String>,
+    locale: Option<String>,
+    return_to_schemes: Option<Vec<String>>,
 ) -> IDKitBuilderWasm {
     IDKitBuilderWasm::for_create_session(
         app_id,
@@ -1175,6 +1523,8 @@ pub fn create_session(
         override_connect_base_url,
         return_to,
         environment,
+        locale,
+        return_to_schemes,
     )
 }
 
@@ -1197,6 +1547,8 @@ pub fn prove_session(
     override_connect_base_url: Option<String>,
     return_to: Option<String>,
     environment: Option<String>,
+    locale: Option<String>,
+    return_to_schemes: Option<Vec<String>>,
 ) -> IDKitBuilderWasm {
     IDKitBuilderWasm::for_prove_session(
         session_id,
@@ -1210,6 +1562,8 @@ pub fn prove_session(
         override_connect_base_url,
         return_to,
         environment,
+        locale,
+        return_to_schemes,
     )
 }
 
@@ -1236,6 +1590,17 @@ impl IDKitRequest {
         Ok(self.inner.connect_url())
     }
 
+    /// Returns the `worldapp://` deep link, which skips the browser hop when
+    /// World App is installed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request state is invalid.
+    #[wasm_bindgen(js_name = deepLinkUrl)]
+    pub fn deep_link_url(&self) -> Result<String, JsValue> {
+        Ok(self.inner.deep_link_url())
+    }
+
     /// Returns the request ID for this request
     ///
     /// # Errors
@@ -1261,12 +1626,61 @@ impl IDKitRequest {
     pub fn poll_for_status(&self) -> js_sys::Promise {
         let inner = self.inner.clone();
 
+        future_to_promise(async move { poll_shared_inner(inner).await })
+    }
+
+    /// Polls the bridge for the current status, wrapped in an `IDKitStatus`.
+    ///
+    /// Same poll as `pollForStatus`; the returned class adds helpers such as
+    /// `isTerminal()` and `proof()`, and `toJSON()` gives back the plain object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response is invalid
+    #[wasm_bindgen(js_name = pollForStatusTyped, unchecked_return_type = "Promise<IDKitStatus>")]
+    pub fn poll_for_status_typed(&self) -> js_sys::Promise {
+        let inner = self.inner.clone();
+
+        future_to_promise(
+            async move { Ok(IDKitStatus::new(poll_status_json(&inner).await?).into()) },
+        )
+    }
+
+    /// Acknowledges the completed response so the bridge can delete it right away.
+    ///
+    /// Call after a `"confirmed"` status. Failures are safe to ignore; the bridge
+    /// expires the response on its own TTL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge rejects the acknowledgment
+    #[wasm_bindgen(js_name = acknowledge)]
+    pub fn acknowledge(&self) -> js_sys::Promise {
+        let inner = self.inner.clone();
+
         future_to_promise(async move {
-            let status = poll_shared_inner(inner).await?;
-            status_to_js_value(&status)
+            inner
+                .acknowledge()
+                .await
+                .map_err(|e| bridge_error_to_js("Acknowledge failed", &e))?;
+            Ok(JsValue::UNDEFINED)
         })
     }
 
+    /// Returns which branch of the request's constraints a confirmed
+    /// `result` satisfied, or `undefined` when there is none to report
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `result` is not a valid `IDKitResult`
+    #[wasm_bindgen(js_name = satisfiedBranch)]
+    pub fn satisfied_branch(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IDKitResult")] result: JsValue,
+    ) -> Result<Option<SatisfiedBranchWasm>, JsValue> {
+        satisfied_branch_of(&self.inner, result)
+    }
+
     /// Returns the latest debug report snapshot for this request.
     ///
     /// # Errors
@@ -1311,6 +1725,18 @@ impl IDKitInviteCodeRequest {
         Ok(self.inner.connect_url())
     }
 
+    /// Returns the `worldapp://` deep link, which skips the browser hop when
+    /// World App is installed. Carries the same `c` and `a` params as
+    /// `connectUrl()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request state is invalid.
+    #[wasm_bindgen(js_name = deepLinkUrl)]
+    pub fn deep_link_url(&self) -> Result<String, JsValue> {
+        Ok(self.inner.deep_link_url())
+    }
+
     /// Unix-seconds expiry of the unredeemed code.
     ///
     /// # Errors
@@ -1348,12 +1774,61 @@ impl IDKitInviteCodeRequest {
     pub fn poll_for_status(&self) -> js_sys::Promise {
         let inner = self.inner.clone();
 
+        future_to_promise(async move { poll_shared_inner(inner).await })
+    }
+
+    /// Polls the bridge for the current status, wrapped in an `IDKitStatus`.
+    ///
+    /// Same poll as `pollForStatus`; the returned class adds helpers such as
+    /// `isTerminal()` and `proof()`, and `toJSON()` gives back the plain object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response is invalid
+    #[wasm_bindgen(js_name = pollForStatusTyped, unchecked_return_type = "Promise<IDKitStatus>")]
+    pub fn poll_for_status_typed(&self) -> js_sys::Promise {
+        let inner = self.inner.clone();
+
+        future_to_promise(
+            async move { Ok(IDKitStatus::new(poll_status_json(&inner).await?).into()) },
+        )
+    }
+
+    /// Acknowledges the completed response so the bridge can delete it right away.
+    ///
+    /// Call after a `"confirmed"` status. Failures are safe to ignore; the bridge
+    /// expires the response on its own TTL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bridge rejects the acknowledgment
+    #[wasm_bindgen(js_name = acknowledge)]
+    pub fn acknowledge(&self) -> js_sys::Promise {
+        let inner = self.inner.clone();
+
         future_to_promise(async move {
-            let status = poll_shared_inner(inner).await?;
-            status_to_js_value(&status)
+            inner
+                .acknowledge()
+                .await
+                .map_err(|e| bridge_error_to_js("Acknowledge failed", &e))?;
+            Ok(JsValue::UNDEFINED)
         })
     }
 
+    /// Returns which branch of the request's constraints a confirmed
+    /// `result` satisfied, or `undefined` when there is none to report
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `result` is not a valid `IDKitResult`
+    #[wasm_bindgen(js_name = satisfiedBranch)]
+    pub fn satisfied_branch(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "IDKitResult")] result: JsValue,
+    ) -> Result<Option<SatisfiedBranchWasm>, JsValue> {
+        satisfied_branch_of(&self.inner, result)
+    }
+
     /// Returns the latest debug report snapshot for this invite-code request.
     ///
     /// # Errors
@@ -1366,34 +1841,268 @@ impl IDKitInviteCodeRequest {
     }
 }
 
-async fn poll_shared_inner(request: Rc<crate::BridgeConnection>) -> Result<crate::Status, JsValue> {
-    let result = request.poll_for_status().await;
+#[cfg(feature = "qr")]
+#[wasm_bindgen]
+impl IDKitRequest {
+    /// Renders the connect URL as an SVG QR code at least `size` pixels wide
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is zero or the URL does not fit in a QR code
+    #[wasm_bindgen(js_name = connectUrlQrSvg)]
+    pub fn connect_url_qr_svg(&self, size: u32) -> Result<String, JsValue> {
+        self.inner
+            .connect_url_qr_svg(size)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Returns the connect URL's QR modules row by row, `true` for dark,
+    /// without the quiet zone
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL does not fit in a QR code
+    #[wasm_bindgen(js_name = connectUrlQrMatrix, unchecked_return_type = "boolean[][]")]
+    pub fn connect_url_qr_matrix(&self) -> Result<JsValue, JsValue> {
+        let matrix = self
+            .inner
+            .connect_url_qr_matrix()
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(serde_wasm_bindgen::to_value(&matrix)?)
+    }
+}
+
+#[cfg(feature = "qr")]
+#[wasm_bindgen]
+impl IDKitInviteCodeRequest {
+    /// Renders the connect URL as an SVG QR code at least `size` pixels wide
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is zero or the URL does not fit in a QR code
+    #[wasm_bindgen(js_name = connectUrlQrSvg)]
+    pub fn connect_url_qr_svg(&self, size: u32) -> Result<String, JsValue> {
+        self.inner
+            .connect_url_qr_svg(size)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 
-    result.map_err(|e| JsValue::from_str(&format!("Poll failed: {e}")))
+    /// Returns the connect URL's QR modules row by row, `true` for dark,
+    /// without the quiet zone
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL does not fit in a QR code
+    #[wasm_bindgen(js_name = connectUrlQrMatrix, unchecked_return_type = "boolean[][]")]
+    pub fn connect_url_qr_matrix(&self) -> Result<JsValue, JsValue> {
+        let matrix = self
+            .inner
+            .connect_url_qr_matrix()
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(serde_wasm_bindgen::to_value(&matrix)?)
+    }
 }
 
-/// Converts a Rust `Status` to a plain JS object via
-/// `serialize_maps_as_objects(true)` so JS sees `{ type: "..." }` instead of a
-/// `Map`. Pulled out so both URL/QR and invite-code wrappers can reuse it.
-fn status_to_js_value(status: &crate::Status) -> Result<JsValue, JsValue> {
-    let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+async fn poll_shared_inner(request: Rc<crate::BridgeConnection>) -> Result<JsValue, JsValue> {
+    json_to_js_value(&poll_status_json(&request).await?)
+}
 
-    let result = match status {
-        crate::Status::WaitingForConnection => {
-            serde_json::json!({"type": "waiting_for_connection"}).serialize(&ser)
+/// Polls once and returns the status in its plain-object shape, shared by
+/// `pollForStatus` and `pollForStatusTyped`.
+async fn poll_status_json(request: &crate::BridgeConnection) -> Result<serde_json::Value, JsValue> {
+    match request.poll_for_status().await {
+        Ok(status) => Ok(status_to_json(&status)),
+        // A bridge HTTP rejection ends polling as a failed status rather than
+        // a rejected promise, with the status code attached for callers.
+        Err(crate::Error::BridgeHttp { status, .. }) => Ok(serde_json::json!({
+            "type": "failed",
+            "error": app_error_code(crate::error::AppError::ConnectionFailed)?,
+            "status_code": status,
+        })),
+        Err(e @ crate::Error::ResponseDecryption { .. }) => {
+            Err(bridge_error_to_js("Poll failed", &e))
         }
-        crate::Status::AwaitingConfirmation => {
-            serde_json::json!({"type": "awaiting_confirmation"}).serialize(&ser)
+        Err(e) => Err(JsValue::from_str(&format!("Poll failed: {e}"))),
+    }
+}
+
+/// Converts a bridge error into a JS `Error`. HTTP rejections carry
+/// `statusCode`, `endpoint` and `body` properties so callers can tell a
+/// rate limit apart from a malformed payload. Decryption failures carry
+/// `requestId`, `decryptionFailure` and `retryable`.
+fn bridge_error_to_js(context: &str, error: &crate::Error) -> JsValue {
+    let js_error = js_sys::Error::new(&format!("{context}: {error}"));
+    match error {
+        crate::Error::BridgeHttp {
+            status,
+            endpoint,
+            body,
+        } => {
+            let _ = js_sys::Reflect::set(&js_error, &"statusCode".into(), &(*status).into());
+            let _ = js_sys::Reflect::set(&js_error, &"endpoint".into(), &endpoint.as_str().into());
+            let _ = js_sys::Reflect::set(&js_error, &"body".into(), &body.as_str().into());
         }
-        crate::Status::Confirmed(result) => {
-            serde_json::json!({"type": "confirmed", "result": result}).serialize(&ser)
+        crate::Error::ResponseDecryption { request_id, kind } => {
+            let _ =
+                js_sys::Reflect::set(&js_error, &"requestId".into(), &request_id.as_str().into());
+            let _ = js_sys::Reflect::set(
+                &js_error,
+                &"decryptionFailure".into(),
+                &kind.as_str().into(),
+            );
+            let _ =
+                js_sys::Reflect::set(&js_error, &"retryable".into(), &kind.is_retryable().into());
         }
-        crate::Status::Failed(error) => {
-            serde_json::json!({"type": "failed", "error": serde_json::to_value(error).unwrap_or_else(|_| serde_json::Value::String(format!("{error:?}")))}).serialize(&ser)
+        _ => {}
+    }
+    js_error.into()
+}
+
+/// Converts a Rust `Status` to the `{ type: "..." }` shape exposed to JS.
+/// Pulled out so both URL/QR and invite-code wrappers can reuse it.
+fn status_to_json(status: &crate::Status) -> serde_json::Value {
+    let mut json = serde_json::to_value(status).unwrap_or_else(
+        |e| serde_json::json!({"type": "failed", "error": format!("serialization_failed: {e}")}),
+    );
+    // Lets callers tell the user when to try again
+    if let crate::Status::Failed(error) = status {
+        if error.is_retryable() {
+            json["retry_after_secs"] = crate::INCLUSION_PENDING_RETRY_HINT.as_secs().into();
         }
-    };
+    }
+    json
+}
+
+/// Serializes JSON via `serialize_maps_as_objects(true)` so JS sees a plain
+/// object instead of a `Map`.
+fn json_to_js_value(value: &serde_json::Value) -> Result<JsValue, JsValue> {
+    let ser = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    value
+        .serialize(&ser)
+        .map_err(|e| JsValue::from_str(&format!("Serialization failed: {e}")))
+}
+
+/// Poll status as a class instance.
+///
+/// Wraps the plain object returned by `pollForStatus`, so `toJSON()` and
+/// `IDKitStatus.fromJSON()` round-trip the existing shape unchanged.
+#[wasm_bindgen]
+pub struct IDKitStatus {
+    #[wasm_bindgen(skip)]
+    json: serde_json::Value,
+}
+
+#[wasm_bindgen]
+impl IDKitStatus {
+    /// Returns the status kind: `"waiting_for_connection"`,
+    /// `"awaiting_confirmation"`, `"confirmed"` or `"failed"`
+    #[must_use]
+    pub fn kind(&self) -> String {
+        self.kind_str().to_string()
+    }
+
+    /// Returns true once polling can stop (`"confirmed"` or `"failed"`)
+    #[must_use]
+    #[wasm_bindgen(js_name = isTerminal)]
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.kind_str(), "confirmed" | "failed")
+    }
+
+    /// Returns the `IDKitResult` of a confirmed status, or `undefined`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    #[wasm_bindgen(unchecked_return_type = "IDKitResult | undefined")]
+    pub fn proof(&self) -> Result<JsValue, JsValue> {
+        self.proof_json()
+            .map_or(Ok(JsValue::UNDEFINED), json_to_js_value)
+    }
+
+    /// Returns the error code of a failed status, or `undefined`
+    #[must_use]
+    #[wasm_bindgen(js_name = errorCode, unchecked_return_type = "IDKitErrorCode | undefined")]
+    pub fn error_code(&self) -> Option<String> {
+        self.error_code_str().map(ToOwned::to_owned)
+    }
+
+    /// Returns the suggested wait in seconds before retrying a failed status,
+    /// or `undefined` if retrying is not expected to help
+    #[must_use]
+    #[wasm_bindgen(js_name = retryAfterSecs)]
+    pub fn retry_after_secs(&self) -> Option<u32> {
+        self.json["retry_after_secs"]
+            .as_u64()
+            .and_then(|secs| u32::try_from(secs).ok())
+    }
+
+    /// Returns the plain status object, identical to what `pollForStatus` resolves to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "Status")]
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        json_to_js_value(&self.json)
+    }
+
+    /// Rebuilds an `IDKitStatus` from a plain status object
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the object is not a valid status
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(
+        #[wasm_bindgen(unchecked_param_type = "Status")] value: JsValue,
+    ) -> Result<Self, JsValue> {
+        let json: serde_json::Value = serde_wasm_bindgen::from_value(value)
+            .map_err(|e| JsValue::from_str(&format!("Invalid status: {e}")))?;
+        Self::from_json_value(json).map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl IDKitStatus {
+    fn new(json: serde_json::Value) -> Self {
+        Self { json }
+    }
+
+    fn from_json_value(json: serde_json::Value) -> Result<Self, String> {
+        let status = Self::new(json);
+        match status.kind_str() {
+            "waiting_for_connection" | "awaiting_confirmation" => {}
+            "confirmed" => {
+                let result = status
+                    .proof_json()
+                    .ok_or("Invalid status: confirmed status is missing result")?;
+                serde_json::from_value::<crate::IDKitResult>(result.clone())
+                    .map_err(|e| format!("Invalid status: {e}"))?;
+            }
+            "failed" => {
+                // Unknown codes are kept as-is, matching `AppError::Other`
+                status
+                    .error_code_str()
+                    .ok_or("Invalid status: failed status is missing error")?;
+            }
+            other => return Err(format!("Invalid status: unknown type \"{other}\"")),
+        }
+        Ok(status)
+    }
+
+    fn kind_str(&self) -> &str {
+        self.json["type"].as_str().unwrap_or_default()
+    }
+
+    fn proof_json(&self) -> Option<&serde_json::Value> {
+        (self.kind_str() == "confirmed")
+            .then(|| self.json.get("result"))
+            .flatten()
+    }
 
-    result.map_err(|e| JsValue::from_str(&format!("Serialization failed: {e}")))
+    fn error_code_str(&self) -> Option<&str> {
+        (self.kind_str() == "failed")
+            .then(|| self.json["error"].as_str())
+            .flatten()
+    }
 }
 
 fn debug_report_to_js_value(report: &crate::bridge::BridgeDebugReport) -> Result<JsValue, JsValue> {
@@ -1406,6 +2115,12 @@ fn debug_report_to_js_value(report: &crate::bridge::BridgeDebugReport) -> Result
 // TypeScript type definitions
 #[wasm_bindgen(typescript_custom_section)]
 const TS_TYPES: &str = r#"
+export type AbiSignalValue =
+    | { type: "address"; value: string }
+    | { type: "uint256"; value: string | number }
+    | { type: "bytes32"; value: string }
+    | { type: "string"; value: string };
+
 export type CredentialType = "proof_of_human" | "selfie" | "passport" | "mnc";
 
 export interface CredentialRequestType {
@@ -1414,13 +2129,17 @@ export interface CredentialRequestType {
     signal?: string | Uint8Array;
     genesis_issued_at_min?: number;
     expires_at_min?: number;
+    /** Requires a user presence check for the whole request */
+    require_user_presence?: boolean;
 }
 
 export type ConstraintNode =
     | CredentialRequestType
     | { any: ConstraintNode[] }
     | { all: ConstraintNode[] }
-    | { enumerate: ConstraintNode[] };
+    | { enumerate: ConstraintNode[] }
+    | { not: ConstraintNode }
+    | { at_least: number; of: ConstraintNode[] };
 
 /**
  * Hashes a Signal (string or Uint8Array) to a signal hash.
@@ -1593,8 +2312,12 @@ export interface IDKitSessionConfig {
     bridge_url?: string;
     /** Optional deep-link callback URL appended as `return_to` on the connector URL */
     return_to?: string;
+    /** Custom URL schemes `return_to` may use besides https, e.g. "myapp" */
+    return_to_schemes?: string[];
     /** Require World App to perform a user-presence check before verification. Defaults to false. */
     require_user_presence?: boolean;
+    /** Optional language hint for World App, such as "en" or "en-US" */
+    locale?: string;
 }
 
 /** RpContext for proof requests */
@@ -1611,7 +2334,7 @@ export interface RpContext {
     signature: string;
 }
 
-/** Error codes from World App (mirrors Rust AppError) */
+/** Error codes from World App (mirrors Rust AppError); unknown codes pass through as-is */
 export type IDKitErrorCode =
     | "user_rejected"
     | "verification_rejected"
@@ -1637,14 +2360,22 @@ export type IDKitErrorCode =
     | "invalid_timestamp"
     | "rp_signature_expired"
     | "identity_attributes_not_matched"
-    | "generic_error";
+    | "request_not_found"
+    | "rate_limited"
+    | "generic_error"
+    | (string & {});
 
-/** Status returned from pollForStatus() */
+/** Status returned from pollForStatus(), and by IDKitStatus.toJSON() */
 export type Status =
     | { type: "waiting_for_connection" }
     | { type: "awaiting_confirmation" }
     | { type: "confirmed"; result: IDKitResult }
-    | { type: "failed"; error: IDKitErrorCode };
+    | {
+        type: "failed";
+        error: IDKitErrorCode;
+        status_code?: number;
+        retry_after_secs?: number;
+      };
 "#;
 
 // Export preset types
@@ -1743,6 +2474,16 @@ export function identityCheck(params: {
 "#;
 
 // Export RP signature types
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ACTION_INFO: &str = r#"
+/** Developer Portal metadata for an action, returned by `precheckAction` */
+export interface ActionInfo {
+    exists: boolean;
+    max_verifications?: number;
+    external_nullifier?: string;
+}
+"#;
+
 #[wasm_bindgen(typescript_custom_section)]
 const TS_RP_SIGNATURE: &str = r#"
 export interface RpSignature {
@@ -1795,7 +2536,9 @@ export function createSession(
     require_user_presence?: boolean,
     override_connect_base_url?: string,
     return_to?: string,
-    environment?: string
+    environment?: string,
+    locale?: string,
+    return_to_schemes?: string[]
 ): IDKitBuilder;
 
 /**
@@ -1816,14 +2559,22 @@ export function proveSession(
     require_user_presence?: boolean,
     override_connect_base_url?: string,
     return_to?: string,
-    environment?: string
+    environment?: string,
+    locale?: string,
+    return_to_schemes?: string[]
 ): IDKitBuilder;
 "#;
 
 #[cfg(test)]
 mod tests {
-    use super::{validate_v1_preset_support, IDKitConfigWasm};
-    use crate::{types::IdentityAttribute, ConstraintNode, Preset, RpContext};
+    use super::{
+        abi_encode_packed_values, parse_abi_address, status_to_json, validate_constraints,
+        validate_v1_preset_support, AbiSignalValue, IDKitConfigWasm, IDKitStatus,
+    };
+    use crate::{
+        types::IdentityAttribute, ConstraintNode, CredentialRequest, CredentialType, Preset,
+        RpContext,
+    };
 
     fn sample_rp_context() -> RpContext {
         RpContext::new("rp_123456789abcdef0", "0x01", 1, 2, "0x1234").expect("valid rp_context")
@@ -1843,6 +2594,8 @@ mod tests {
             override_connect_base_url: None,
             return_to: None,
             environment: None,
+            locale: None,
+            return_to_schemes: Vec::new(),
         }
     }
 
@@ -1861,6 +2614,8 @@ mod tests {
             override_connect_base_url: None,
             return_to: Some("idkit://callback?step=request".to_string()),
             environment: None,
+            locale: None,
+            return_to_schemes: Vec::new(),
         };
 
         let params = config
@@ -1873,6 +2628,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn request_params_preserve_locale() {
+        let config = IDKitConfigWasm::Request {
+            app_id: "app_staging_test".to_string(),
+            package_name: "idkit_js_core".to_string(),
+            package_version: env!("CARGO_PKG_VERSION").to_string(),
+            action: "test-action".to_string(),
+            rp_context: sample_rp_context(),
+            action_description: None,
+            bridge_url: None,
+            allow_legacy_proofs: false,
+            require_user_presence: false,
+            override_connect_base_url: None,
+            return_to: None,
+            environment: None,
+            locale: Some("pt-BR".to_string()),
+            return_to_schemes: Vec::new(),
+        };
+
+        let params = config
+            .to_params(Some(ConstraintNode::Any { any: Vec::new() }))
+            .expect("request params");
+
+        assert_eq!(params.locale.as_deref(), Some("pt-BR"));
+    }
+
     #[test]
     fn request_params_preserve_user_presence_requirement() {
         let config = IDKitConfigWasm::Request {
@@ -1888,6 +2669,8 @@ mod tests {
             override_connect_base_url: None,
             return_to: None,
             environment: None,
+            locale: None,
+            return_to_schemes: Vec::new(),
         };
 
         let params = config
@@ -1910,6 +2693,8 @@ mod tests {
             override_connect_base_url: None,
             return_to: Some("idkit://callback?step=create".to_string()),
             environment: None,
+            locale: None,
+            return_to_schemes: Vec::new(),
         };
 
         let params = config
@@ -1922,6 +2707,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn constraints_are_validated_before_creating_requests() {
+        let face_auth = |required| {
+            ConstraintNode::item(
+                CredentialRequest::new(CredentialType::ProofOfHuman, None)
+                    .with_user_presence(required),
+            )
+        };
+
+        let err = validate_constraints(&ConstraintNode::any(vec![
+            face_auth(true),
+            face_auth(false),
+        ]))
+        .expect_err("conflicting face auth should be rejected");
+        assert!(err.starts_with("Invalid constraints: "));
+        assert!(err.contains("require_user_presence"));
+        assert!(err.contains("proof_of_human"));
+
+        assert!(validate_constraints(&ConstraintNode::Any { any: Vec::new() }).is_err());
+        validate_constraints(&ConstraintNode::any(vec![face_auth(true)]))
+            .expect("consistent face auth should be accepted");
+    }
+
     #[test]
     fn prove_session_params_preserve_return_to() {
         let config = IDKitConfigWasm::ProveSession {
@@ -1936,6 +2744,8 @@ mod tests {
             override_connect_base_url: None,
             return_to: Some("idkit://callback?step=prove".to_string()),
             environment: None,
+            locale: None,
+            return_to_schemes: Vec::new(),
         };
 
         let params = config
@@ -1967,4 +2777,214 @@ mod tests {
             .to_params_from_preset(preset)
             .expect("legacy preset should produce a v1 payload");
     }
+
+    fn sample_idkit_result() -> crate::IDKitResult {
+        serde_json::from_value(serde_json::json!({
+            "protocol_version": "4.0",
+            "nonce": "0x01",
+            "action": "my-action",
+            "responses": [],
+            "user_presence_completed": false,
+            "environment": "production",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn idkit_status_helpers_for_each_kind() {
+        let pending = [
+            crate::Status::WaitingForConnection,
+            crate::Status::AwaitingConfirmation,
+        ];
+        for status in pending {
+            let typed = IDKitStatus::new(status_to_json(&status));
+            assert!(!typed.is_terminal());
+            assert!(typed.proof_json().is_none());
+            assert!(typed.error_code().is_none());
+        }
+
+        let confirmed = IDKitStatus::new(status_to_json(&crate::Status::Confirmed(
+            sample_idkit_result(),
+        )));
+        assert_eq!(confirmed.kind(), "confirmed");
+        assert!(confirmed.is_terminal());
+        assert_eq!(confirmed.proof_json().unwrap()["action"], "my-action");
+        assert!(confirmed.error_code().is_none());
+
+        let failed = IDKitStatus::new(status_to_json(&crate::Status::Failed(
+            crate::error::AppError::UserRejected,
+        )));
+        assert_eq!(failed.kind(), "failed");
+        assert!(failed.is_terminal());
+        assert!(failed.proof_json().is_none());
+        assert_eq!(failed.error_code().as_deref(), Some("user_rejected"));
+    }
+
+    #[test]
+    fn idkit_status_json_round_trips_plain_shape() {
+        assert_eq!(
+            status_to_json(&crate::Status::WaitingForConnection),
+            serde_json::json!({"type": "waiting_for_connection"})
+        );
+        assert_eq!(
+            status_to_json(&crate::Status::Failed(
+                crate::error::AppError::ConnectionFailed
+            )),
+            serde_json::json!({"type": "failed", "error": "connection_failed"})
+        );
+
+        let statuses = [
+            crate::Status::WaitingForConnection,
+            crate::Status::AwaitingConfirmation,
+            crate::Status::Confirmed(sample_idkit_result()),
+            crate::Status::Failed(crate::error::AppError::UserRejected),
+        ];
+        for status in statuses {
+            let json = status_to_json(&status);
+            let typed = IDKitStatus::from_json_value(json.clone()).expect("valid status");
+            assert_eq!(typed.json, json);
+        }
+
+        // Bridge HTTP failures carry an extra status code, which must survive too
+        let http_failure =
+            serde_json::json!({"type": "failed", "error": "connection_failed", "status_code": 503});
+        let typed = IDKitStatus::from_json_value(http_failure.clone()).unwrap();
+        assert_eq!(typed.json, http_failure);
+        assert_eq!(typed.retry_after_secs(), None);
+    }
+
+    #[test]
+    fn idkit_status_keeps_unknown_error_codes() {
+        let json = status_to_json(&crate::Status::Failed(crate::error::AppError::Other(
+            "verification_cancelled_v2".to_string(),
+        )));
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "failed", "error": "verification_cancelled_v2"})
+        );
+
+        let typed = IDKitStatus::from_json_value(json).unwrap();
+        assert_eq!(
+            typed.error_code().as_deref(),
+            Some("verification_cancelled_v2")
+        );
+    }
+
+    #[test]
+    fn idkit_status_surfaces_inclusion_pending_retry_hint() {
+        let json = status_to_json(&crate::Status::Failed(
+            crate::error::AppError::InclusionProofPending,
+        ));
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "failed",
+                "error": "inclusion_proof_pending",
+                "retry_after_secs": 1800,
+            })
+        );
+
+        let typed = IDKitStatus::from_json_value(json).unwrap();
+        assert_eq!(typed.retry_after_secs(), Some(1800));
+    }
+
+    #[test]
+    fn idkit_status_from_json_rejects_invalid_shapes() {
+        for invalid in [
+            serde_json::json!({}),
+            serde_json::json!({"type": "pending"}),
+            serde_json::json!({"type": "confirmed"}),
+            serde_json::json!({"type": "confirmed", "result": {"action": "x"}}),
+            serde_json::json!({"type": "failed"}),
+            serde_json::json!({"type": "failed", "error": 7}),
+        ] {
+            assert!(
+                IDKitStatus::from_json_value(invalid.clone()).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    fn abi_values(json: serde_json::Value) -> Vec<AbiSignalValue> {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn abi_encode_packed_matches_solidity_packing() {
+        use alloy::sol_types::SolValue;
+
+        let address: alloy_primitives::Address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+            .parse()
+            .unwrap();
+        let amount = alloy_primitives::U256::from(1_000_000u64);
+        let tag = alloy_primitives::B256::repeat_byte(0xab);
+        let expected = (address, amount, tag, "hello".to_string()).abi_encode_packed();
+
+        let packed = abi_encode_packed_values(&abi_values(serde_json::json!([
+            { "type": "address", "value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed" },
+            { "type": "uint256", "value": "1000000" },
+            { "type": "bytes32", "value": format!("0x{}", "ab".repeat(32)) },
+            { "type": "string", "value": "hello" },
+        ])))
+        .unwrap();
+
+        assert_eq!(packed, expected);
+        assert_eq!(packed.len(), 20 + 32 + 32 + 5);
+    }
+
+    #[test]
+    fn abi_encode_packed_accepts_hex_and_numeric_uint256() {
+        let hex = abi_encode_packed_values(&abi_values(serde_json::json!([
+            { "type": "uint256", "value": "0xff" },
+        ])))
+        .unwrap();
+        let number = abi_encode_packed_values(&abi_values(serde_json::json!([
+            { "type": "uint256", "value": 255 },
+        ])))
+        .unwrap();
+
+        assert_eq!(hex, number);
+        assert_eq!(hex[31], 0xff);
+    }
+
+    #[test]
+    fn abi_encode_packed_rejects_invalid_values() {
+        for (entry, expected) in [
+            (
+                serde_json::json!({ "type": "int8", "value": "1" }),
+                "Unsupported ABI type",
+            ),
+            (
+                serde_json::json!({ "type": "uint256", "value": -1 }),
+                "Invalid uint256",
+            ),
+            (
+                serde_json::json!({ "type": "bytes32", "value": "0xabcd" }),
+                "Invalid bytes32",
+            ),
+            (
+                serde_json::json!({ "type": "string", "value": 1 }),
+                "must be a string",
+            ),
+        ] {
+            let err =
+                abi_encode_packed_values(&abi_values(serde_json::json!([entry]))).unwrap_err();
+            assert!(err.contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn parse_abi_address_validates_format_and_checksum() {
+        assert!(parse_abi_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_ok());
+        assert!(parse_abi_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_ok());
+
+        for invalid in [
+            "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea",
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beazz",
+            "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        ] {
+            assert!(parse_abi_address(invalid).is_err(), "{invalid}");
+        }
+    }
 }
@@ -0,0 +1,156 @@
+#ifndef IDKIT_H
+#define IDKIT_H
+
+/* Generated by cbindgen from rust/ffi-c. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result codes returned by the fallible functions
+typedef enum IdkitCode {
+  // The call succeeded
+  IDKIT_CODE_OK = 0,
+  // A required pointer was null, or a string was not valid UTF-8 or JSON
+  IDKIT_CODE_INVALID_ARGUMENT = 1,
+  // The configuration was rejected
+  IDKIT_CODE_INVALID_CONFIGURATION = 2,
+  // The bridge or the Developer Portal could not be reached, or answered
+  // with an unexpected response
+  IDKIT_CODE_NETWORK = 3,
+  // World App reported a failure; the last error message is its error code
+  IDKIT_CODE_APP_ERROR = 4,
+  // The wait ended before World App responded
+  IDKIT_CODE_TIMEOUT = 5,
+  // The Developer Portal rejected the proof
+  IDKIT_CODE_INVALID_PROOF = 6,
+  // A panic was caught at the boundary
+  IDKIT_CODE_PANIC = 7,
+  // World App's response could not be decrypted; the last error message
+  // names the failure kind and how to recover
+  IDKIT_CODE_RESPONSE_DECRYPTION = 8,
+  // The request's proof was already handed out and `single_consumption`
+  // is set
+  IDKIT_CODE_PROOF_ALREADY_CONSUMED = 9,
+} IdkitCode;
+
+// Opaque handle to a verification request
+typedef struct IdkitSession IdkitSession;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a verification request from a JSON configuration and registers it
+// with the bridge.
+//
+// The configuration holds `app_id`, `action`, `rp_context` and `preset`
+// (e.g. `{"type": "OrbLegacy", "signal": null}`), plus the optional
+// `action_description`, `bridge_url`, `allow_legacy_proofs`,
+// `require_user_presence`, `return_to`, `return_to_schemes` (the custom
+// app schemes `return_to` may use besides `https`), `environment`, the
+// `connect_timeout_ms` and `request_timeout_ms` bridge HTTP timeouts
+// (10 s and 30 s by default), `cancel_on_drop`, which deletes an
+// unfinished request from the bridge in [`idkit_session_free`],
+// `single_consumption`, which hands out the proof to one wait only,
+// `check_constraints`, which rejects proofs with credentials the request
+// does not accept (on by default), `session_ttl_ms`, after which waits
+// treat the request as expired (at most the bridge's 15 minutes), and
+// `locale`, a language hint for World App such as `"en-US"`.
+//
+// On success `*out_handle` receives a handle to release with
+// [`idkit_session_free`]; on failure it is left untouched.
+//
+// # Safety
+//
+// `config_json` must be null or a valid NUL-terminated string, and
+// `out_handle` must be null or valid for writes.
+enum IdkitCode idkit_session_create(const char *config_json, struct IdkitSession **out_handle);
+
+// Releases a handle from [`idkit_session_create`]. Passing null is a no-op.
+//
+// # Safety
+//
+// `handle` must be null or a live handle, and must not be used afterwards.
+void idkit_session_free(struct IdkitSession *handle);
+
+// Copies the connect URL into `buf` as a NUL-terminated string.
+//
+// Returns the URL length in bytes, excluding the terminator. The URL is only
+// written when `len` is larger than that, so passing a null `buf` first
+// reports the size to allocate. Returns -1 on error.
+//
+// # Safety
+//
+// `handle` must be null or a live handle, and `buf` must be null or valid for
+// `len` bytes of writes.
+ptrdiff_t idkit_session_connect_url(const struct IdkitSession *handle, char *buf, size_t len);
+
+// Polls the bridge once and writes the status as JSON to `*out_status_json`.
+//
+// The status has a `type` of `waiting_for_connection`, `awaiting_confirmation`,
+// `confirmed` (with `result`), `failed` (with the World App `error` code, and
+// `retry_after_secs` when retrying later may succeed) or `networking_error`
+// (with `error`; polling again may succeed).
+//
+// # Safety
+//
+// `handle` must be null or a live handle, and `out_status_json` must be null
+// or valid for writes.
+enum IdkitCode idkit_session_poll(const struct IdkitSession *handle, char **out_status_json);
+
+// Polls until World App responds or `timeout_ms` passes, then writes the
+// proof as JSON to `*out_proof_json`.
+//
+// Returns [`IdkitCode::AppError`] with the World App error code as the last
+// error message if the request failed, [`IdkitCode::Timeout`], or
+// [`IdkitCode::InvalidConfiguration`] if `timeout_ms` is zero.
+//
+// # Safety
+//
+// `handle` must be null or a live handle, and `out_proof_json` must be null
+// or valid for writes.
+enum IdkitCode idkit_session_wait(const struct IdkitSession *handle,
+                                  uint64_t timeout_ms,
+                                  char **out_proof_json);
+
+// Verifies a proof JSON (as written by [`idkit_session_wait`]) with the
+// Developer Portal.
+//
+// Takes `rp_id` and `endpoint` rather than an `app_id` and action: World ID
+// 4.0 proofs are verified at `/api/v4/verify/{rp_id}`, which keys relying
+// parties by RP ID and reads the action from the proof itself, unlike the
+// World ID 3.0 `/api/v2/verify/{app_id}` endpoint. `endpoint` replaces the
+// production portal's verify URL, e.g. for staging, and may be null.
+// On failure, `*out_err` receives the error message when `out_err` is not null.
+//
+// # Safety
+//
+// `proof_json`, `rp_id` and `endpoint` must be null or valid NUL-terminated
+// strings, and `out_err` must be null or valid for writes.
+enum IdkitCode idkit_verify_proof(const char *proof_json,
+                                  const char *rp_id,
+                                  const char *endpoint,
+                                  char **out_err);
+
+// Releases a string returned by this library. Passing null is a no-op.
+//
+// # Safety
+//
+// `s` must be null or a string from this library, and must not be used
+// afterwards.
+void idkit_string_free(char *s);
+
+// Returns the message of the last failed call on this thread, or null.
+//
+// The string is owned by the library and stays valid until the next call
+// into the library on the same thread; do not free it.
+const char *idkit_last_error_message(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* IDKIT_H */
@@ -0,0 +1,769 @@
+//! # `IDKit` C ABI
+//!
+//! A small, JSON-based C API over `idkit-core` for consumers that cannot use
+//! `UniFFI`, such as C++ or .NET (P/Invoke). The matching header is committed
+//! at `include/idkit.h` and generated with cbindgen.
+//!
+//! Memory contract:
+//! - Strings returned through `out_*` pointers belong to the caller and must
+//!   be released with [`idkit_string_free`].
+//! - Session handles must be released with [`idkit_session_free`].
+//! - [`idkit_last_error_message`] returns a thread-local string owned by the
+//!   library, valid until the next call into the library on the same thread.
+//!
+//! Every exported function catches panics, which are reported as
+//! [`IdkitCode::Panic`] instead of unwinding into foreign code.
+
+#![deny(clippy::all, clippy::pedantic, clippy::nursery)]
+
+use idkit::bridge::{Environment, IDKitRequestWrapper, PollConfig, StatusWrapper};
+use idkit::error::IdkitError;
+use idkit::{IDKitRequestConfig, IDKitResult, Preset, RpContext};
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Package name reported to the bridge for request attribution
+const PACKAGE_NAME: &str = "idkit_c";
+
+/// Longest delay between polls in `idkit_session_wait`; short timeouts poll
+/// faster so the wait can still see a response
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Result codes returned by the fallible functions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdkitCode {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer was null, or a string was not valid UTF-8 or JSON
+    InvalidArgument = 1,
+    /// The configuration was rejected
+    InvalidConfiguration = 2,
+    /// The bridge or the Developer Portal could not be reached, or answered
+    /// with an unexpected response
+    Network = 3,
+    /// World App reported a failure; the last error message is its error code
+    AppError = 4,
+    /// The wait ended before World App responded
+    Timeout = 5,
+    /// The Developer Portal rejected the proof
+    InvalidProof = 6,
+    /// A panic was caught at the boundary
+    Panic = 7,
+    /// World App's response could not be decrypted; the last error message
+    /// names the failure kind and how to recover
+    ResponseDecryption = 8,
+    /// The request's proof was already handed out and `single_consumption`
+    /// is set
+    ProofAlreadyConsumed = 9,
+}
+
+/// Opaque handle to a verification request
+pub struct IdkitSession {
+    request: Arc<IDKitRequestWrapper>,
+}
+
+/// Configuration accepted by [`idkit_session_create`]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SessionConfig {
+    app_id: String,
+    action: String,
+    rp_context: RpContext,
+    preset: Preset,
+    action_description: Option<String>,
+    bridge_url: Option<String>,
+    #[serde(default)]
+    allow_legacy_proofs: bool,
+    require_user_presence: Option<bool>,
+    return_to: Option<String>,
+    return_to_schemes: Option<Vec<String>>,
+    environment: Option<Environment>,
+    connect_timeout_ms: Option<u64>,
+    request_timeout_ms: Option<u64>,
+    cancel_on_drop: Option<bool>,
+    single_consumption: Option<bool>,
+    check_constraints: Option<bool>,
+    session_ttl_ms: Option<u64>,
+    locale: Option<String>,
+}
+
+/// An error on its way out through the C ABI
+struct FfiError {
+    code: IdkitCode,
+    message: String,
+}
+
+impl FfiError {
+    fn invalid_argument(message: impl Into<String>) -> Self {
+        Self {
+            code: IdkitCode::InvalidArgument,
+            message: message.into(),
+        }
+    }
+
+    /// Records the message as the last error and returns the code.
+    fn report(self) -> IdkitCode {
+        set_last_error(&self.message);
+        self.code
+    }
+}
+
+impl From<IdkitError> for FfiError {
+    fn from(error: IdkitError) -> Self {
+        let code = match &error {
+            IdkitError::InvalidConfiguration { .. } | IdkitError::UrlError { .. } => {
+                IdkitCode::InvalidConfiguration
+            }
+            IdkitError::JsonError { .. }
+            | IdkitError::CryptoError { .. }
+            | IdkitError::Base64Error { .. }
+            | IdkitError::BridgeError { .. }
+            | IdkitError::BridgeHttpError { .. }
+            | IdkitError::UnexpectedResponse
+            | IdkitError::ConnectionFailed
+            | IdkitError::BridgeUnhealthy { .. } => IdkitCode::Network,
+            IdkitError::AppError { .. } | IdkitError::InclusionPending { .. } => {
+                IdkitCode::AppError
+            }
+            IdkitError::Timeout | IdkitError::Cancelled => IdkitCode::Timeout,
+            IdkitError::InvalidProof { .. } => IdkitCode::InvalidProof,
+            IdkitError::ResponseDecryption { .. } => IdkitCode::ResponseDecryption,
+            IdkitError::ProofAlreadyConsumed => IdkitCode::ProofAlreadyConsumed,
+        };
+        Self {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<idkit::Error> for FfiError {
+    fn from(error: idkit::Error) -> Self {
+        IdkitError::from(error).into()
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+/// Runs `f`, turning a panic into an [`IdkitCode::Panic`] error.
+fn catch<T>(f: impl FnOnce() -> Result<T, FfiError>) -> Result<T, FfiError> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let detail = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(FfiError {
+            code: IdkitCode::Panic,
+            message: format!("panic: {detail}"),
+        })
+    })
+}
+
+/// Runs `f` at the C boundary and converts its outcome into a result code.
+fn ffi_boundary(f: impl FnOnce() -> Result<(), FfiError>) -> IdkitCode {
+    match catch(f) {
+        Ok(()) => {
+            clear_last_error();
+            IdkitCode::Ok
+        }
+        Err(error) => error.report(),
+    }
+}
+
+/// Borrows a required NUL-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid NUL-terminated string that outlives `'a`.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::invalid_argument(format!("{name} is null")));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| FfiError::invalid_argument(format!("{name} is not valid UTF-8")))
+}
+
+fn require_out<T>(out: *mut T, name: &str) -> Result<(), FfiError> {
+    if out.is_null() {
+        Err(FfiError::invalid_argument(format!("{name} is null")))
+    } else {
+        Ok(())
+    }
+}
+
+/// Borrows the session behind a handle.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from [`idkit_session_create`].
+unsafe fn session<'a>(handle: *const IdkitSession) -> Result<&'a IdkitSession, FfiError> {
+    // SAFETY: live handle per the caller's contract
+    unsafe { handle.as_ref() }.ok_or_else(|| FfiError::invalid_argument("handle is null"))
+}
+
+fn into_c_string(value: String) -> Result<*mut c_char, FfiError> {
+    CString::new(value)
+        .map(CString::into_raw)
+        .map_err(|_| FfiError::invalid_argument("string contains a NUL byte"))
+}
+
+fn to_json(value: &impl serde::Serialize) -> Result<String, FfiError> {
+    serde_json::to_string(value).map_err(|e| FfiError {
+        code: IdkitCode::Network,
+        message: format!("JSON error: {e}"),
+    })
+}
+
+fn app_error_code(error: &idkit::error::AppError) -> String {
+    serde_json::to_value(error)
+        .ok()
+        .and_then(|value| value.as_str().map(ToOwned::to_owned))
+        .unwrap_or_else(|| error.to_string())
+}
+
+/// Mirrors the WASM status object, plus `networking_error` for polls that
+/// never reached the bridge.
+fn status_json(status: StatusWrapper) -> serde_json::Value {
+    match status {
+        StatusWrapper::WaitingForConnection => {
+            serde_json::json!({"type": "waiting_for_connection"})
+        }
+        StatusWrapper::AwaitingConfirmation => serde_json::json!({"type": "awaiting_confirmation"}),
+        StatusWrapper::Confirmed { result } => {
+            serde_json::json!({"type": "confirmed", "result": result})
+        }
+        StatusWrapper::Failed {
+            error,
+            retry_after_secs,
+        } => {
+            let mut json = serde_json::json!({"type": "failed", "error": app_error_code(&error)});
+            if let Some(retry_after_secs) = retry_after_secs {
+                json["retry_after_secs"] = retry_after_secs.into();
+            }
+            json
+        }
+        StatusWrapper::NetworkingError { error } => {
+            serde_json::json!({"type": "networking_error", "error": app_error_code(&error)})
+        }
+    }
+}
+
+fn create_request(config: SessionConfig) -> Result<Arc<IDKitRequestWrapper>, FfiError> {
+    let builder = idkit::bridge::request(IDKitRequestConfig {
+        app_id: config.app_id,
+        package_name: PACKAGE_NAME.to_string(),
+        package_version: env!("CARGO_PKG_VERSION").to_string(),
+        action: config.action,
+        rp_context: Arc::new(config.rp_context),
+        action_description: config.action_description,
+        bridge_url: config.bridge_url,
+        allow_legacy_proofs: config.allow_legacy_proofs,
+        require_user_presence: config.require_user_presence,
+        override_connect_base_url: None,
+        return_to: config.return_to,
+        environment: config.environment,
+        connect_url_mode: None,
+        connect_timeout_ms: config.connect_timeout_ms,
+        request_timeout_ms: config.request_timeout_ms,
+        normalize_constraints: None,
+        cancel_on_drop: config.cancel_on_drop,
+        single_consumption: config.single_consumption,
+        check_constraints: config.check_constraints,
+        session_ttl_ms: config.session_ttl_ms,
+        locale: config.locale,
+        return_to_schemes: config.return_to_schemes,
+        compress_payload: None,
+    });
+    Ok(builder.preset(config.preset)?)
+}
+
+/// Creates a verification request from a JSON configuration and registers it
+/// with the bridge.
+///
+/// The configuration holds `app_id`, `action`, `rp_context` and `preset`
+/// (e.g. `{"type": "OrbLegacy", "signal": null}`), plus the optional
+/// `action_description`, `bridge_url`, `allow_legacy_proofs`,
+/// `require_user_presence`, `return_to`, `return_to_schemes` (the custom
+/// app schemes `return_to` may use besides `https`), `environment`, the
+/// `connect_timeout_ms` and `request_timeout_ms` bridge HTTP timeouts
+/// (10 s and 30 s by default), `cancel_on_drop`, which deletes an
+/// unfinished request from the bridge in [`idkit_session_free`],
+/// `single_consumption`, which hands out the proof to one wait only,
+/// `check_constraints`, which rejects proofs with credentials the request
+/// does not accept (on by default), `session_ttl_ms`, after which waits
+/// treat the request as expired (at most the bridge's 15 minutes), and
+/// `locale`, a language hint for World App such as `"en-US"`.
+///
+/// On success `*out_handle` receives a handle to release with
+/// [`idkit_session_free`]; on failure it is left untouched.
+///
+/// # Safety
+///
+/// `config_json` must be null or a valid NUL-terminated string, and
+/// `out_handle` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_session_create(
+    config_json: *const c_char,
+    out_handle: *mut *mut IdkitSession,
+) -> IdkitCode {
+    ffi_boundary(|| {
+        require_out(out_handle, "out_handle")?;
+        // SAFETY: forwarded from this function's contract
+        let config = unsafe { read_str(config_json, "config_json") }?;
+        let config: SessionConfig = serde_json::from_str(config)
+            .map_err(|e| FfiError::invalid_argument(format!("Invalid config_json: {e}")))?;
+
+        let request = create_request(config)?;
+        let handle = Box::into_raw(Box::new(IdkitSession { request }));
+        // SAFETY: checked non-null above, valid for writes per the contract
+        unsafe { out_handle.write(handle) };
+        Ok(())
+    })
+}
+
+/// Releases a handle from [`idkit_session_create`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_session_free(handle: *mut IdkitSession) {
+    let _ = catch(|| {
+        if !handle.is_null() {
+            // SAFETY: the handle came from `Box::into_raw` and is released once
+            drop(unsafe { Box::from_raw(handle) });
+        }
+        Ok(())
+    });
+}
+
+/// Copies the connect URL into `buf` as a NUL-terminated string.
+///
+/// Returns the URL length in bytes, excluding the terminator. The URL is only
+/// written when `len` is larger than that, so passing a null `buf` first
+/// reports the size to allocate. Returns -1 on error.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, and `buf` must be null or valid for
+/// `len` bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_session_connect_url(
+    handle: *const IdkitSession,
+    buf: *mut c_char,
+    len: usize,
+) -> isize {
+    let result = catch(|| {
+        // SAFETY: forwarded from this function's contract
+        let session = unsafe { session(handle) }?;
+        let url = session.request.connect_url();
+        let required = isize::try_from(url.len())
+            .map_err(|_| FfiError::invalid_argument("connect URL is too long"))?;
+
+        if !buf.is_null() && len > url.len() {
+            // SAFETY: `buf` holds at least `url.len() + 1` bytes
+            unsafe {
+                std::ptr::copy_nonoverlapping(url.as_ptr().cast::<c_char>(), buf, url.len());
+                buf.add(url.len()).write(0);
+            }
+        }
+        Ok(required)
+    });
+
+    match result {
+        Ok(required) => {
+            clear_last_error();
+            required
+        }
+        Err(error) => {
+            error.report();
+            -1
+        }
+    }
+}
+
+/// Polls the bridge once and writes the status as JSON to `*out_status_json`.
+///
+/// The status has a `type` of `waiting_for_connection`, `awaiting_confirmation`,
+/// `confirmed` (with `result`), `failed` (with the World App `error` code, and
+/// `retry_after_secs` when retrying later may succeed) or `networking_error`
+/// (with `error`; polling again may succeed).
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, and `out_status_json` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_session_poll(
+    handle: *const IdkitSession,
+    out_status_json: *mut *mut c_char,
+) -> IdkitCode {
+    ffi_boundary(|| {
+        require_out(out_status_json, "out_status_json")?;
+        // SAFETY: forwarded from this function's contract
+        let session = unsafe { session(handle) }?;
+
+        let status = to_json(&status_json(session.request.poll_status_once()))?;
+        let status = into_c_string(status)?;
+        // SAFETY: checked non-null above, valid for writes per the contract
+        unsafe { out_status_json.write(status) };
+        Ok(())
+    })
+}
+
+/// Polls until World App responds or `timeout_ms` passes, then writes the
+/// proof as JSON to `*out_proof_json`.
+///
+/// Returns [`IdkitCode::AppError`] with the World App error code as the last
+/// error message if the request failed, [`IdkitCode::Timeout`], or
+/// [`IdkitCode::InvalidConfiguration`] if `timeout_ms` is zero.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, and `out_proof_json` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_session_wait(
+    handle: *const IdkitSession,
+    timeout_ms: u64,
+    out_proof_json: *mut *mut c_char,
+) -> IdkitCode {
+    ffi_boundary(|| {
+        require_out(out_proof_json, "out_proof_json")?;
+        // SAFETY: forwarded from this function's contract
+        let session = unsafe { session(handle) }?;
+
+        let result = wait_for_result(&session.request, Duration::from_millis(timeout_ms))?;
+        let proof = into_c_string(to_json(&result)?)?;
+        // SAFETY: checked non-null above, valid for writes per the contract
+        unsafe { out_proof_json.write(proof) };
+        Ok(())
+    })
+}
+
+fn wait_for_result(
+    request: &IDKitRequestWrapper,
+    timeout: Duration,
+) -> Result<IDKitResult, FfiError> {
+    let config = PollConfig {
+        interval: POLL_INTERVAL.min(timeout / 2),
+        timeout,
+        ..PollConfig::default()
+    };
+    request
+        .wait_for_proof_with_config(config)
+        .map_err(|error| match error {
+            idkit::Error::AppError(error) => FfiError {
+                code: IdkitCode::AppError,
+                message: app_error_code(&error),
+            },
+            idkit::Error::InclusionPending { .. } => FfiError {
+                code: IdkitCode::AppError,
+                message: app_error_code(&idkit::error::AppError::InclusionProofPending),
+            },
+            error => error.into(),
+        })
+}
+
+/// Verifies a proof JSON (as written by [`idkit_session_wait`]) with the
+/// Developer Portal.
+///
+/// Takes `rp_id` and `endpoint` rather than an `app_id` and action: World ID
+/// 4.0 proofs are verified at `/api/v4/verify/{rp_id}`, which keys relying
+/// parties by RP ID and reads the action from the proof itself, unlike the
+/// World ID 3.0 `/api/v2/verify/{app_id}` endpoint. `endpoint` replaces the
+/// production portal's verify URL, e.g. for staging, and may be null.
+/// On failure, `*out_err` receives the error message when `out_err` is not null.
+///
+/// # Safety
+///
+/// `proof_json`, `rp_id` and `endpoint` must be null or valid NUL-terminated
+/// strings, and `out_err` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_verify_proof(
+    proof_json: *const c_char,
+    rp_id: *const c_char,
+    endpoint: *const c_char,
+    out_err: *mut *mut c_char,
+) -> IdkitCode {
+    let code = ffi_boundary(|| {
+        // SAFETY: forwarded from this function's contract
+        let (proof_json, rp_id) = unsafe {
+            (
+                read_str(proof_json, "proof_json")?,
+                read_str(rp_id, "rp_id")?,
+            )
+        };
+        let endpoint = if endpoint.is_null() {
+            None
+        } else {
+            // SAFETY: forwarded from this function's contract
+            Some(unsafe { read_str(endpoint, "endpoint") }?)
+        };
+        let result: IDKitResult = serde_json::from_str(proof_json)
+            .map_err(|e| FfiError::invalid_argument(format!("Invalid proof_json: {e}")))?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| FfiError {
+                code: IdkitCode::Network,
+                message: format!("Failed to create runtime: {e}"),
+            })?;
+        runtime.block_on(idkit::verification::verify_proof_with_endpoint(
+            &result, rp_id, endpoint,
+        ))?;
+        Ok(())
+    });
+
+    if code != IdkitCode::Ok && !out_err.is_null() {
+        let message = LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map(|message| message.clone().into_raw())
+        });
+        if let Some(message) = message {
+            // SAFETY: non-null, valid for writes per the contract
+            unsafe { out_err.write(message) };
+        }
+    }
+    code
+}
+
+/// Releases a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a string from this library, and must not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn idkit_string_free(s: *mut c_char) {
+    let _ = catch(|| {
+        if !s.is_null() {
+            // SAFETY: the string came from `CString::into_raw` and is released once
+            drop(unsafe { CString::from_raw(s) });
+        }
+        Ok(())
+    });
+}
+
+/// Returns the message of the last failed call on this thread, or null.
+///
+/// The string is owned by the library and stays valid until the next call
+/// into the library on the same thread; do not free it.
+#[no_mangle]
+pub extern "C" fn idkit_last_error_message() -> *const c_char {
+    catch(|| {
+        Ok(LAST_ERROR.with(|last| {
+            last.borrow()
+                .as_ref()
+                .map_or(std::ptr::null(), |message| message.as_ptr())
+        }))
+    })
+    .unwrap_or(std::ptr::null())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use idkit::test_utils::server::CannedServer;
+    use std::ptr;
+
+    /// Serves the given responses in order on a local port and returns its URL.
+    fn serve(responses: Vec<(u16, String)>) -> String {
+        CannedServer::start(responses).unwrap().url()
+    }
+
+    fn config_json(bridge_url: &str) -> CString {
+        let config = serde_json::json!({
+            "app_id": "app_staging_test",
+            "action": "my-action",
+            "rp_context": {
+                "rp_id": "rp_1234567890abcdef",
+                "nonce": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "created_at": 1_700_000_000_u64,
+                "expires_at": 1_700_003_600_u64,
+                "signature": format!("0x{}1b", "00".repeat(64)),
+            },
+            "preset": {"type": "OrbLegacy", "signal": "signal"},
+            "bridge_url": bridge_url,
+        });
+        CString::new(config.to_string()).unwrap()
+    }
+
+    fn last_error() -> String {
+        let message = idkit_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    /// Takes ownership of a library string, releasing it through the C ABI.
+    fn take_string(s: *mut c_char) -> String {
+        assert!(!s.is_null());
+        let value = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { idkit_string_free(s) };
+        value
+    }
+
+    #[test]
+    fn test_session_lifecycle_through_c_abi() {
+        let initialized = r#"{"status":"initialized"}"#.to_string();
+        let bridge_url = serve(vec![
+            (200, r#"{"request_id":"req_c_abi"}"#.to_string()),
+            (200, initialized.clone()),
+            (200, initialized.clone()),
+            (200, initialized),
+        ]);
+        let config = config_json(&bridge_url);
+
+        let mut handle = ptr::null_mut();
+        let code = unsafe { idkit_session_create(config.as_ptr(), &raw mut handle) };
+        assert_eq!(code, IdkitCode::Ok);
+        assert!(!handle.is_null());
+        assert!(idkit_last_error_message().is_null());
+
+        // Sizing call, too-small buffer and exact-fit buffer
+        let len = unsafe { idkit_session_connect_url(handle, ptr::null_mut(), 0) };
+        let len = usize::try_from(len).unwrap();
+        let mut small = vec![0x7f_u8; len];
+        let reported =
+            unsafe { idkit_session_connect_url(handle, small.as_mut_ptr().cast(), small.len()) };
+        assert_eq!(usize::try_from(reported).unwrap(), len);
+        assert!(small.iter().all(|&byte| byte == 0x7f));
+
+        let mut buf = vec![0x7f_u8; len + 1];
+        unsafe { idkit_session_connect_url(handle, buf.as_mut_ptr().cast(), buf.len()) };
+        let url = CStr::from_bytes_with_nul(&buf).unwrap().to_str().unwrap();
+        assert!(url.contains("i=req_c_abi"), "{url}");
+
+        let mut status = ptr::null_mut();
+        let code = unsafe { idkit_session_poll(handle, &raw mut status) };
+        assert_eq!(code, IdkitCode::Ok);
+        let status: serde_json::Value = serde_json::from_str(&take_string(status)).unwrap();
+        assert_eq!(
+            status,
+            serde_json::json!({"type": "waiting_for_connection"})
+        );
+
+        let mut proof = ptr::null_mut();
+        let code = unsafe { idkit_session_wait(handle, 50, &raw mut proof) };
+        assert_eq!(code, IdkitCode::Timeout);
+        assert!(proof.is_null());
+        assert_eq!(last_error(), "Request timed out");
+
+        let code = unsafe { idkit_session_wait(handle, 0, &raw mut proof) };
+        assert_eq!(code, IdkitCode::InvalidConfiguration);
+        assert!(proof.is_null());
+
+        unsafe { idkit_session_free(handle) };
+    }
+
+    #[test]
+    fn test_invalid_arguments_leave_outputs_untouched() {
+        let mut handle = ptr::null_mut();
+        let code = unsafe { idkit_session_create(ptr::null(), &raw mut handle) };
+        assert_eq!(code, IdkitCode::InvalidArgument);
+        assert_eq!(last_error(), "config_json is null");
+
+        let config = CString::new(r#"{"app_id": "app_staging_test"}"#).unwrap();
+        let code = unsafe { idkit_session_create(config.as_ptr(), &raw mut handle) };
+        assert_eq!(code, IdkitCode::InvalidArgument);
+        assert!(last_error().starts_with("Invalid config_json"));
+        assert!(handle.is_null());
+
+        let config = config_json("https://bridge.worldcoin.org");
+        let code = unsafe { idkit_session_create(config.as_ptr(), ptr::null_mut()) };
+        assert_eq!(code, IdkitCode::InvalidArgument);
+        assert_eq!(last_error(), "out_handle is null");
+
+        let mut status = ptr::null_mut();
+        let code = unsafe { idkit_session_poll(ptr::null(), &raw mut status) };
+        assert_eq!(code, IdkitCode::InvalidArgument);
+        assert!(status.is_null());
+        assert_eq!(
+            unsafe { idkit_session_connect_url(ptr::null(), ptr::null_mut(), 0) },
+            -1
+        );
+
+        // Releasing null is allowed
+        unsafe {
+            idkit_string_free(ptr::null_mut());
+            idkit_session_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_reports_portal_rejection() {
+        let proof = CString::new(
+            serde_json::json!({
+                "protocol_version": "4.0",
+                "nonce": "0x01",
+                "action": "my-action",
+                "responses": [],
+                "user_presence_completed": false,
+                "environment": "production",
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let rp_id = CString::new("rp_1234567890abcdef").unwrap();
+
+        let endpoint = CString::new(serve(vec![(200, r#"{"success":true}"#.to_string())])).unwrap();
+        let mut err = ptr::null_mut();
+        let code = unsafe {
+            idkit_verify_proof(
+                proof.as_ptr(),
+                rp_id.as_ptr(),
+                endpoint.as_ptr(),
+                &raw mut err,
+            )
+        };
+        assert_eq!(code, IdkitCode::Ok);
+        assert!(err.is_null());
+
+        let endpoint = CString::new(serve(vec![(
+            400,
+            r#"{"code":"invalid_proof","detail":"Proof is invalid"}"#.to_string(),
+        )]))
+        .unwrap();
+        let code = unsafe {
+            idkit_verify_proof(
+                proof.as_ptr(),
+                rp_id.as_ptr(),
+                endpoint.as_ptr(),
+                &raw mut err,
+            )
+        };
+        assert_eq!(code, IdkitCode::InvalidProof);
+        assert_eq!(take_string(err), "Invalid proof: Proof is invalid");
+    }
+
+    #[test]
+    fn test_panics_do_not_cross_the_boundary() {
+        let code = ffi_boundary(|| panic!("boom"));
+
+        assert_eq!(code, IdkitCode::Panic);
+        assert_eq!(last_error(), "panic: boom");
+    }
+}
@@ -244,6 +244,7 @@ final class SampleModel: ObservableObject {
                 requireUserPresence: false,
                 overrideConnectBaseUrl: nil,
                 returnTo: returnToURL,
+                returnToSchemes: ["idkitsample"],
                 environment: {
                     switch environment {
                     case .production: return .production
@@ -401,7 +402,7 @@ final class SampleModel: ObservableObject {
                         }
                         return
 
-                    case .failed(let error):
+                    case .failed(let error, _, _):
                         log("Proof completion failed: \(error.rawValue)")
                         return
 
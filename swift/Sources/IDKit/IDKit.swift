@@ -22,8 +22,15 @@ public struct IDKitRequestConfig {
     public let requireUserPresence: Bool
     public let overrideConnectBaseUrl: String?
     public let returnTo: String?
+    public let returnToSchemes: [String]?
     public let environment: Environment?
     public let connectUrlMode: ConnectUrlMode?
+    public let connectTimeoutMs: UInt64?
+    public let requestTimeoutMs: UInt64?
+    public let normalizeConstraints: Bool?
+    public let cancelOnDrop: Bool?
+    public let singleConsumption: Bool?
+    public let compressPayload: Bool?
 
     public init(
         appId: String,
@@ -35,8 +42,15 @@ public struct IDKitRequestConfig {
         requireUserPresence: Bool = false,
         overrideConnectBaseUrl: String? = nil,
         returnTo: String? = nil,
+        returnToSchemes: [String]? = nil,
         environment: Environment? = nil,
-        connectUrlMode: ConnectUrlMode? = nil
+        connectUrlMode: ConnectUrlMode? = nil,
+        connectTimeoutMs: UInt64? = nil,
+        requestTimeoutMs: UInt64? = nil,
+        normalizeConstraints: Bool? = nil,
+        cancelOnDrop: Bool? = nil,
+        singleConsumption: Bool? = nil,
+        compressPayload: Bool? = nil
     ) {
         self.appId = appId
         self.action = action
@@ -47,8 +61,15 @@ public struct IDKitRequestConfig {
         self.requireUserPresence = requireUserPresence
         self.overrideConnectBaseUrl = overrideConnectBaseUrl
         self.returnTo = returnTo
+        self.returnToSchemes = returnToSchemes
         self.environment = environment
         self.connectUrlMode = connectUrlMode
+        self.connectTimeoutMs = connectTimeoutMs
+        self.requestTimeoutMs = requestTimeoutMs
+        self.normalizeConstraints = normalizeConstraints
+        self.cancelOnDrop = cancelOnDrop
+        self.singleConsumption = singleConsumption
+        self.compressPayload = compressPayload
     }
 
     fileprivate var native: IdKitRequestConfig {
@@ -65,7 +86,14 @@ public struct IDKitRequestConfig {
             overrideConnectBaseUrl: overrideConnectBaseUrl,
             returnTo: returnTo,
             environment: environment,
-            connectUrlMode: connectUrlMode
+            connectUrlMode: connectUrlMode,
+            connectTimeoutMs: connectTimeoutMs,
+            requestTimeoutMs: requestTimeoutMs,
+            normalizeConstraints: normalizeConstraints,
+            cancelOnDrop: cancelOnDrop,
+            singleConsumption: singleConsumption,
+            returnToSchemes: returnToSchemes,
+            compressPayload: compressPayload
         )
     }
 }
@@ -79,7 +107,14 @@ public struct IDKitSessionConfig {
     public let requireUserPresence: Bool
     public let overrideConnectBaseUrl: String?
     public let returnTo: String?
+    public let returnToSchemes: [String]?
     public let environment: Environment?
+    public let connectTimeoutMs: UInt64?
+    public let requestTimeoutMs: UInt64?
+    public let normalizeConstraints: Bool?
+    public let cancelOnDrop: Bool?
+    public let singleConsumption: Bool?
+    public let compressPayload: Bool?
 
     public init(
         appId: String,
@@ -89,7 +124,14 @@ public struct IDKitSessionConfig {
         requireUserPresence: Bool = false,
         overrideConnectBaseUrl: String? = nil,
         returnTo: String? = nil,
-        environment: Environment? = nil
+        returnToSchemes: [String]? = nil,
+        environment: Environment? = nil,
+        connectTimeoutMs: UInt64? = nil,
+        requestTimeoutMs: UInt64? = nil,
+        normalizeConstraints: Bool? = nil,
+        cancelOnDrop: Bool? = nil,
+        singleConsumption: Bool? = nil,
+        compressPayload: Bool? = nil
     ) {
         self.appId = appId
         self.rpContext = rpContext
@@ -98,7 +140,14 @@ public struct IDKitSessionConfig {
         self.requireUserPresence = requireUserPresence
         self.overrideConnectBaseUrl = overrideConnectBaseUrl
         self.returnTo = returnTo
+        self.returnToSchemes = returnToSchemes
         self.environment = environment
+        self.connectTimeoutMs = connectTimeoutMs
+        self.requestTimeoutMs = requestTimeoutMs
+        self.normalizeConstraints = normalizeConstraints
+        self.cancelOnDrop = cancelOnDrop
+        self.singleConsumption = singleConsumption
+        self.compressPayload = compressPayload
     }
 
     fileprivate var native: IdKitSessionConfig {
@@ -112,7 +161,14 @@ public struct IDKitSessionConfig {
             requireUserPresence: requireUserPresence,
             overrideConnectBaseUrl: overrideConnectBaseUrl,
             returnTo: returnTo,
-            environment: environment
+            environment: environment,
+            connectTimeoutMs: connectTimeoutMs,
+            requestTimeoutMs: requestTimeoutMs,
+            normalizeConstraints: normalizeConstraints,
+            cancelOnDrop: cancelOnDrop,
+            singleConsumption: singleConsumption,
+            returnToSchemes: returnToSchemes,
+            compressPayload: compressPayload
         )
     }
 }
@@ -190,6 +246,12 @@ public final class IDKitBuilder {
         return try IDKitRequest(inner: request)
     }
 
+    /// Orb-only request, shorthand for `preset(orbLegacy(signal:))`.
+    public func orb(signal: String? = nil) throws -> IDKitRequest {
+        let request = try inner.orb(signal: signal)
+        return try IDKitRequest(inner: request)
+    }
+
     /// Builds the request in invite-code mode.
     ///
     /// Returns an `IDKitInviteCodeRequest` exposing the canonical 6-character
@@ -206,14 +268,16 @@ public enum IDKitStatus: Equatable {
     case waitingForConnection
     case awaitingConfirmation
     case confirmed(IDKitResult)
-    case failed(IDKitErrorCode)
+    /// `retryAfterSecs` suggests when a new request is expected to succeed.
+    /// `rawCode` keeps World App's code when this version does not know it and reports `.genericError`.
+    case failed(IDKitErrorCode, retryAfterSecs: UInt64? = nil, rawCode: String? = nil)
     case networkingError(IDKitErrorCode)
 }
 
 /// Result returned by `IDKitRequest.pollUntilCompletion(options:)`.
 public enum IDKitCompletionResult: Equatable {
     case success(IDKitResult)
-    case failure(IDKitErrorCode)
+    case failure(IDKitErrorCode, retryAfterSecs: UInt64? = nil, rawCode: String? = nil)
 }
 
 /// Polling options for `pollUntilCompletion`.
@@ -256,6 +320,8 @@ public enum IDKitErrorCode: String, Equatable {
     case invalidTimestamp = "invalid_timestamp"
     case rpSignatureExpired = "rp_signature_expired"
     case identityAttributesNotMatched = "identity_attributes_not_matched"
+    case requestNotFound = "request_not_found"
+    case rateLimited = "rate_limited"
     case genericError = "generic_error"
     case timeout = "timeout"
     case cancelled = "cancelled"
@@ -310,9 +376,24 @@ public enum IDKitErrorCode: String, Equatable {
             .rpSignatureExpired
         case .identityAttributesNotMatched:
             .identityAttributesNotMatched
+        case .requestNotFound:
+            .requestNotFound
+        case .rateLimited:
+            .rateLimited
         case .genericError:
             .genericError
+        // Codes added in newer World App versions; see `rawCode(of:)`
+        case .other:
+            .genericError
+        }
+    }
+
+    /// World App's code when `from(appError:)` could only map it to `.genericError`.
+    static func rawCode(of appError: AppError) -> String? {
+        if case .other(let code) = appError {
+            return code
         }
+        return nil
     }
 }
 
@@ -387,8 +468,12 @@ public final class IDKitRequest {
             .awaitingConfirmation
         case .confirmed(let result):
             .confirmed(result)
-        case .failed(let error):
-            .failed(IDKitErrorCode.from(appError: error))
+        case .failed(let error, let retryAfterSecs):
+            .failed(
+                IDKitErrorCode.from(appError: error),
+                retryAfterSecs: retryAfterSecs,
+                rawCode: IDKitErrorCode.rawCode(of: error)
+            )
         case .networkingError(let error):
             .networkingError(IDKitErrorCode.from(appError: error))
         }
@@ -418,8 +503,8 @@ private func idkitPollUntilCompletion(
         switch status {
         case .confirmed(let result):
             return .success(result)
-        case .failed(let error):
-            return .failure(error)
+        case .failed(let error, let retryAfterSecs, let rawCode):
+            return .failure(error, retryAfterSecs: retryAfterSecs, rawCode: rawCode)
         case .waitingForConnection, .awaitingConfirmation, .networkingError:
             break
         }
@@ -89,6 +89,7 @@ func bridgeRequestPayloadIdentityCheck() throws {
         requireUserPresence: true,
         overrideConnectBaseUrl: nil,
         returnTo: "idkitsample://callback",
+        returnToSchemes: ["idkitsample"],
         environment: .staging,
         connectUrlMode: nil
     )
@@ -184,17 +185,25 @@ func statusMapping() {
     #expect(IDKitRequest.mapStatus(.waitingForConnection) == .waitingForConnection)
     #expect(IDKitRequest.mapStatus(.awaitingConfirmation) == .awaitingConfirmation)
     #expect(IDKitRequest.mapStatus(.confirmed(result: result)) == .confirmed(result))
-    #expect(IDKitRequest.mapStatus(.failed(error: .invalidNetwork)) == .failed(.invalidNetwork))
-    #expect(IDKitRequest.mapStatus(.failed(error: .userPresenceFailed)) == .failed(.userPresenceFailed))
-    #expect(IDKitRequest.mapStatus(.failed(error: .invalidRpSignature)) == .failed(.invalidRpSignature))
-    #expect(IDKitRequest.mapStatus(.failed(error: .nullifierReplayed)) == .failed(.nullifierReplayed))
-    #expect(IDKitRequest.mapStatus(.failed(error: .duplicateNonce)) == .failed(.duplicateNonce))
-    #expect(IDKitRequest.mapStatus(.failed(error: .unknownRp)) == .failed(.unknownRp))
-    #expect(IDKitRequest.mapStatus(.failed(error: .inactiveRp)) == .failed(.inactiveRp))
-    #expect(IDKitRequest.mapStatus(.failed(error: .timestampTooOld)) == .failed(.timestampTooOld))
-    #expect(IDKitRequest.mapStatus(.failed(error: .timestampTooFarInFuture)) == .failed(.timestampTooFarInFuture))
-    #expect(IDKitRequest.mapStatus(.failed(error: .invalidTimestamp)) == .failed(.invalidTimestamp))
-    #expect(IDKitRequest.mapStatus(.failed(error: .rpSignatureExpired)) == .failed(.rpSignatureExpired))
+    #expect(IDKitRequest.mapStatus(.failed(error: .invalidNetwork, retryAfterSecs: nil)) == .failed(.invalidNetwork))
+    #expect(IDKitRequest.mapStatus(.failed(error: .userPresenceFailed, retryAfterSecs: nil)) == .failed(.userPresenceFailed))
+    #expect(IDKitRequest.mapStatus(.failed(error: .invalidRpSignature, retryAfterSecs: nil)) == .failed(.invalidRpSignature))
+    #expect(IDKitRequest.mapStatus(.failed(error: .nullifierReplayed, retryAfterSecs: nil)) == .failed(.nullifierReplayed))
+    #expect(IDKitRequest.mapStatus(.failed(error: .duplicateNonce, retryAfterSecs: nil)) == .failed(.duplicateNonce))
+    #expect(IDKitRequest.mapStatus(.failed(error: .unknownRp, retryAfterSecs: nil)) == .failed(.unknownRp))
+    #expect(IDKitRequest.mapStatus(.failed(error: .inactiveRp, retryAfterSecs: nil)) == .failed(.inactiveRp))
+    #expect(IDKitRequest.mapStatus(.failed(error: .timestampTooOld, retryAfterSecs: nil)) == .failed(.timestampTooOld))
+    #expect(IDKitRequest.mapStatus(.failed(error: .timestampTooFarInFuture, retryAfterSecs: nil)) == .failed(.timestampTooFarInFuture))
+    #expect(IDKitRequest.mapStatus(.failed(error: .invalidTimestamp, retryAfterSecs: nil)) == .failed(.invalidTimestamp))
+    #expect(IDKitRequest.mapStatus(.failed(error: .rpSignatureExpired, retryAfterSecs: nil)) == .failed(.rpSignatureExpired))
+    #expect(
+        IDKitRequest.mapStatus(.failed(error: .inclusionProofPending, retryAfterSecs: 1_800))
+            == .failed(.inclusionProofPending, retryAfterSecs: 1_800)
+    )
+    #expect(
+        IDKitRequest.mapStatus(.failed(error: .other("new_world_app_code"), retryAfterSecs: nil))
+            == .failed(.genericError, rawCode: "new_world_app_code")
+    )
     #expect(IDKitRequest.mapStatus(.networkingError(error: .connectionFailed)) == .networkingError(.connectionFailed))
 }
 
@@ -277,6 +286,18 @@ func pollUntilCompletionAppFailure() async {
     #expect(completion == .failure(.userRejected))
 }
 
+@Test("pollUntilCompletion keeps the retry hint of a pending inclusion proof")
+func pollUntilCompletionInclusionPending() async {
+    let request = IDKitRequest(
+        connectorURL: URL(string: "https://world.org/verify?t=wld")!,
+        requestID: UUID(),
+        pollOnce: { .failed(.inclusionProofPending, retryAfterSecs: 1_800) }
+    )
+
+    let completion = await request.pollUntilCompletion(options: .init(pollIntervalMs: 1, timeoutMs: 1_000))
+    #expect(completion == .failure(.inclusionProofPending, retryAfterSecs: 1_800))
+}
+
 @Test("hashSignal string and data overloads are deterministic")
 func hashSignalOverloads() {
     let raw = "test-signal"